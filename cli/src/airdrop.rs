@@ -117,7 +117,7 @@ pub async fn deposit_to_l2(
         program_id: *program_id,
         accounts: vec![
             AccountMeta::new(user_solkey.pubkey(), true),
-            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new(config_pda, false),
             AccountMeta::new(vault_pda, false),
             AccountMeta::new(receipt_pda, false),
             AccountMeta::new_readonly(system_id, false),
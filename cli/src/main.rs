@@ -85,6 +85,16 @@ async fn main() {
                 std::process::exit(1);
             }
         }
+        "config-schema" => {
+            let output = args.get(2).cloned();
+            if let Err(e) = config_schema(output) {
+                eprintln!(" Error writing config schema: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "env-vars" => {
+            print_env_vars();
+        }
         "add" => {
             if args.len() < 4 {
                 println!("Usage: add <a> <b>");
@@ -120,6 +130,8 @@ fn print_usage() {
     println!("  airdrop <amount> [file]    Request Solana airdrop and bridge to L2");
     println!();
     println!("OTHER COMMANDS:");
+    println!("  config-schema [file]       Write the config.toml JSON Schema (default: stdout)");
+    println!("  env-vars                   List environment variables that override config.toml");
     println!("  help                       Show this help message");
     println!();
     println!("DEV OPTIONS:");
@@ -142,6 +154,7 @@ fn print_usage() {
     println!("  zelana deploy --network devnet       # Deploy to devnet");
     println!("  zelana genkey                        # Generate keypair");
     println!("  zelana airdrop 1000000000            # Airdrop and bridge");
+    println!("  zelana config-schema schema.json     # Schema for editor validation");
     println!();
     println!("ENVIRONMENT VARIABLES:");
     println!("  SOLANA_RPC_URL       Solana RPC endpoint");
@@ -296,6 +309,29 @@ fn genkey(filename: Option<String>) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Write the JSON Schema for `config.toml`, for taplo / editor validation
+fn config_schema(output: Option<String>) -> anyhow::Result<()> {
+    let schema = serde_json::to_string_pretty(&zelana_config::ZelanaConfig::json_schema())?;
+
+    match output {
+        Some(path) => {
+            fs::write(&path, schema)?;
+            println!(" Wrote config schema to {}", path);
+        }
+        None => println!("{}", schema),
+    }
+    Ok(())
+}
+
+/// Print every env var the config loader reads
+fn print_env_vars() {
+    println!("Environment variables (override config.toml):");
+    println!();
+    for doc in zelana_config::ZelanaConfig::env_var_docs() {
+        println!("  {:<28} {:<34} {}", doc.name, doc.field, doc.description);
+    }
+}
+
 fn add(a: &str, b: &str) {
     match (a.parse::<i32>(), b.parse::<i32>()) {
         (Ok(x), Ok(y)) => println!("{} + {} = {}", x, y, x + y),
@@ -3,15 +3,23 @@
 //! Shared configuration crate for all Zelana components.
 //!
 //! Handles loading configuration from:
-//! 1. ZL_CONFIG env var (explicit path)
+//! 1. ZL_CONFIG env var (explicit path to a file or a directory)
 //! 2. ./config.toml (current directory)
 //! 3. ~/.zelana/config.toml (user home)
 //!
-//! Environment variables take precedence over TOML config.
+//! When ZL_CONFIG points at a directory, every `*.toml` file in it is loaded
+//! in lexical file-name order and deep-merged: tables are merged key by key,
+//! any other value in a later file replaces the earlier one. Prefix files to
+//! control precedence (`00-base.toml`, `10-api.toml`, `90-local.toml`).
+//!
+//! Environment variables take precedence over TOML config (file or merged directory).
 
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::OnceLock;
 use std::{env, fs};
 
@@ -27,6 +35,7 @@ const CONFIG_DIR_NAME: &str = ".zelana";
 
 const DEFAULT_SEQUENCER: &str = "127.0.0.1:8080";
 const DEFAULT_PORT: u16 = 8080;
+const DEFAULT_CHAIN_ID: u64 = 1;
 const DEFAULT_DB_PATH: &str = "./zelana-db";
 const DEFAULT_WS_URL: &str = "ws://127.0.0.1:8900/";
 const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
@@ -40,6 +49,7 @@ const DEFAULT_MAX_TRANSACTIONS: usize = 100;
 const DEFAULT_MAX_BATCH_AGE_SECS: u64 = 60;
 const DEFAULT_MAX_SHIELDED: usize = 10;
 const DEFAULT_MIN_TRANSACTIONS: usize = 1;
+const DEFAULT_MAX_PENDING_PER_ACCOUNT: usize = 16;
 const DEFAULT_THRESHOLD_K: usize = 2;
 const DEFAULT_THRESHOLD_N: usize = 3;
 
@@ -48,7 +58,7 @@ const DEFAULT_THRESHOLD_N: usize = 3;
 // ============================================================================
 
 /// Root configuration structure (matches TOML layout)
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ZelanaConfig {
     #[serde(default)]
     pub api: ApiConfig,
@@ -65,7 +75,7 @@ pub struct ZelanaConfig {
 }
 
 /// API configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ApiConfig {
     #[serde(default = "default_sequencer")]
     pub sequencer: String,
@@ -73,6 +83,12 @@ pub struct ApiConfig {
     pub port: u16,
     #[serde(default)]
     pub udp_port: Option<u16>,
+    #[serde(default)]
+    pub health_port: Option<u16>,
+    /// Deployment chain id (1 = mainnet, 2 = devnet); domain-separates
+    /// Zephyr session keys so handshakes don't carry across networks
+    #[serde(default = "default_chain_id")]
+    pub chain_id: u64,
 }
 
 impl Default for ApiConfig {
@@ -81,6 +97,8 @@ impl Default for ApiConfig {
             sequencer: DEFAULT_SEQUENCER.into(),
             port : DEFAULT_PORT.into(),
             udp_port: None,
+            health_port: None,
+            chain_id: DEFAULT_CHAIN_ID,
         }
     }
 }
@@ -93,17 +111,26 @@ fn default_port() -> u16 {
     DEFAULT_PORT.into()
 }
 
+fn default_chain_id() -> u64 {
+    DEFAULT_CHAIN_ID
+}
+
 /// Database configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DatabaseConfig {
     #[serde(default = "default_db_path")]
     pub path: String,
+    /// Keep transaction summaries for only the last N batches (unset keeps all).
+    /// Older transactions can no longer be looked up by hash or account.
+    #[serde(default)]
+    pub tx_retention_batches: Option<u64>,
 }
 
 impl Default for DatabaseConfig {
     fn default() -> Self {
         Self {
             path: DEFAULT_DB_PATH.into(),
+            tx_retention_batches: None,
         }
     }
 }
@@ -113,11 +140,13 @@ fn default_db_path() -> String {
 }
 
 /// Pipeline configuration (TOML format)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PipelineTomlConfig {
     #[serde(default)]
     pub prover_mode: ProverModeToml,
     #[serde(default)]
+    pub proving_backend: ProvingBackendToml,
+    #[serde(default)]
     pub settlement_enabled: bool,
     #[serde(default)]
     pub proving_key_path: Option<String>,
@@ -138,7 +167,7 @@ pub struct PipelineTomlConfig {
 }
 
 /// Prover mode for TOML config
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ProverModeToml {
     #[default]
@@ -147,10 +176,27 @@ pub enum ProverModeToml {
     Noir,
 }
 
+/// Where batch proofs are produced (TOML config)
+///
+/// - `integrated`: core proves batches in-process (mock or Groth16). No extra
+///   service to run, but proving blocks a core worker thread and competes with
+///   execution for CPU.
+/// - `coordinator`: core submits batches to the external prover coordinator
+///   (Noir/Sunspot). Proving scales out across workers, but adds a network hop
+///   and requires `noir_coordinator_url` to be set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProvingBackendToml {
+    #[default]
+    Integrated,
+    Coordinator,
+}
+
 impl Default for PipelineTomlConfig {
     fn default() -> Self {
         Self {
             prover_mode: ProverModeToml::Mock,
+            proving_backend: ProvingBackendToml::Integrated,
             settlement_enabled: false,
             proving_key_path: None,
             verifying_key_path: None,
@@ -175,9 +221,10 @@ fn default_poll_interval() -> u64 {
 }
 
 /// Batch configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchTomlConfig {
     #[serde(default = "default_max_transactions")]
+    #[schemars(range(min = 1))]
     pub max_transactions: usize,
     #[serde(default = "default_max_batch_age")]
     pub max_batch_age_secs: u64,
@@ -185,6 +232,10 @@ pub struct BatchTomlConfig {
     pub max_shielded: usize,
     #[serde(default = "default_min_transactions")]
     pub min_transactions: usize,
+    /// Unexecuted transactions one account may have queued at once
+    #[serde(default = "default_max_pending_per_account")]
+    #[schemars(range(min = 1))]
+    pub max_pending_per_account: usize,
 }
 
 impl Default for BatchTomlConfig {
@@ -194,6 +245,7 @@ impl Default for BatchTomlConfig {
             max_batch_age_secs: DEFAULT_MAX_BATCH_AGE_SECS,
             max_shielded: DEFAULT_MAX_SHIELDED,
             min_transactions: DEFAULT_MIN_TRANSACTIONS,
+            max_pending_per_account: DEFAULT_MAX_PENDING_PER_ACCOUNT,
         }
     }
 }
@@ -210,9 +262,12 @@ fn default_max_shielded() -> usize {
 fn default_min_transactions() -> usize {
     DEFAULT_MIN_TRANSACTIONS
 }
+fn default_max_pending_per_account() -> usize {
+    DEFAULT_MAX_PENDING_PER_ACCOUNT
+}
 
 /// Solana connection configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SolanaConfig {
     #[serde(default = "default_ws_url")]
     pub ws_url: String,
@@ -222,7 +277,9 @@ pub struct SolanaConfig {
     pub bridge_program_id: String,
     #[serde(default = "default_verifier_program")]
     pub verifier_program_id: String,
+    /// Bridge/verifier domain name (1-32 bytes, zero-padded into PDA seeds)
     #[serde(default)]
+    #[schemars(length(min = 1, max = 32))]
     pub domain: Option<String>,
 }
 
@@ -251,8 +308,82 @@ fn default_verifier_program() -> String {
     DEFAULT_VERIFIER_PROGRAM.into()
 }
 
+impl SolanaConfig {
+    /// Parsed domain, falling back to [`Domain::default`] when unset.
+    pub fn domain(&self) -> Result<Domain> {
+        match self.domain.as_deref() {
+            Some(name) => name.parse(),
+            None => Ok(Domain::default()),
+        }
+    }
+}
+
+// ============================================================================
+// Domain
+// ============================================================================
+
+const DEFAULT_DOMAIN: &str = "solana";
+
+/// Bridge/verifier domain, the 32-byte value mixed into every PDA seed
+/// (`[b"config", domain]`, `[b"vault", domain]`, `[b"batch_vk", domain]`, ...).
+///
+/// The conversion rule is: the UTF-8 bytes of the name, right-padded with
+/// zeros to 32 bytes. Names are never hashed, so `"solana"` on chain is
+/// `b"solana\0\0..."`. Empty names and names longer than 32 bytes are
+/// rejected rather than truncated, since a truncated domain would silently
+/// derive a different PDA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Domain([u8; 32]);
+
+impl Domain {
+    /// The 32-byte PDA seed for this domain
+    pub fn as_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl Default for Domain {
+    fn default() -> Self {
+        DEFAULT_DOMAIN.parse().expect("default domain is valid")
+    }
+}
+
+impl FromStr for Domain {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        let bytes = name.as_bytes();
+        if bytes.is_empty() {
+            anyhow::bail!("domain must not be empty");
+        }
+        if bytes.len() > 32 {
+            anyhow::bail!(
+                "domain {:?} is {} bytes, the maximum is 32",
+                name,
+                bytes.len()
+            );
+        }
+        let mut domain = [0u8; 32];
+        domain[..bytes.len()].copy_from_slice(bytes);
+        Ok(Self(domain))
+    }
+}
+
+impl From<Domain> for [u8; 32] {
+    fn from(domain: Domain) -> Self {
+        domain.0
+    }
+}
+
+impl std::fmt::Display for Domain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let len = self.0.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+        f.write_str(&String::from_utf8_lossy(&self.0[..len]))
+    }
+}
+
 /// Feature flags
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct FeatureFlags {
     #[serde(default)]
     pub dev_mode: bool,
@@ -264,6 +395,8 @@ pub struct FeatureFlags {
     pub threshold_k: usize,
     #[serde(default = "default_threshold_n")]
     pub threshold_n: usize,
+    /// INSECURE: generate every committee key in-process instead of running
+    /// DKG, so one node can decrypt alone (local development only)
     #[serde(default)]
     pub threshold_dev: bool,
 }
@@ -276,51 +409,430 @@ fn default_threshold_n() -> usize {
 }
 
 // ============================================================================
-// Environment Variable Helpers
+// Environment Variables
 // ============================================================================
 
-/// Set field from env var if present
-fn env_string(key: &str, field: &mut String) {
-    if let Ok(v) = env::var(key) {
-        *field = v;
-    }
+/// One environment variable read while loading the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvVarDoc {
+    /// Variable name
+    pub name: &'static str,
+    /// TOML field it overrides
+    pub field: &'static str,
+    pub description: &'static str,
+}
+
+/// Config file (or directory) the loader reads; the only variable that is
+/// not a field override
+const CONFIG_PATH_VAR: EnvVarDoc = EnvVarDoc {
+    name: "ZL_CONFIG",
+    field: "-",
+    description: "Config file, or directory of *.toml files to merge",
+};
+
+/// An env var and how it overrides the config
+struct EnvOverride {
+    doc: EnvVarDoc,
+    /// Called with the variable's value when it is set
+    apply: fn(&mut ZelanaConfig, &str),
 }
 
-/// Set Option<String> from env var if present
-fn env_option_string(key: &str, field: &mut Option<String>) {
-    if let Ok(v) = env::var(key) {
-        *field = Some(v);
+/// Every field override, applied in this order (so the legacy
+/// `ZL_MOCK_PROVER` wins over `ZL_PROVER_MODE`). Both the loader and
+/// [`ZelanaConfig::env_var_docs`] read this table.
+const ENV_OVERRIDES: &[EnvOverride] = &[
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_DB_PATH",
+            field: "database.path",
+            description: "RocksDB directory",
+        },
+        apply: |c, v| c.database.path = v.to_string(),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_TX_RETENTION_BATCHES",
+            field: "database.tx_retention_batches",
+            description: "Keep transaction summaries for the last N batches",
+        },
+        apply: |c, v| parse_option_into(v, &mut c.database.tx_retention_batches),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_API_HOST",
+            field: "api.sequencer",
+            description: "Sequencer address clients connect to",
+        },
+        apply: |c, v| c.api.sequencer = v.to_string(),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_UDP_PORT",
+            field: "api.udp_port",
+            description: "Zephyr UDP port (disabled when unset)",
+        },
+        apply: |c, v| parse_option_into(v, &mut c.api.udp_port),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_HEALTH_PORT",
+            field: "api.health_port",
+            description: "Standalone health check port (disabled when unset)",
+        },
+        apply: |c, v| parse_option_into(v, &mut c.api.health_port),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_CHAIN_ID",
+            field: "api.chain_id",
+            description: "Network id (1 = mainnet, 2 = devnet)",
+        },
+        apply: |c, v| parse_into(v, &mut c.api.chain_id),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "SOLANA_WS_URL",
+            field: "solana.ws_url",
+            description: "Solana websocket endpoint",
+        },
+        apply: |c, v| c.solana.ws_url = v.to_string(),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "SOLANA_RPC_URL",
+            field: "solana.rpc_url",
+            description: "Solana RPC endpoint",
+        },
+        apply: |c, v| c.solana.rpc_url = v.to_string(),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_BRIDGE_PROGRAM",
+            field: "solana.bridge_program_id",
+            description: "Bridge program id",
+        },
+        apply: |c, v| c.solana.bridge_program_id = v.to_string(),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_VERIFIER_PROGRAM_ID",
+            field: "solana.verifier_program_id",
+            description: "Verifier program id",
+        },
+        apply: |c, v| c.solana.verifier_program_id = v.to_string(),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_DOMAIN",
+            field: "solana.domain",
+            description: "Bridge/verifier domain name",
+        },
+        apply: |c, v| c.solana.domain = Some(v.to_string()),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_PROVER_MODE",
+            field: "pipeline.prover_mode",
+            description: "mock, groth16 or noir",
+        },
+        apply: |c, v| {
+            c.pipeline.prover_mode = match v.to_ascii_lowercase().as_str() {
+                "groth16" => ProverModeToml::Groth16,
+                "noir" => ProverModeToml::Noir,
+                _ => ProverModeToml::Mock,
+            }
+        },
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_PROVING_BACKEND",
+            field: "pipeline.proving_backend",
+            description: "integrated or coordinator",
+        },
+        apply: |c, v| {
+            c.pipeline.proving_backend = match v.to_ascii_lowercase().as_str() {
+                "coordinator" => ProvingBackendToml::Coordinator,
+                _ => ProvingBackendToml::Integrated,
+            }
+        },
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_MOCK_PROVER",
+            field: "pipeline.prover_mode",
+            description: "Legacy: true selects mock, false groth16",
+        },
+        apply: |c, v| {
+            c.pipeline.prover_mode = if truthy(v) {
+                ProverModeToml::Mock
+            } else {
+                ProverModeToml::Groth16
+            }
+        },
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_SETTLEMENT_ENABLED",
+            field: "pipeline.settlement_enabled",
+            description: "Submit batches to L1 (true/1)",
+        },
+        apply: |c, v| c.pipeline.settlement_enabled = truthy(v),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_PROVING_KEY",
+            field: "pipeline.proving_key_path",
+            description: "Groth16 proving key",
+        },
+        apply: |c, v| c.pipeline.proving_key_path = Some(v.to_string()),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_VERIFYING_KEY",
+            field: "pipeline.verifying_key_path",
+            description: "Groth16 verifying key",
+        },
+        apply: |c, v| c.pipeline.verifying_key_path = Some(v.to_string()),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_NOIR_COORDINATOR_URL",
+            field: "pipeline.noir_coordinator_url",
+            description: "Prover coordinator URL",
+        },
+        apply: |c, v| c.pipeline.noir_coordinator_url = Some(v.to_string()),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_NOIR_PROOF_TIMEOUT_SECS",
+            field: "pipeline.noir_proof_timeout_secs",
+            description: "Coordinator proof timeout",
+        },
+        apply: |c, v| parse_option_into(v, &mut c.pipeline.noir_proof_timeout_secs),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_SEQUENCER_KEYPAIR",
+            field: "pipeline.sequencer_keypair_path",
+            description: "Keypair that signs settlement transactions",
+        },
+        apply: |c, v| c.pipeline.sequencer_keypair_path = Some(v.to_string()),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "ZL_SETTLEMENT_RETRIES",
+            field: "pipeline.max_settlement_retries",
+            description: "Settlement attempts before giving up",
+        },
+        apply: |c, v| parse_into(v, &mut c.pipeline.max_settlement_retries),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "BATCH_MAX_TXS",
+            field: "batch.max_transactions",
+            description: "Transactions per batch",
+        },
+        apply: |c, v| parse_into(v, &mut c.batch.max_transactions),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "BATCH_MAX_AGE",
+            field: "batch.max_batch_age_secs",
+            description: "Seconds before a batch is force-sealed",
+        },
+        apply: |c, v| parse_into(v, &mut c.batch.max_batch_age_secs),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "BATCH_MAX_SHIELDED",
+            field: "batch.max_shielded",
+            description: "Shielded transactions per batch",
+        },
+        apply: |c, v| parse_into(v, &mut c.batch.max_shielded),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "BATCH_MAX_PENDING_PER_ACCOUNT",
+            field: "batch.max_pending_per_account",
+            description: "Unexecuted transactions allowed per account",
+        },
+        apply: |c, v| parse_into(v, &mut c.batch.max_pending_per_account),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "DEV_MODE",
+            field: "features.dev_mode",
+            description: "Enable dev endpoints (true/1)",
+        },
+        apply: |c, v| c.features.dev_mode = truthy(v),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "FAST_WITHDRAW_ENABLED",
+            field: "features.fast_withdrawals",
+            description: "Enable fast withdrawals (presence enables)",
+        },
+        apply: |c, _| c.features.fast_withdrawals = true,
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "THRESHOLD_ENABLED",
+            field: "features.threshold_encryption",
+            description: "Enable the encrypted mempool (presence enables)",
+        },
+        apply: |c, _| c.features.threshold_encryption = true,
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "THRESHOLD_K",
+            field: "features.threshold_k",
+            description: "Committee shares needed to decrypt",
+        },
+        apply: |c, v| parse_into(v, &mut c.features.threshold_k),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "THRESHOLD_N",
+            field: "features.threshold_n",
+            description: "Committee size",
+        },
+        apply: |c, v| parse_into(v, &mut c.features.threshold_n),
+    },
+    EnvOverride {
+        doc: EnvVarDoc {
+            name: "THRESHOLD_DEV",
+            field: "features.threshold_dev",
+            description: "INSECURE in-process dealer committee (presence enables)",
+        },
+        apply: |c, _| c.features.threshold_dev = true,
+    },
+];
+
+// Environment Variable Helpers
+
+/// Set field if the value parses
+fn parse_into<T: FromStr>(value: &str, field: &mut T) {
+    if let Ok(parsed) = value.parse() {
+        *field = parsed;
     }
 }
 
-/// Set field from env var if present and parseable
-fn env_parse<T: std::str::FromStr>(key: &str, field: &mut T) {
-    if let Ok(v) = env::var(key) {
-        if let Ok(parsed) = v.parse() {
-            *field = parsed;
-        }
+/// Set Option<T> if the value parses
+fn parse_option_into<T: FromStr>(value: &str, field: &mut Option<T>) {
+    if let Ok(parsed) = value.parse() {
+        *field = Some(parsed);
     }
 }
 
-/// Set Option<T> from env var if present and parseable
-fn env_parse_option<T: std::str::FromStr>(key: &str, field: &mut Option<T>) {
-    if let Ok(v) = env::var(key) {
-        if let Ok(parsed) = v.parse() {
-            *field = Some(parsed);
+/// Whether a value is truthy ("1" or "true")
+fn truthy(value: &str) -> bool {
+    value == "1" || value.eq_ignore_ascii_case("true")
+}
+
+/// Deep-merge `overlay` into `base`: tables merge recursively, other values replace
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
         }
+        (base, overlay) => *base = overlay,
     }
 }
 
-/// Check if env var is set to a truthy value ("1" or "true")
-fn env_bool(key: &str) -> Option<bool> {
-    env::var(key)
-        .ok()
-        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+// ============================================================================
+// Change Detection
+// ============================================================================
+
+/// Fields the sequencer applies on a SIGHUP reload (see
+/// [`ZelanaConfig::apply_hot`]). Anything not listed (paths, ports, keys,
+/// programs, feature flags) requires a restart, so new fields are safe by
+/// default.
+const HOT_APPLICABLE_FIELDS: &[&str] = &[
+    "pipeline.poll_interval_ms",
+    "pipeline.max_settlement_retries",
+    "pipeline.settlement_retry_base_ms",
+    "batch.max_transactions",
+    "batch.max_batch_age_secs",
+    "batch.max_shielded",
+    "batch.min_transactions",
+    "batch.max_pending_per_account",
+];
+
+/// How a changed field can be applied to a running service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Safe to apply live
+    HotApplicable,
+    /// Only takes effect after a restart
+    RequiresRestart,
+}
+
+/// One field that differs between two configs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    /// Dotted TOML path (`database.path`)
+    pub field: String,
+    pub kind: ChangeKind,
 }
 
-/// Check if env var exists (presence = true)
-fn env_exists(key: &str) -> bool {
-    env::var(key).is_ok()
+/// Changed fields between two configs, ordered by path
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    pub changes: Vec<FieldChange>,
+}
+
+impl ConfigDiff {
+    /// No field changed
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Changed fields that can be applied live
+    pub fn hot_applicable(&self) -> impl Iterator<Item = &str> {
+        self.fields(ChangeKind::HotApplicable)
+    }
+
+    /// Changed fields that need a restart (reloads should warn about these)
+    pub fn requires_restart(&self) -> impl Iterator<Item = &str> {
+        self.fields(ChangeKind::RequiresRestart)
+    }
+
+    fn fields(&self, kind: ChangeKind) -> impl Iterator<Item = &str> {
+        self.changes
+            .iter()
+            .filter(move |c| c.kind == kind)
+            .map(|c| c.field.as_str())
+    }
+}
+
+/// Collect leaf values of a serialized config under dotted paths
+fn flatten_json(
+    prefix: &str,
+    value: serde_json::Value,
+    out: &mut std::collections::BTreeMap<String, serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::Object(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_json(&path, value, out);
+            }
+        }
+        leaf => {
+            out.insert(prefix.to_string(), leaf);
+        }
+    }
 }
 
 // ============================================================================
@@ -333,10 +845,9 @@ impl ZelanaConfig {
         let mut config = match Self::find_config_file() {
             Some(path) => {
                 log::info!("Loading config from: {}", path.display());
-                let contents = fs::read_to_string(&path)
-                    .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-                toml::from_str(&contents)
-                    .with_context(|| format!("Failed to parse config file: {}", path.display()))?
+                Self::read_config_source(&path)?
+                    .try_into()
+                    .with_context(|| format!("Invalid config in: {}", path.display()))?
             }
             None => {
                 log::info!("No config file found, using defaults and environment variables");
@@ -345,24 +856,117 @@ impl ZelanaConfig {
         };
 
         config.apply_env_overrides();
+        config.solana.domain().context("Invalid solana.domain")?;
         Ok(config)
     }
 
-    /// Load configuration from a specific file path
+    /// Load configuration from a specific file or directory path
     pub fn load_from(path: &std::path::Path) -> Result<Self> {
-        let contents = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-        let mut config: Self = toml::from_str(&contents)
-            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        let mut config: Self = Self::read_config_source(path)?
+            .try_into()
+            .with_context(|| format!("Invalid config in: {}", path.display()))?;
 
         config.apply_env_overrides();
+        config.solana.domain().context("Invalid solana.domain")?;
         Ok(config)
     }
 
+    /// Build configuration purely from defaults + environment variables.
+    ///
+    /// Never touches the filesystem (no `ZL_CONFIG`, `./config.toml` or
+    /// `~/.zelana` lookup), for container deployments configured only via env.
+    pub fn from_env_only() -> Result<Self> {
+        let mut config = Self::default();
+        config.apply_env_overrides();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check values that parse fine but can't work at runtime
+    pub fn validate(&self) -> Result<()> {
+        Pubkey::from_str(&self.solana.bridge_program_id).with_context(|| {
+            format!(
+                "Invalid bridge_program_id: {}",
+                self.solana.bridge_program_id
+            )
+        })?;
+        Pubkey::from_str(&self.solana.verifier_program_id).with_context(|| {
+            format!(
+                "Invalid verifier_program_id: {}",
+                self.solana.verifier_program_id
+            )
+        })?;
+        self.solana.domain().context("Invalid solana.domain")?;
+
+        // Real proofs are checked against the VK stored at [b"batch_vk", domain];
+        // without an explicit domain settlement would only fail on L1.
+        if self.pipeline.settlement_enabled
+            && self.pipeline.prover_mode != ProverModeToml::Mock
+            && self.solana.domain.is_none()
+        {
+            anyhow::bail!(
+                "solana.domain (or ZL_DOMAIN) must be set when settlement is enabled with the {:?} prover, \
+                 the verifier looks up the batch VK by domain",
+                self.pipeline.prover_mode
+            );
+        }
+
+        if self.pipeline.proving_backend == ProvingBackendToml::Coordinator
+            && self.pipeline.noir_coordinator_url.is_none()
+        {
+            anyhow::bail!("proving_backend = \"coordinator\" requires noir_coordinator_url");
+        }
+        if self.batch.max_transactions == 0 {
+            anyhow::bail!("batch.max_transactions must be greater than 0");
+        }
+        if self.batch.max_pending_per_account == 0 {
+            anyhow::bail!("batch.max_pending_per_account must be greater than 0");
+        }
+        if self.features.threshold_encryption
+            && (self.features.threshold_k == 0
+                || self.features.threshold_k > self.features.threshold_n)
+        {
+            anyhow::bail!(
+                "threshold_k must be between 1 and threshold_n (k={}, n={})",
+                self.features.threshold_k,
+                self.features.threshold_n
+            );
+        }
+        Ok(())
+    }
+
+    /// Read a config file, or deep-merge every `*.toml` in a directory
+    fn read_config_source(path: &std::path::Path) -> Result<toml::Value> {
+        if !path.is_dir() {
+            return Self::read_toml(path);
+        }
+
+        let mut files: Vec<PathBuf> = fs::read_dir(path)
+            .with_context(|| format!("Failed to read config directory: {}", path.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        files.sort();
+
+        let mut merged = toml::Value::Table(toml::map::Map::new());
+        for file in &files {
+            log::info!("Merging config file: {}", file.display());
+            merge_toml(&mut merged, Self::read_toml(file)?);
+        }
+        Ok(merged)
+    }
+
+    fn read_toml(path: &std::path::Path) -> Result<toml::Value> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+    }
+
     /// Find the config file path
     fn find_config_file() -> Option<PathBuf> {
         // 1. Check ZL_CONFIG env var
-        if let Ok(path) = env::var("ZL_CONFIG") {
+        if let Ok(path) = env::var(CONFIG_PATH_VAR.name) {
             let path = PathBuf::from(path);
             if path.exists() {
                 return Some(path);
@@ -383,84 +987,18 @@ impl ZelanaConfig {
 
     /// Apply environment variable overrides
     fn apply_env_overrides(&mut self) {
-        // Database
-        env_string("ZL_DB_PATH", &mut self.database.path);
-
-        // API
-        env_string("ZL_API_HOST", &mut self.api.sequencer);
-        env_parse_option("ZL_UDP_PORT", &mut self.api.udp_port);
-
-        // Solana
-        env_string("SOLANA_WS_URL", &mut self.solana.ws_url);
-        env_string("SOLANA_RPC_URL", &mut self.solana.rpc_url);
-        env_string("ZL_BRIDGE_PROGRAM", &mut self.solana.bridge_program_id);
-        env_string(
-            "ZL_VERIFIER_PROGRAM_ID",
-            &mut self.solana.verifier_program_id,
-        );
-        env_option_string("ZL_DOMAIN", &mut self.solana.domain);
-
-        // Pipeline - prover mode
-        if let Ok(v) = env::var("ZL_PROVER_MODE") {
-            self.pipeline.prover_mode = match v.to_ascii_lowercase().as_str() {
-                "groth16" => ProverModeToml::Groth16,
-                "noir" => ProverModeToml::Noir,
-                _ => ProverModeToml::Mock,
-            };
-        }
-
-        // Legacy: ZL_MOCK_PROVER
-        if let Some(enabled) = env_bool("ZL_MOCK_PROVER") {
-            self.pipeline.prover_mode = if enabled {
-                ProverModeToml::Mock
-            } else {
-                ProverModeToml::Groth16
-            };
-        }
-
-        if let Some(v) = env_bool("ZL_SETTLEMENT_ENABLED") {
-            self.pipeline.settlement_enabled = v;
+        for var in ENV_OVERRIDES {
+            if let Ok(value) = env::var(var.doc.name) {
+                (var.apply)(self, &value);
+            }
         }
+    }
 
-        env_option_string("ZL_PROVING_KEY", &mut self.pipeline.proving_key_path);
-        env_option_string("ZL_VERIFYING_KEY", &mut self.pipeline.verifying_key_path);
-        env_option_string(
-            "ZL_NOIR_COORDINATOR_URL",
-            &mut self.pipeline.noir_coordinator_url,
-        );
-        env_parse_option(
-            "ZL_NOIR_PROOF_TIMEOUT_SECS",
-            &mut self.pipeline.noir_proof_timeout_secs,
-        );
-        env_option_string(
-            "ZL_SEQUENCER_KEYPAIR",
-            &mut self.pipeline.sequencer_keypair_path,
-        );
-        env_parse(
-            "ZL_SETTLEMENT_RETRIES",
-            &mut self.pipeline.max_settlement_retries,
-        );
-
-        // Batch
-        env_parse("BATCH_MAX_TXS", &mut self.batch.max_transactions);
-        env_parse("BATCH_MAX_AGE", &mut self.batch.max_batch_age_secs);
-        env_parse("BATCH_MAX_SHIELDED", &mut self.batch.max_shielded);
-
-        // Features
-        if let Some(v) = env_bool("DEV_MODE") {
-            self.features.dev_mode = v;
-        }
-        if env_exists("FAST_WITHDRAW_ENABLED") {
-            self.features.fast_withdrawals = true;
-        }
-        if env_exists("THRESHOLD_ENABLED") {
-            self.features.threshold_encryption = true;
-        }
-        env_parse("THRESHOLD_K", &mut self.features.threshold_k);
-        env_parse("THRESHOLD_N", &mut self.features.threshold_n);
-        if env_exists("THRESHOLD_DEV") {
-            self.features.threshold_dev = true;
-        }
+    /// Every environment variable the loader reads, in override order
+    pub fn env_var_docs() -> Vec<EnvVarDoc> {
+        std::iter::once(CONFIG_PATH_VAR)
+            .chain(ENV_OVERRIDES.iter().map(|var| var.doc))
+            .collect()
     }
 
     /// Get the default config file path
@@ -476,6 +1014,53 @@ impl ZelanaConfig {
         toml::to_string_pretty(&sample).unwrap_or_default()
     }
 
+    /// JSON Schema for the TOML layout (fields, types, defaults, constraints).
+    ///
+    /// Point `taplo` or an editor's TOML plugin at it for validation and
+    /// completion. Checks that span several fields stay in [`Self::validate`].
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(ZelanaConfig))
+            .expect("schema serializes to JSON")
+    }
+
+    /// Fields that differ in `other`, each classified as live-applicable or
+    /// restart-only, for reloading a running service's config
+    pub fn diff(&self, other: &ZelanaConfig) -> ConfigDiff {
+        let flatten = |config: &ZelanaConfig| {
+            let mut fields = std::collections::BTreeMap::new();
+            let value = serde_json::to_value(config).expect("config serializes to JSON");
+            flatten_json("", value, &mut fields);
+            fields
+        };
+        let before = flatten(self);
+        let after = flatten(other);
+
+        let fields: std::collections::BTreeSet<&String> =
+            before.keys().chain(after.keys()).collect();
+        let changes = fields
+            .into_iter()
+            .filter(|field| before.get(*field) != after.get(*field))
+            .map(|field| FieldChange {
+                field: field.clone(),
+                kind: if HOT_APPLICABLE_FIELDS.contains(&field.as_str()) {
+                    ChangeKind::HotApplicable
+                } else {
+                    ChangeKind::RequiresRestart
+                },
+            })
+            .collect();
+        ConfigDiff { changes }
+    }
+
+    /// Take the live-applicable fields from `other`, keeping the restart-only
+    /// ones: the config a running service has after reloading `other`
+    pub fn apply_hot(&mut self, other: &ZelanaConfig) {
+        self.pipeline.poll_interval_ms = other.pipeline.poll_interval_ms;
+        self.pipeline.max_settlement_retries = other.pipeline.max_settlement_retries;
+        self.pipeline.settlement_retry_base_ms = other.pipeline.settlement_retry_base_ms;
+        self.batch = other.batch.clone();
+    }
+
     /// Get the global config instance, initializing it if necessary.
     ///
     /// This is the recommended way to access config in most code.
@@ -514,8 +1099,6 @@ pub fn global_config() -> &'static ZelanaConfig {
 // Parsed Config (lazy-initialized constants)
 // ============================================================================
 
-use solana_sdk::pubkey::Pubkey;
-use std::str::FromStr;
 use std::sync::LazyLock;
 
 /// Pre-parsed Solana configuration - access fields directly like a constant.
@@ -568,6 +1151,7 @@ pub static API: LazyLock<ApiRuntime> = LazyLock::new(|| {
         sequencer_url: &cfg.api.sequencer,
         port: &cfg.api.port,
         udp_port: cfg.api.udp_port,
+        health_port: cfg.api.health_port,
     }
 });
 
@@ -578,6 +1162,8 @@ pub struct ApiRuntime<'a> {
     pub port: &'a u16,
     /// UDP port (if configured)
     pub udp_port: Option<u16>,
+    /// Health check port (if configured)
+    pub health_port: Option<u16>,
 }
 
 /// Database configuration constant.
@@ -585,11 +1171,13 @@ pub static DATABASE: LazyLock<DatabaseRuntime> = LazyLock::new(|| {
     let cfg = ZelanaConfig::global();
     DatabaseRuntime {
         path: &cfg.database.path,
+        tx_retention_batches: cfg.database.tx_retention_batches,
     }
 });
 
 pub struct DatabaseRuntime {
     pub path: &'static str,
+    pub tx_retention_batches: Option<u64>,
 }
 
 /// Pipeline configuration constant.
@@ -597,6 +1185,7 @@ pub static PIPELINE: LazyLock<PipelineRuntime> = LazyLock::new(|| {
     let cfg = ZelanaConfig::global();
     PipelineRuntime {
         prover_mode: cfg.pipeline.prover_mode.clone(),
+        proving_backend: cfg.pipeline.proving_backend.clone(),
         settlement_enabled: cfg.pipeline.settlement_enabled,
         proving_key_path: cfg.pipeline.proving_key_path.as_deref(),
         verifying_key_path: cfg.pipeline.verifying_key_path.as_deref(),
@@ -611,6 +1200,7 @@ pub static PIPELINE: LazyLock<PipelineRuntime> = LazyLock::new(|| {
 
 pub struct PipelineRuntime {
     pub prover_mode: ProverModeToml,
+    pub proving_backend: ProvingBackendToml,
     pub settlement_enabled: bool,
     pub proving_key_path: Option<&'static str>,
     pub verifying_key_path: Option<&'static str>,
@@ -630,6 +1220,7 @@ pub static BATCH: LazyLock<BatchRuntime> = LazyLock::new(|| {
         max_batch_age_secs: cfg.batch.max_batch_age_secs,
         max_shielded: cfg.batch.max_shielded,
         min_transactions: cfg.batch.min_transactions,
+        max_pending_per_account: cfg.batch.max_pending_per_account,
     }
 });
 
@@ -638,6 +1229,7 @@ pub struct BatchRuntime {
     pub max_batch_age_secs: u64,
     pub max_shielded: usize,
     pub min_transactions: usize,
+    pub max_pending_per_account: usize,
 }
 
 /// Feature flags constant.
@@ -670,6 +1262,36 @@ pub struct FeaturesRuntime {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_diff_classifies_changes() {
+        let current = ZelanaConfig::default();
+        assert!(current.diff(&current.clone()).is_empty());
+
+        let mut updated = current.clone();
+        updated.pipeline.poll_interval_ms = 250;
+        updated.batch.max_transactions = 500;
+        updated.database.path = "/var/lib/zelana".into();
+        updated.api.udp_port = Some(9000);
+        updated.solana.domain = Some("devnet".into());
+
+        let diff = current.diff(&updated);
+        assert_eq!(
+            diff.hot_applicable().collect::<Vec<_>>(),
+            ["batch.max_transactions", "pipeline.poll_interval_ms"]
+        );
+        assert_eq!(
+            diff.requires_restart().collect::<Vec<_>>(),
+            ["api.udp_port", "database.path", "solana.domain"]
+        );
+
+        // After a reload only the restart-only changes are still pending
+        let mut running = current.clone();
+        running.apply_hot(&updated);
+        let pending = running.diff(&updated);
+        assert_eq!(pending.hot_applicable().count(), 0);
+        assert_eq!(pending.requires_restart().count(), 3);
+    }
+
     #[test]
     fn test_default_config() {
         let config = ZelanaConfig::default();
@@ -677,6 +1299,51 @@ mod tests {
         assert_eq!(config.database.path, DEFAULT_DB_PATH);
         assert_eq!(config.pipeline.prover_mode, ProverModeToml::Mock);
         assert!(!config.pipeline.settlement_enabled);
+        assert_eq!(
+            config.pipeline.proving_backend,
+            ProvingBackendToml::Integrated
+        );
+    }
+
+    #[test]
+    fn test_json_schema_validates_sample() {
+        let schema = ZelanaConfig::json_schema();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        let to_json = |toml_str: &str| {
+            let value: toml::Value = toml::from_str(toml_str).unwrap();
+            serde_json::to_value(value).unwrap()
+        };
+
+        let sample = to_json(&ZelanaConfig::generate_sample());
+        let errors: Vec<String> = validator
+            .iter_errors(&sample)
+            .map(|e| e.to_string())
+            .collect();
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        assert!(!validator.is_valid(&to_json("[batch]\nmax_transactions = 0")));
+        assert!(!validator.is_valid(&to_json("[batch]\nmax_pending_per_account = 0")));
+        assert!(!validator.is_valid(&to_json("[pipeline]\nprover_mode = \"sp1\"")));
+        assert_eq!(
+            schema["definitions"]["ApiConfig"]["properties"]["port"]["default"],
+            DEFAULT_PORT
+        );
+    }
+
+    #[test]
+    fn test_parse_proving_backend() {
+        let parsed: ZelanaConfig = toml::from_str(
+            r#"
+            [pipeline]
+            proving_backend = "coordinator"
+            noir_coordinator_url = "http://127.0.0.1:8080"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.pipeline.proving_backend,
+            ProvingBackendToml::Coordinator
+        );
     }
 
     #[test]
@@ -696,6 +1363,118 @@ mod tests {
         assert!(parsed.features.dev_mode);
     }
 
+    #[test]
+    fn test_directory_config_merges_in_order() {
+        let dir = env::temp_dir().join(format!("zelana-config-dir-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("00-base.toml"),
+            "[api]\nport = 9000\nudp_port = 9001\n\n[database]\npath = \"/data/base\"\n",
+        )
+        .unwrap();
+        fs::write(dir.join("10-api.toml"), "[api]\nport = 9100\n").unwrap();
+        fs::write(
+            dir.join("20-features.toml"),
+            "[features]\ndev_mode = true\n\n[database]\npath = \"/data/override\"\n",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "[api]\nport = 1\n").unwrap();
+
+        let config: ZelanaConfig = ZelanaConfig::read_config_source(&dir)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        // Later files override earlier ones key by key
+        assert_eq!(config.api.port, 9100);
+        assert_eq!(config.api.udp_port, Some(9001));
+        assert_eq!(config.database.path, "/data/override");
+        assert!(config.features.dev_mode);
+        // Unset sections keep their defaults
+        assert_eq!(config.solana.rpc_url, DEFAULT_RPC_URL);
+    }
+
+    #[test]
+    fn test_domain_matches_vk_pda_seed() {
+        let domain = Domain::from_str("zelana-mainnet").unwrap();
+
+        let mut padded = [0u8; 32];
+        padded[..14].copy_from_slice(b"zelana-mainnet");
+        assert_eq!(domain.as_bytes(), padded);
+        assert_eq!(domain.to_string(), "zelana-mainnet");
+
+        let verifier = Pubkey::from_str(DEFAULT_VERIFIER_PROGRAM).unwrap();
+        let (from_domain, _) =
+            Pubkey::find_program_address(&[b"batch_vk", &domain.as_bytes()], &verifier);
+        let (from_padded, _) = Pubkey::find_program_address(&[b"batch_vk", &padded], &verifier);
+        assert_eq!(from_domain, from_padded);
+
+        assert!(Domain::from_str("").is_err());
+        assert!(Domain::from_str(&"x".repeat(33)).is_err());
+        assert_eq!(
+            SolanaConfig::default().domain().unwrap(),
+            Domain::from_str("solana").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_env_only() {
+        let vars = [
+            ("ZL_DB_PATH", "/var/lib/zelana"),
+            ("ZL_TX_RETENTION_BATCHES", "1000"),
+            ("ZL_UDP_PORT", "9400"),
+            ("ZL_CHAIN_ID", "2"),
+            ("ZL_DOMAIN", "zelana-k8s"),
+            ("ZL_PROVING_BACKEND", "coordinator"),
+            ("ZL_NOIR_COORDINATOR_URL", "http://prover-coordinator:8080"),
+            ("BATCH_MAX_TXS", "250"),
+        ];
+        for (key, value) in vars {
+            env::set_var(key, value);
+        }
+        let config = ZelanaConfig::from_env_only();
+        for (key, _) in vars {
+            env::remove_var(key);
+        }
+
+        let config = config.unwrap();
+        assert_eq!(config.database.path, "/var/lib/zelana");
+        assert_eq!(config.database.tx_retention_batches, Some(1000));
+        assert_eq!(config.api.udp_port, Some(9400));
+        assert_eq!(config.api.chain_id, 2);
+        assert_eq!(config.solana.domain.as_deref(), Some("zelana-k8s"));
+        assert_eq!(
+            config.pipeline.proving_backend,
+            ProvingBackendToml::Coordinator
+        );
+        assert_eq!(
+            config.pipeline.noir_coordinator_url.as_deref(),
+            Some("http://prover-coordinator:8080")
+        );
+        assert_eq!(config.batch.max_transactions, 250);
+        // Untouched fields keep their defaults
+        assert_eq!(config.solana.rpc_url, DEFAULT_RPC_URL);
+    }
+
+    #[test]
+    fn test_settlement_requires_domain() {
+        let mut config = ZelanaConfig::default();
+        config.pipeline.settlement_enabled = true;
+        config.pipeline.prover_mode = ProverModeToml::Noir;
+
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("solana.domain"));
+
+        config.solana.domain = Some("zelana-devnet".into());
+        config.validate().unwrap();
+
+        // Mock proofs never hit the verifier, so no domain is needed
+        config.solana.domain = None;
+        config.pipeline.prover_mode = ProverModeToml::Mock;
+        config.validate().unwrap();
+    }
+
     #[test]
     fn test_constants_match_defaults() {
         let config = ZelanaConfig::default();
@@ -6,7 +6,7 @@ use zephyr::client::ZelanaClient;
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let wallet = Keypair::new_random();
-    let mut client = ZelanaClient::connect("127.0.0.1:9000").await?;
+    let mut client = ZelanaClient::connect("127.0.0.1:9000", 1).await?;
 
     // Prepare a tx
     let tx = wallet.sign_transaction(TransactionData {
@@ -15,6 +15,7 @@ async fn main() -> anyhow::Result<()> {
         amount: 1,
         nonce: 0, // In bench we ignore nonce ordering for raw speed
         chain_id: 1,
+        valid_until_batch: None,
     });
 
     let count = 10_000;
@@ -43,6 +43,8 @@ async fn main() -> anyhow::Result<()> {
         let init_params = InitParams {
             sequencer_authority: sequencer.pubkey().to_bytes(),
             domain: domain_padded,
+            min_deposit: 1_000_000,
+            max_deposit: 1_000_000_000_000,
         };
 
         let mut init_data = vec![0];
@@ -93,7 +95,7 @@ async fn main() -> anyhow::Result<()> {
         program_id,
         accounts: vec![
             AccountMeta::new(payer.pubkey(), true),
-            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new(config_pda, false),
             AccountMeta::new(vault_pda, false),
             AccountMeta::new(receipt_pda, false),
             AccountMeta::new_readonly(system_id, false),
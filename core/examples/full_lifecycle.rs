@@ -77,7 +77,7 @@ async fn main() -> anyhow::Result<()> {
         program_id,
         accounts: vec![
             AccountMeta::new(user.pubkey(), true),
-            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new(config_pda, false),
             AccountMeta::new(vault_pda, false),
             AccountMeta::new(receipt_pda, false),
             AccountMeta::new_readonly(system_id, false),
@@ -101,7 +101,7 @@ async fn main() -> anyhow::Result<()> {
 
     // 5. Connect to L2
     println!(" Connecting to Zelana L2...");
-    let mut client = ZelanaClient::connect(sequencer_url).await?;
+    let mut client = ZelanaClient::connect(sequencer_url, 1).await?;
 
     // 6. Send L2 Transfer
     println!(" Sending L2 Transfer...");
@@ -112,6 +112,7 @@ async fn main() -> anyhow::Result<()> {
         amount: 50,
         nonce: 0,
         chain_id: 1,
+        valid_until_batch: None,
     };
 
     let msg = wincode::serialize(&tx_data)?;
@@ -48,7 +48,7 @@ async fn main() -> anyhow::Result<()> {
     deposit_to_l2(&rpc, &program_id, &user1_solkey, 1_300_000_000, 101).await?;
     deposit_to_l2(&rpc, &program_id, &user2_solkey, 1_300_000_000, 102).await?;
 
-    let mut client = ZelanaClient::connect("127.0.0.1:9000").await?;
+    let mut client = ZelanaClient::connect("127.0.0.1:9000", 1).await?;
 
     let signed_tx = user1.sign_transaction(TransactionData {
         from: user1_id,
@@ -56,6 +56,7 @@ async fn main() -> anyhow::Result<()> {
         amount: 10,
         nonce: 0,
         chain_id: 1,
+        valid_until_batch: None,
     });
 
     client.send_transaction(signed_tx).await?;
@@ -97,7 +98,7 @@ async fn deposit_to_l2(
         program_id: *program_id,
         accounts: vec![
             AccountMeta::new(user_solkey.pubkey(), true),
-            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new(config_pda, false),
             AccountMeta::new(vault_pda, false),
             AccountMeta::new(receipt_pda, false),
             AccountMeta::new_readonly(system_id, false),
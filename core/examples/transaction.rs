@@ -34,6 +34,7 @@ async fn main() -> anyhow::Result<()> {
             amount: 10,
             nonce,
             chain_id: 1,
+            valid_until_batch: None,
         };
 
         let signed_tx = wallet.sign_transaction(tx_data);
@@ -69,7 +70,7 @@ async fn main() -> anyhow::Result<()> {
     // println!("CLIENT: Identity: {}", my_id.to_hex());
 
     // println!("CLIENT: Connecting...");
-    // let mut client = ZelanaClient::connect("127.0.0.1:9000").await?;
+    // let mut client = ZelanaClient::connect("127.0.0.1:9000", 1).await?;
     // println!("CLIENT: Connected!");
 
     // // 2. Send Txs starting from Nonce 0
@@ -13,9 +13,10 @@ use log::{error, info, warn};
 use tokio::sync::Mutex;
 
 use super::types::*;
+use crate::sequencer::pipeline::PipelineState;
 use crate::sequencer::{
-    FastWithdrawManager, PipelineService, RocksDbStore, ShieldedState, ThresholdMempoolManager,
-    WithdrawalQueue, WithdrawalState,
+    FastWithdrawManager, PipelineService, RocksDbStore, ShieldedState, SubmitError,
+    ThresholdMempoolManager, WithdrawalQueue, WithdrawalState,
 };
 use crate::storage::StateStore;
 use zelana_account::AccountId;
@@ -35,6 +36,8 @@ pub struct ApiState {
     pub start_time: std::time::Instant,
     /// Dev mode enables testing endpoints like /dev/deposit and /dev/seal
     pub dev_mode: bool,
+    /// Solana RPC URL used to compute indexer slot lag (None disables the check)
+    pub l1_rpc_url: Option<String>,
 }
 
 // Health & Status
@@ -50,6 +53,80 @@ pub async fn health(State(state): State<ApiState>) -> impl IntoResponse {
     })
 }
 
+/// Aggregated sequencer health: DB, indexer, mempool and prover backend.
+///
+/// Returns 503 when the DB is unreachable or the pipeline is not running so
+/// load balancers can take the node out of rotation.
+pub async fn sequencer_health(State(state): State<ApiState>) -> impl IntoResponse {
+    let uptime = state.start_time.elapsed().as_secs();
+
+    let (db_open, indexer_last_slot) = match state.db.get_last_processed_slot() {
+        Ok(slot) => (true, slot),
+        Err(e) => {
+            warn!("Health check: DB read failed: {}", e);
+            (false, None)
+        }
+    };
+
+    let indexer_slot_lag = match (&state.l1_rpc_url, indexer_last_slot) {
+        (Some(url), Some(last_slot)) => {
+            use solana_client::nonblocking::rpc_client::RpcClient;
+            let rpc = RpcClient::new_with_timeout(url.clone(), std::time::Duration::from_secs(2));
+            match rpc.get_slot().await {
+                Ok(current) => Some(current.saturating_sub(last_slot)),
+                Err(e) => {
+                    warn!("Health check: failed to fetch L1 slot: {}", e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
+    let encrypted_mempool_depth = match &state.threshold_mempool {
+        Some(mempool) => mempool.pending_count().await,
+        None => 0,
+    };
+
+    let (mempool_depth, prover_backend, prover, pipeline_running) =
+        match state.pipeline_service.stats().await {
+            Ok(stats) => (
+                stats.batch_stats.current_batch_txs,
+                format!("{:?}", stats.proving_backend).to_lowercase(),
+                stats.prover_name.to_string(),
+                stats.state == PipelineState::Running,
+            ),
+            Err(e) => {
+                warn!("Health check: pipeline unavailable: {}", e);
+                (0, "unknown".to_string(), "unknown".to_string(), false)
+            }
+        };
+
+    let healthy = db_open && pipeline_running;
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(SequencerHealthResponse {
+            healthy,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime_secs: uptime,
+            db_open,
+            indexer_last_slot,
+            indexer_slot_lag,
+            mempool_depth,
+            encrypted_mempool_depth,
+            prover_backend,
+            prover,
+            pipeline_running,
+        }),
+    )
+}
+
 /// Get current state roots
 pub async fn get_state_roots(State(state): State<ApiState>) -> impl IntoResponse {
     let shielded = state.shielded_state.lock().await;
@@ -173,6 +250,14 @@ pub async fn get_account(
 
 // Transfer Operations
 
+/// Status for a rejected submission: 429 when the sender is over its pending cap
+fn rejection_status(e: &anyhow::Error) -> StatusCode {
+    match e.downcast_ref::<SubmitError>() {
+        Some(SubmitError::TooManyPending { .. }) => StatusCode::TOO_MANY_REQUESTS,
+        Some(SubmitError::Expired(_)) | None => StatusCode::BAD_REQUEST,
+    }
+}
+
 /// Submit a transparent transfer transaction
 pub async fn submit_transfer(
     State(state): State<ApiState>,
@@ -188,6 +273,7 @@ pub async fn submit_transfer(
         amount: req.amount,
         nonce: req.nonce,
         chain_id: req.chain_id,
+        valid_until_batch: req.valid_until_batch,
     };
 
     let signed_tx = SignedTransaction {
@@ -198,7 +284,7 @@ pub async fn submit_transfer(
 
     let tx = TransactionType::Transfer(signed_tx);
 
-    // Compute tx hash (matches TxRouter::compute_tx_hash)
+    // Compute tx hash (matches tx_router::tx_hash)
     let tx_hash = {
         let mut hasher = blake3::Hasher::new();
         hasher.update(&req.signer_pubkey);
@@ -226,7 +312,7 @@ pub async fn submit_transfer(
         Err(e) => {
             warn!("Transfer rejected: {}", e);
             (
-                StatusCode::BAD_REQUEST,
+                rejection_status(&e),
                 Json(super::types::TransferResponse {
                     tx_hash: hex::encode(tx_hash),
                     accepted: false,
@@ -537,7 +623,7 @@ pub async fn scan_notes(
             // commitment = MiMC_hash3(owner_pk, value, blinding)
             let computed_commitment = zelana_ownership_prover::compute_commitment_bytes(
                 &req.owner_pk,
-                note.value.0,
+                note.value.as_u64(),
                 &note.randomness,
             );
 
@@ -557,7 +643,7 @@ pub async fn scan_notes(
             scanned_notes.push(ScannedNote {
                 position,
                 commitment: hex::encode(commitment),
-                value: note.value.0,
+                value: note.value.as_u64(),
                 blinding: hex::encode(note.randomness),
                 memo: memo_str,
             });
@@ -627,7 +713,7 @@ pub async fn submit_withdrawal(
         Err(e) => {
             warn!("Withdrawal rejected: {}", e);
             (
-                StatusCode::BAD_REQUEST,
+                rejection_status(&e),
                 Json(WithdrawResponse {
                     tx_hash: hex::encode(tx_hash),
                     accepted: false,
@@ -659,6 +745,9 @@ pub async fn get_withdrawal_status(
     let mut arr = [0u8; 32];
     arr.copy_from_slice(&tx_hash_bytes);
 
+    // L1 progress recorded by the bridge indexer, reported alongside L2 state
+    let l1 = state.db.get_l1_withdrawal(&arr).ok().flatten();
+
     // First check in-memory queue
     let queue = state.withdrawal_queue.lock().await;
 
@@ -683,6 +772,7 @@ pub async fn get_withdrawal_status(
             amount: withdrawal.amount,
             to_l1_address: hex::encode(withdrawal.to_l1_address),
             l1_tx_sig: l1_sig,
+            l1,
         })
         .into_response();
     }
@@ -698,11 +788,30 @@ pub async fn get_withdrawal_status(
                 amount: pw.amount,
                 to_l1_address: hex::encode(pw.to_l1_address),
                 l1_tx_sig: None,
+                l1,
             })
             .into_response();
         }
     }
 
+    // Seen only on L1, e.g. a withdrawal from before this node's state
+    if let Some(record) = l1 {
+        let state_str = if record.finalized_slot.is_some() {
+            "finalized"
+        } else {
+            "submitted"
+        };
+        return Json(WithdrawalStatusResponse {
+            tx_hash: req.tx_hash,
+            state: state_str.to_string(),
+            amount: record.amount,
+            to_l1_address: record.recipient.clone(),
+            l1_tx_sig: None,
+            l1: Some(record),
+        })
+        .into_response();
+    }
+
     (
         StatusCode::NOT_FOUND,
         Json(ErrorResponse::not_found("Withdrawal not found")),
@@ -1247,8 +1356,13 @@ pub async fn dev_deposit(
                 hex::encode(tx_hash)
             );
 
-            // Track dev deposit amount
-            if let Err(e) = state.db.add_dev_deposit(req.amount) {
+            // Track dev deposit amount (dry runs leave the database untouched)
+            let tracked = if state.pipeline_service.is_dry_run() {
+                Ok(())
+            } else {
+                state.db.add_dev_deposit(req.amount)
+            };
+            if let Err(e) = tracked {
                 warn!("[DEV] Failed to track deposit amount: {}", e);
             }
 
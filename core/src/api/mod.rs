@@ -6,6 +6,7 @@
 pub mod handlers;
 pub mod routes;
 pub mod types;
+pub mod udp_capture;
 pub mod udp_server;
 
 // Re-export UDP server types (used by main.rs)
@@ -63,3 +63,70 @@ pub fn create_router(state: ApiState) -> Router {
         .layer(CorsLayer::permissive())
         .with_state(state)
 }
+
+/// Create the standalone health router (served on `api.health_port`)
+pub fn create_health_router(state: ApiState) -> Router {
+    Router::new()
+        .route("/health", get(handlers::sequencer_health))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use tokio::net::TcpListener;
+    use tokio::sync::Mutex;
+
+    use crate::sequencer::{
+        PipelineConfig, PipelineService, RocksDbStore, ShieldedState, WithdrawalQueue,
+    };
+
+    #[tokio::test]
+    async fn test_health_endpoint_reports_components() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Arc::new(RocksDbStore::open(temp_dir.path()).unwrap());
+        db.set_last_processed_slot(42).unwrap();
+
+        let pipeline_service =
+            Arc::new(PipelineService::start(db.clone(), PipelineConfig::default(), None).unwrap());
+
+        let state = ApiState {
+            db: db.clone(),
+            pipeline_service: pipeline_service.clone(),
+            shielded_state: Arc::new(Mutex::new(ShieldedState::new())),
+            withdrawal_queue: Arc::new(Mutex::new(WithdrawalQueue::new(db.clone()))),
+            fast_withdraw: None,
+            threshold_mempool: None,
+            start_time: std::time::Instant::now(),
+            dev_mode: false,
+            l1_rpc_url: None,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, create_health_router(state))
+                .await
+                .unwrap();
+        });
+
+        let resp = reqwest::get(format!("http://{}/health", addr))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::OK);
+
+        let body: serde_json::Value = resp.json().await.unwrap();
+        assert_eq!(body["healthy"], true);
+        assert_eq!(body["db_open"], true);
+        assert_eq!(body["indexer_last_slot"], 42);
+        assert!(body["indexer_slot_lag"].is_null());
+        assert_eq!(body["mempool_depth"], 0);
+        assert_eq!(body["prover_backend"], "integrated");
+        assert_eq!(body["prover"], "mock");
+        assert_eq!(body["pipeline_running"], true);
+
+        pipeline_service.shutdown().await.unwrap();
+    }
+}
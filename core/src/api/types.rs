@@ -169,6 +169,9 @@ pub struct TransferRequest {
     pub nonce: u64,
     /// Chain ID (for replay protection across networks)
     pub chain_id: u64,
+    /// Last batch the transfer may execute in (signed; omit for no expiry)
+    #[serde(default)]
+    pub valid_until_batch: Option<u64>,
     /// Ed25519 signature over the serialized TransactionData
     pub signature: Vec<u8>,
     /// Signer's public key (must match 'from')
@@ -225,6 +228,38 @@ pub struct WithdrawalStatusResponse {
     pub amount: u64,
     pub to_l1_address: String,
     pub l1_tx_sig: Option<String>,
+    /// Settlement and payout seen on L1 by the bridge indexer
+    pub l1: Option<L1WithdrawalRecord>,
+}
+
+/// Which batch a withdrawal was included in, with the withdrawal root that
+/// batch's proof committed to and the withdrawal's path to it, so it can be
+/// claimed with `FinalizeWithdrawal` against the root settled on L1
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WithdrawalIndexEntry {
+    /// Batch the withdrawal was included in
+    pub batch_id: u64,
+    /// Withdrawal root committed to the batch proof's public inputs (hex)
+    pub withdrawal_root: String,
+    /// Leaf position in the batch's withdrawal tree
+    pub leaf_position: u64,
+    /// Sibling path from the leaf to `withdrawal_root`, leaf level first (hex)
+    pub merkle_path: Vec<String>,
+}
+
+/// A withdrawal's progress on L1, from the bridge's `WithdrawalInitiated`
+/// (emitted by `SubmitBatch`) and `Withdraw` (emitted on payout) events
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L1WithdrawalRecord {
+    /// Batch whose settlement made the withdrawal payable
+    pub batch_id: Option<u64>,
+    /// L1 recipient (hex)
+    pub recipient: String,
+    pub amount: u64,
+    /// Slot of the settling `SubmitBatch`
+    pub initiated_slot: Option<u64>,
+    /// Slot of the payout
+    pub finalized_slot: Option<u64>,
 }
 
 // State Queries
@@ -255,6 +290,30 @@ pub struct HealthResponse {
     pub uptime_secs: u64,
 }
 
+/// Aggregated sequencer health (for load balancer checks)
+#[derive(Debug, Serialize)]
+pub struct SequencerHealthResponse {
+    pub healthy: bool,
+    pub version: String,
+    pub uptime_secs: u64,
+    /// Whether RocksDB answered a read
+    pub db_open: bool,
+    /// Last L1 slot processed by the deposit indexer
+    pub indexer_last_slot: Option<u64>,
+    /// Current L1 slot minus `indexer_last_slot` (None if L1 is unreachable)
+    pub indexer_slot_lag: Option<u64>,
+    /// Transactions waiting in the current batch
+    pub mempool_depth: usize,
+    /// Encrypted transactions waiting for decryption
+    pub encrypted_mempool_depth: usize,
+    /// Resolved proving backend ("integrated" or "coordinator")
+    pub prover_backend: String,
+    /// Active prover client (e.g. "mock", "groth16", "noir")
+    pub prover: String,
+    /// Whether the pipeline is running (not paused or stopping)
+    pub pipeline_running: bool,
+}
+
 // Fast Withdrawal Types
 
 /// Request for fast withdrawal quote
@@ -521,6 +580,9 @@ pub struct TxSummary {
     pub tx_type: TxType,
     /// Batch ID this transaction was included in
     pub batch_id: Option<u64>,
+    /// Position in the batch's canonical execution order
+    #[serde(default)]
+    pub batch_index: Option<u32>,
     /// Status of the transaction
     pub status: TxStatus,
     /// Unix timestamp when transaction was received
@@ -529,10 +591,16 @@ pub struct TxSummary {
     pub executed_at: Option<u64>,
     /// Amount (for transfers/withdrawals)
     pub amount: Option<u64>,
-    /// From account (for transparent txs)
+    /// From account (for transparent txs, or the SHIELD source)
     pub from: Option<String>,
-    /// To account/address
+    /// To account/address (or the UNSHIELD recipient)
     pub to: Option<String>,
+    /// Lamports a shielded tx debited from `from`
+    #[serde(default)]
+    pub shield_amount: Option<u64>,
+    /// Lamports a shielded tx credited to `to`
+    #[serde(default)]
+    pub unshield_amount: Option<u64>,
 }
 
 /// Transaction type
@@ -7,25 +7,31 @@
 //! 1. ClientHello (1 byte kind + 32 byte X25519 pubkey)
 //! 2. ServerHello (1 byte kind + 32 byte X25519 pubkey)
 //! 3. AppData (1 byte kind + 12 byte nonce + ciphertext)
+//! 4. AppDataFrag (1 byte kind + 4 byte msg_id + 2 byte index + 2 byte count + payload),
+//!    reassembled per (peer, msg_id) into an AppData payload
 //!
 //! The server maintains per-client session state for encrypted communication.
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use log::{debug, error, info, warn};
 use tokio::net::UdpSocket;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use x25519_dalek::{PublicKey, StaticSecret};
 
 use zelana_transaction::TransactionType;
 use zephyr::keys::SessionKeys;
-use zephyr::packet::{KIND_SERVER_HELLO, Packet};
+use zephyr::packet::{KIND_SERVER_HELLO, NONCE_SIZE, Packet};
+use zephyr::reassembly::{Reassembler, ReassemblyConfig};
 
 use super::handlers::ApiState;
+use super::udp_capture::CaptureWriter;
 
 // Constants
 
@@ -80,6 +86,10 @@ pub struct UdpServerConfig {
     pub port: u16,
     /// Maximum concurrent sessions
     pub max_sessions: usize,
+    /// Deployment chain id, mixed into session key derivation
+    pub chain_id: u64,
+    /// Append every received datagram to this file (see `udp_capture`)
+    pub capture_path: Option<PathBuf>,
 }
 
 impl Default for UdpServerConfig {
@@ -87,6 +97,8 @@ impl Default for UdpServerConfig {
         Self {
             port: 8081,
             max_sessions: 10000,
+            chain_id: 1,
+            capture_path: None,
         }
     }
 }
@@ -100,6 +112,11 @@ pub struct ZephyrUdpServer {
     server_pubkey: PublicKey,
     /// Active sessions
     sessions: SessionStore,
+    /// Partially received fragmented AppData messages
+    reassembly: Mutex<Reassembler<SocketAddr>>,
+    /// ServerHello packets received from peers (clients never send one; a
+    /// steady stream suggests probing)
+    unexpected_server_hellos: AtomicU64,
     /// API state for transaction processing
     api_state: ApiState,
 }
@@ -115,6 +132,8 @@ impl ZephyrUdpServer {
             server_secret,
             server_pubkey,
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            reassembly: Mutex::new(Reassembler::new(ReassemblyConfig::default())),
+            unexpected_server_hellos: AtomicU64::new(0),
             api_state,
         }
     }
@@ -128,6 +147,14 @@ impl ZephyrUdpServer {
 
         info!("Zephyr UDP server listening on {}", addr);
 
+        let mut capture = match &self.config.capture_path {
+            Some(path) => {
+                info!("Capturing received datagrams to {}", path.display());
+                Some(CaptureWriter::open(path)?)
+            }
+            None => None,
+        };
+
         // Wrap self in Arc for sharing across tasks
         let server = Arc::new(self);
 
@@ -145,6 +172,13 @@ impl ZephyrUdpServer {
             match socket.recv_from(&mut buf).await {
                 Ok((len, src)) => {
                     let packet_data = buf[..len].to_vec();
+                    let captured = capture
+                        .as_mut()
+                        .map(|writer| writer.record(src, &packet_data));
+                    if let Some(Err(e)) = captured {
+                        warn!("Failed to capture datagram from {}: {}", src, e);
+                    }
+
                     let server_clone = server.clone();
                     let socket_clone = socket.local_addr().ok();
 
@@ -181,8 +215,24 @@ impl ZephyrUdpServer {
             Packet::AppData { nonce, ciphertext } => {
                 self.handle_app_data(src, nonce, ciphertext).await?;
             }
+            Packet::AppDataFrag {
+                msg_id,
+                frag_index,
+                frag_count,
+                payload,
+            } => {
+                self.handle_app_data_frag(src, msg_id, frag_index, frag_count, payload)
+                    .await?;
+            }
             Packet::ServerHello { .. } => {
-                warn!("Received ServerHello from client {} (unexpected)", src);
+                let total = self
+                    .unexpected_server_hellos
+                    .fetch_add(1, Ordering::Relaxed)
+                    + 1;
+                debug!(
+                    "Ignoring ServerHello from client {} (unexpected, {} total)",
+                    src, total
+                );
             }
         }
 
@@ -212,6 +262,7 @@ impl ZephyrUdpServer {
             shared_secret.to_bytes(),
             client_pubkey,
             &server_pubkey_bytes,
+            self.config.chain_id,
         );
 
         // Store session
@@ -261,13 +312,45 @@ impl ZephyrUdpServer {
         };
 
         // Deserialize transaction
-        let tx: TransactionType =
-            wincode::deserialize(&plaintext).context("Failed to deserialize transaction")?;
+        let tx =
+            TransactionType::decode(&plaintext).context("Failed to deserialize transaction")?;
 
         // Route transaction based on type
         self.process_transaction(tx, src).await
     }
 
+    /// Handle AppDataFrag - buffer until the message is complete, then process it
+    async fn handle_app_data_frag(
+        &self,
+        src: SocketAddr,
+        msg_id: u32,
+        frag_index: u16,
+        frag_count: u16,
+        payload: &[u8],
+    ) -> Result<()> {
+        // Only buffer for established sessions so strangers can't fill the buffer
+        if !self.sessions.read().await.contains_key(&src) {
+            anyhow::bail!("No session for {}", src);
+        }
+
+        let message = self.reassembly.lock().await.insert(
+            src,
+            msg_id,
+            frag_index,
+            frag_count,
+            payload,
+            Instant::now(),
+        )?;
+        let Some(message) = message else {
+            return Ok(());
+        };
+
+        let Some((nonce, ciphertext)) = message.split_first_chunk::<NONCE_SIZE>() else {
+            anyhow::bail!("Reassembled AppData from {} too short", src);
+        };
+        self.handle_app_data(src, nonce, ciphertext).await
+    }
+
     /// Process a decrypted transaction
     async fn process_transaction(&self, tx: TransactionType, src: SocketAddr) -> Result<()> {
         match tx {
@@ -355,9 +438,20 @@ impl ZephyrUdpServer {
             if removed > 0 {
                 debug!("Cleaned up {} expired sessions", removed);
             }
+            drop(sessions);
+
+            let evicted = self.reassembly.lock().await.evict_expired(Instant::now());
+            if evicted > 0 {
+                debug!("Dropped {} incomplete fragmented messages", evicted);
+            }
         }
     }
 
+    /// ServerHello packets received from peers since startup
+    pub fn unexpected_server_hello_count(&self) -> u64 {
+        self.unexpected_server_hellos.load(Ordering::Relaxed)
+    }
+
     /// Get current session count
     pub async fn session_count(&self) -> usize {
         self.sessions.read().await.len()
@@ -378,11 +472,48 @@ pub async fn start_udp_server(config: UdpServerConfig, api_state: ApiState) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
+
+    use crate::sequencer::{
+        PipelineConfig, PipelineService, RocksDbStore, ShieldedState, WithdrawalQueue,
+    };
 
     #[test]
     fn test_config_default() {
         let config = UdpServerConfig::default();
         assert_eq!(config.port, 8081);
         assert_eq!(config.max_sessions, 10000);
+        assert_eq!(config.chain_id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_server_hello_from_client_is_counted() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Arc::new(RocksDbStore::open(temp_dir.path()).unwrap());
+        let pipeline_service =
+            Arc::new(PipelineService::start(db.clone(), PipelineConfig::default(), None).unwrap());
+        let api_state = ApiState {
+            db: db.clone(),
+            pipeline_service: pipeline_service.clone(),
+            shielded_state: Arc::new(Mutex::new(ShieldedState::new())),
+            withdrawal_queue: Arc::new(Mutex::new(WithdrawalQueue::new(db.clone()))),
+            fast_withdraw: None,
+            threshold_mempool: None,
+            start_time: Instant::now(),
+            dev_mode: false,
+            l1_rpc_url: None,
+        };
+        let server = ZephyrUdpServer::new(UdpServerConfig::default(), api_state);
+        let src: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+
+        let mut packet = vec![KIND_SERVER_HELLO];
+        packet.extend_from_slice(&[7u8; 32]);
+        server.handle_packet(&packet, src, None).await.unwrap();
+        server.handle_packet(&packet, src, None).await.unwrap();
+
+        assert_eq!(server.unexpected_server_hello_count(), 2);
+        assert_eq!(server.session_count().await, 0);
+
+        pipeline_service.shutdown().await.unwrap();
     }
 }
@@ -0,0 +1,53 @@
+//! Zephyr UDP replay CLI
+//!
+//! Usage: udp-replay <capture-file> <target-addr> [--fast]
+//!
+//! Re-sends a capture recorded with the sequencer's `--udp-capture` flag,
+//! keeping the original inter-arrival timing unless `--fast` is given.
+
+use anyhow::{Context, Result, bail};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use zelana_core::api::udp_capture::{ReplayTiming, read_capture, replay};
+
+const USAGE: &str = "usage: udp-replay <capture-file> <target-addr> [--fast]";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut timing = ReplayTiming::Original;
+    let mut positional = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--fast" => timing = ReplayTiming::AsFastAsPossible,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let [capture, target] = positional.as_slice() else {
+        bail!(USAGE);
+    };
+    let capture = PathBuf::from(capture);
+    let target: SocketAddr = target
+        .parse()
+        .with_context(|| format!("Invalid target address '{}'", target))?;
+
+    let records = read_capture(&capture)?;
+    println!(
+        "Replaying {} datagrams from {} to {} ({:?})",
+        records.len(),
+        capture.display(),
+        target,
+        timing
+    );
+
+    let stats = replay(&records, target, timing).await?;
+    println!(
+        "Sent {} datagrams from {} peers",
+        stats.datagrams, stats.peers
+    );
+    Ok(())
+}
@@ -2,7 +2,11 @@
 
 pub use zelana_config::*;
 
-use crate::sequencer::{BatchConfig, PipelineConfig, ProverMode, SettlerConfig};
+use anyhow::{Context, Result};
+
+use crate::sequencer::{
+    BatchConfig, PipelineConfig, PipelineReload, ProverMode, ProvingBackend, SettlerConfig,
+};
 
 /// Convert ProverModeToml (config) to ProverMode (core)
 impl From<ProverModeToml> for ProverMode {
@@ -15,13 +19,26 @@ impl From<ProverModeToml> for ProverMode {
     }
 }
 
+/// Convert ProvingBackendToml (config) to ProvingBackend (core)
+impl From<ProvingBackendToml> for ProvingBackend {
+    fn from(backend: ProvingBackendToml) -> Self {
+        match backend {
+            ProvingBackendToml::Integrated => ProvingBackend::Integrated,
+            ProvingBackendToml::Coordinator => ProvingBackend::Coordinator,
+        }
+    }
+}
+
 /// Extension trait for ZelanaConfig - adds core-specific conversion methods
 pub trait ZelanaConfigExt {
     /// Convert to BatchConfig (core-specific type)
     fn to_batch_config(&self) -> BatchConfig;
 
     /// Convert to PipelineConfig (core-specific type)
-    fn to_pipeline_config(&self) -> PipelineConfig;
+    fn to_pipeline_config(&self) -> Result<PipelineConfig>;
+
+    /// The live-applicable settings, for reloading a running pipeline
+    fn to_pipeline_reload(&self) -> PipelineReload;
 }
 
 impl ZelanaConfigExt for ZelanaConfig {
@@ -31,19 +48,20 @@ impl ZelanaConfigExt for ZelanaConfig {
             max_batch_age_secs: self.batch.max_batch_age_secs,
             max_shielded: self.batch.max_shielded,
             min_transactions: self.batch.min_transactions,
+            max_pending_per_account: self.batch.max_pending_per_account,
         }
     }
 
-    fn to_pipeline_config(&self) -> PipelineConfig {
+    fn to_pipeline_config(&self) -> Result<PipelineConfig> {
         let batch_config = self.to_batch_config();
 
         // Build settler config if settlement is enabled
         let settler_config = if self.pipeline.settlement_enabled {
-            let mut domain = [0u8; 32];
-            let domain_str = self.solana.domain.as_deref().unwrap_or("solana");
-            let domain_bytes = domain_str.as_bytes();
-            domain[..domain_bytes.len().min(32)]
-                .copy_from_slice(&domain_bytes[..domain_bytes.len().min(32)]);
+            let domain = self
+                .solana
+                .domain()
+                .context("Invalid solana.domain")?
+                .as_bytes();
 
             Some(SettlerConfig {
                 rpc_url: self.solana.rpc_url.clone(),
@@ -58,8 +76,9 @@ impl ZelanaConfigExt for ZelanaConfig {
             None
         };
 
-        PipelineConfig {
+        Ok(PipelineConfig {
             prover_mode: self.pipeline.prover_mode.clone().into(),
+            proving_backend: self.pipeline.proving_backend.clone().into(),
             proving_key_path: self.pipeline.proving_key_path.clone(),
             verifying_key_path: self.pipeline.verifying_key_path.clone(),
             noir_coordinator_url: self.pipeline.noir_coordinator_url.clone(),
@@ -72,6 +91,18 @@ impl ZelanaConfigExt for ZelanaConfig {
             batch_config,
             settler_config,
             dev_mode: self.features.dev_mode,
+            tx_retention_batches: self.database.tx_retention_batches,
+            // Set from the sequencer's --dry-run flag
+            dry_run: false,
+        })
+    }
+
+    fn to_pipeline_reload(&self) -> PipelineReload {
+        PipelineReload {
+            poll_interval_ms: self.pipeline.poll_interval_ms,
+            max_settlement_retries: self.pipeline.max_settlement_retries,
+            settlement_retry_base_ms: self.pipeline.settlement_retry_base_ms,
+            batch_config: self.to_batch_config(),
         }
     }
 }
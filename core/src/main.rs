@@ -29,13 +29,14 @@
 use anyhow::Result;
 use log::info;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tokio::signal;
 use tokio::sync::Mutex;
 
 use crate::api::handlers::ApiState;
-use crate::api::routes::create_router;
+use crate::api::routes::{create_health_router, create_router};
 use crate::config::ZelanaConfig;
 use crate::config::ZelanaConfigExt;
 use crate::sequencer::{
@@ -55,9 +56,20 @@ async fn main() -> Result<()> {
 
     // Load configuration from ~/.zelana/config.toml + env vars
     let config = ZelanaConfig::load().expect("Failed to load configuration");
+    config.validate().expect("Invalid configuration");
+
+    // --dry-run validates submitted transactions without persisting anything
+    let dry_run = std::env::args().skip(1).any(|arg| arg == "--dry-run");
+
+    // --udp-capture <path> records received Zephyr datagrams for `udp-replay`
+    let udp_capture = std::env::args()
+        .skip_while(|arg| arg != "--udp-capture")
+        .nth(1)
+        .map(PathBuf::from);
 
     // Convert to pipeline config
-    let pipeline_config = config.to_pipeline_config();
+    let mut pipeline_config = config.to_pipeline_config()?;
+    pipeline_config.dry_run = dry_run;
     let batch_config = config.to_batch_config();
 
     info!("============================================");
@@ -84,6 +96,10 @@ async fn main() -> Result<()> {
     info!("Batch max shielded: {}", batch_config.max_shielded);
     info!("--------------------------------------------");
     info!("Prover mode       : {:?}", pipeline_config.prover_mode);
+    info!(
+        "Proving backend   : {:?}",
+        pipeline_config.resolved_backend()
+    );
     match &pipeline_config.prover_mode {
         crate::sequencer::ProverMode::Groth16 => {
             info!(
@@ -129,6 +145,7 @@ async fn main() -> Result<()> {
         pipeline_config.max_settlement_retries
     );
     info!("Dev mode          : {}", config.features.dev_mode);
+    info!("Dry run           : {}", dry_run);
     info!("============================================");
 
     // Open database
@@ -159,15 +176,19 @@ async fn main() -> Result<()> {
         PipelineService::start(db.clone(), pipeline_config.clone(), None)
             .expect("failed to start pipeline service"),
     );
-    info!(
-        "Pipeline service started (prover: {:?}, settlement: {})",
-        pipeline_config.prover_mode,
-        if pipeline_config.settlement_enabled {
-            "enabled"
-        } else {
-            "mock"
-        }
-    );
+    if dry_run {
+        info!("Pipeline service started in dry-run mode (validation only, no writes)");
+    } else {
+        info!(
+            "Pipeline service started (prover: {:?}, settlement: {})",
+            pipeline_config.prover_mode,
+            if pipeline_config.settlement_enabled {
+                "enabled"
+            } else {
+                "mock"
+            }
+        );
+    }
 
     // Initialize fast withdrawal service (optional)
     let fast_withdraw = if config.features.fast_withdrawals {
@@ -183,8 +204,9 @@ async fn main() -> Result<()> {
     // Initialize threshold encryption mempool (optional)
     let threshold_mempool = if config.features.threshold_encryption {
         use crate::sequencer::{
-            EncryptedMempoolConfig, ThresholdMempoolManager, create_test_committee,
+            CommitteeProvisioning, EncryptedMempoolConfig, ThresholdMempoolManager,
         };
+        use zelana_transaction::TransactionType;
 
         let threshold = config.features.threshold_k;
         let total = config.features.threshold_n;
@@ -198,22 +220,35 @@ async fn main() -> Result<()> {
 
         let manager = Arc::new(ThresholdMempoolManager::new(mempool_config));
 
-        // For development: create a test committee
-        // In production, this would be loaded from configuration or DKG
-        if config.features.threshold_dev {
-            let (committee, local_members) = create_test_committee(threshold, total);
-            manager.initialize_committee(committee).await;
-            // Set this node as the first committee member (for dev)
-            manager.set_local_member(local_members[0].clone()).await;
-            info!(
-                "Threshold mempool initialized (DEV mode): K={}, N={}",
-                threshold, total
-            );
-        } else {
-            info!(
-                "Threshold mempool enabled: K={}, N={} (committee not initialized)",
-                threshold, total
-            );
+        // threshold_dev swaps DKG for an insecure in-process dealer
+        let provisioning = manager.provision(config.features.threshold_dev).await;
+        info!(
+            "Threshold mempool enabled: K={}, N={} ({:?})",
+            threshold, total, provisioning
+        );
+
+        // A dealer node holds every key, so it stands in for the committee:
+        // drain the encrypted mempool in order and execute the plaintexts
+        if provisioning == CommitteeProvisioning::InsecureDealer {
+            let drain_manager = manager.clone();
+            let drain_pipeline = pipeline_service.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+                let mut drain_id = 0u64;
+                loop {
+                    interval.tick().await;
+                    drain_id += 1;
+                    for plaintext in drain_manager.dealer_drain(drain_id).await {
+                        let result = match TransactionType::decode(&plaintext) {
+                            Ok(tx) => drain_pipeline.submit(tx).await,
+                            Err(e) => Err(e.into()),
+                        };
+                        if let Err(e) = result {
+                            log::warn!("Decrypted tx rejected: {:#}", e);
+                        }
+                    }
+                }
+            });
         }
 
         Some(manager)
@@ -232,6 +267,7 @@ async fn main() -> Result<()> {
         threshold_mempool,
         start_time: std::time::Instant::now(),
         dev_mode: config.features.dev_mode,
+        l1_rpc_url: Some(config.solana.rpc_url.clone()),
     };
 
     // Create and start HTTP server
@@ -245,6 +281,18 @@ async fn main() -> Result<()> {
         axum::serve(listener, router).await.unwrap();
     });
 
+    // Spawn health check server if configured
+    if let Some(health_port) = config.api.health_port {
+        let health_router = create_health_router(api_state.clone());
+        let health_addr = SocketAddr::from(([0, 0, 0, 0], health_port));
+        let health_listener = TcpListener::bind(health_addr).await?;
+        info!("Health check listening on {}", health_addr);
+
+        tokio::spawn(async move {
+            axum::serve(health_listener, health_router).await.unwrap();
+        });
+    }
+
     // Spawn Zephyr UDP server if configured
     if let Some(udp_port) = config.api.udp_port {
         use crate::api::{UdpServerConfig, start_udp_server};
@@ -252,6 +300,8 @@ async fn main() -> Result<()> {
         let udp_config = UdpServerConfig {
             port: udp_port,
             max_sessions: 10000,
+            chain_id: config.api.chain_id,
+            capture_path: udp_capture,
         };
         let udp_api_state = api_state.clone();
 
@@ -261,8 +311,11 @@ async fn main() -> Result<()> {
         info!("Zephyr UDP server listening on 0.0.0.0:{}", udp_port);
     }
 
-    // Spawn Solana indexer for deposits (with pipeline integration)
-    {
+    // Spawn Solana indexer for deposits (with pipeline integration); it
+    // records processed slots directly, so dry runs go without it
+    if dry_run {
+        info!("Deposit indexer disabled (dry run)");
+    } else {
         let db_clone = db.clone();
         let pipeline_clone = pipeline_service.clone();
         let indexer_config = IndexerConfig {
@@ -276,15 +329,54 @@ async fn main() -> Result<()> {
         tokio::spawn(async move {
             start_indexer_with_pipeline(db_clone, indexer_config, pipeline_clone).await;
         });
+        info!("Deposit indexer started (finalized commitment, pipeline routing)");
     }
-    info!("Deposit indexer started (finalized commitment, pipeline routing)");
+
+    // Reload config on SIGHUP: live-applicable fields go to the running
+    // pipeline, restart-only changes are reported and left alone
+    let mut hangup = signal::unix::signal(signal::unix::SignalKind::hangup())?;
+    let reload_pipeline = pipeline_service.clone();
+    let mut running_config = config.clone();
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            let reloaded = match ZelanaConfig::load().and_then(|c| c.validate().map(|_| c)) {
+                Ok(reloaded) => reloaded,
+                Err(e) => {
+                    log::warn!("Config reload failed, keeping current config: {:#}", e);
+                    continue;
+                }
+            };
+
+            let diff = running_config.diff(&reloaded);
+            for field in diff.requires_restart() {
+                log::warn!("Config reload: {} changed, restart to apply it", field);
+            }
+            let hot: Vec<&str> = diff.hot_applicable().collect();
+            if hot.is_empty() {
+                continue;
+            }
+            match reload_pipeline.reload(reloaded.to_pipeline_reload()).await {
+                Ok(()) => {
+                    running_config.apply_hot(&reloaded);
+                    info!("Config reload applied: {}", hot.join(", "));
+                }
+                Err(e) => log::warn!("Config reload not applied: {:#}", e),
+            }
+        }
+    });
 
     info!("============================================");
     info!("  Zelana sequencer is ready!");
+    if dry_run {
+        info!("  DRY RUN: transactions are validated, never persisted");
+    }
     info!("  HTTP API: http://0.0.0.0:{}", config.api.port);
     if let Some(udp_port) = config.api.udp_port {
         info!("  UDP API:  udp://0.0.0.0:{}", udp_port);
     }
+    if let Some(health_port) = config.api.health_port {
+        info!("  Health:   http://0.0.0.0:{}/health", health_port);
+    }
     if config.features.dev_mode {
         info!("  Dev endpoints enabled:");
         info!("    POST /dev/deposit - Simulate L1 deposit");
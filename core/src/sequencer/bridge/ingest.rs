@@ -2,28 +2,35 @@
 //! Deposit Indexer
 //!
 //! Watches the Solana L1 for deposit events emitted by the bridge program
-//! and routes them through the pipeline for L2 processing.
+//! and routes them through the pipeline for L2 processing. Withdrawal events
+//! (`WithdrawalInitiated` from SubmitBatch, `Withdraw` on payout) are recorded
+//! per L2 tx hash so the withdrawal status API can report L1 progress.
 //!
 //! Features:
 //! - Uses finalized commitment for reliability
-//! - Deduplicates deposits by L1 sequence number
+//! - Skips failed transactions and events logged by other programs
+//! - Deduplicates deposits by the bridge's global L1 sequence number
 //! - Persists last processed slot for restart recovery
 //! - Routes deposits through pipeline (not direct DB update)
 //!
-//! Log format: "Program log: ZE_DEPOSIT:<Pubkey>:<Amount>:<Nonce>"
+//! Log format: "Program data: <base64 DepositEvent>", laid out by
+//! `zelana_bridge_events`. Only these structured events are trusted: the
+//! legacy "Program log: ZE_DEPOSIT:..." text carried a per-depositor nonce,
+//! which collides with `l1_seq` in the processed-deposits key space.
 
 use anyhow::Result;
+use base64::prelude::*;
 use log::{debug, error, info, warn};
 use solana_client::nonblocking::pubsub_client::PubsubClient;
 use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
 use solana_commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
-use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio_stream::StreamExt;
 
 use zelana_account::AccountId;
+use zelana_bridge_events::{BridgeEvent, WithdrawEvent, WithdrawalInitiatedEvent};
 use zelana_transaction::{DepositEvent, TransactionType};
 
 use crate::sequencer::pipeline::PipelineService;
@@ -104,72 +111,90 @@ async fn run_subscription(
     let pubsub = PubsubClient::new(&config.ws_url).await?;
     info!("Connected to Solana pubsub at {}", config.ws_url);
 
-    // Use finalized commitment for reliability
+    // Use finalized commitment for reliability: a deposit credited from a
+    // fork that is later dropped could never be taken back
     let (mut stream, _unsub) = pubsub
         .logs_subscribe(
             RpcTransactionLogsFilter::Mentions(vec![config.bridge_program_id.clone()]),
-            // RpcTransactionLogsFilter::All,
             RpcTransactionLogsConfig {
-                commitment: Some(CommitmentConfig::processed()),
+                commitment: Some(CommitmentConfig::finalized()),
             },
         )
         .await?;
-    info!("hi");
     info!("Subscribed to bridge program logs (finalized commitment)");
 
     while let Some(response) = stream.next().await {
         // Extract slot from context
         let slot = response.context.slot;
-        info!("{slot}");
-        for log in response.value.logs {
-            // Check for our specific log prefix
-            info!("{}", log);
-            if let Some(payload) = log.strip_prefix("Program log: ZE_DEPOSIT:") {
-                if let Some(event) = parse_deposit_log(payload) {
-                    // Check for duplicate
-                    if db.is_deposit_processed(event.l1_seq)? {
-                        debug!(
-                            "Skipping duplicate deposit l1_seq={} (already processed)",
-                            event.l1_seq
-                        );
-                        continue;
+
+        // A failed transaction's effects were reverted, its events with them
+        if response.value.err.is_some() {
+            debug!(
+                "Skipping failed transaction {} at slot {}",
+                response.value.signature, slot
+            );
+            continue;
+        }
+
+        for event in bridge_events(&response.value.logs, &config.bridge_program_id) {
+            let event = match event {
+                BridgeEvent::Deposit(event) => to_l2_deposit(&event),
+                BridgeEvent::WithdrawalInitiated(event) => {
+                    if let Err(e) = record_withdrawal_initiated(db, &event, slot) {
+                        error!("Failed to record initiated withdrawal: {}", e);
                     }
+                    continue;
+                }
+                BridgeEvent::Withdraw(event) => {
+                    if let Err(e) = record_withdrawal_finalized(db, &event, slot) {
+                        error!("Failed to record withdrawal payout: {}", e);
+                    }
+                    continue;
+                }
+            };
+
+            // Check for duplicate
+            if db.is_deposit_processed(event.l1_seq)? {
+                debug!(
+                    "Skipping duplicate deposit l1_seq={} (already processed)",
+                    event.l1_seq
+                );
+                continue;
+            }
 
-                    info!(
-                        "Deposit detected: to={:?}, amount={}, l1_seq={}, slot={}",
-                        event.to, event.amount, event.l1_seq, slot
-                    );
+            info!(
+                "Deposit detected: to={:?}, amount={}, l1_seq={}, slot={}",
+                event.to, event.amount, event.l1_seq, slot
+            );
 
-                    // Route through pipeline
-                    match pipeline
-                        .submit(TransactionType::Deposit(event.clone()))
-                        .await
-                    {
-                        Ok(()) => {
-                            // Mark as processed only after successful submission
-                            if let Err(e) = db.mark_deposit_processed(event.l1_seq, slot) {
-                                error!("Failed to mark deposit as processed: {}", e);
-                            }
-                            if let Err(e) = db.set_last_processed_slot(slot) {
-                                error!("Failed to update last processed slot: {}", e);
-                            }
-                            // Track L1 deposit amount for stats
-                            if let Err(e) = db.add_l1_deposit(event.amount) {
-                                error!("Failed to track L1 deposit amount: {}", e);
-                            }
-                            info!(
-                                "DEPOSIT: +{} lamports for {:?} (l1_seq={})",
-                                event.amount, event.to, event.l1_seq
-                            );
-                        }
-                        Err(e) => {
-                            error!(
-                                "Failed to submit deposit to pipeline (l1_seq={}): {}",
-                                event.l1_seq, e
-                            );
-                            // Don't mark as processed - will retry on next run
-                        }
+            // Route through pipeline
+            match pipeline
+                .submit(TransactionType::Deposit(event.clone()))
+                .await
+            {
+                Ok(()) => {
+                    // Mark as processed only after successful submission
+                    if let Err(e) = db.mark_deposit_processed(event.l1_seq, slot) {
+                        error!("Failed to mark deposit as processed: {}", e);
                     }
+                    if let Err(e) = db.set_last_processed_slot(slot) {
+                        error!("Failed to update last processed slot: {}", e);
+                    }
+                    // Track L1 deposit amount for stats
+                    if let Err(e) = db.add_l1_deposit(event.amount) {
+                        error!("Failed to track L1 deposit amount: {}", e);
+                    }
+                    info!(
+                        "DEPOSIT: +{} lamports for {:?} (l1_seq={})",
+                        event.amount, event.to, event.l1_seq
+                    );
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to submit deposit to pipeline (l1_seq={}): {}",
+                        event.l1_seq, e
+                    );
+                    // Don't mark as processed - will retry on next run
                 }
             }
         }
@@ -216,48 +241,95 @@ async fn fetch_historical_deposits(
     Ok(())
 }
 
-/// Parses format: "ZE_DEPOSIT:<Pubkey>:<Amount>:<Nonce>"
-fn parse_deposit_log(payload: &str) -> Option<DepositEvent> {
-    let parts: Vec<&str> = payload.split(':').collect();
-    if parts.len() != 3 {
-        warn!("Malformed deposit log: {}", payload);
-        return None;
+/// Bridge events in a transaction's logs
+///
+/// Only "Program data:" lines logged while the bridge program itself is the
+/// innermost executing program are trusted: any program the transaction
+/// invokes can log data in the same format, and the runtime brackets each
+/// invocation with "Program <id> invoke [depth]" and
+/// "Program <id> success" / "Program <id> failed: ..." lines.
+fn bridge_events(logs: &[String], bridge_program_id: &str) -> Vec<BridgeEvent> {
+    let mut frames: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for log in logs {
+        if log.starts_with("Program data: ") {
+            if frames.last() == Some(&bridge_program_id) {
+                events.extend(parse_bridge_event(log));
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            match (words.next(), words.next()) {
+                (Some(program_id), Some("invoke")) => frames.push(program_id),
+                (Some(program_id), Some("success" | "failed:"))
+                    if frames.last() == Some(&program_id) =>
+                {
+                    frames.pop();
+                }
+                _ => {}
+            }
+        } else if log == "Log truncated" {
+            // The frames can no longer be tracked
+            break;
+        }
     }
-    info!("{}", payload);
-
-    let pubkey_str = parts[0];
-    let pubkey = parse_log_pubkey(pubkey_str)?;
-    let amount = parts[1].parse::<u64>().ok()?;
-    let nonce = parts[2].parse::<u64>().ok()?;
-
-    Some(DepositEvent {
-        to: map_l1_to_l2(pubkey),
-        amount,
-        l1_seq: nonce,
-    })
+    events
 }
 
-/// Parse a pubkey from log output (handles both base58 and byte array formats)
-fn parse_log_pubkey(log_val: &str) -> Option<Pubkey> {
-    let log_val = log_val.trim();
+/// Parse a bridge event out of a program log line, if it carries one
+fn parse_bridge_event(log: &str) -> Option<BridgeEvent> {
+    let data = log.strip_prefix("Program data: ")?;
+    let bytes = BASE64_STANDARD.decode(data.trim()).ok()?;
+    BridgeEvent::from_bytes(&bytes)
+}
 
-    // Handle byte array format: [1, 2, 3, ...]
-    if log_val.starts_with('[') {
-        let bytes_str = log_val.trim_matches(|c| c == '[' || c == ']');
-        let bytes: Result<Vec<u8>, _> = bytes_str
-            .split(',')
-            .map(|s| s.trim().parse::<u8>())
-            .collect();
+/// Parse a deposit out of a bridge program log line, if it carries one
+fn parse_deposit(log: &str) -> Option<DepositEvent> {
+    match parse_bridge_event(log)? {
+        BridgeEvent::Deposit(event) => Some(to_l2_deposit(&event)),
+        BridgeEvent::Withdraw(_) | BridgeEvent::WithdrawalInitiated(_) => None,
+    }
+}
 
-        if let Ok(vec) = bytes {
-            if vec.len() == 32 {
-                return Some(Pubkey::new_from_array(vec.try_into().unwrap()));
-            }
-        }
+/// Convert a bridge `DepositEvent` into the L2 deposit transaction
+fn to_l2_deposit(event: &zelana_bridge_events::DepositEvent) -> DepositEvent {
+    DepositEvent {
+        to: map_l1_to_l2(Pubkey::new_from_array(event.depositor)),
+        amount: event.amount,
+        l1_seq: event.l1_seq,
     }
+}
 
-    // Handle base58 format
-    Pubkey::from_str(log_val).ok()
+/// Record a withdrawal settled by SubmitBatch, keyed by its L2 tx hash
+fn record_withdrawal_initiated(
+    db: &RocksDbStore,
+    event: &WithdrawalInitiatedEvent,
+    slot: u64,
+) -> Result<()> {
+    let mut record = db.get_l1_withdrawal(&event.tx_hash)?.unwrap_or_default();
+    record.batch_id = Some(event.batch_id);
+    record.recipient = hex::encode(event.recipient);
+    record.amount = event.amount;
+    record.initiated_slot = Some(slot);
+    db.put_l1_withdrawal(&event.tx_hash, &record)?;
+    info!(
+        "WITHDRAWAL INITIATED: {} lamports to {} (batch={}, slot={})",
+        event.amount, record.recipient, event.batch_id, slot
+    );
+    Ok(())
+}
+
+/// Record a withdrawal paid out on L1; its nullifier is the L2 tx hash
+fn record_withdrawal_finalized(db: &RocksDbStore, event: &WithdrawEvent, slot: u64) -> Result<()> {
+    let mut record = db.get_l1_withdrawal(&event.nullifier)?.unwrap_or_default();
+    record.recipient = hex::encode(event.recipient);
+    record.amount = event.amount;
+    record.finalized_slot = Some(slot);
+    db.put_l1_withdrawal(&event.nullifier, &record)?;
+    info!(
+        "WITHDRAWAL FINALIZED: {} lamports to {} (slot={})",
+        event.amount, record.recipient, slot
+    );
+    Ok(())
 }
 
 /// Map L1 Solana pubkey to L2 account ID
@@ -297,7 +369,7 @@ pub async fn start_indexer(db: Arc<RocksDbStore>, ws_url: String, bridge_program
 
     let (mut stream, _unsub) = match pubsub
         .logs_subscribe(
-            RpcTransactionLogsFilter::Mentions(vec![bridge_program_id]),
+            RpcTransactionLogsFilter::Mentions(vec![bridge_program_id.clone()]),
             RpcTransactionLogsConfig {
                 commitment: Some(CommitmentConfig::finalized()),
             },
@@ -313,44 +385,45 @@ pub async fn start_indexer(db: Arc<RocksDbStore>, ws_url: String, bridge_program
 
     while let Some(response) = stream.next().await {
         let slot = response.context.slot;
+        if response.value.err.is_some() {
+            continue;
+        }
 
-        for log in response.value.logs {
-            // Check for our specific log prefix
-            if let Some(payload) = log.strip_prefix("Program log: ZE_DEPOSIT:") {
-                if let Some(event) = parse_deposit_log(payload) {
-                    // Check for duplicate
-                    match db.is_deposit_processed(event.l1_seq) {
-                        Ok(true) => {
-                            debug!(
-                                "Skipping duplicate deposit l1_seq={} (already processed)",
-                                event.l1_seq
-                            );
-                            continue;
-                        }
-                        Err(e) => {
-                            error!("Failed to check deposit status: {}", e);
-                            continue;
-                        }
-                        Ok(false) => {}
-                    }
-
-                    info!("Deposit detected: {:?}", event);
-
-                    // Direct DB update (legacy behavior)
-                    let mut account_state = db.get_account_state(&event.to).unwrap_or_default();
-                    account_state.balance = account_state.balance.saturating_add(event.amount);
-
-                    if let Err(e) = db.set_account_state(event.to, account_state) {
-                        error!("Failed to persist deposit: {}", e);
-                    } else {
-                        // Mark as processed
-                        let _ = db.mark_deposit_processed(event.l1_seq, slot);
-                        let _ = db.set_last_processed_slot(slot);
-                        info!(
-                            "DEPOSIT (legacy): +{} lamports for {:?}",
-                            event.amount, event.to
+        for event in bridge_events(&response.value.logs, &bridge_program_id) {
+            if let BridgeEvent::Deposit(event) = event {
+                let event = to_l2_deposit(&event);
+                // Check for duplicate
+                match db.is_deposit_processed(event.l1_seq) {
+                    Ok(true) => {
+                        debug!(
+                            "Skipping duplicate deposit l1_seq={} (already processed)",
+                            event.l1_seq
                         );
+                        continue;
+                    }
+                    Err(e) => {
+                        error!("Failed to check deposit status: {}", e);
+                        continue;
                     }
+                    Ok(false) => {}
+                }
+
+                info!("Deposit detected: {:?}", event);
+
+                // Direct DB update (legacy behavior)
+                let mut account_state = db.get_account_state(&event.to).unwrap_or_default();
+                account_state.balance = account_state.balance.saturating_add(event.amount);
+
+                if let Err(e) = db.set_account_state(event.to, account_state) {
+                    error!("Failed to persist deposit: {}", e);
+                } else {
+                    // Mark as processed
+                    let _ = db.mark_deposit_processed(event.l1_seq, slot);
+                    let _ = db.set_last_processed_slot(slot);
+                    info!(
+                        "DEPOSIT (legacy): +{} lamports for {:?}",
+                        event.amount, event.to
+                    );
                 }
             }
         }
@@ -362,23 +435,182 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_deposit_log() {
-        // Test base58 pubkey format
-        let payload = "11111111111111111111111111111111:1000000:42";
-        let event = parse_deposit_log(payload).unwrap();
-        assert_eq!(event.amount, 1000000);
+    fn test_parse_deposit_event_from_program_data() {
+        use zelana_bridge_events::{DEPOSIT_EVENT_TAG, WithdrawEvent};
+
+        // Encoded exactly as the bridge program's `sol_log_data` call does
+        let depositor = Pubkey::new_unique();
+        let emitted = zelana_bridge_events::DepositEvent {
+            depositor: depositor.to_bytes(),
+            amount: 1_000_000,
+            nonce: 3,
+            l1_seq: 42,
+        }
+        .to_bytes();
+        assert_eq!(emitted[0], DEPOSIT_EVENT_TAG);
+
+        let log = format!("Program data: {}", BASE64_STANDARD.encode(emitted));
+        let event = parse_deposit(&log).unwrap();
+        assert_eq!(event.to, map_l1_to_l2(depositor));
+        assert_eq!(event.amount, 1_000_000);
         assert_eq!(event.l1_seq, 42);
+
+        // Legacy text logs are no longer trusted
+        assert!(
+            parse_deposit("Program log: ZE_DEPOSIT:11111111111111111111111111111111:5:6").is_none()
+        );
+
+        // Withdrawals and unrelated data are not deposits
+        let withdraw = WithdrawEvent {
+            recipient: [1u8; 32],
+            amount: 5,
+            nullifier: [2u8; 32],
+            timestamp: 0,
+        };
+        let log = format!(
+            "Program data: {}",
+            BASE64_STANDARD.encode(withdraw.to_bytes())
+        );
+        assert!(parse_deposit(&log).is_none());
+        assert!(parse_deposit("Program data: not-base64!").is_none());
+        assert!(parse_deposit("Program log: withdraw:5").is_none());
+    }
+
+    #[test]
+    fn test_bridge_events_only_from_bridge_frames() {
+        let bridge = Pubkey::new_unique().to_string();
+        let other = Pubkey::new_unique().to_string();
+        let deposit = |l1_seq: u64| {
+            let emitted = zelana_bridge_events::DepositEvent {
+                depositor: [3u8; 32],
+                amount: 10,
+                nonce: 0,
+                l1_seq,
+            }
+            .to_bytes();
+            format!("Program data: {}", BASE64_STANDARD.encode(emitted))
+        };
+
+        let logs = vec![
+            // A program mentioning the bridge logs a forged deposit at top level
+            format!("Program {} invoke [1]", other),
+            deposit(1),
+            // ... and from a nested frame inside its bridge CPI
+            format!("Program {} invoke [2]", bridge),
+            deposit(2),
+            format!("Program {} invoke [3]", other),
+            deposit(3),
+            format!("Program {} consumed 100 of 200000 compute units", other),
+            format!("Program {} success", other),
+            deposit(4),
+            format!("Program {} success", bridge),
+            deposit(5),
+            format!("Program {} failed: custom program error: 0x1", other),
+            // Top-level bridge instruction
+            format!("Program {} invoke [1]", bridge),
+            "Program log: Instruction: Deposit".to_string(),
+            deposit(6),
+            format!("Program {} success", bridge),
+            deposit(7),
+        ];
+
+        let seqs: Vec<u64> = bridge_events(&logs, &bridge)
+            .into_iter()
+            .map(|event| match event {
+                BridgeEvent::Deposit(event) => event.l1_seq,
+                _ => panic!("expected deposits"),
+            })
+            .collect();
+        assert_eq!(seqs, vec![2, 4, 6]);
+
+        // Nothing is trusted past a truncated log
+        let truncated = vec![
+            format!("Program {} invoke [1]", bridge),
+            "Log truncated".to_string(),
+            deposit(8),
+        ];
+        assert!(bridge_events(&truncated, &bridge).is_empty());
+    }
+
+    #[test]
+    fn test_deposit_dedupe_keys_do_not_collide() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = RocksDbStore::open(tmp.path()).unwrap();
+
+        // Two depositors' first deposits share nonce 0 but not l1_seq
+        let deposit = |depositor: Pubkey, l1_seq: u64| {
+            let emitted = zelana_bridge_events::DepositEvent {
+                depositor: depositor.to_bytes(),
+                amount: 10,
+                nonce: 0,
+                l1_seq,
+            }
+            .to_bytes();
+            parse_deposit(&format!(
+                "Program data: {}",
+                BASE64_STANDARD.encode(emitted)
+            ))
+            .unwrap()
+        };
+        let first = deposit(Pubkey::new_unique(), 7);
+        let second = deposit(Pubkey::new_unique(), 8);
+
+        db.mark_deposit_processed(first.l1_seq, 100).unwrap();
+        assert!(db.is_deposit_processed(first.l1_seq).unwrap());
+        assert!(!db.is_deposit_processed(second.l1_seq).unwrap());
+
+        // A text log naming an unprocessed seq as its "nonce" can't claim it
+        assert!(
+            parse_deposit("Program log: ZE_DEPOSIT:11111111111111111111111111111111:5:8").is_none()
+        );
+        assert!(!db.is_deposit_processed(second.l1_seq).unwrap());
     }
 
     #[test]
-    fn test_parse_deposit_log_malformed() {
-        // Missing field
-        let payload = "11111111111111111111111111111111:1000000";
-        assert!(parse_deposit_log(payload).is_none());
-
-        // Too many fields
-        let payload = "11111111111111111111111111111111:1000000:42:extra";
-        assert!(parse_deposit_log(payload).is_none());
+    fn test_withdrawal_events_are_indexed_by_tx_hash() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = RocksDbStore::open(tmp.path()).unwrap();
+        let tx_hash = [9u8; 32];
+
+        let initiated = WithdrawalInitiatedEvent {
+            batch_id: 3,
+            tx_hash,
+            recipient: [1u8; 32],
+            amount: 500,
+        };
+        let log = format!(
+            "Program data: {}",
+            BASE64_STANDARD.encode(initiated.to_bytes())
+        );
+        let Some(BridgeEvent::WithdrawalInitiated(event)) = parse_bridge_event(&log) else {
+            panic!("expected a WithdrawalInitiated event");
+        };
+        record_withdrawal_initiated(&db, &event, 100).unwrap();
+
+        let record = db.get_l1_withdrawal(&tx_hash).unwrap().unwrap();
+        assert_eq!(record.batch_id, Some(3));
+        assert_eq!(record.recipient, hex::encode([1u8; 32]));
+        assert_eq!(record.amount, 500);
+        assert_eq!(record.initiated_slot, Some(100));
+        assert_eq!(record.finalized_slot, None);
+
+        let paid = WithdrawEvent {
+            recipient: [1u8; 32],
+            amount: 500,
+            nullifier: tx_hash,
+            timestamp: 0,
+        };
+        let log = format!("Program data: {}", BASE64_STANDARD.encode(paid.to_bytes()));
+        let Some(BridgeEvent::Withdraw(event)) = parse_bridge_event(&log) else {
+            panic!("expected a Withdraw event");
+        };
+        record_withdrawal_finalized(&db, &event, 150).unwrap();
+
+        let record = db.get_l1_withdrawal(&tx_hash).unwrap().unwrap();
+        assert_eq!(record.batch_id, Some(3));
+        assert_eq!(record.initiated_slot, Some(100));
+        assert_eq!(record.finalized_slot, Some(150));
+        assert!(db.get_l1_withdrawal(&[0u8; 32]).unwrap().is_none());
     }
 
     #[test]
@@ -23,9 +23,11 @@ use std::sync::Arc;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::api::types::WithdrawalIndexEntry;
 use crate::sequencer::execution::tx_router::PendingWithdrawal;
 use crate::sequencer::storage::db::RocksDbStore;
 use zelana_account::AccountId;
+use zelana_bridge_events::merkle::{WithdrawalTree, withdrawal_leaf};
 
 // Withdrawal States
 
@@ -361,48 +363,47 @@ pub struct WithdrawalStats {
 
 // Withdrawal Merkle Tree (for L1 verification)
 
-/// Build a merkle tree of withdrawals for L1 verification
-pub fn build_withdrawal_merkle_root(withdrawals: &[TrackedWithdrawal]) -> [u8; 32] {
-    if withdrawals.is_empty() {
-        return [0u8; 32];
-    }
-
-    // Compute leaf hashes
-    let mut leaves: Vec<[u8; 32]> = withdrawals
+/// Build the Poseidon withdrawal tree a batch's proof commits to, leaves in
+/// batch order. `None` if the batch holds more withdrawals than the circuit
+/// has slots.
+pub fn build_withdrawal_tree(withdrawals: &[TrackedWithdrawal]) -> Option<WithdrawalTree> {
+    let leaves: Vec<[u8; 32]> = withdrawals
         .iter()
-        .map(|w| {
-            let mut hasher = blake3::Hasher::new();
-            hasher.update(&w.tx_hash);
-            hasher.update(&w.to_l1_address);
-            hasher.update(&w.amount.to_le_bytes());
-            *hasher.finalize().as_bytes()
-        })
+        .map(|w| withdrawal_leaf(&w.tx_hash, &w.to_l1_address, w.amount))
         .collect();
+    WithdrawalTree::new(&leaves)
+}
 
-    // Pad to power of 2
-    let target_len = leaves.len().next_power_of_two();
-    while leaves.len() < target_len {
-        leaves.push([0u8; 32]);
-    }
-
-    // Build tree
-    while leaves.len() > 1 {
-        let mut next_level = Vec::with_capacity(leaves.len() / 2);
-        for chunk in leaves.chunks(2) {
-            let mut hasher = blake3::Hasher::new();
-            hasher.update(&chunk[0]);
-            hasher.update(&chunk[1]);
-            next_level.push(*hasher.finalize().as_bytes());
-        }
-        leaves = next_level;
-    }
-
-    leaves[0]
+/// Index entries for a batch's withdrawals, keyed by withdrawal tx hash
+///
+/// `tree` must be built from `withdrawals` by [`build_withdrawal_tree`]; each
+/// entry stores its leaf's sibling path, so the withdrawal can be claimed
+/// against the root the batch settled on L1.
+pub fn withdrawal_index_entries(
+    batch_id: u64,
+    tree: &WithdrawalTree,
+    withdrawals: &[TrackedWithdrawal],
+) -> Vec<([u8; 32], WithdrawalIndexEntry)> {
+    let withdrawal_root = hex::encode(tree.root());
+    withdrawals
+        .iter()
+        .enumerate()
+        .filter_map(|(position, w)| {
+            let entry = WithdrawalIndexEntry {
+                batch_id,
+                withdrawal_root: withdrawal_root.clone(),
+                leaf_position: position as u64,
+                merkle_path: tree.proof(position)?.iter().map(hex::encode).collect(),
+            };
+            Some((w.tx_hash, entry))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use zelana_bridge_events::merkle::{MAX_BATCH_WITHDRAWALS, verify_withdrawal_proof};
 
     fn make_pending(amount: u64) -> PendingWithdrawal {
         PendingWithdrawal {
@@ -432,15 +433,86 @@ mod tests {
     }
 
     #[test]
-    fn test_withdrawal_merkle_root_empty() {
-        let root = build_withdrawal_merkle_root(&[]);
-        assert_eq!(root, [0u8; 32]);
+    fn test_withdrawal_tree_empty() {
+        let tree = build_withdrawal_tree(&[]).unwrap();
+        assert_eq!(tree.root(), [0u8; 32]);
     }
 
     #[test]
-    fn test_withdrawal_merkle_root_single() {
+    fn test_withdrawal_tree_single() {
         let withdrawals = vec![TrackedWithdrawal::from(make_pending(1000))];
-        let root = build_withdrawal_merkle_root(&withdrawals);
-        assert_ne!(root, [0u8; 32]);
+        let tree = build_withdrawal_tree(&withdrawals).unwrap();
+        assert_ne!(tree.root(), [0u8; 32]);
+
+        // More withdrawals than the circuit has slots
+        let withdrawals: Vec<_> = (0..=MAX_BATCH_WITHDRAWALS as u64)
+            .map(|amount| TrackedWithdrawal::from(make_pending(amount)))
+            .collect();
+        assert!(build_withdrawal_tree(&withdrawals).is_none());
+    }
+
+    #[test]
+    fn test_withdrawal_index_proves_against_committed_root() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksDbStore::open(dir.path()).unwrap();
+
+        let withdrawals: Vec<_> = [1000, 2000, 3000]
+            .into_iter()
+            .map(|amount| TrackedWithdrawal::from(make_pending(amount)))
+            .collect();
+        let tree = build_withdrawal_tree(&withdrawals).unwrap();
+        let committed_root = tree.root();
+        db.store_withdrawal_index(&withdrawal_index_entries(7, &tree, &withdrawals))
+            .unwrap();
+
+        for (position, withdrawal) in withdrawals.iter().enumerate() {
+            let entry = db
+                .get_withdrawal_index(&withdrawal.tx_hash)
+                .unwrap()
+                .unwrap();
+            assert_eq!(entry.batch_id, 7);
+            assert_eq!(entry.leaf_position as usize, position);
+            assert_eq!(entry.withdrawal_root, hex::encode(committed_root));
+
+            // The stored path leads from this withdrawal's leaf to the root,
+            // as FinalizeWithdrawal checks it on L1
+            let path: Vec<[u8; 32]> = entry
+                .merkle_path
+                .iter()
+                .map(|node| hex::decode(node).unwrap().try_into().unwrap())
+                .collect();
+            let leaf = withdrawal_leaf(
+                &withdrawal.tx_hash,
+                &withdrawal.to_l1_address,
+                withdrawal.amount,
+            );
+            assert!(verify_withdrawal_proof(
+                leaf,
+                entry.leaf_position,
+                &path,
+                committed_root
+            ));
+
+            // Not for another withdrawal's leaf or position
+            let other = withdrawal_leaf(&withdrawal.tx_hash, &withdrawal.to_l1_address, 1);
+            assert!(!verify_withdrawal_proof(
+                other,
+                entry.leaf_position,
+                &path,
+                committed_root
+            ));
+            assert!(!verify_withdrawal_proof(
+                leaf,
+                entry.leaf_position ^ 1,
+                &path,
+                committed_root
+            ));
+        }
+
+        // A rolled-back batch's withdrawals drop out of the index
+        let hashes: Vec<_> = withdrawals.iter().map(|w| w.tx_hash).collect();
+        db.delete_withdrawal_index(&hashes).unwrap();
+        assert!(db.get_withdrawal_index(&hashes[0]).unwrap().is_none());
+        assert!(db.get_withdrawal_index(&[9u8; 32]).unwrap().is_none());
     }
 }
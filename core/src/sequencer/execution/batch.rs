@@ -26,6 +26,9 @@
 //! 5. Settling: L1 transaction submitted
 //! 6. Finalized: L1 confirmed, batch complete
 //! ```
+//!
+//! Transactions execute in canonical order, not arrival order, so the state
+//! root is reproducible from the transaction set alone (see [`canonical_order`]).
 
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
@@ -34,16 +37,19 @@ use anyhow::{Context, Result};
 use tokio::sync::{Mutex, mpsc, oneshot};
 use tracing::warn;
 
-use super::tx_router::{BatchDiff, TxResult, TxResultType, TxRouter};
+use super::tx_router::{BatchDiff, RouterSnapshot, TxResult, TxResultType, TxRouter};
 use crate::api::types::{TxStatus, TxSummary, TxType};
+use crate::sequencer::bridge::withdrawals::{
+    TrackedWithdrawal, WithdrawalState, build_withdrawal_tree, withdrawal_index_entries,
+};
 use crate::sequencer::settlement::prover::{
     BatchPublicInputs, BatchWitness, build_public_inputs, build_witness_with_proofs,
 };
-use crate::sequencer::storage::compute_withdrawal_root_mimc;
 use crate::sequencer::storage::db::RocksDbStore;
 use crate::storage::StateStore;
 use zelana_account::{AccountId, AccountState};
-use zelana_transaction::TransactionType;
+use zelana_bridge_events::merkle::MAX_BATCH_WITHDRAWALS;
+use zelana_transaction::{TransactionType, TxExpired};
 
 // Configuration
 
@@ -58,6 +64,9 @@ pub struct BatchConfig {
     pub max_shielded: usize,
     /// Minimum transactions before considering seal (unless timeout)
     pub min_transactions: usize,
+    /// Maximum unexecuted transactions per sending account, so one account
+    /// can't fill batches with a run of sequential nonces
+    pub max_pending_per_account: usize,
 }
 
 impl Default for BatchConfig {
@@ -67,6 +76,119 @@ impl Default for BatchConfig {
             max_batch_age_secs: 60,
             max_shielded: 10,
             min_transactions: 1,
+            max_pending_per_account: 16,
+        }
+    }
+}
+
+/// Submission rejected before the transaction reached a batch
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SubmitError {
+    /// The sender already has `limit` transactions waiting to execute
+    #[error("too many pending transactions for account {} (limit {limit})", .account.to_hex())]
+    TooManyPending { account: AccountId, limit: usize },
+    /// The transfer's `valid_until_batch` is before the batch it would join
+    #[error(transparent)]
+    Expired(#[from] TxExpired),
+}
+
+/// Expiry check for a transaction executing in `batch_id` (only signed
+/// transfers carry an expiry)
+fn check_expiry(tx: &TransactionType, batch_id: u64) -> Result<(), TxExpired> {
+    match tx {
+        TransactionType::Transfer(t) => t.data.check_expiry(batch_id),
+        _ => Ok(()),
+    }
+}
+
+/// Account whose pending count a transaction adds to (deposits and shielded
+/// transactions have no transparent sender)
+fn pending_sender(tx: &TransactionType) -> Option<AccountId> {
+    match tx {
+        TransactionType::Transfer(t) => Some(AccountId(t.signer_pubkey)),
+        TransactionType::Withdraw(w) => Some(w.from),
+        TransactionType::Deposit(_) | TransactionType::Shielded(_) => None,
+    }
+}
+
+// Canonical Ordering
+
+/// Sort key for [`canonical_order`]: (class, account, sequence, tiebreak).
+/// The tiebreak only separates conflicting transactions (same account and
+/// sequence), of which at most one executes.
+fn canonical_key(tx: &TransactionType) -> (u8, [u8; 32], u64, &[u8]) {
+    match tx {
+        TransactionType::Deposit(d) => (0, [0u8; 32], d.l1_seq, &d.to.0),
+        TransactionType::Transfer(t) => (1, t.signer_pubkey, t.data.nonce, &t.signature),
+        TransactionType::Withdraw(w) => (1, w.from.0, w.nonce, &w.signature),
+        TransactionType::Shielded(p) => (2, p.nullifier, 0, &p.commitment),
+    }
+}
+
+/// Account a transfer credits (withdrawals leave L2)
+fn credited_account(tx: &TransactionType) -> Option<[u8; 32]> {
+    match tx {
+        TransactionType::Transfer(t) if t.data.to.0 != t.signer_pubkey => Some(t.data.to.0),
+        _ => None,
+    }
+}
+
+/// Put a batch's transactions into canonical execution order.
+///
+/// The order depends only on the transaction set, never on arrival order,
+/// so the state root is reproducible from the batch's contents:
+///
+/// 1. Deposits, by L1 sequence number, so funds bridged in are spendable by
+///    every transaction after them
+/// 2. Transfers and withdrawals, each sender's in nonce order, scheduled by
+///    the dependency rule below
+/// 3. Shielded transactions, by nullifier
+///
+/// Dependency rule: a sender's next transaction (its lowest remaining
+/// nonce) is ready once every transfer in the batch crediting that sender
+/// has been placed, so a transfer may spend funds received in the same
+/// batch (A -> B, then B -> C). Of the ready transactions, the one with the
+/// smallest (sender, nonce) goes next. When none is ready the credits form
+/// a cycle (A -> B and B -> A), and the smallest next transaction goes
+/// first. There are no fees, so nothing else is prioritised.
+///
+/// The witness builder and prover read `Batch::transactions` after sealing,
+/// and each transaction's position is recorded as `TxSummary::batch_index`
+/// for replay tools.
+pub fn canonical_order(transactions: &mut [TransactionType]) {
+    transactions.sort_by(|a, b| canonical_key(a).cmp(&canonical_key(b)));
+
+    let start = transactions.partition_point(|tx| canonical_key(tx).0 < 1);
+    let end = transactions.partition_point(|tx| canonical_key(tx).0 < 2);
+    schedule_account_txs(&mut transactions[start..end]);
+}
+
+/// Order transfers and withdrawals, sorted by [`canonical_key`], by the
+/// dependency rule of [`canonical_order`]
+fn schedule_account_txs(txs: &mut [TransactionType]) {
+    let sender = |tx: &TransactionType| canonical_key(tx).1;
+
+    let mut incoming: std::collections::HashMap<[u8; 32], usize> = std::collections::HashMap::new();
+    for to in txs.iter().filter_map(credited_account) {
+        *incoming.entry(to).or_default() += 1;
+    }
+
+    for next in 0..txs.len() {
+        // The unplaced transactions stay sorted, so each sender's next
+        // transaction is the first of its run
+        let remaining = &txs[next..];
+        let ready = (0..remaining.len())
+            .filter(|&i| i == 0 || sender(&remaining[i - 1]) != sender(&remaining[i]))
+            .find(|&i| {
+                incoming
+                    .get(&sender(&remaining[i]))
+                    .is_none_or(|&credits| credits == 0)
+            });
+
+        // With a credit cycle nothing is ready, and the smallest key goes first
+        txs[next..=next + ready.unwrap_or(0)].rotate_right(1);
+        if let Some(credits) = credited_account(&txs[next]).and_then(|to| incoming.get_mut(&to)) {
+            *credits -= 1;
         }
     }
 }
@@ -125,6 +247,10 @@ pub struct Batch {
     pub committed: bool,
     /// Pre-computed witness (built before commit in DEV mode)
     pub witness: Option<BatchWitness>,
+    /// Router state before this batch executed (for settlement-failure rollback)
+    pub snapshot: Option<RouterSnapshot>,
+    /// Withdrawal root committed to the proof's public inputs
+    pub withdrawal_root: Option<[u8; 32]>,
 }
 
 impl Batch {
@@ -146,6 +272,8 @@ impl Batch {
             l1_tx_sig: None,
             committed: false,
             witness: None,
+            snapshot: None,
+            withdrawal_root: None,
         }
     }
 
@@ -241,6 +369,108 @@ impl Batch {
     pub fn finalize(&mut self) {
         self.state = BatchState::Finalized;
     }
+
+    /// Withdrawals that executed successfully, in batch order
+    pub fn withdrawals(&self) -> Vec<TrackedWithdrawal> {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.results
+            .iter()
+            .filter(|r| r.success)
+            .filter_map(|r| match &r.tx_type {
+                TxResultType::Withdrawal {
+                    from,
+                    to_l1,
+                    amount,
+                } => Some(TrackedWithdrawal {
+                    tx_hash: r.tx_hash,
+                    from: *from,
+                    to_l1_address: *to_l1,
+                    amount: *amount,
+                    l2_nonce: 0, // Not tracked in result, but not needed for L1
+                    state: WithdrawalState::InBatch { batch_id: self.id },
+                    created_at,
+                    batch_id: Some(self.id),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// Batch ID Allocation
+
+/// Hands out strictly sequential batch ids, persisted in RocksDB.
+///
+/// An id is reserved (the persisted `next_batch_id` is bumped) before the
+/// batch that uses it can be sealed. On startup the reservation is compared
+/// with the last settled batch:
+///
+/// - reserved ids above the last settled batch belong to batches that were
+///   lost in flight; their state was never committed, so the ids are
+///   reclaimed and the transactions re-batched under the same ids
+/// - a settled batch at or above the reservation, or a hole in the settled
+///   sequence, means the `prev_root` chain is broken and startup is refused
+pub struct BatchIdAllocator {
+    db: Arc<RocksDbStore>,
+    next: u64,
+}
+
+impl BatchIdAllocator {
+    /// Recover the allocator from the database, checking for gaps
+    pub fn recover(db: Arc<RocksDbStore>) -> Result<Self> {
+        if let Some(missing) = db.find_batch_id_gap()? {
+            anyhow::bail!(
+                "Batch id gap detected: batch {} is missing from the settled sequence",
+                missing
+            );
+        }
+
+        let expected = db.get_latest_batch_id()?.map(|id| id + 1).unwrap_or(1);
+        let next = match db.get_next_batch_id()? {
+            None => expected,
+            Some(reserved) if reserved == expected => expected,
+            Some(reserved) if reserved > expected => {
+                warn!(
+                    "Reclaiming batch ids {}..{} reserved but never settled",
+                    expected, reserved
+                );
+                expected
+            }
+            Some(reserved) => {
+                anyhow::bail!(
+                    "Batch id allocator is behind settled batches (next {}, expected {})",
+                    reserved,
+                    expected
+                );
+            }
+        };
+
+        db.set_next_batch_id(next)?;
+        Ok(Self { db, next })
+    }
+
+    /// Reserve the next batch id
+    pub fn reserve(&mut self) -> Result<u64> {
+        let id = self.next;
+        self.db.set_next_batch_id(id + 1)?;
+        self.next = id + 1;
+        Ok(id)
+    }
+
+    /// Next id that will be reserved
+    pub fn peek(&self) -> u64 {
+        self.next
+    }
+
+    /// Hand out `next` again (after its batch was rolled back)
+    pub fn rewind(&mut self, next: u64) -> Result<()> {
+        self.db.set_next_batch_id(next)?;
+        self.next = next;
+        Ok(())
+    }
 }
 
 // Batch Manager
@@ -253,8 +483,8 @@ pub struct BatchManager {
     router: TxRouter,
     /// Configuration
     config: BatchConfig,
-    /// Next batch ID
-    next_batch_id: u64,
+    /// Sequential batch ID allocator
+    batch_ids: BatchIdAllocator,
     /// Current accumulating batch
     current_batch: Option<Batch>,
     /// Batches in proving stage
@@ -264,6 +494,8 @@ pub struct BatchManager {
     /// Pending account states for transactions in current batch (not yet executed)
     /// This tracks expected balances/nonces for rapid successive transactions
     pending_states: std::collections::HashMap<AccountId, AccountState>,
+    /// Unexecuted transactions per sender in the current batch
+    pending_counts: std::collections::HashMap<AccountId, usize>,
 }
 
 impl BatchManager {
@@ -271,41 +503,84 @@ impl BatchManager {
     pub fn new(db: Arc<RocksDbStore>, config: BatchConfig) -> Result<Self> {
         let router = TxRouter::load(db.clone())?;
 
-        // Resume from last batch ID if available
-        let next_batch_id = db
-            .get_latest_batch_id()
-            .unwrap_or(None)
-            .map(|id| id + 1)
-            .unwrap_or(1);
+        // Resume from the last settled batch, reclaiming ids lost in flight
+        let batch_ids = BatchIdAllocator::recover(db.clone())?;
 
         Ok(Self {
             db,
             router,
             config,
-            next_batch_id,
+            batch_ids,
             current_batch: None,
             proving_batches: Vec::new(),
             pending_settlement: Vec::new(),
             pending_states: std::collections::HashMap::new(),
+            pending_counts: std::collections::HashMap::new(),
         })
     }
 
+    /// Replace the batch limits (config reload)
+    pub fn set_config(&mut self, config: BatchConfig) {
+        self.config = config;
+    }
+
     /// Start a new batch if none is active
-    fn ensure_batch(&mut self) {
+    fn ensure_batch(&mut self) -> Result<()> {
         if self.current_batch.is_none() {
             let batch = Batch::new(
-                self.next_batch_id,
+                self.batch_ids.reserve()?,
                 self.router.transparent_root(),
                 self.router.shielded_root(),
             );
-            self.next_batch_id += 1;
             self.current_batch = Some(batch);
         }
+        Ok(())
     }
 
     /// Submit a transaction to the current batch
+    ///
+    /// Fails with [`SubmitError::TooManyPending`] once the sender has
+    /// `max_pending_per_account` transactions waiting to execute, and with
+    /// [`SubmitError::Expired`] for a transfer that expired before the
+    /// current batch.
     pub fn submit_transaction(&mut self, tx: TransactionType) -> Result<()> {
-        self.ensure_batch();
+        let sender = pending_sender(&tx);
+        if let Some(account) = sender {
+            let pending = self.pending_counts.get(&account).copied().unwrap_or(0);
+            if pending >= self.config.max_pending_per_account {
+                return Err(SubmitError::TooManyPending {
+                    account,
+                    limit: self.config.max_pending_per_account,
+                }
+                .into());
+            }
+        }
+
+        self.ensure_batch()?;
+
+        // Expired transfers never reach a batch
+        let batch_id = self.current_batch.as_ref().unwrap().id;
+        check_expiry(&tx, batch_id).map_err(SubmitError::from)?;
+
+        self.enqueue(tx)
+    }
+
+    /// Put transactions discarded by [`Self::rollback_batch`] back in line,
+    /// in their original order.
+    ///
+    /// They were admitted once, so the pending cap doesn't apply; any that
+    /// have expired since are dropped when their new batch is sealed.
+    pub fn requeue_transactions(&mut self, txs: Vec<TransactionType>) -> Result<()> {
+        for tx in txs {
+            self.ensure_batch()?;
+            self.enqueue(tx)?;
+        }
+        Ok(())
+    }
+
+    /// Add an admitted transaction to the current batch, sealing it when full
+    fn enqueue(&mut self, tx: TransactionType) -> Result<()> {
+        let sender = pending_sender(&tx);
 
         // Track pending state changes for rapid successive transactions
         // This allows get_pending_account to return correct nonces before batch execution
@@ -313,6 +588,9 @@ impl BatchManager {
 
         let batch = self.current_batch.as_mut().unwrap();
         batch.add_transaction(tx)?;
+        if let Some(account) = sender {
+            *self.pending_counts.entry(account).or_default() += 1;
+        }
 
         // Check if batch should be sealed
         if batch.should_seal(&self.config) {
@@ -325,6 +603,8 @@ impl BatchManager {
     /// Update pending states based on a transaction being added to the batch
     /// This tracks expected balances/nonces before the batch is actually executed
     fn update_pending_state(&mut self, tx: &TransactionType) {
+        // Applied on copies, so a transaction execution would reject leaves
+        // the pending state untouched
         match tx {
             TransactionType::Transfer(signed_tx) => {
                 let from = AccountId(signed_tx.signer_pubkey);
@@ -332,46 +612,37 @@ impl BatchManager {
                 let amount = signed_tx.data.amount;
 
                 // Get current state (from pending_states or DB)
-                let from_state = self.get_account_state_internal(&from);
-                let to_state = self.get_account_state_internal(&to);
-
-                // Update sender: debit + nonce increment
-                let new_from = AccountState {
-                    balance: from_state.balance.saturating_sub(amount),
-                    nonce: from_state.nonce + 1,
-                };
-                self.pending_states.insert(from, new_from);
-
-                // Update receiver: credit (no nonce change)
-                if from != to {
-                    let new_to = AccountState {
-                        balance: to_state.balance + amount,
-                        nonce: to_state.nonce,
-                    };
-                    self.pending_states.insert(to, new_to);
+                let mut new_from = self.get_account_state_internal(&from);
+                if new_from.apply_transfer(amount, 0).is_err() {
+                    return;
+                }
+
+                if from == to {
+                    // Self-transfer: only nonce changes
+                    if new_from.apply_credit(amount).is_ok() {
+                        self.pending_states.insert(from, new_from);
+                    }
+                } else {
+                    let mut new_to = self.get_account_state_internal(&to);
+                    if new_to.apply_credit(amount).is_ok() {
+                        self.pending_states.insert(from, new_from);
+                        self.pending_states.insert(to, new_to);
+                    }
                 }
             }
             TransactionType::Withdraw(withdraw) => {
                 let from = withdraw.from;
-                let amount = withdraw.amount;
-
-                let from_state = self.get_account_state_internal(&from);
-                let new_from = AccountState {
-                    balance: from_state.balance.saturating_sub(amount),
-                    nonce: from_state.nonce + 1,
-                };
-                self.pending_states.insert(from, new_from);
+                let mut new_from = self.get_account_state_internal(&from);
+                if new_from.apply_transfer(withdraw.amount, 0).is_ok() {
+                    self.pending_states.insert(from, new_from);
+                }
             }
             TransactionType::Deposit(deposit) => {
                 let to = deposit.to;
-                let amount = deposit.amount;
-
-                let to_state = self.get_account_state_internal(&to);
-                let new_to = AccountState {
-                    balance: to_state.balance + amount,
-                    nonce: to_state.nonce,
-                };
-                self.pending_states.insert(to, new_to);
+                let mut new_to = self.get_account_state_internal(&to);
+                if new_to.apply_credit(deposit.amount).is_ok() {
+                    self.pending_states.insert(to, new_to);
+                }
             }
             TransactionType::Shielded(_) => {
                 // Shielded transactions don't affect transparent account state
@@ -420,21 +691,37 @@ impl BatchManager {
             return Ok(None);
         };
 
+        let batch_id = batch.id;
+
+        // Drop transfers that expired while queued (e.g. re-queued by a rollback)
+        batch
+            .transactions
+            .retain(|tx| match check_expiry(tx, batch_id) {
+                Ok(()) => true,
+                Err(e) => {
+                    log::info!("Dropping transaction from batch {}: {}", batch_id, e);
+                    false
+                }
+            });
+
         if batch.transactions.is_empty() {
-            // Don't seal empty batches
+            // Don't seal empty batches; anything pending was just dropped
+            self.pending_states.clear();
+            self.pending_counts.clear();
             self.current_batch = Some(batch);
             return Ok(None);
         }
 
-        let batch_id = batch.id;
-
         // Execute all transactions
+        canonical_order(&mut batch.transactions);
+        batch.snapshot = Some(self.router.snapshot());
         let txs = std::mem::take(&mut batch.transactions);
-        let diff = self.router.execute_batch(txs.clone());
+        let diff = self.router.execute_batch(batch_id, txs.clone());
         batch.transactions = txs;
 
         // Clear pending_states - the router's account_cache now has the executed state
         self.pending_states.clear();
+        self.pending_counts.clear();
 
         // Get post-execution roots
         // CRITICAL: We must compute post_state_root by processing transactions in the SAME ORDER
@@ -483,39 +770,43 @@ impl BatchManager {
             for tx in &batch.transactions {
                 match tx {
                     TransactionType::Transfer(t) => {
-                        // 1. Update sender (debit + nonce increment) - circuit does this first
+                        // Both sides are applied on copies first: a transfer the
+                        // router rejected changes nothing here either
                         let sender_id = AccountId(t.signer_pubkey);
-                        let sender_state =
+                        let receiver_id = t.data.to;
+                        let mut new_sender =
                             account_states.get(&sender_id).cloned().unwrap_or_default();
-                        let new_sender = AccountState {
-                            balance: sender_state.balance.saturating_sub(t.data.amount),
-                            nonce: sender_state.nonce + 1,
+                        if new_sender.apply_transfer(t.data.amount, 0).is_err() {
+                            continue;
+                        }
+                        let mut new_receiver = if receiver_id == sender_id {
+                            new_sender.clone()
+                        } else {
+                            account_states
+                                .get(&receiver_id)
+                                .cloned()
+                                .unwrap_or_default()
                         };
+                        if new_receiver.apply_credit(t.data.amount).is_err() {
+                            continue;
+                        }
+
+                        // 1. Update sender (debit + nonce increment) - circuit does this first
                         sim_tree.insert(&sender_id, &new_sender);
                         account_states.insert(sender_id, new_sender);
 
                         // 2. Update receiver (credit, no nonce change) - circuit does this second
-                        let receiver_id = t.data.to;
-                        let receiver_state = account_states
-                            .get(&receiver_id)
-                            .cloned()
-                            .unwrap_or_default();
-                        let new_receiver = AccountState {
-                            balance: receiver_state.balance + t.data.amount,
-                            nonce: receiver_state.nonce,
-                        };
                         sim_tree.insert(&receiver_id, &new_receiver);
                         account_states.insert(receiver_id, new_receiver);
                     }
                     TransactionType::Withdraw(w) => {
                         // Update sender (debit + nonce increment)
                         let sender_id = w.from;
-                        let sender_state =
+                        let mut new_sender =
                             account_states.get(&sender_id).cloned().unwrap_or_default();
-                        let new_sender = AccountState {
-                            balance: sender_state.balance.saturating_sub(w.amount),
-                            nonce: sender_state.nonce + 1,
-                        };
+                        if new_sender.apply_transfer(w.amount, 0).is_err() {
+                            continue;
+                        }
                         sim_tree.insert(&sender_id, &new_sender);
                         account_states.insert(sender_id, new_sender);
                     }
@@ -643,9 +934,80 @@ impl BatchManager {
             );
         }
 
+        // Earlier batches can no longer be rolled back
+        if let Some(snapshot) = &batch.snapshot {
+            self.router.release(snapshot);
+        }
+
         Ok(diff)
     }
 
+    /// Whether every batch before `batch_id` is finalized, so it can be rolled back
+    pub fn can_roll_back(&self, batch_id: u64) -> bool {
+        !self
+            .proving_batches
+            .iter()
+            .chain(self.pending_settlement.iter())
+            .any(|b| b.id < batch_id)
+    }
+
+    /// Roll back a batch that failed to settle, along with every batch after it.
+    ///
+    /// State is reverted to the snapshot taken before the batch executed and
+    /// its id is handed out again. Returns the discarded transactions in order
+    /// so they can be re-batched with [`Self::requeue_transactions`]. All
+    /// earlier batches must be finalized.
+    pub fn rollback_batch(&mut self, batch_id: u64) -> Result<Vec<TransactionType>> {
+        if !self.can_roll_back(batch_id) {
+            anyhow::bail!(
+                "cannot roll back batch {}: earlier batches are not finalized",
+                batch_id
+            );
+        }
+
+        let snapshot = self
+            .proving_batches
+            .iter()
+            .chain(self.pending_settlement.iter())
+            .find(|b| b.id == batch_id)
+            .context("batch not found for rollback")?
+            .snapshot
+            .context("batch has no snapshot")?;
+
+        // Discarded withdrawals must not point claims at a batch that never settles
+        let withdrawal_hashes: Vec<[u8; 32]> = self
+            .proving_batches
+            .iter()
+            .chain(self.pending_settlement.iter())
+            .filter(|b| b.id >= batch_id)
+            .flat_map(|b| b.withdrawals())
+            .map(|w| w.tx_hash)
+            .collect();
+        self.db.delete_withdrawal_index(&withdrawal_hashes)?;
+
+        self.router.rollback_to(&snapshot)?;
+
+        let mut discarded: Vec<Batch> = self
+            .pending_settlement
+            .drain(..)
+            .chain(self.proving_batches.drain(..))
+            .chain(self.current_batch.take())
+            .collect();
+        discarded.sort_by_key(|b| b.id);
+
+        self.pending_states.clear();
+        self.pending_counts.clear();
+        self.batch_ids.rewind(batch_id)?;
+
+        warn!(
+            "Rolled back batches {}..={}",
+            batch_id,
+            discarded.last().map(|b| b.id).unwrap_or(batch_id)
+        );
+
+        Ok(discarded.into_iter().flat_map(|b| b.transactions).collect())
+    }
+
     /// Get statistics about the pipeline
     pub fn stats(&self) -> BatchManagerStats {
         BatchManagerStats {
@@ -656,7 +1018,7 @@ impl BatchManager {
                 .unwrap_or(0),
             proving_count: self.proving_batches.len(),
             pending_settlement_count: self.pending_settlement.len(),
-            next_batch_id: self.next_batch_id,
+            next_batch_id: self.batch_ids.peek(),
         }
     }
 
@@ -724,16 +1086,31 @@ impl BatchManager {
             batch.transactions.len()
         );
 
-        // Compute withdrawal root using MiMC (matches Noir circuit)
-        // For now, we only count withdrawals (deposit-only batches have 0)
-        let num_withdrawals = batch
-            .transactions
-            .iter()
-            .filter(|tx| matches!(tx, TransactionType::Withdraw(_)))
-            .count() as u64;
-        let withdrawal_root = compute_withdrawal_root_mimc(batch_id, num_withdrawals);
+        // Commit the withdrawals to the Poseidon tree the circuit builds over
+        // its withdrawal slots and FinalizeWithdrawal claims against
+        let withdrawals = batch.withdrawals();
+        let tree = build_withdrawal_tree(&withdrawals);
+        let inputs = match &tree {
+            Some(tree) => build_public_inputs(batch, tree.root()),
+            None => Err(anyhow::anyhow!(
+                "batch {} has {} withdrawals, the circuit commits to at most {}",
+                batch_id,
+                withdrawals.len(),
+                MAX_BATCH_WITHDRAWALS
+            )),
+        };
 
-        let inputs = build_public_inputs(batch, withdrawal_root);
+        // Index withdrawals with their paths to the root the proof commits
+        // to, so they can be claimed once the batch settles
+        if let Some(tree) = tree.as_ref().filter(|tree| !tree.is_empty()) {
+            let entries = withdrawal_index_entries(batch_id, tree, &withdrawals);
+            if let Err(e) = self.db.store_withdrawal_index(&entries) {
+                warn!(
+                    "Failed to store withdrawal index for batch {}: {}",
+                    batch_id, e
+                );
+            }
+        }
 
         // Use pre-computed witness if available (DEV mode builds it before commit),
         // otherwise build it now (production mode - commit hasn't happened yet)
@@ -749,6 +1126,7 @@ impl BatchManager {
         };
 
         // Mark as proving
+        self.proving_batches[batch_idx].withdrawal_root = tree.map(|tree| tree.root());
         self.proving_batches[batch_idx].start_proving();
 
         Some((batch_id, inputs, witness))
@@ -761,9 +1139,21 @@ impl BatchManager {
             .map(|d| d.as_secs())
             .unwrap_or(0);
 
-        for result in results {
+        for (index, result) in results.iter().enumerate() {
+            let (mut shield_amount, mut unshield_amount) = (None, None);
             let (tx_type, amount, from, to) = match &result.tx_type {
-                TxResultType::Shielded { .. } => (TxType::Shielded, None, None, None),
+                TxResultType::Shielded {
+                    shield, unshield, ..
+                } => {
+                    shield_amount = shield.map(|(_, amount)| amount);
+                    unshield_amount = unshield.map(|(_, amount)| amount);
+                    (
+                        TxType::Shielded,
+                        None,
+                        shield.map(|(from, _)| hex::encode(from.0)),
+                        unshield.map(|(to, _)| hex::encode(to.0)),
+                    )
+                }
                 TxResultType::Transfer { from, to, amount } => (
                     TxType::Transfer,
                     Some(*amount),
@@ -798,12 +1188,15 @@ impl BatchManager {
                 tx_hash: hex::encode(&result.tx_hash),
                 tx_type,
                 batch_id: Some(batch_id),
+                batch_index: Some(index as u32),
                 status,
                 received_at: now,
                 executed_at: Some(now),
                 amount,
                 from,
                 to,
+                shield_amount,
+                unshield_amount,
             };
 
             if let Err(e) = self.db.store_tx_summary(&result.tx_hash, &summary) {
@@ -929,6 +1322,7 @@ impl BatchService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use zelana_bridge_events::merkle;
 
     #[test]
     fn test_batch_config_default() {
@@ -973,4 +1367,308 @@ mod tests {
             }));
         assert!(batch.should_seal(&config));
     }
+
+    #[test]
+    fn test_canonical_order_ignores_arrival_order() {
+        let alice = zelana_keypair::Keypair::new_random();
+        let bob = zelana_keypair::Keypair::new_random();
+        let carol = zelana_keypair::Keypair::new_random();
+        let dave = zelana_keypair::Keypair::new_random();
+        let erin = zelana_keypair::Keypair::new_random();
+        let transfer = |from: &zelana_keypair::Keypair, to: AccountId, amount, nonce| {
+            TransactionType::Transfer(from.sign_transaction(zelana_transaction::TransactionData {
+                from: from.account_id(),
+                to,
+                amount,
+                nonce,
+                chain_id: 1,
+                valid_until_batch: None,
+            }))
+        };
+        let txs = vec![
+            transfer(&alice, bob.account_id(), 100, 0),
+            // Bob spends what Alice and Dave send him in the same batch
+            transfer(&bob, carol.account_id(), 1_100, 0),
+            transfer(&alice, carol.account_id(), 20, 2),
+            transfer(&carol, erin.account_id(), 500, 0),
+            transfer(&alice, carol.account_id(), 10, 1),
+            // Dave is only funded by the deposit in the same batch
+            transfer(&dave, bob.account_id(), 250, 0),
+            TransactionType::Deposit(zelana_transaction::DepositEvent {
+                to: dave.account_id(),
+                amount: 300,
+                l1_seq: 7,
+            }),
+        ];
+
+        let seal = |txs: Vec<TransactionType>| {
+            let dir = tempfile::TempDir::new().unwrap();
+            let db = Arc::new(RocksDbStore::open(dir.path()).unwrap());
+            for id in [alice.account_id(), bob.account_id()] {
+                db.set_account_state(
+                    id,
+                    AccountState {
+                        balance: 1_000,
+                        nonce: 0,
+                    },
+                )
+                .unwrap();
+            }
+            let mut manager = BatchManager::new(db, BatchConfig::default()).unwrap();
+            manager.submit_transactions(txs).unwrap();
+            manager.seal_current_batch().unwrap().unwrap();
+
+            let batch = manager.proving_batches.pop().unwrap();
+            assert!(
+                batch.results.iter().all(|r| r.success),
+                "{:?}",
+                batch.results
+            );
+            (
+                wincode::serialize(&batch.transactions).unwrap(),
+                batch.post_state_root.unwrap(),
+            )
+        };
+
+        let expected = seal(txs.clone());
+        let mut reversed = txs.clone();
+        reversed.reverse();
+        assert_eq!(seal(reversed), expected);
+        let mut rotated = txs.clone();
+        rotated.rotate_left(3);
+        assert_eq!(seal(rotated), expected);
+
+        // A credit cycle has no dependency order; the smaller sender goes first
+        let (first, second) = if alice.account_id().0 < bob.account_id().0 {
+            (&alice, &bob)
+        } else {
+            (&bob, &alice)
+        };
+        let cycle = vec![
+            transfer(second, first.account_id(), 5, 0),
+            transfer(first, second.account_id(), 5, 0),
+        ];
+        let mut ordered = cycle.clone();
+        canonical_order(&mut ordered);
+        assert_eq!(
+            wincode::serialize(&ordered).unwrap(),
+            wincode::serialize(&cycle.iter().rev().cloned().collect::<Vec<_>>()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pending_cap_is_per_account() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = Arc::new(RocksDbStore::open(dir.path()).unwrap());
+        let alice = zelana_keypair::Keypair::new_random();
+        let bob = zelana_keypair::Keypair::new_random();
+        let transfer = |from: &zelana_keypair::Keypair, nonce| {
+            TransactionType::Transfer(from.sign_transaction(zelana_transaction::TransactionData {
+                from: from.account_id(),
+                to: AccountId([9; 32]),
+                amount: 1,
+                nonce,
+                chain_id: 1,
+                valid_until_batch: None,
+            }))
+        };
+
+        let config = BatchConfig {
+            max_pending_per_account: 3,
+            ..Default::default()
+        };
+        let mut manager = BatchManager::new(db, config).unwrap();
+        for nonce in 0..3 {
+            manager.submit_transaction(transfer(&alice, nonce)).unwrap();
+        }
+
+        // Alice's fourth pending transaction is refused with a typed error
+        let err = manager.submit_transaction(transfer(&alice, 3)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SubmitError>(),
+            Some(&SubmitError::TooManyPending {
+                account: alice.account_id(),
+                limit: 3,
+            })
+        );
+        assert_eq!(manager.current_batch_tx_count(), 3);
+
+        // Other senders and deposits are unaffected
+        for nonce in 0..3 {
+            manager.submit_transaction(transfer(&bob, nonce)).unwrap();
+        }
+        manager
+            .submit_transaction(TransactionType::Deposit(zelana_transaction::DepositEvent {
+                to: alice.account_id(),
+                amount: 100,
+                l1_seq: 1,
+            }))
+            .unwrap();
+        assert_eq!(manager.current_batch_tx_count(), 7);
+
+        // Executing the batch frees the slots
+        manager.seal_current_batch().unwrap().unwrap();
+        manager.submit_transaction(transfer(&alice, 3)).unwrap();
+    }
+
+    #[test]
+    fn test_batch_ids_recover_after_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = Arc::new(RocksDbStore::open(dir.path()).unwrap());
+        let deposit = |l1_seq| {
+            TransactionType::Deposit(zelana_transaction::DepositEvent {
+                to: zelana_account::AccountId([1; 32]),
+                amount: 100,
+                l1_seq,
+            })
+        };
+
+        // Batch 1 settles, batch 2 is reserved and then lost in a crash
+        let summary = |batch_id| crate::api::types::BatchSummary {
+            batch_id,
+            tx_count: 1,
+            state_root: String::new(),
+            shielded_root: String::new(),
+            l1_tx_sig: None,
+            status: crate::api::types::BatchStatus::Settled,
+            created_at: 0,
+            settled_at: None,
+        };
+        {
+            let mut manager = BatchManager::new(db.clone(), BatchConfig::default()).unwrap();
+            manager.submit_transaction(deposit(1)).unwrap();
+            assert_eq!(manager.seal_current_batch().unwrap(), Some(1));
+            db.store_batch_summary(&summary(1)).unwrap();
+
+            manager.submit_transaction(deposit(2)).unwrap();
+            assert_eq!(db.get_next_batch_id().unwrap(), Some(3));
+        }
+
+        // Restart reclaims the unused id so the sequence stays contiguous
+        let mut manager = BatchManager::new(db.clone(), BatchConfig::default()).unwrap();
+        assert_eq!(manager.stats().next_batch_id, 2);
+        manager.submit_transaction(deposit(2)).unwrap();
+        assert_eq!(manager.seal_current_batch().unwrap(), Some(2));
+        drop(manager);
+
+        // A hole in the settled sequence refuses to start
+        db.store_batch_summary(&summary(4)).unwrap();
+        assert!(BatchManager::new(db, BatchConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_expired_transfer_is_refused_at_admission() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = Arc::new(RocksDbStore::open(dir.path()).unwrap());
+        let alice = zelana_keypair::Keypair::new_random();
+        let transfer = |valid_until_batch| {
+            TransactionType::Transfer(alice.sign_transaction(zelana_transaction::TransactionData {
+                from: alice.account_id(),
+                to: AccountId([9; 32]),
+                amount: 1,
+                nonce: 0,
+                chain_id: 1,
+                valid_until_batch,
+            }))
+        };
+
+        let mut manager = BatchManager::new(db, BatchConfig::default()).unwrap();
+
+        // The first batch is 1, so a transfer valid until batch 0 is stale
+        let err = manager.submit_transaction(transfer(Some(0))).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SubmitError>(),
+            Some(&SubmitError::Expired(TxExpired {
+                valid_until: 0,
+                batch_id: 1,
+            }))
+        );
+        assert_eq!(manager.current_batch_tx_count(), 0);
+
+        manager.submit_transaction(transfer(Some(1))).unwrap();
+        assert_eq!(manager.current_batch_tx_count(), 1);
+    }
+
+    #[test]
+    fn test_rollback_requeues_discarded_transactions() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = Arc::new(RocksDbStore::open(dir.path()).unwrap());
+        let deposit = |l1_seq| {
+            TransactionType::Deposit(zelana_transaction::DepositEvent {
+                to: AccountId([7; 32]),
+                amount: 100,
+                l1_seq,
+            })
+        };
+
+        let mut manager = BatchManager::new(db, BatchConfig::default()).unwrap();
+        let genesis_root = manager.router().transparent_root();
+        manager.submit_transaction(deposit(1)).unwrap();
+        assert_eq!(manager.seal_current_batch().unwrap(), Some(1));
+        manager.submit_transaction(deposit(2)).unwrap();
+
+        // Batch 1 failed to settle: it and the accumulating batch 2 go back in line
+        assert!(manager.can_roll_back(1));
+        assert!(!manager.can_roll_back(2));
+        let txs = manager.rollback_batch(1).unwrap();
+        assert_eq!(txs.len(), 2);
+        assert_eq!(manager.router().transparent_root(), genesis_root);
+
+        manager.requeue_transactions(txs).unwrap();
+        assert_eq!(manager.current_batch_tx_count(), 2);
+        assert_eq!(manager.seal_current_batch().unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_withdrawal_index_tracks_committed_root() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = Arc::new(RocksDbStore::open(dir.path()).unwrap());
+        let alice = zelana_keypair::Keypair::new_random();
+        db.set_account_state(
+            alice.account_id(),
+            AccountState {
+                balance: 1_000,
+                nonce: 0,
+            },
+        )
+        .unwrap();
+
+        let mut manager = BatchManager::new(db.clone(), BatchConfig::default()).unwrap();
+        manager
+            .submit_transaction(TransactionType::Withdraw(
+                alice.sign_withdrawal([42u8; 32], 500, 0),
+            ))
+            .unwrap();
+        assert_eq!(manager.seal_current_batch().unwrap(), Some(1));
+        let tx_hash = manager.proving_batches[0].results[0].tx_hash;
+        assert!(db.get_withdrawal_index(&tx_hash).unwrap().is_none());
+
+        // Indexed when the public inputs are built, against the root they commit to
+        let (_, inputs, _) = manager.prepare_batch_for_proving().unwrap();
+        let committed_root = inputs.unwrap().withdrawal_root;
+        assert_eq!(
+            manager.proving_batches[0].withdrawal_root,
+            Some(committed_root)
+        );
+        let entry = db.get_withdrawal_index(&tx_hash).unwrap().unwrap();
+        assert_eq!(entry.batch_id, 1);
+        assert_eq!(entry.withdrawal_root, hex::encode(committed_root));
+
+        // Its stored path proves the withdrawal's leaf against that root
+        let path: Vec<[u8; 32]> = entry
+            .merkle_path
+            .iter()
+            .map(|node| hex::decode(node).unwrap().try_into().unwrap())
+            .collect();
+        assert!(merkle::verify_withdrawal_proof(
+            merkle::withdrawal_leaf(&tx_hash, &[42u8; 32], 500),
+            entry.leaf_position,
+            &path,
+            committed_root
+        ));
+
+        // A batch that fails to settle takes its index entries with it
+        manager.rollback_batch(1).unwrap();
+        assert!(db.get_withdrawal_index(&tx_hash).unwrap().is_none());
+    }
 }
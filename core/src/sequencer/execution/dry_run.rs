@@ -0,0 +1,83 @@
+//! Dry-run Execution
+//!
+//! Runs submitted transactions through the same signature, nonce and balance
+//! checks as batch execution, against the database as a read-only base.
+//! Resulting state lives only in the router's in-memory cache, so later
+//! transactions see earlier ones (replayed traffic validates in order) while
+//! nothing is ever committed back to RocksDB.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::sequencer::execution::tx_router::{TxResult, TxRouter};
+use crate::sequencer::storage::db::RocksDbStore;
+use zelana_account::{AccountId, AccountState};
+use zelana_transaction::TransactionType;
+
+/// Executes transactions one at a time and discards the writes
+pub struct DryRunExecutor {
+    router: TxRouter,
+    /// Batch the transactions would have landed in (for expiry checks)
+    batch_id: u64,
+    accepted: u64,
+    rejected: u64,
+}
+
+impl DryRunExecutor {
+    /// Load the current state without writing anything back
+    pub fn new(db: Arc<RocksDbStore>) -> Result<Self> {
+        let batch_id = db.get_next_batch_id()?.unwrap_or(1);
+        Ok(Self {
+            router: TxRouter::load(db)?,
+            batch_id,
+            accepted: 0,
+            rejected: 0,
+        })
+    }
+
+    /// Validate and execute `tx`, logging what would have happened
+    pub fn execute(&mut self, tx: TransactionType) -> TxResult {
+        let mut diff = self.router.execute_batch(self.batch_id, vec![tx]);
+        let result = diff
+            .results
+            .pop()
+            .expect("execute_batch yields one result per transaction");
+
+        // Nothing will ever roll back to these changes
+        let snapshot = self.router.snapshot();
+        self.router.release(&snapshot);
+
+        if result.success {
+            self.accepted += 1;
+            log::info!(
+                "[DRY RUN] Would accept {:?} (tx_hash: {})",
+                result.tx_type,
+                hex::encode(result.tx_hash)
+            );
+        } else {
+            self.rejected += 1;
+            log::info!(
+                "[DRY RUN] Would reject tx {}: {}",
+                hex::encode(result.tx_hash),
+                result.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+        result
+    }
+
+    /// Account state after the transactions executed so far, if they touched it
+    pub fn get_pending_account(&self, id: &AccountId) -> Option<AccountState> {
+        self.router.get_pending_account(id).cloned()
+    }
+
+    /// Batch id reported for dry-run transactions
+    pub fn batch_id(&self) -> u64 {
+        self.batch_id
+    }
+
+    /// Transactions accepted and rejected so far
+    pub fn counts(&self) -> (u64, u64) {
+        (self.accepted, self.rejected)
+    }
+}
@@ -5,7 +5,7 @@ use anyhow::Result;
 use log::info;
 use sha2::{Digest, Sha256};
 use std::{collections::HashMap, sync::Arc};
-use zelana_account::{AccountId, AccountState};
+use zelana_account::{AccountId, AccountState, ExecError};
 use zelana_transaction::{SignedTransaction, TransactionData};
 
 /// Compute the state root from a set of accounts
@@ -36,6 +36,17 @@ pub enum ExecutionError {
     InvalidTransaction,
 }
 
+impl From<ExecError> for ExecutionError {
+    fn from(e: ExecError) -> Self {
+        match e {
+            ExecError::InsufficientBalance { .. } => ExecutionError::InsufficientBalance,
+            ExecError::AmountOverflow | ExecError::BalanceOverflow | ExecError::NonceOverflow => {
+                ExecutionError::InvalidTransaction
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StateDiff {
     /// Updated account states
@@ -118,34 +129,23 @@ impl Executor {
             .load_account(&self.db, &from)
             .map_err(|_| ExecutionError::AccountNotFound)?;
 
+        // Validate and apply on copies, so a rejected transfer changes nothing
+        let expected_nonce = from_state.nonce;
+        from_state.apply_transfer(amount, 0)?;
+        if expected_nonce != nonce {
+            return Err(ExecutionError::InvalidNonce);
+        }
+
         if from == to {
             // Self-transfer: only nonce changes
-            if from_state.balance < amount {
-                return Err(ExecutionError::InsufficientBalance);
-            }
-            if from_state.nonce != nonce {
-                return Err(ExecutionError::InvalidNonce);
-            }
-
-            from_state.nonce += 1;
-
+            from_state.apply_credit(amount)?;
             self.state.set_account(from, from_state);
         } else {
             let mut to_state = self
                 .state
                 .load_account(&self.db, &to)
                 .map_err(|_| ExecutionError::AccountNotFound)?;
-
-            if from_state.balance < amount {
-                return Err(ExecutionError::InsufficientBalance);
-            }
-            if from_state.nonce != nonce {
-                return Err(ExecutionError::InvalidNonce);
-            }
-
-            from_state.balance -= amount;
-            from_state.nonce += 1;
-            to_state.balance += amount;
+            to_state.apply_credit(amount)?;
 
             self.state.set_account(from, from_state);
             self.state.set_account(to, to_state);
@@ -1,3 +1,4 @@
 pub mod batch;
+pub mod dry_run;
 pub mod executor;
 pub mod tx_router;
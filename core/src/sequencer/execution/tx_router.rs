@@ -32,7 +32,7 @@ use anyhow::{Context, Result, bail};
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 
 use crate::sequencer::storage::account_tree::{AccountMerklePath, AccountTree};
-use crate::sequencer::storage::db::{DbBatch, RocksDbStore};
+use crate::sequencer::storage::db::{DbBatch, DbRevert, RocksDbStore};
 use crate::sequencer::storage::shielded_state::{ShieldedState, ShieldedStateDiff};
 use crate::storage::StateStore;
 use zelana_account::{AccountId, AccountState};
@@ -64,6 +64,10 @@ pub enum TxResultType {
         nullifier: Nullifier,
         commitment: Commitment,
         position: u32,
+        /// Transparent account debited by a SHIELD, with the amount
+        shield: Option<(AccountId, u64)>,
+        /// Transparent account credited by an UNSHIELD, with the amount
+        unshield: Option<(AccountId, u64)>,
     },
     /// Transfer result
     Transfer {
@@ -104,6 +108,28 @@ pub struct PendingWithdrawal {
     pub l2_nonce: u64,
 }
 
+// Snapshots
+
+/// A point in the router's change-log that state can be rolled back to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouterSnapshot {
+    /// Absolute position in the change-log
+    position: usize,
+}
+
+/// A single reversible mutation made by the router
+#[derive(Debug, Clone)]
+enum ChangeLogEntry {
+    /// Account state before it was overwritten (`None` if it did not exist)
+    Account(AccountId, Option<AccountState>),
+    /// Nullifier marked as spent
+    Nullifier(Nullifier),
+    /// Commitment appended to the note tree
+    Commitment(u32, Commitment),
+    /// Withdrawal queued for L1
+    Withdrawal([u8; 32]),
+}
+
 // Transaction Router
 
 /// The main transaction execution router
@@ -115,6 +141,10 @@ pub struct TxRouter {
     shielded_state: ShieldedState,
     /// Transparent account Merkle tree (for ZK proofs)
     account_tree: AccountTree,
+    /// Mutations since the oldest live snapshot
+    change_log: Vec<ChangeLogEntry>,
+    /// Absolute position of `change_log[0]`
+    change_log_base: usize,
 }
 
 impl TxRouter {
@@ -125,6 +155,8 @@ impl TxRouter {
             account_cache: HashMap::new(),
             shielded_state,
             account_tree: AccountTree::new(),
+            change_log: Vec::new(),
+            change_log_base: 0,
         }
     }
 
@@ -155,13 +187,18 @@ impl TxRouter {
 
     /// Execute a batch of transactions
     ///
-    /// Returns a BatchDiff that can be atomically committed
-    pub fn execute_batch(&mut self, transactions: Vec<TransactionType>) -> BatchDiff {
+    /// `batch_id` is the batch being built; transfers that expired before it
+    /// are rejected. Returns a BatchDiff that can be atomically committed
+    pub fn execute_batch(
+        &mut self,
+        batch_id: u64,
+        transactions: Vec<TransactionType>,
+    ) -> BatchDiff {
         let mut diff = BatchDiff::default();
 
         for tx in transactions {
-            let tx_hash = self.compute_tx_hash(&tx);
-            let result = self.execute_single(tx, tx_hash, &mut diff);
+            let tx_hash = tx_hash(&tx);
+            let result = self.execute_single(tx, tx_hash, batch_id, &mut diff);
 
             match result {
                 Ok(tx_result) => {
@@ -198,13 +235,16 @@ impl TxRouter {
         &mut self,
         tx: TransactionType,
         tx_hash: [u8; 32],
+        batch_id: u64,
         diff: &mut BatchDiff,
     ) -> Result<TxResult> {
         match tx {
             TransactionType::Shielded(private_tx) => {
                 self.execute_shielded(private_tx, tx_hash, diff)
             }
-            TransactionType::Transfer(signed_tx) => self.execute_transfer(signed_tx, tx_hash),
+            TransactionType::Transfer(signed_tx) => {
+                self.execute_transfer(signed_tx, tx_hash, batch_id)
+            }
             TransactionType::Deposit(deposit) => self.execute_deposit(deposit, tx_hash),
             TransactionType::Withdraw(withdraw) => self.execute_withdraw(withdraw, tx_hash, diff),
         }
@@ -274,21 +314,28 @@ impl TxRouter {
             // In production, this would cryptographically verify the proof
         }
 
+        // Amounts are client-supplied: reject an unshield credit that would
+        // overflow before any state is touched
+        if let (Some(to), Some(amount)) = (tx.unshield_to, tx.unshield_amount) {
+            let balance = self.load_account(&AccountId(to))?.balance;
+            if balance.checked_add(amount).is_none() {
+                bail!(
+                    "unshield amount {} overflows balance of {}",
+                    amount,
+                    hex::encode(&to[..8])
+                );
+            }
+        }
+
         // SHIELD: Debit transparent account BEFORE adding commitment
         // This moves funds from transparent balance into a shielded note
         if let (Some(from), Some(amount)) = (tx.shield_from, tx.shield_amount) {
             let account_id = AccountId(from);
             let mut state = self.load_account(&account_id)?;
-            if state.balance < amount {
-                bail!(
-                    "insufficient balance for shield: has {}, needs {}",
-                    state.balance,
-                    amount
-                );
+            if let Err(e) = state.apply_transfer(amount, 0) {
+                bail!("shield rejected: {}", e);
             }
-            state.balance -= amount;
-            state.nonce += 1;
-            self.account_cache.insert(account_id, state);
+            self.set_account(account_id, state)?;
             log::info!(
                 "SHIELD: debited {} lamports from {}",
                 amount,
@@ -298,20 +345,23 @@ impl TxRouter {
 
         // Add commitment to shielded state
         let position = self.shielded_state.insert_commitment(commitment);
+        self.change_log
+            .push(ChangeLogEntry::Commitment(position, commitment));
 
         // Mark nullifier as spent (in memory - will persist on commit)
         self.shielded_state
             .spend_nullifier(nullifier)
             .context("failed to mark nullifier")?;
+        self.change_log.push(ChangeLogEntry::Nullifier(nullifier));
 
         // UNSHIELD: Credit transparent account AFTER spending nullifier
         // This moves funds from a shielded note back to transparent balance
         if let (Some(to), Some(amount)) = (tx.unshield_to, tx.unshield_amount) {
             let account_id = AccountId(to);
             let mut state = self.load_account(&account_id)?;
-            state.balance += amount;
-            // No nonce increment for credit (receiving funds)
-            self.account_cache.insert(account_id, state);
+            // Overflow was ruled out above
+            state.apply_credit(amount)?;
+            self.set_account(account_id, state)?;
             log::info!(
                 "UNSHIELD: credited {} lamports to {}",
                 amount,
@@ -339,6 +389,14 @@ impl TxRouter {
                 nullifier,
                 commitment,
                 position,
+                shield: tx
+                    .shield_from
+                    .zip(tx.shield_amount)
+                    .map(|(from, amount)| (AccountId(from), amount)),
+                unshield: tx
+                    .unshield_to
+                    .zip(tx.unshield_amount)
+                    .map(|(to, amount)| (AccountId(to), amount)),
             },
             success: true,
             error: None,
@@ -346,7 +404,12 @@ impl TxRouter {
     }
 
     /// Execute a standard transfer
-    fn execute_transfer(&mut self, tx: SignedTransaction, tx_hash: [u8; 32]) -> Result<TxResult> {
+    fn execute_transfer(
+        &mut self,
+        tx: SignedTransaction,
+        tx_hash: [u8; 32],
+        batch_id: u64,
+    ) -> Result<TxResult> {
         let from = AccountId(tx.signer_pubkey);
         let to = tx.data.to;
         let amount = tx.data.amount;
@@ -363,6 +426,9 @@ impl TxRouter {
         // Verify Ed25519 signature over the transaction data
         Self::verify_transfer_signature(&tx)?;
 
+        // The expiry is covered by the signature, so it can't be stripped
+        tx.data.check_expiry(batch_id)?;
+
         // Load sender state
         let mut from_state = self.load_account(&from)?;
         log::debug!(
@@ -371,36 +437,22 @@ impl TxRouter {
             from_state.nonce
         );
 
-        // Validate
-        if from_state.balance < amount {
-            bail!(
-                "insufficient balance: has {}, needs {}",
-                from_state.balance,
-                amount
-            );
-        }
-        if from_state.nonce != nonce {
-            bail!(
-                "invalid nonce: expected {}, got {}",
-                from_state.nonce,
-                nonce
-            );
+        // Validate and apply on copies, so a rejected transfer changes nothing
+        let expected_nonce = from_state.nonce;
+        from_state.apply_transfer(amount, 0)?;
+        if expected_nonce != nonce {
+            bail!("invalid nonce: expected {}, got {}", expected_nonce, nonce);
         }
 
-        // Apply transfer
         if from == to {
             // Self-transfer: only nonce changes
-            from_state.nonce += 1;
-            self.account_cache.insert(from, from_state);
+            from_state.apply_credit(amount)?;
+            self.set_account(from, from_state)?;
         } else {
             let mut to_state = self.load_account(&to)?;
+            to_state.apply_credit(amount)?;
 
-            from_state.balance -= amount;
-            from_state.nonce += 1;
-            to_state.balance += amount;
-
-            self.account_cache.insert(from, from_state);
-            self.account_cache.insert(to, to_state);
+            self.set_accounts([(from, from_state), (to, to_state)])?;
         }
 
         Ok(TxResult {
@@ -418,8 +470,8 @@ impl TxRouter {
 
         // Load recipient state and credit
         let mut to_state = self.load_account(&to)?;
-        to_state.balance += amount;
-        self.account_cache.insert(to, to_state);
+        to_state.apply_credit(amount)?;
+        self.set_account(to, to_state)?;
 
         Ok(TxResult {
             tx_hash,
@@ -446,20 +498,19 @@ impl TxRouter {
 
         // Load and validate
         let mut from_state = self.load_account(&from)?;
+        let expected_nonce = from_state.nonce;
 
-        if from_state.balance < amount {
-            bail!("insufficient balance for withdrawal");
+        // Debit immediately (funds locked until L1 settlement)
+        if let Err(e) = from_state.apply_transfer(amount, 0) {
+            bail!("withdrawal rejected: {}", e);
         }
-        if from_state.nonce != nonce {
+        if expected_nonce != nonce {
             bail!("invalid nonce for withdrawal");
         }
-
-        // Debit immediately (funds locked until L1 settlement)
-        from_state.balance -= amount;
-        from_state.nonce += 1;
-        self.account_cache.insert(from, from_state);
+        self.set_account(from, from_state)?;
 
         // Queue withdrawal for L1 settlement
+        self.change_log.push(ChangeLogEntry::Withdrawal(tx_hash));
         diff.withdrawals.push(PendingWithdrawal {
             tx_hash,
             from,
@@ -480,6 +531,38 @@ impl TxRouter {
         })
     }
 
+    /// Write an account into the batch cache, recording its prior state
+    fn set_account(&mut self, id: AccountId, state: AccountState) -> Result<()> {
+        self.set_accounts([(id, state)])
+    }
+
+    /// Write accounts into the batch cache, recording their prior states.
+    ///
+    /// Every prior state is read before anything is written, so a storage
+    /// error fails the transaction without a partial update.
+    fn set_accounts<const N: usize>(
+        &mut self,
+        updates: [(AccountId, AccountState); N],
+    ) -> Result<()> {
+        let mut priors = Vec::with_capacity(N);
+        for (id, _) in &updates {
+            let prior = match self.account_cache.get(id) {
+                Some(prior) => Some(prior.clone()),
+                None => self
+                    .db
+                    .get_account(id)
+                    .context("failed to read prior account state")?,
+            };
+            priors.push(prior);
+        }
+
+        for ((id, state), prior) in updates.into_iter().zip(priors) {
+            self.change_log.push(ChangeLogEntry::Account(id, prior));
+            self.account_cache.insert(id, state);
+        }
+        Ok(())
+    }
+
     /// Load account from cache or database
     fn load_account(&mut self, id: &AccountId) -> Result<AccountState> {
         if let Some(state) = self.account_cache.get(id) {
@@ -491,34 +574,6 @@ impl TxRouter {
         Ok(state)
     }
 
-    /// Compute transaction hash
-    fn compute_tx_hash(&self, tx: &TransactionType) -> [u8; 32] {
-        // Simple hash of serialized transaction
-        let bytes = match tx {
-            TransactionType::Shielded(p) => {
-                let mut data = p.nullifier.to_vec();
-                data.extend_from_slice(&p.commitment);
-                data
-            }
-            TransactionType::Transfer(t) => {
-                let mut data = t.signer_pubkey.to_vec();
-                data.extend_from_slice(&t.data.nonce.to_le_bytes());
-                data
-            }
-            TransactionType::Deposit(d) => {
-                let mut data = d.to.0.to_vec();
-                data.extend_from_slice(&d.l1_seq.to_le_bytes());
-                data
-            }
-            TransactionType::Withdraw(w) => {
-                let mut data = w.from.0.to_vec();
-                data.extend_from_slice(&w.nonce.to_le_bytes());
-                data
-            }
-        };
-        *blake3::hash(&bytes).as_bytes()
-    }
-
     /// Commit a batch diff to persistent storage
     pub fn commit(&mut self, diff: BatchDiff) -> Result<()> {
         let mut db_batch = DbBatch::default();
@@ -569,6 +624,72 @@ impl TxRouter {
         Ok(())
     }
 
+    /// Mark the current state so it can be restored with `rollback_to`
+    pub fn snapshot(&self) -> RouterSnapshot {
+        RouterSnapshot {
+            position: self.change_log_base + self.change_log.len(),
+        }
+    }
+
+    /// Revert account states, nullifiers, commitments and queued withdrawals
+    /// to `snapshot`, both in memory and in the database.
+    ///
+    /// Everything executed before the snapshot must already be committed
+    /// (the settlement-failure case, where all earlier batches have settled):
+    /// the in-memory trees are rebuilt from the reverted database.
+    pub fn rollback_to(&mut self, snapshot: &RouterSnapshot) -> Result<()> {
+        let Some(start) = snapshot.position.checked_sub(self.change_log_base) else {
+            bail!("snapshot has already been released");
+        };
+        if start > self.change_log.len() {
+            bail!("snapshot is ahead of the change-log");
+        }
+
+        // Undo newest-first so each account ends at its earliest prior state
+        let mut revert = DbRevert::default();
+        let mut accounts = HashMap::new();
+        for entry in self.change_log.drain(start..).rev() {
+            match entry {
+                ChangeLogEntry::Account(id, prior) => {
+                    accounts.insert(id, prior);
+                }
+                ChangeLogEntry::Nullifier(nullifier) => revert.nullifiers.push(nullifier),
+                ChangeLogEntry::Commitment(position, commitment) => {
+                    revert.commitments.push((position, commitment.0))
+                }
+                ChangeLogEntry::Withdrawal(tx_hash) => revert.withdrawals.push(tx_hash),
+            }
+        }
+        revert.accounts = accounts.into_iter().collect();
+
+        log::info!(
+            "Rolling back {} accounts, {} nullifiers, {} commitments, {} withdrawals",
+            revert.accounts.len(),
+            revert.nullifiers.len(),
+            revert.commitments.len(),
+            revert.withdrawals.len()
+        );
+        self.db.revert_batch(revert)?;
+
+        // Rebuild in-memory state from the reverted database
+        self.account_cache.clear();
+        self.shielded_state = ShieldedState::load(&self.db)?;
+        self.account_tree = AccountTree::new();
+        self.load_accounts_into_tree()?;
+
+        Ok(())
+    }
+
+    /// Forget changes made before `snapshot` once it can no longer be rolled back to
+    pub fn release(&mut self, snapshot: &RouterSnapshot) {
+        let count = snapshot
+            .position
+            .saturating_sub(self.change_log_base)
+            .min(self.change_log.len());
+        self.change_log.drain(..count);
+        self.change_log_base += count;
+    }
+
     /// Get current shielded state root
     pub fn shielded_root(&self) -> [u8; 32] {
         self.shielded_state.root()
@@ -627,20 +748,26 @@ impl TxRouter {
         amount: u64,
         nonce: u64,
         chain_id: u64,
+        valid_until_batch: Option<u64>,
     ) -> String {
+        // Omitted when unset so messages without an expiry are unchanged
+        let expiry = valid_until_batch
+            .map(|batch| format!("Valid until batch: {}\n", batch))
+            .unwrap_or_default();
         format!(
             "Zelana L2 Transfer\n\n\
              From: {}\n\
              To: {}\n\
              Amount: {} lamports\n\
              Nonce: {}\n\
-             Chain ID: {}\n\n\
+             Chain ID: {}\n{}\n\
              Sign to authorize this L2 transfer.",
             hex::encode(from),
             hex::encode(to),
             amount,
             nonce,
-            chain_id
+            chain_id,
+            expiry
         )
     }
 
@@ -693,6 +820,7 @@ impl TxRouter {
             tx.data.amount,
             tx.data.nonce,
             tx.data.chain_id,
+            tx.data.valid_until_batch,
         );
         let human_readable_bytes = human_readable_msg.as_bytes();
 
@@ -712,7 +840,7 @@ impl TxRouter {
         }
 
         // Fallback to legacy wincode format for backwards compatibility
-        let tx_bytes = wincode::serialize(&tx.data).context("failed to serialize tx data")?;
+        let tx_bytes = tx.data.signing_bytes();
         if verifying_key.verify(&tx_bytes, &signature).is_ok() {
             if tx.data.from.0 != tx.signer_pubkey {
                 bail!(
@@ -793,6 +921,35 @@ impl TxRouter {
     }
 }
 
+/// Transaction hash recorded in the tx's result, and committed to the
+/// withdrawal tree for withdrawals
+pub fn tx_hash(tx: &TransactionType) -> [u8; 32] {
+    // Simple hash of serialized transaction
+    let bytes = match tx {
+        TransactionType::Shielded(p) => {
+            let mut data = p.nullifier.to_vec();
+            data.extend_from_slice(&p.commitment);
+            data
+        }
+        TransactionType::Transfer(t) => {
+            let mut data = t.signer_pubkey.to_vec();
+            data.extend_from_slice(&t.data.nonce.to_le_bytes());
+            data
+        }
+        TransactionType::Deposit(d) => {
+            let mut data = d.to.0.to_vec();
+            data.extend_from_slice(&d.l1_seq.to_le_bytes());
+            data
+        }
+        TransactionType::Withdraw(w) => {
+            let mut data = w.from.0.to_vec();
+            data.extend_from_slice(&w.nonce.to_le_bytes());
+            data
+        }
+    };
+    *blake3::hash(&bytes).as_bytes()
+}
+
 // Serialization for PendingWithdrawal
 
 impl serde::Serialize for PendingWithdrawal {
@@ -958,12 +1115,13 @@ mod tests {
             amount,
             nonce: 0,
             chain_id: 1,
+            valid_until_batch: None,
         };
         let signed_tx = sender.sign_transaction(tx_data);
 
         // Execute the transfer
         let transactions = vec![TransactionType::Transfer(signed_tx)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         // Verify success
         assert_eq!(diff.results.len(), 1);
@@ -1021,6 +1179,7 @@ mod tests {
             amount: 100_000,
             nonce: 0,
             chain_id: 1,
+            valid_until_batch: None,
         };
 
         // But sign with attacker's key
@@ -1030,7 +1189,7 @@ mod tests {
 
         // Execute - should fail signature verification
         let transactions = vec![TransactionType::Transfer(signed_tx)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert_eq!(diff.results.len(), 1);
         assert!(!diff.results[0].success, "Transfer should fail");
@@ -1070,11 +1229,12 @@ mod tests {
             amount: 1_000_000,
             nonce: 0,
             chain_id: 1,
+            valid_until_batch: None,
         };
         let signed_tx = sender.sign_transaction(tx_data);
 
         let transactions = vec![TransactionType::Transfer(signed_tx)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert!(!diff.results[0].success);
         assert!(
@@ -1110,11 +1270,12 @@ mod tests {
             amount: 1_000,
             nonce: 0, // Wrong nonce
             chain_id: 1,
+            valid_until_batch: None,
         };
         let signed_tx = sender.sign_transaction(tx_data);
 
         let transactions = vec![TransactionType::Transfer(signed_tx)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert!(!diff.results[0].success);
         assert!(
@@ -1126,6 +1287,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_transfer_expiry() {
+        let (mut router, db, _temp) = create_test_router();
+
+        let sender = zelana_keypair::Keypair::new_random();
+        let sender_id = sender.account_id();
+        let recipient = zelana_keypair::Keypair::new_random();
+
+        db.set_account_state(
+            sender_id,
+            AccountState {
+                balance: 1_000_000,
+                nonce: 0,
+            },
+        )
+        .unwrap();
+
+        let tx_data = TransactionData {
+            from: sender_id,
+            to: recipient.account_id(),
+            amount: 1_000,
+            nonce: 0,
+            chain_id: 1,
+            valid_until_batch: Some(4),
+        };
+        let signed_tx = sender.sign_transaction(tx_data);
+
+        // Past its last batch
+        let diff = router.execute_batch(5, vec![TransactionType::Transfer(signed_tx.clone())]);
+        assert!(!diff.results[0].success);
+        assert!(diff.results[0].error.as_ref().unwrap().contains("expired"));
+
+        // Dropping the expiry invalidates the signature
+        let mut stripped = signed_tx.clone();
+        stripped.data.valid_until_batch = None;
+        let diff = router.execute_batch(5, vec![TransactionType::Transfer(stripped)]);
+        assert!(!diff.results[0].success);
+        assert!(
+            diff.results[0]
+                .error
+                .as_ref()
+                .unwrap()
+                .contains("signature")
+        );
+
+        // Still valid in its last batch
+        let diff = router.execute_batch(4, vec![TransactionType::Transfer(signed_tx)]);
+        assert!(diff.results[0].success);
+        assert_eq!(diff.account_updates.get(&sender_id).unwrap().nonce, 1);
+    }
+
     #[test]
     fn test_self_transfer() {
         let (mut router, db, _temp) = create_test_router();
@@ -1150,11 +1362,12 @@ mod tests {
             amount: 100,
             nonce: 0,
             chain_id: 1,
+            valid_until_batch: None,
         };
         let signed_tx = sender.sign_transaction(tx_data);
 
         let transactions = vec![TransactionType::Transfer(signed_tx)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert!(diff.results[0].success);
         let state = diff.account_updates.get(&sender_id).unwrap();
@@ -1190,7 +1403,7 @@ mod tests {
         let withdraw_req = sender.sign_withdrawal(l1_address, amount, 0);
 
         let transactions = vec![TransactionType::Withdraw(withdraw_req)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert_eq!(diff.results.len(), 1);
         assert!(
@@ -1234,7 +1447,7 @@ mod tests {
         withdraw_req.signer_pubkey = sender_id.0;
 
         let transactions = vec![TransactionType::Withdraw(withdraw_req)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert!(!diff.results[0].success);
         assert!(
@@ -1265,7 +1478,7 @@ mod tests {
         let withdraw_req = sender.sign_withdrawal([42u8; 32], 1_000_000, 0);
 
         let transactions = vec![TransactionType::Withdraw(withdraw_req)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert!(!diff.results[0].success);
         assert!(
@@ -1300,7 +1513,7 @@ mod tests {
         };
 
         let transactions = vec![TransactionType::Shielded(private_tx)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert_eq!(diff.results.len(), 1);
         assert!(
@@ -1332,7 +1545,7 @@ mod tests {
         };
 
         let transactions = vec![TransactionType::Shielded(private_tx)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert!(!diff.results[0].success);
         assert!(
@@ -1344,6 +1557,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unshield_overflow_rejected_without_state_change() {
+        let (mut router, db, _temp) = create_test_router();
+
+        let recipient = [7u8; 32];
+        db.set_account_state(
+            AccountId(recipient),
+            AccountState {
+                balance: 1,
+                nonce: 0,
+            },
+        )
+        .unwrap();
+
+        let private_tx = PrivateTransaction {
+            proof: vec![1, 2, 3, 4],
+            nullifier: [1u8; 32],
+            commitment: [2u8; 32],
+            ciphertext: vec![5, 6, 7, 8],
+            ephemeral_key: [9u8; 32],
+            nonce: None,
+            shield_from: None,
+            shield_amount: None,
+            unshield_to: Some(recipient),
+            unshield_amount: Some(u64::MAX),
+        };
+
+        let diff = router.execute_batch(1, vec![TransactionType::Shielded(private_tx)]);
+
+        assert!(!diff.results[0].success);
+        assert!(
+            diff.results[0]
+                .error
+                .as_ref()
+                .unwrap()
+                .contains("overflows")
+        );
+        assert!(diff.shielded_diff.spent_nullifiers.is_empty());
+        assert!(diff.shielded_diff.new_commitments.is_empty());
+    }
+
     #[test]
     fn test_shielded_tx_double_spend() {
         let (mut router, _, _temp) = create_test_router();
@@ -1382,7 +1636,7 @@ mod tests {
             TransactionType::Shielded(tx1),
             TransactionType::Shielded(tx2),
         ];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         // First should succeed
         assert!(diff.results[0].success, "First tx should succeed");
@@ -1412,7 +1666,7 @@ mod tests {
         };
 
         let transactions = vec![TransactionType::Deposit(deposit)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert!(diff.results[0].success);
 
@@ -1445,7 +1699,7 @@ mod tests {
         };
 
         let transactions = vec![TransactionType::Deposit(deposit)];
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         assert!(diff.results[0].success);
 
@@ -1488,6 +1742,7 @@ mod tests {
             amount: 1_000_000,
             nonce: 0,
             chain_id: 1,
+            valid_until_batch: None,
         };
         let transfer = alice.sign_transaction(transfer_data);
 
@@ -1510,7 +1765,7 @@ mod tests {
             TransactionType::Shielded(shielded),
         ];
 
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         // All 3 should succeed
         assert_eq!(diff.results.len(), 3);
@@ -1555,6 +1810,7 @@ mod tests {
             amount: 1_000_000,
             nonce: 0,
             chain_id: 1,
+            valid_until_batch: None,
         });
 
         let tx2 = sender.sign_transaction(TransactionData {
@@ -1563,6 +1819,7 @@ mod tests {
             amount: 2_000_000,
             nonce: 1, // Incremented nonce
             chain_id: 1,
+            valid_until_batch: None,
         });
 
         let tx3 = sender.sign_transaction(TransactionData {
@@ -1571,6 +1828,7 @@ mod tests {
             amount: 500_000,
             nonce: 2,
             chain_id: 1,
+            valid_until_batch: None,
         });
 
         let transactions = vec![
@@ -1579,7 +1837,7 @@ mod tests {
             TransactionType::Transfer(tx3),
         ];
 
-        let diff = router.execute_batch(transactions);
+        let diff = router.execute_batch(1, transactions);
 
         // All should succeed
         for result in &diff.results {
@@ -1600,4 +1858,86 @@ mod tests {
         assert_eq!(sender_state.nonce, 3);
         assert_eq!(recipient_state.balance, 1_000_000 + 2_000_000 + 500_000);
     }
+
+    // Snapshot Tests
+
+    #[test]
+    fn test_rollback_to_snapshot() {
+        let (mut router, db, _temp) = create_test_router();
+        let alice = AccountId([1u8; 32]);
+        let bob = AccountId([2u8; 32]);
+        let deposit =
+            |to, amount, l1_seq| TransactionType::Deposit(DepositEvent { to, amount, l1_seq });
+
+        // First batch is committed before the snapshot
+        let diff = router.execute_batch(1, vec![deposit(alice, 1_000, 1)]);
+        router.commit(diff).unwrap();
+
+        let snapshot = router.snapshot();
+        let transparent_root = router.transparent_root();
+        let shielded_root = router.shielded_root();
+
+        // Second batch touches an existing account, a new account and the shielded pool
+        let diff = router.execute_batch(
+            1,
+            vec![
+                deposit(alice, 500, 2),
+                deposit(bob, 250, 3),
+                TransactionType::Shielded(PrivateTransaction {
+                    proof: vec![1, 2, 3, 4],
+                    nullifier: [7u8; 32],
+                    commitment: [8u8; 32],
+                    ciphertext: vec![5, 6, 7, 8],
+                    ephemeral_key: [9u8; 32],
+                    nonce: None,
+                    shield_from: None,
+                    shield_amount: None,
+                    unshield_to: None,
+                    unshield_amount: None,
+                }),
+            ],
+        );
+        assert!(diff.results.iter().all(|r| r.success));
+        router.commit(diff).unwrap();
+        assert_ne!(router.transparent_root(), transparent_root);
+        assert_ne!(router.shielded_root(), shielded_root);
+
+        router.rollback_to(&snapshot).unwrap();
+
+        assert_eq!(db.get_account_state(&alice).unwrap().balance, 1_000);
+        assert_eq!(db.get_account(&bob).unwrap(), None);
+        assert!(!db.nullifier_exists(&[7u8; 32]).unwrap());
+        assert_eq!(db.get_commitment(0).unwrap(), None);
+        assert!(db.get_encrypted_note(&[8u8; 32]).unwrap().is_none());
+        assert_eq!(router.transparent_root(), transparent_root);
+        assert_eq!(router.shielded_root(), shielded_root);
+        assert!(
+            !router
+                .shielded_state()
+                .nullifier_exists(&Nullifier([7u8; 32]))
+        );
+
+        // The nullifier can be spent again after the rollback
+        let diff = router.execute_batch(
+            1,
+            vec![TransactionType::Shielded(PrivateTransaction {
+                proof: vec![1, 2, 3, 4],
+                nullifier: [7u8; 32],
+                commitment: [8u8; 32],
+                ciphertext: vec![],
+                ephemeral_key: [9u8; 32],
+                nonce: None,
+                shield_from: None,
+                shield_amount: None,
+                unshield_to: None,
+                unshield_amount: None,
+            })],
+        );
+        assert!(diff.results[0].success);
+
+        // Released snapshots cannot be rolled back to
+        let later = router.snapshot();
+        router.release(&later);
+        assert!(router.rollback_to(&snapshot).is_err());
+    }
 }
@@ -17,7 +17,7 @@
 use std::sync::Arc;
 
 use anyhow::{Context, Result, bail};
-use log::{debug, info};
+use log::{debug, info, warn};
 use tokio::sync::Mutex;
 
 use zelana_threshold::{
@@ -49,6 +49,15 @@ impl Default for EncryptedMempoolConfig {
     }
 }
 
+/// How the committee is provisioned at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitteeProvisioning {
+    /// `threshold_dev`: this process generates and holds every member's key
+    InsecureDealer,
+    /// Committee keys are installed by DKG via `initialize_committee`
+    Dkg,
+}
+
 /// State for managing the encrypted mempool
 pub struct ThresholdMempoolState {
     config: EncryptedMempoolConfig,
@@ -60,6 +69,8 @@ pub struct ThresholdMempoolState {
     coordinator: DecryptionCoordinator,
     /// Local committee member (if this node is a committee member)
     local_member: Option<LocalCommitteeMember>,
+    /// Every member's key, held only in insecure dealer mode
+    dealer_members: Vec<LocalCommitteeMember>,
 }
 
 impl ThresholdMempoolState {
@@ -72,6 +83,7 @@ impl ThresholdMempoolState {
             mempool: EncryptedMempool::new(),
             coordinator: DecryptionCoordinator::new(threshold),
             local_member: None,
+            dealer_members: Vec::new(),
         }
     }
 
@@ -164,6 +176,52 @@ impl ThresholdMempoolState {
         local_member.decrypt_share(our_share)
     }
 
+    /// Decrypt with shares from every dealer-held key (insecure dealer mode only)
+    pub fn dealer_decrypt(&mut self, encrypted_tx: &EncryptedTransaction) -> Result<Vec<u8>> {
+        if self.dealer_members.is_empty() {
+            bail!("Dealer decryption is only available with threshold_dev");
+        }
+
+        for member in &self.dealer_members {
+            let share = encrypted_tx
+                .encrypted_shares
+                .iter()
+                .find(|s| s.member_id == member.id)
+                .and_then(|s| member.decrypt_share(s));
+            if let Some(share) = share {
+                self.coordinator.submit_share(encrypted_tx.tx_id, share);
+            }
+        }
+        self.try_decrypt(encrypted_tx)
+    }
+
+    /// Order every pending transaction and decrypt it with the dealer keys
+    ///
+    /// The insecure-dealer stand-in for the committee decrypting at batch
+    /// seal. Returns plaintexts in committed order; transactions that fail
+    /// to decrypt are logged and dropped. Without dealer keys nothing is
+    /// ordered, so pending transactions wait for the committee.
+    pub fn dealer_drain(&mut self, batch_id: u64) -> Vec<Vec<u8>> {
+        if self.dealer_members.is_empty() || self.mempool.pending_count() == 0 {
+            return Vec::new();
+        }
+
+        let ordered = self.order_for_batch(batch_id);
+        let mut plaintexts = Vec::with_capacity(ordered.len());
+        for tx in &ordered {
+            match self.dealer_decrypt(&tx.encrypted_tx) {
+                Ok(plaintext) => plaintexts.push(plaintext),
+                Err(e) => warn!(
+                    "Dropping encrypted tx {}: {:#}",
+                    hex::encode(tx.encrypted_tx.tx_id),
+                    e
+                ),
+            }
+        }
+        self.clear_batch(batch_id);
+        plaintexts
+    }
+
     /// Get pending count
     pub fn pending_count(&self) -> usize {
         self.mempool.pending_count()
@@ -200,6 +258,35 @@ impl ThresholdMempoolManager {
         info!("Threshold mempool committee initialized");
     }
 
+    /// Provision the committee according to `features.threshold_dev`.
+    ///
+    /// With the flag on, this process is a trusted dealer: it generates all N
+    /// member keys, skipping DKG, and can decrypt any transaction by itself.
+    /// That defeats the encrypted mempool and is for local development only.
+    /// With the flag off, nothing is generated and the committee waits for DKG.
+    pub async fn provision(&self, threshold_dev: bool) -> CommitteeProvisioning {
+        if !threshold_dev {
+            info!("Threshold committee not initialized, waiting for DKG");
+            return CommitteeProvisioning::Dkg;
+        }
+
+        let mut state = self.inner.lock().await;
+        let (committee, members) =
+            create_test_committee(state.config.threshold, state.config.total_members);
+        warn!("==============================================================");
+        warn!("  INSECURE: threshold_dev is enabled");
+        warn!(
+            "  This node holds all {} committee keys (trusted dealer, no DKG)",
+            members.len()
+        );
+        warn!("  Encrypted transactions are NOT private. Never use in production.");
+        warn!("==============================================================");
+        state.committee = Some(committee);
+        state.local_member = members.first().cloned();
+        state.dealer_members = members;
+        CommitteeProvisioning::InsecureDealer
+    }
+
     /// Set local committee member
     pub async fn set_local_member(&self, member: LocalCommitteeMember) {
         let mut state = self.inner.lock().await;
@@ -237,6 +324,11 @@ impl ThresholdMempoolManager {
         self.inner.lock().await.decrypt_local_share(encrypted_tx)
     }
 
+    /// Order and decrypt pending txs with the dealer keys (insecure dealer mode only)
+    pub async fn dealer_drain(&self, batch_id: u64) -> Vec<Vec<u8>> {
+        self.inner.lock().await.dealer_drain(batch_id)
+    }
+
     /// Get pending count
     pub async fn pending_count(&self) -> usize {
         self.inner.lock().await.pending_count()
@@ -319,4 +411,51 @@ mod tests {
             .expect("decryption failed");
         assert_eq!(decrypted, plaintext);
     }
+
+    fn dev_config() -> EncryptedMempoolConfig {
+        EncryptedMempoolConfig {
+            enabled: true,
+            threshold: 2,
+            total_members: 3,
+            max_pending: 100,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_threshold_dev_dealer_decrypts_locally() {
+        let manager = ThresholdMempoolManager::new(dev_config());
+        assert_eq!(
+            manager.provision(true).await,
+            CommitteeProvisioning::InsecureDealer
+        );
+        assert!(manager.is_active().await);
+
+        let committee = manager.committee().await.unwrap();
+        let plaintext = b"dev transaction";
+        let encrypted = encrypt_for_committee(plaintext, &committee, None).unwrap();
+        manager.add_encrypted_tx(encrypted).await.unwrap();
+
+        assert_eq!(manager.dealer_drain(1).await, vec![plaintext.to_vec()]);
+        assert_eq!(manager.pending_count().await, 0);
+        assert!(manager.dealer_drain(2).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_insecure_dealer_unused_without_threshold_dev() {
+        let manager = ThresholdMempoolManager::new(dev_config());
+        assert_eq!(manager.provision(false).await, CommitteeProvisioning::Dkg);
+        assert!(!manager.is_active().await);
+        assert!(manager.committee().await.is_none());
+
+        // A committee installed by DKG still gives this node no dealer keys
+        let (committee, _) = create_test_committee(2, 3);
+        manager.initialize_committee(committee.clone()).await;
+        let encrypted = encrypt_for_committee(b"tx", &committee, None).unwrap();
+        manager.add_encrypted_tx(encrypted).await.unwrap();
+        assert_eq!(manager.pending_count().await, 1);
+        assert!(manager.dealer_drain(1).await.is_empty());
+
+        // The transaction is neither ordered nor dropped
+        assert_eq!(manager.pending_count().await, 1);
+    }
 }
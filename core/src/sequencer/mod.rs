@@ -8,23 +8,23 @@ pub mod storage;
 pub use storage::db::RocksDbStore;
 pub use storage::shielded_state::ShieldedState;
 
-pub use execution::batch::{Batch, BatchConfig, BatchManager};
+pub use execution::batch::{Batch, BatchConfig, BatchManager, SubmitError};
 pub use execution::tx_router::{PendingWithdrawal, TxResult};
 
 pub use mempool::threshold_mempool::{
-    EncryptedMempoolConfig, ThresholdMempoolManager, create_test_committee,
+    CommitteeProvisioning, EncryptedMempoolConfig, ThresholdMempoolManager, create_test_committee,
 };
 
 pub use bridge::fast_withdrawals::{FastWithdrawConfig, FastWithdrawManager};
 pub use bridge::ingest::{IndexerConfig, start_indexer_with_pipeline};
 pub use bridge::withdrawals::{
-    TrackedWithdrawal, WithdrawalQueue, WithdrawalState, build_withdrawal_merkle_root,
+    TrackedWithdrawal, WithdrawalQueue, WithdrawalState, build_withdrawal_tree,
 };
 
 pub use settlement::prover::BatchProof;
 pub use settlement::settler::SettlerConfig;
 
-pub use pipeline::{PipelineConfig, PipelineService, ProverMode};
+pub use pipeline::{PipelineConfig, PipelineReload, PipelineService, ProverMode, ProvingBackend};
 
 #[cfg(test)]
 mod tests;
@@ -35,11 +35,8 @@ use tokio::sync::{Mutex, mpsc, oneshot};
 use tracing::{debug, error, info, warn};
 
 use crate::api::types::{BatchStatus, BatchSummary, TxStatus};
-use crate::sequencer::bridge::withdrawals::{
-    TrackedWithdrawal, WithdrawalState, build_withdrawal_merkle_root,
-};
 use crate::sequencer::execution::batch::{BatchConfig, BatchManager, BatchManagerStats};
-use crate::sequencer::execution::tx_router::TxResultType;
+use crate::sequencer::execution::dry_run::DryRunExecutor;
 use crate::sequencer::settlement::noir_client::{NoirProverClient, NoirProverConfig};
 use crate::sequencer::settlement::prover::compute_batch_hash;
 use crate::sequencer::settlement::prover::{
@@ -54,6 +51,9 @@ use zelana_transaction::TransactionType;
 
 // Configuration
 
+/// How often old transaction summaries are pruned (when retention is set)
+const TX_COMPACTION_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Prover mode selection
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum ProverMode {
@@ -66,11 +66,31 @@ pub enum ProverMode {
     Noir,
 }
 
+/// Where batch proofs are produced
+///
+/// `Integrated` keeps proving inside the sequencer process (Mock or Groth16).
+/// It needs no extra services, but a real proof occupies a blocking worker
+/// and competes with execution for CPU.
+///
+/// `Coordinator` hands batches to the external prover coordinator over HTTP
+/// (Noir/Sunspot). Proving can scale out across workers, at the cost of a
+/// network hop and a separate service to operate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProvingBackend {
+    /// Prove in-process (default)
+    #[default]
+    Integrated,
+    /// Submit batches to the prover coordinator
+    Coordinator,
+}
+
 /// Pipeline configuration
 #[derive(Debug, Clone)]
 pub struct PipelineConfig {
     /// Prover mode selection (Mock, Groth16, or Noir)
     pub prover_mode: ProverMode,
+    /// Whether batches are proved in-process or by the coordinator
+    pub proving_backend: ProvingBackend,
     /// Path to proving key (for Groth16 mode)
     pub proving_key_path: Option<String>,
     /// Path to verifying key (for Groth16 mode)
@@ -93,12 +113,17 @@ pub struct PipelineConfig {
     pub settler_config: Option<SettlerConfig>,
     /// Dev mode - enables immediate state commit on seal (bypasses prove/settle)
     pub dev_mode: bool,
+    /// Keep transaction summaries for only the last N batches (None keeps all)
+    pub tx_retention_batches: Option<u64>,
+    /// Validate submitted transactions without batching or persisting them
+    pub dry_run: bool,
 }
 
 impl Default for PipelineConfig {
     fn default() -> Self {
         Self {
             prover_mode: ProverMode::Mock,
+            proving_backend: ProvingBackend::Integrated,
             proving_key_path: None,
             verifying_key_path: None,
             noir_coordinator_url: None,
@@ -111,10 +136,37 @@ impl Default for PipelineConfig {
             batch_config: BatchConfig::default(),
             settler_config: None,
             dev_mode: false,
+            tx_retention_batches: None,
+            dry_run: false,
         }
     }
 }
 
+impl PipelineConfig {
+    /// Backend that will actually be used.
+    ///
+    /// Noir proofs are only produced by the coordinator, so `prover_mode = noir`
+    /// implies `Coordinator` even when the backend was left at its default.
+    pub fn resolved_backend(&self) -> ProvingBackend {
+        match self.prover_mode {
+            ProverMode::Noir => ProvingBackend::Coordinator,
+            _ => self.proving_backend,
+        }
+    }
+}
+
+/// Settings a running pipeline picks up on config reload
+///
+/// Mirrors the live-applicable fields of `ZelanaConfig::diff`; everything
+/// else in [`PipelineConfig`] only changes on restart.
+#[derive(Debug, Clone)]
+pub struct PipelineReload {
+    pub poll_interval_ms: u64,
+    pub max_settlement_retries: u32,
+    pub settlement_retry_base_ms: u64,
+    pub batch_config: BatchConfig,
+}
+
 // Pipeline State
 
 /// Pipeline operational state
@@ -147,6 +199,10 @@ pub struct PipelineStats {
     pub prover_busy: bool,
     /// Whether settler is currently working
     pub settler_busy: bool,
+    /// Resolved proving backend
+    pub proving_backend: ProvingBackend,
+    /// Name of the active prover client
+    pub prover_name: &'static str,
 }
 
 /// Result of sealing a batch (for dev/testing)
@@ -176,6 +232,8 @@ pub enum PipelineCommand {
     Pause(String, oneshot::Sender<()>),
     /// Resume the pipeline
     Resume(oneshot::Sender<Result<()>>),
+    /// Apply reloaded settings
+    Reload(PipelineReload, oneshot::Sender<()>),
     /// Shutdown
     Shutdown,
 }
@@ -202,6 +260,9 @@ pub struct PipelineOrchestrator {
     settling_batch: Option<u64>,
     /// Settlement retry count for current batch
     settlement_retries: u32,
+    /// Failed batch waiting for earlier batches to finalize before it can
+    /// be rolled back (batch_id, failure reason)
+    pending_rollback: Option<(u64, String)>,
 }
 
 impl PipelineOrchestrator {
@@ -213,72 +274,7 @@ impl PipelineOrchestrator {
     ) -> Result<Self> {
         let batch_manager = BatchManager::new(db.clone(), config.batch_config.clone())?;
 
-        // Create prover based on config
-        let prover: Arc<dyn BatchProver> = match &config.prover_mode {
-            ProverMode::Mock => {
-                info!("Using MockProver for batch proving");
-                Arc::new(MockProver::new())
-            }
-            ProverMode::Groth16 => {
-                // Try to load real Groth16 prover from key files
-                match (&config.proving_key_path, &config.verifying_key_path) {
-                    (Some(pk_path), Some(vk_path)) => {
-                        info!("Loading Groth16 prover from key files");
-                        info!("  Proving key:   {}", pk_path);
-                        info!("  Verifying key: {}", vk_path);
-                        match Groth16Prover::from_files(pk_path, vk_path) {
-                            Ok(prover) => {
-                                info!("Groth16 prover initialized successfully");
-                                Arc::new(prover)
-                            }
-                            Err(e) => {
-                                warn!(
-                                    "Failed to load Groth16 prover: {}. Falling back to MockProver",
-                                    e
-                                );
-                                Arc::new(MockProver::new())
-                            }
-                        }
-                    }
-                    _ => {
-                        warn!(
-                            "Groth16 prover requested but key paths not configured. \
-                            Set ZL_PROVING_KEY and ZL_VERIFYING_KEY environment variables. \
-                            Using MockProver instead."
-                        );
-                        Arc::new(MockProver::new())
-                    }
-                }
-            }
-            ProverMode::Noir => {
-                // Create Noir prover client for HTTP coordinator
-                match &config.noir_coordinator_url {
-                    Some(url) => {
-                        info!("Using Noir prover via HTTP coordinator");
-                        info!("  Coordinator URL: {}", url);
-                        let mut noir_config = NoirProverConfig::default();
-                        noir_config.coordinator_url = url.clone();
-                        if let Some(timeout_secs) = config.noir_proof_timeout_secs {
-                            noir_config.proof_timeout =
-                                std::time::Duration::from_secs(timeout_secs);
-                        }
-                        info!(
-                            "  Proof timeout:   {} seconds",
-                            noir_config.proof_timeout.as_secs()
-                        );
-                        Arc::new(NoirProverClient::new(noir_config))
-                    }
-                    None => {
-                        warn!(
-                            "Noir prover requested but coordinator URL not configured. \
-                            Set ZL_NOIR_COORDINATOR_URL environment variable. \
-                            Using MockProver instead."
-                        );
-                        Arc::new(MockProver::new())
-                    }
-                }
-            }
-        };
+        let prover = Self::build_prover(&config);
 
         // Create settler based on config
         let (mock_settler, settler_svc) = if config.settlement_enabled {
@@ -337,9 +333,87 @@ impl PipelineOrchestrator {
             proving_batch: None,
             settling_batch: None,
             settlement_retries: 0,
+            pending_rollback: None,
         })
     }
 
+    /// Select the prover client for the resolved proving backend
+    fn build_prover(config: &PipelineConfig) -> Arc<dyn BatchProver> {
+        match config.resolved_backend() {
+            ProvingBackend::Coordinator => Self::build_coordinator_prover(config),
+            ProvingBackend::Integrated => Self::build_integrated_prover(config),
+        }
+    }
+
+    /// In-process prover (Mock or Groth16)
+    fn build_integrated_prover(config: &PipelineConfig) -> Arc<dyn BatchProver> {
+        match config.prover_mode {
+            ProverMode::Groth16 => {
+                // Try to load real Groth16 prover from key files
+                match (&config.proving_key_path, &config.verifying_key_path) {
+                    (Some(pk_path), Some(vk_path)) => {
+                        info!("Loading Groth16 prover from key files");
+                        info!("  Proving key:   {}", pk_path);
+                        info!("  Verifying key: {}", vk_path);
+                        match Groth16Prover::from_files(pk_path, vk_path) {
+                            Ok(prover) => {
+                                info!("Groth16 prover initialized successfully");
+                                Arc::new(prover)
+                            }
+                            Err(e) => {
+                                warn!(
+                                    "Failed to load Groth16 prover: {}. Falling back to MockProver",
+                                    e
+                                );
+                                Arc::new(MockProver::new())
+                            }
+                        }
+                    }
+                    _ => {
+                        warn!(
+                            "Groth16 prover requested but key paths not configured. \
+                            Set ZL_PROVING_KEY and ZL_VERIFYING_KEY environment variables. \
+                            Using MockProver instead."
+                        );
+                        Arc::new(MockProver::new())
+                    }
+                }
+            }
+            _ => {
+                info!("Using MockProver for batch proving");
+                Arc::new(MockProver::new())
+            }
+        }
+    }
+
+    /// HTTP client for the external prover coordinator
+    fn build_coordinator_prover(config: &PipelineConfig) -> Arc<dyn BatchProver> {
+        match &config.noir_coordinator_url {
+            Some(url) => {
+                info!("Using Noir prover via HTTP coordinator");
+                info!("  Coordinator URL: {}", url);
+                let mut noir_config = NoirProverConfig::default();
+                noir_config.coordinator_url = url.clone();
+                if let Some(timeout_secs) = config.noir_proof_timeout_secs {
+                    noir_config.proof_timeout = std::time::Duration::from_secs(timeout_secs);
+                }
+                info!(
+                    "  Proof timeout:   {} seconds",
+                    noir_config.proof_timeout.as_secs()
+                );
+                Arc::new(NoirProverClient::new(noir_config))
+            }
+            None => {
+                warn!(
+                    "Coordinator proving requested but coordinator URL not configured. \
+                    Set ZL_NOIR_COORDINATOR_URL environment variable. \
+                    Using MockProver instead."
+                );
+                Arc::new(MockProver::new())
+            }
+        }
+    }
+
     /// Load keypair from file and create SettlerService
     fn load_keypair_and_create_settler(
         settler_config: SettlerConfig,
@@ -377,6 +451,10 @@ impl PipelineOrchestrator {
             debug!("try_prove: skipping - state is {:?}", self.state);
             return Ok(false);
         }
+        if let Some((batch_id, _)) = &self.pending_rollback {
+            debug!("try_prove: skipping - batch {} awaits rollback", batch_id);
+            return Ok(false);
+        }
 
         let mut manager = self.batch_manager.lock().await;
 
@@ -421,6 +499,8 @@ impl PipelineOrchestrator {
             Err(e) => {
                 error!(batch_id, error = %e, "Proof generation failed");
                 self.proving_batch = None;
+                let reason = format!("Proof generation failed for batch {}: {}", batch_id, e);
+                self.roll_back_failed_batch(batch_id, reason).await;
                 Err(e)
             }
         }
@@ -446,41 +526,13 @@ impl PipelineOrchestrator {
             let tx_hashes: Vec<[u8; 32]> = b.results.iter().map(|r| r.tx_hash).collect();
 
             // Extract withdrawals from transaction results
-            let withdrawals: Vec<TrackedWithdrawal> = b
-                .results
-                .iter()
-                .filter_map(|r| {
-                    if let TxResultType::Withdrawal {
-                        from,
-                        to_l1,
-                        amount,
-                    } = &r.tx_type
-                    {
-                        if r.success {
-                            Some(TrackedWithdrawal {
-                                tx_hash: r.tx_hash,
-                                from: from.clone(),
-                                to_l1_address: *to_l1,
-                                amount: *amount,
-                                l2_nonce: 0, // Not tracked in result, but not needed for L1
-                                state: WithdrawalState::InBatch { batch_id },
-                                created_at: std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap_or_default()
-                                    .as_secs(),
-                                batch_id: Some(batch_id),
-                            })
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+            let withdrawals = b.withdrawals();
 
-            // Compute withdrawal merkle root
-            let withdrawal_root = build_withdrawal_merkle_root(&withdrawals);
+            // The withdrawal root the proof committed to, recorded when its
+            // public inputs were built
+            let Some(withdrawal_root) = b.withdrawal_root else {
+                return Err(batch_id);
+            };
 
             // Compute batch hash
             let batch_hash = compute_batch_hash(&b.transactions);
@@ -498,7 +550,7 @@ impl PipelineOrchestrator {
                 proof_bytes: b.proof.clone().unwrap_or_default(),
                 proving_time_ms: 0,
             };
-            (
+            Ok((
                 batch_id,
                 proof,
                 withdrawals,
@@ -506,12 +558,12 @@ impl PipelineOrchestrator {
                 tx_hashes,
                 post_state_root,
                 post_shielded_root,
-            )
+            ))
         });
 
         drop(manager);
 
-        let Some((
+        let (
             batch_id,
             proof,
             withdrawals,
@@ -519,9 +571,15 @@ impl PipelineOrchestrator {
             tx_hashes,
             post_state_root,
             post_shielded_root,
-        )) = batch_to_settle
-        else {
-            return Ok(false);
+        ) = match batch_to_settle {
+            None => return Ok(false),
+            Some(Ok(batch)) => batch,
+            Some(Err(batch_id)) => {
+                // Settling against a made-up root would strand its withdrawals
+                let reason = format!("Batch {} has no committed withdrawal root", batch_id);
+                self.roll_back_failed_batch(batch_id, reason.clone()).await;
+                anyhow::bail!(reason);
+            }
         };
 
         let withdrawal_count = withdrawals.len();
@@ -675,16 +733,12 @@ impl PipelineOrchestrator {
                 );
 
                 if self.settlement_retries >= self.config.max_settlement_retries {
-                    // Pause the pipeline
                     let reason = format!(
                         "Settlement failed {} times for batch {}: {}",
                         self.settlement_retries, batch_id, e
                     );
-                    error!("{}", reason);
-                    self.state = PipelineState::Paused {
-                        reason: reason.clone(),
-                    };
                     self.settling_batch = None;
+                    self.roll_back_failed_batch(batch_id, reason.clone()).await;
                     return Err(anyhow::anyhow!(reason));
                 }
 
@@ -700,6 +754,42 @@ impl PipelineOrchestrator {
         }
     }
 
+    /// Roll back a batch that failed to prove or settle, along with every
+    /// later batch, re-queue their transactions and pause the pipeline.
+    ///
+    /// Earlier batches must finalize first; until they have, the rollback
+    /// stays pending (retried each tick) and proving is held back.
+    async fn roll_back_failed_batch(&mut self, batch_id: u64, reason: String) {
+        let mut manager = self.batch_manager.lock().await;
+        if !manager.can_roll_back(batch_id) {
+            warn!(batch_id, "Rollback waits for earlier batches to finalize");
+            self.pending_rollback = Some((batch_id, reason));
+            return;
+        }
+        self.pending_rollback = None;
+
+        let reason = match manager.rollback_batch(batch_id) {
+            Ok(txs) => {
+                let requeued = txs.len();
+                match manager.requeue_transactions(txs) {
+                    Ok(()) => format!(
+                        "{}; rolled back to before batch {} and re-queued {} transactions",
+                        reason, batch_id, requeued
+                    ),
+                    Err(e) => format!(
+                        "{}; rolled back to before batch {} but re-queueing failed: {}",
+                        reason, batch_id, e
+                    ),
+                }
+            }
+            Err(e) => format!("{}; rollback of batch {} failed: {}", reason, batch_id, e),
+        };
+        drop(manager);
+
+        error!("{}", reason);
+        self.state = PipelineState::Paused { reason };
+    }
+
     /// Run one iteration of the pipeline loop
     pub async fn tick(&mut self) -> Result<()> {
         if self.state != PipelineState::Running {
@@ -724,6 +814,11 @@ impl PipelineOrchestrator {
             error!(error = %e, "Settlement error");
         }
 
+        // Retry a rollback that was waiting on earlier batches
+        if let Some((batch_id, reason)) = self.pending_rollback.take() {
+            self.roll_back_failed_batch(batch_id, reason).await;
+        }
+
         Ok(())
     }
 
@@ -740,6 +835,8 @@ impl PipelineOrchestrator {
             last_settled_batch: self.last_settled_batch,
             prover_busy: self.proving_batch.is_some(),
             settler_busy: self.settling_batch.is_some(),
+            proving_backend: self.config.resolved_backend(),
+            prover_name: self.prover.name(),
         }
     }
 
@@ -762,6 +859,39 @@ impl PipelineOrchestrator {
             PipelineState::Stopping => Err(anyhow::anyhow!("cannot resume stopping pipeline")),
         }
     }
+
+    /// Apply reloaded settings; batch limits take effect from the next seal check
+    pub async fn reload(&mut self, reload: PipelineReload) {
+        self.config.poll_interval_ms = reload.poll_interval_ms;
+        self.config.max_settlement_retries = reload.max_settlement_retries;
+        self.config.settlement_retry_base_ms = reload.settlement_retry_base_ms;
+        self.config.batch_config = reload.batch_config.clone();
+        self.batch_manager
+            .lock()
+            .await
+            .set_config(reload.batch_config);
+        info!("Pipeline config reloaded");
+    }
+}
+
+/// Prune transaction summaries outside the retention window.
+///
+/// Keeps the last `retention` batches; accounts and block headers are
+/// untouched. Scans the whole tx_index, so it runs on a blocking thread.
+fn compact_tx_index(db: &RocksDbStore, retention: u64) -> Result<usize> {
+    let Some(latest) = db.get_latest_batch_id()? else {
+        return Ok(0);
+    };
+    let before_batch = (latest + 1).saturating_sub(retention);
+    if before_batch == 0 {
+        return Ok(0);
+    }
+
+    let pruned = db.prune_tx_summaries(before_batch)?;
+    if pruned > 0 {
+        info!(pruned, before_batch, "Pruned old transaction summaries");
+    }
+    Ok(pruned)
 }
 
 // Pipeline Service
@@ -769,6 +899,7 @@ impl PipelineOrchestrator {
 /// Async service that runs the pipeline
 pub struct PipelineService {
     command_tx: mpsc::Sender<PipelineCommand>,
+    dry_run: bool,
 }
 
 impl PipelineService {
@@ -778,8 +909,30 @@ impl PipelineService {
         config: PipelineConfig,
         settler_service: Option<SettlerService>,
     ) -> Result<Self> {
+        if config.dry_run {
+            return Self::start_dry_run(db, config);
+        }
+
         let (command_tx, mut command_rx) = mpsc::channel::<PipelineCommand>(1000);
 
+        if let Some(retention) = config.tx_retention_batches {
+            let db = db.clone();
+            tokio::spawn(async move {
+                let mut compaction = tokio::time::interval(TX_COMPACTION_INTERVAL);
+                loop {
+                    compaction.tick().await;
+                    let db = db.clone();
+                    match tokio::task::spawn_blocking(move || compact_tx_index(&db, retention))
+                        .await
+                    {
+                        Ok(Ok(_)) => {}
+                        Ok(Err(e)) => warn!(error = %e, "Transaction index compaction failed"),
+                        Err(e) => warn!(error = %e, "Transaction index compaction panicked"),
+                    }
+                }
+            });
+        }
+
         let mut orchestrator = PipelineOrchestrator::new(db, config.clone(), settler_service)?;
         let batch_manager = orchestrator.batch_manager();
 
@@ -789,6 +942,7 @@ impl PipelineService {
             let mut ticker = tokio::time::interval(poll_interval);
 
             loop {
+                let mut new_poll_interval = None;
                 tokio::select! {
                     Some(cmd) = command_rx.recv() => {
                         match cmd {
@@ -833,6 +987,13 @@ impl PipelineService {
                                 let result = orchestrator.resume();
                                 let _ = reply.send(result);
                             }
+                            PipelineCommand::Reload(reload, reply) => {
+                                if reload.poll_interval_ms != orchestrator.config.poll_interval_ms {
+                                    new_poll_interval = Some(Duration::from_millis(reload.poll_interval_ms));
+                                }
+                                orchestrator.reload(reload).await;
+                                let _ = reply.send(());
+                            }
                             PipelineCommand::Shutdown => {
                                 info!("Pipeline shutting down");
                                 orchestrator.state = PipelineState::Stopping;
@@ -860,10 +1021,100 @@ impl PipelineService {
                         }
                     }
                 }
+
+                if let Some(poll_interval) = new_poll_interval {
+                    ticker = tokio::time::interval(poll_interval);
+                }
             }
         });
 
-        Ok(Self { command_tx })
+        Ok(Self {
+            command_tx,
+            dry_run: false,
+        })
+    }
+
+    /// Start a pipeline that only validates transactions (`--dry-run`)
+    ///
+    /// Each submission is executed on arrival by a [`DryRunExecutor`] and
+    /// rejected with its validation error if it fails. Nothing is batched,
+    /// proved, settled or written to the database.
+    fn start_dry_run(db: Arc<RocksDbStore>, config: PipelineConfig) -> Result<Self> {
+        let (command_tx, mut command_rx) = mpsc::channel::<PipelineCommand>(1000);
+        let mut executor = DryRunExecutor::new(db)?;
+
+        tokio::spawn(async move {
+            while let Some(cmd) = command_rx.recv().await {
+                match cmd {
+                    PipelineCommand::Submit(tx, reply) => {
+                        let result = executor.execute(tx);
+                        let _ = reply.send(if result.success {
+                            Ok(())
+                        } else {
+                            Err(anyhow::anyhow!(
+                                "{}",
+                                result.error.as_deref().unwrap_or("transaction rejected")
+                            ))
+                        });
+                    }
+                    PipelineCommand::Seal(reply) => {
+                        let _ = reply.send(Ok(None));
+                    }
+                    PipelineCommand::ForceSeal(reply) => {
+                        let _ = reply.send(Ok(SealResult {
+                            batch_id: 0,
+                            tx_count: 0,
+                        }));
+                    }
+                    PipelineCommand::Stats(reply) => {
+                        let _ = reply.send(PipelineStats {
+                            batch_stats: BatchManagerStats {
+                                current_batch_txs: 0,
+                                proving_count: 0,
+                                pending_settlement_count: 0,
+                                next_batch_id: executor.batch_id(),
+                            },
+                            state: PipelineState::Running,
+                            batches_proved: 0,
+                            batches_settled: 0,
+                            last_proved_batch: None,
+                            last_settled_batch: None,
+                            prover_busy: false,
+                            settler_busy: false,
+                            proving_backend: config.resolved_backend(),
+                            prover_name: "dry-run",
+                        });
+                    }
+                    PipelineCommand::GetPendingAccount(account_id, reply) => {
+                        let _ = reply.send(executor.get_pending_account(&account_id));
+                    }
+                    PipelineCommand::Pause(_, reply) => {
+                        let _ = reply.send(());
+                    }
+                    PipelineCommand::Resume(reply) => {
+                        let _ = reply.send(Ok(()));
+                    }
+                    PipelineCommand::Reload(_, reply) => {
+                        let _ = reply.send(());
+                    }
+                    PipelineCommand::Shutdown => {
+                        let (accepted, rejected) = executor.counts();
+                        info!(accepted, rejected, "Dry run complete, nothing persisted");
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            command_tx,
+            dry_run: true,
+        })
+    }
+
+    /// Whether this pipeline only validates transactions (`--dry-run`)
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
     }
 
     /// Submit a transaction to the pipeline
@@ -927,6 +1178,17 @@ impl PipelineService {
         reply_rx.await.context("pipeline crashed")?
     }
 
+    /// Apply reloaded settings to the running pipeline
+    pub async fn reload(&self, reload: PipelineReload) -> Result<()> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.command_tx
+            .send(PipelineCommand::Reload(reload, reply_tx))
+            .await
+            .context("pipeline unavailable")?;
+        reply_rx.await.context("pipeline crashed")?;
+        Ok(())
+    }
+
     /// Get pending account state from the current batch.
     /// Returns None if the account has no pending changes.
     pub async fn get_pending_account(
@@ -971,6 +1233,167 @@ mod tests {
         assert_eq!(config.prover_mode, ProverMode::Mock);
         assert!(!config.settlement_enabled);
         assert_eq!(config.max_settlement_retries, 5);
+        assert_eq!(config.resolved_backend(), ProvingBackend::Integrated);
+    }
+
+    #[tokio::test]
+    async fn test_proving_backend_selects_client() {
+        let (_temp_dir, db) = create_test_db();
+
+        // Integrated backend proves in-process
+        let orchestrator =
+            PipelineOrchestrator::new(db.clone(), PipelineConfig::default(), None).unwrap();
+        let stats = orchestrator.stats().await;
+        assert_eq!(stats.proving_backend, ProvingBackend::Integrated);
+        assert_eq!(stats.prover_name, "mock");
+
+        // Coordinator backend uses the HTTP client
+        let config = PipelineConfig {
+            proving_backend: ProvingBackend::Coordinator,
+            noir_coordinator_url: Some("http://127.0.0.1:8080".to_string()),
+            ..Default::default()
+        };
+        let orchestrator = PipelineOrchestrator::new(db.clone(), config, None).unwrap();
+        let stats = orchestrator.stats().await;
+        assert_eq!(stats.proving_backend, ProvingBackend::Coordinator);
+        assert_eq!(stats.prover_name, "noir");
+
+        // Legacy noir prover mode implies the coordinator backend
+        let config = PipelineConfig {
+            prover_mode: ProverMode::Noir,
+            ..Default::default()
+        };
+        assert_eq!(config.resolved_backend(), ProvingBackend::Coordinator);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_leaves_db_unchanged() {
+        use crate::storage::StateStore;
+        use zelana_transaction::TransactionData;
+
+        let (_temp_dir, db) = create_test_db();
+        let sender = zelana_keypair::Keypair::new_random();
+        let recipient = zelana_keypair::Keypair::new_random();
+        db.set_account_state(
+            sender.account_id(),
+            AccountState {
+                balance: 1_000,
+                nonce: 0,
+            },
+        )
+        .unwrap();
+        let accounts_before = db.get_all_accounts().unwrap();
+
+        let config = PipelineConfig {
+            dry_run: true,
+            ..Default::default()
+        };
+        let service = PipelineService::start(db.clone(), config, None).unwrap();
+        assert!(service.is_dry_run());
+
+        let transfer = |amount, nonce| {
+            TransactionType::Transfer(sender.sign_transaction(TransactionData {
+                from: sender.account_id(),
+                to: recipient.account_id(),
+                amount,
+                nonce,
+                chain_id: 1,
+                valid_until_batch: None,
+            }))
+        };
+
+        // Accepted transfers build on each other in memory
+        service.submit(transfer(400, 0)).await.unwrap();
+        service.submit(transfer(400, 1)).await.unwrap();
+        let pending = service
+            .get_pending_account(&sender.account_id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pending.balance, 200);
+        assert_eq!(pending.nonce, 2);
+
+        // Validation failures are reported back to the submitter
+        let err = service.submit(transfer(400, 2)).await.unwrap_err();
+        assert!(err.to_string().contains("insufficient balance"), "{}", err);
+        let err = service.submit(transfer(100, 0)).await.unwrap_err();
+        assert!(err.to_string().contains("invalid nonce"), "{}", err);
+
+        // Sealing is a no-op and nothing reaches RocksDB
+        assert_eq!(service.seal().await.unwrap(), None);
+        service.shutdown().await.unwrap();
+        assert_eq!(db.get_all_accounts().unwrap(), accounts_before);
+        assert_eq!(db.get_next_batch_id().unwrap(), None);
+        assert_eq!(db.get_latest_batch_id().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_compact_tx_index_keeps_retention_window() {
+        use crate::api::types::{TxSummary, TxType};
+        use crate::storage::StateStore;
+
+        let (_temp_dir, db) = create_test_db();
+        let alice = AccountId([0xaa; 32]);
+        db.set_account_state(
+            alice,
+            AccountState {
+                balance: 500,
+                nonce: 3,
+            },
+        )
+        .unwrap();
+
+        for batch_id in 1..=4u64 {
+            db.store_batch_summary(&BatchSummary {
+                batch_id,
+                tx_count: 1,
+                state_root: String::new(),
+                shielded_root: String::new(),
+                l1_tx_sig: None,
+                status: BatchStatus::Settled,
+                created_at: batch_id,
+                settled_at: Some(batch_id),
+            })
+            .unwrap();
+            db.store_tx_summary(
+                &[batch_id as u8; 32],
+                &TxSummary {
+                    tx_hash: hex::encode([batch_id as u8; 32]),
+                    tx_type: TxType::Deposit,
+                    batch_id: Some(batch_id),
+                    batch_index: Some(0),
+                    status: TxStatus::Settled,
+                    received_at: batch_id,
+                    executed_at: Some(batch_id),
+                    amount: Some(100),
+                    from: None,
+                    to: Some(alice.to_hex()),
+                    shield_amount: None,
+                    unshield_amount: None,
+                },
+            )
+            .unwrap();
+        }
+
+        assert_eq!(compact_tx_index(&db, 2).unwrap(), 2);
+        assert_eq!(db.get_tx_index_pruned_before().unwrap(), Some(3));
+
+        // Batches 1-2 are outside the window, 3-4 are kept
+        assert!(db.get_tx_summary(&[1; 32]).unwrap().is_none());
+        assert!(db.get_tx_summary(&[2; 32]).unwrap().is_none());
+        assert!(db.get_tx_summary(&[3; 32]).unwrap().is_some());
+        assert!(db.get_tx_summary(&[4; 32]).unwrap().is_some());
+        assert_eq!(
+            db.list_account_tx_hashes(&alice, 0, 10).unwrap(),
+            vec![[3; 32], [4; 32]]
+        );
+
+        // Balances and batches are never pruned
+        assert_eq!(db.get_account_state(&alice).unwrap().balance, 500);
+        assert_eq!(db.count_batches().unwrap(), 4);
+
+        // Nothing left to prune on the next pass
+        assert_eq!(compact_tx_index(&db, 2).unwrap(), 0);
     }
 
     #[tokio::test]
@@ -1040,6 +1463,40 @@ mod tests {
         service.shutdown().await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_pipeline_reload_applies_batch_limits() {
+        let (_temp_dir, db) = create_test_db();
+        let service = PipelineService::start(db, PipelineConfig::default(), None).unwrap();
+
+        let batch_config = BatchConfig {
+            max_transactions: 2,
+            ..BatchConfig::default()
+        };
+        service
+            .reload(PipelineReload {
+                poll_interval_ms: 50,
+                max_settlement_retries: 3,
+                settlement_retry_base_ms: 1000,
+                batch_config,
+            })
+            .await
+            .unwrap();
+
+        // The reloaded limit seals the batch at two transactions, not 100
+        for i in 0..2u8 {
+            let tx = TransactionType::Deposit(DepositEvent {
+                to: zelana_account::AccountId([i; 32]),
+                amount: 1000,
+                l1_seq: i as u64,
+            });
+            service.submit(tx).await.unwrap();
+        }
+        let stats = service.stats().await.unwrap();
+        assert_eq!(stats.batch_stats.current_batch_txs, 0);
+
+        service.shutdown().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_pipeline_full_flow() {
         let (_temp_dir, db) = create_test_db();
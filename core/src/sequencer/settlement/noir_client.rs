@@ -105,6 +105,8 @@ pub struct CoreWithdrawalWitness {
     pub sender_path_indices: Vec<u8>,
     pub l1_recipient: String,
     pub amount: u64,
+    /// L2 tx hash (hex), committed to the withdrawal tree leaf
+    pub tx_hash: String,
     pub signature: String,
 }
 
@@ -324,6 +326,7 @@ impl NoirProverClient {
                 sender_path_indices: ww.sender_path_indices.clone(),
                 l1_recipient: hex::encode(ww.l1_recipient),
                 amount: ww.amount,
+                tx_hash: hex::encode(ww.tx_hash),
                 signature: hex::encode(&ww.signature),
             });
         }
@@ -629,6 +632,10 @@ impl BatchProver for NoirProverClient {
     fn verification_key_hash(&self) -> [u8; 32] {
         self.vk_hash
     }
+
+    fn name(&self) -> &'static str {
+        "noir"
+    }
 }
 
 // Tests
@@ -31,6 +31,7 @@ use tokio::sync::{mpsc, oneshot};
 
 use crate::sequencer::Batch;
 use crate::sequencer::TxResult;
+use crate::sequencer::execution::tx_router::tx_hash;
 use zelana_transaction::TransactionType;
 
 // Arkworks imports for real Groth16 proving
@@ -150,6 +151,8 @@ pub struct WithdrawalWitnessData {
     pub l1_recipient: [u8; 32],
     /// Withdrawal amount
     pub amount: u64,
+    /// L2 tx hash, committed to the withdrawal tree leaf
+    pub tx_hash: [u8; 32],
     /// Transaction signature
     pub signature: Vec<u8>,
 }
@@ -166,6 +169,9 @@ pub trait BatchProver: Send + Sync {
 
     /// Get the verification key hash (for L1 contract)
     fn verification_key_hash(&self) -> [u8; 32];
+
+    /// Short identifier for logs and status reporting
+    fn name(&self) -> &'static str;
 }
 
 // Mock Prover (MVP)
@@ -241,6 +247,10 @@ impl BatchProver for MockProver {
     fn verification_key_hash(&self) -> [u8; 32] {
         self.vk_hash
     }
+
+    fn name(&self) -> &'static str {
+        "mock"
+    }
 }
 
 // Groth16 Prover (Real ZK Proving)
@@ -444,6 +454,10 @@ impl BatchProver for Groth16Prover {
     fn verification_key_hash(&self) -> [u8; 32] {
         self.vk_hash
     }
+
+    fn name(&self) -> &'static str {
+        "groth16"
+    }
 }
 
 // Async Prover Service
@@ -646,19 +660,30 @@ pub fn build_witness_with_proofs(
                 let sender_state = account_states.get(&sender_id).cloned().unwrap_or_default();
                 let sender_path = sim_tree.path(&sender_id).unwrap_or_default();
 
-                // Simulate sender update in the tree
-                let new_sender_state = AccountState {
-                    balance: sender_state.balance.saturating_sub(amount),
-                    nonce: sender_state.nonce + 1,
+                // Apply both sides on copies first; a transfer the router
+                // rejected changes no state and gets no witness
+                let mut new_sender_state = sender_state.clone();
+                if new_sender_state.apply_transfer(amount, 0).is_err() {
+                    continue;
+                }
+                let receiver_state = if receiver_id == sender_id {
+                    new_sender_state.clone()
+                } else {
+                    account_states
+                        .get(&receiver_id)
+                        .cloned()
+                        .unwrap_or_default()
                 };
+                let mut new_receiver_state = receiver_state.clone();
+                if new_receiver_state.apply_credit(amount).is_err() {
+                    continue;
+                }
+
+                // Simulate sender update in the tree
                 sim_tree.insert(&sender_id, &new_sender_state);
                 account_states.insert(sender_id, new_sender_state);
 
                 // Now get receiver's merkle path AFTER sender update
-                let receiver_state = account_states
-                    .get(&receiver_id)
-                    .cloned()
-                    .unwrap_or_default();
                 let receiver_path = sim_tree.path(&receiver_id).unwrap_or_else(|| {
                     // Receiver doesn't exist yet - insert with current balance first
                     sim_tree.insert(&receiver_id, &receiver_state);
@@ -666,10 +691,6 @@ pub fn build_witness_with_proofs(
                 });
 
                 // Simulate receiver update in the tree
-                let new_receiver_state = AccountState {
-                    balance: receiver_state.balance + amount,
-                    nonce: receiver_state.nonce,
-                };
                 sim_tree.insert(&receiver_id, &new_receiver_state);
                 account_states.insert(receiver_id, new_receiver_state);
 
@@ -719,11 +740,11 @@ pub fn build_witness_with_proofs(
                 let sender_state = account_states.get(&sender_id).cloned().unwrap_or_default();
                 let sender_path = sim_tree.path(&sender_id).unwrap_or_default();
 
-                // Simulate sender update in the tree
-                let new_sender_state = AccountState {
-                    balance: sender_state.balance.saturating_sub(amount),
-                    nonce: sender_state.nonce + 1,
-                };
+                // Simulate sender update in the tree (skipped if rejected)
+                let mut new_sender_state = sender_state.clone();
+                if new_sender_state.apply_transfer(amount, 0).is_err() {
+                    continue;
+                }
                 sim_tree.insert(&sender_id, &new_sender_state);
                 account_states.insert(sender_id, new_sender_state);
 
@@ -735,6 +756,7 @@ pub fn build_witness_with_proofs(
                     sender_path_indices: sender_path.path_indices.to_vec(),
                     l1_recipient: w.to_l1_address,
                     amount,
+                    tx_hash: tx_hash(tx),
                     signature: w.signature.clone(),
                 });
 
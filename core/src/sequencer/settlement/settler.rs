@@ -37,6 +37,8 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use tokio::sync::Mutex;
+use zelana_config::Domain;
+use zelana_transaction::WithdrawAttestedParams;
 
 use crate::sequencer::BatchProof;
 use crate::sequencer::TrackedWithdrawal;
@@ -51,7 +53,7 @@ pub struct SettlerConfig {
     pub bridge_program_id: String,
     /// Verifier program ID
     pub verifier_program_id: String,
-    /// Domain for the bridge (e.g., "solana", "testnet")
+    /// Domain for the bridge (e.g., "solana", "testnet"), see [`Domain`]
     pub domain: [u8; 32],
     /// Confirmation commitment level
     pub commitment: CommitmentConfig,
@@ -63,14 +65,11 @@ pub struct SettlerConfig {
 
 impl Default for SettlerConfig {
     fn default() -> Self {
-        let mut domain = [0u8; 32];
-        domain[..6].copy_from_slice(b"solana");
-
         Self {
             rpc_url: "http://127.0.0.1:8899".to_string(),
             bridge_program_id: "8SE6gCijcFQixvDQqWu29mCm9AydN8hcwWh2e2Q6RQgE".to_string(),
             verifier_program_id: "8TveT3mvH59qLzZNwrTT6hBqDHEobW2XnCPb7xZLBYHd".to_string(),
-            domain,
+            domain: Domain::default().as_bytes(),
             commitment: CommitmentConfig::confirmed(),
             max_retries: 3,
             retry_delay_ms: 1000,
@@ -146,6 +145,19 @@ impl Settler {
         Ok((config_pda, state_pda))
     }
 
+    /// Get the withdrawal root PDA `SubmitBatch` creates for `batch_id`
+    fn get_withdrawal_root_pda(&self, batch_id: u64) -> Pubkey {
+        let (withdrawal_root_pda, _) = Pubkey::find_program_address(
+            &[
+                b"withdrawal_root",
+                &self.config.domain,
+                &batch_id.to_le_bytes(),
+            ],
+            &self.program_id,
+        );
+        withdrawal_root_pda
+    }
+
     /// Get VK PDA for the verifier
     fn get_vk_pda(&self) -> Pubkey {
         let (vk_pda, _) = Pubkey::find_program_address(
@@ -237,12 +249,14 @@ impl Settler {
             inputs_end
         );
 
-        // Append WithdrawalRequest structs (recipient: Pubkey (32) + amount: u64 (8) = 40 bytes each)
+        // Append WithdrawalRequest structs (recipient: Pubkey (32) + amount: u64 (8) + tx_hash: [u8; 32] (32) = 72 bytes each)
         for withdrawal in withdrawals {
             // recipient: [u8; 32]
             data.extend_from_slice(&withdrawal.to_l1_address);
             // amount: u64
             data.extend_from_slice(&withdrawal.amount.to_le_bytes());
+            // tx_hash: [u8; 32]
+            data.extend_from_slice(&withdrawal.tx_hash);
         }
 
         tracing::info!(
@@ -261,12 +275,20 @@ impl Settler {
         // 1. config (writable)
         // 2. verifier_program
         // 3. vk_account
-        // 4+. recipient accounts for each withdrawal
+        // 4. withdrawal_root (writable)
+        // 5. system_program
+        // 6+. recipient accounts for each withdrawal
+        let withdrawal_root_pda = self.get_withdrawal_root_pda(inputs.batch_id);
         let mut accounts = vec![
             AccountMeta::new(self.sequencer_keypair.pubkey(), true), // sequencer (signer)
             AccountMeta::new(config_pda, false),                     // config (writable)
             AccountMeta::new_readonly(self.verifier_program_id, false), // verifier program
             AccountMeta::new_readonly(vk_pda, false),                // vk_account
+            AccountMeta::new(withdrawal_root_pda, false),            // withdrawal_root (writable)
+            AccountMeta::new_readonly(
+                Pubkey::from_str("11111111111111111111111111111111").unwrap(),
+                false,
+            ), // system program
         ];
 
         // Add recipient accounts for each withdrawal
@@ -405,11 +427,19 @@ impl Settler {
         // 1. config (writable)
         // 2. verifier_program (Sunspot)
         // 3. vk_account
+        // 4. withdrawal_root (writable)
+        // 5. system_program
+        let withdrawal_root_pda = self.get_withdrawal_root_pda(inputs.batch_id);
         let accounts = vec![
             AccountMeta::new(self.sequencer_keypair.pubkey(), true), // sequencer (signer)
             AccountMeta::new(config_pda, false),                     // config (writable)
             AccountMeta::new_readonly(sunspot_verifier, false),      // Sunspot verifier program
             AccountMeta::new_readonly(vk_pda, false),                // vk_account
+            AccountMeta::new(withdrawal_root_pda, false),            // withdrawal_root (writable)
+            AccountMeta::new_readonly(
+                Pubkey::from_str("11111111111111111111111111111111").unwrap(),
+                false,
+            ), // system program
         ];
 
         let instruction = Instruction {
@@ -804,19 +834,18 @@ impl Settler {
                 &self.program_id,
             );
 
-            // Build instruction data matching WithdrawAttestedParams layout:
-            // - recipient: Pubkey (32 bytes)
-            // - amount: u64 (8 bytes)
-            // - nullifier: [u8; 32] (32 bytes)
             // Instruction discriminator: 2 = WithdrawAttested
+            let params = WithdrawAttestedParams {
+                recipient: withdrawal.to_l1_address,
+                amount: withdrawal.amount,
+                nullifier: withdrawal.tx_hash, // using tx_hash
+            };
             let mut data = vec![2u8];
-            data.extend_from_slice(&withdrawal.to_l1_address); // recipient (32 bytes)
-            data.extend_from_slice(&withdrawal.amount.to_le_bytes()); // amount (8 bytes)
-            data.extend_from_slice(&withdrawal.tx_hash); // nullifier (32 bytes) - using tx_hash
+            data.extend_from_slice(&params.to_bytes());
 
             // Accounts per IDL:
             // 0. sequencer (signer)
-            // 1. config
+            // 1. config (writable)
             // 2. vault (writable)
             // 3. recipient (writable)
             // 4. used_nullifier (writable)
@@ -825,7 +854,7 @@ impl Settler {
                 program_id: self.program_id,
                 accounts: vec![
                     AccountMeta::new(self.sequencer_keypair.pubkey(), true), // sequencer (signer)
-                    AccountMeta::new_readonly(config_pda, false),            // config
+                    AccountMeta::new(config_pda, false),                     // config (writable)
                     AccountMeta::new(vault_pda, false),                      // vault (writable)
                     AccountMeta::new(recipient, false),                      // recipient (writable)
                     AccountMeta::new(nullifier_pda, false), // used_nullifier (writable)
@@ -123,37 +123,11 @@ pub fn compute_account_leaf(pubkey: &BigUint, balance: u64, nonce: u64) -> BigUi
     )
 }
 
-/// Domain separator for withdrawal hashes (matches circuit's domain_withdrawal())
-fn domain_withdrawal() -> BigUint {
-    BigUint::from(5u64)
-}
-
 /// Domain separator for batch hashes (matches circuit's domain_batch())
 fn domain_batch() -> BigUint {
     BigUint::from(4u64)
 }
 
-/// Compute withdrawal root using MiMC (matches circuit exactly)
-///
-/// Circuit computation:
-///   withdrawal_accumulator = hash_2(domain_withdrawal(), batch_id)
-///   for each withdrawal: wd_acc = hash_2(wd_acc, hash_3(l1_recipient, amount, sender))
-///   final_withdrawal_root = hash_2(withdrawal_accumulator, num_withdrawals)
-pub fn compute_withdrawal_root_mimc(batch_id: u64, num_withdrawals: u64) -> [u8; 32] {
-    // Initial accumulator: hash_2(5, batch_id)
-    let batch_id_field = BigUint::from(batch_id);
-    let wd_acc = mimc_hash_2(&domain_withdrawal(), &batch_id_field);
-
-    // For now, we only support empty batches (deposits only)
-    // TODO: Add withdrawal hashing when we have withdrawal transactions
-
-    // Final: hash_2(wd_acc, num_withdrawals)
-    let num_wd_field = BigUint::from(num_withdrawals);
-    let final_root = mimc_hash_2(&wd_acc, &num_wd_field);
-
-    field_to_bytes(&final_root)
-}
-
 /// Compute batch hash using MiMC (matches circuit exactly)
 ///
 /// Circuit computation:
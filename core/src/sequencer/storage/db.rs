@@ -34,6 +34,18 @@
 //! -  -  Val: JSON      -  -  Val: enc blob  -  -  Val: u64 (slot)            -  -
 //! -  -------------------  -------------------  -------------------------------  -
 //! -                                                                              -
+//! -  -------------------  -------------------------------------------------     -
+//! -  -   BATCH_META    -  -              ACCOUNT_TX_INDEX                 -     -
+//! -  -  Key: string    -  -  Key: AccountId || received_at BE || tx_hash  -     -
+//! -  -  Val: u64 (BE)  -  -  Val: [u8;32] (tx_hash)                       -     -
+//! -  -------------------  -------------------------------------------------     -
+//! -                                                                              -
+//! -  -------------------------------  -------------------------------            -
+//! -  -      WITHDRAWAL_INDEX       -  -       L1_WITHDRAWALS        -            -
+//! -  -  Key: [u8;32] (tx_hash)     -  -  Key: [u8;32] (tx_hash)     -            -
+//! -  -  Val: JSON(IndexEntry)      -  -  Val: JSON(L1Record)        -            -
+//! -  -------------------------------  -------------------------------            -
+//! -                                                                              -
 //! -------------------------------------------------------------------------------
 //! ```
 //!
@@ -53,6 +65,10 @@
 //! | `withdrawals`      | `[u8; 32]` (tx_hash)    | `Vec<u8>` (serialized)    | Pending L2→L1 withdrawals                  |
 //! | `processed_deposits`| `u64` (L1 seq, BE)     | `u64` (slot, BE)          | Dedupe L1→L2 deposits                      |
 //! | `indexer_meta`     | `string` (key name)     | `u64` (slot)              | Deposit indexer checkpoint                 |
+//! | `batch_meta`       | `string` (key name)     | `u64` (BE)                | Batch id allocator                         |
+//! | `account_tx_index` | `AccountId‖u64 BE‖hash` | `[u8; 32]` (tx_hash)      | Per-account transaction history            |
+//! | `withdrawal_index` | `[u8; 32]` (tx_hash)    | `JSON(WithdrawalIndexEntry)` | Batch and withdrawal root for L1 claims |
+//! | `l1_withdrawals`   | `[u8; 32]` (tx_hash)    | `JSON(L1WithdrawalRecord)` | Settlement and payout seen on L1       |
 //!
 //! ## Key Format Details
 //!
@@ -80,7 +96,9 @@
 //! commitments together.
 
 use super::shielded_state::TreeFrontier;
-use crate::api::types::{BatchSummary, TxStatus, TxSummary, TxType};
+use crate::api::types::{
+    BatchSummary, L1WithdrawalRecord, TxStatus, TxSummary, TxType, WithdrawalIndexEntry,
+};
 use crate::storage::StateStore;
 use anyhow::{Context, Result};
 use rocksdb::{ColumnFamilyDescriptor, DB, Options, WriteBatch};
@@ -148,6 +166,26 @@ const CF_STATS: &str = "stats";
 /// Key: [u8; 32] (blinded_proxy), Value: JSON(DelegationInfo)
 const CF_DELEGATIONS: &str = "delegations";
 
+/// Batch id allocator state and tx_index compaction horizon
+/// Keys: "next_batch_id", "tx_index_pruned_before"
+const CF_BATCH_META: &str = "batch_meta";
+
+/// Secondary index from account to its transactions, in time order
+/// Key: [u8; 32] (AccountId) || u64 BE (received_at) || [u8; 32] (tx_hash),
+/// Value: [u8; 32] (tx_hash)
+///
+/// The trailing tx_hash keeps keys unique for transactions received in the
+/// same second.
+const CF_ACCOUNT_TX_INDEX: &str = "account_tx_index";
+
+/// Where each withdrawal landed in its batch's withdrawal tree
+/// Key: [u8; 32] (tx_hash), Value: JSON(WithdrawalIndexEntry)
+const CF_WITHDRAWAL_INDEX: &str = "withdrawal_index";
+
+/// Each withdrawal's progress on L1, as seen by the bridge indexer
+/// Key: [u8; 32] (tx_hash), Value: JSON(L1WithdrawalRecord)
+const CF_L1_WITHDRAWALS: &str = "l1_withdrawals";
+
 // RocksDbStore
 
 /// A thread-safe wrapper around RocksDB for L2 state persistence.
@@ -193,6 +231,10 @@ impl RocksDbStore {
             ColumnFamilyDescriptor::new(CF_INDEXER_META, Options::default()),
             ColumnFamilyDescriptor::new(CF_STATS, Options::default()),
             ColumnFamilyDescriptor::new(CF_DELEGATIONS, Options::default()),
+            ColumnFamilyDescriptor::new(CF_BATCH_META, Options::default()),
+            ColumnFamilyDescriptor::new(CF_ACCOUNT_TX_INDEX, Options::default()),
+            ColumnFamilyDescriptor::new(CF_WITHDRAWAL_INDEX, Options::default()),
+            ColumnFamilyDescriptor::new(CF_L1_WITHDRAWALS, Options::default()),
         ];
 
         let db = DB::open_cf_descriptors(&opts, path, families)
@@ -535,6 +577,76 @@ impl RocksDbStore {
         Ok(withdrawals)
     }
 
+    /// Record which batch and committed withdrawal root each withdrawal is in
+    pub fn store_withdrawal_index(
+        &self,
+        entries: &[([u8; 32], WithdrawalIndexEntry)],
+    ) -> Result<()> {
+        let cf = self
+            .db
+            .cf_handle(CF_WITHDRAWAL_INDEX)
+            .context("withdrawal_index CF missing")?;
+
+        let mut batch = WriteBatch::default();
+        for (tx_hash, entry) in entries {
+            batch.put_cf(cf, tx_hash, serde_json::to_vec(entry)?);
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Drop the index entries of withdrawals whose batch was rolled back
+    pub fn delete_withdrawal_index(&self, tx_hashes: &[[u8; 32]]) -> Result<()> {
+        let cf = self
+            .db
+            .cf_handle(CF_WITHDRAWAL_INDEX)
+            .context("withdrawal_index CF missing")?;
+
+        let mut batch = WriteBatch::default();
+        for tx_hash in tx_hashes {
+            batch.delete_cf(cf, tx_hash);
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Get the batch and committed withdrawal root for a withdrawal by tx hash
+    pub fn get_withdrawal_index(&self, tx_hash: &[u8; 32]) -> Result<Option<WithdrawalIndexEntry>> {
+        let cf = self
+            .db
+            .cf_handle(CF_WITHDRAWAL_INDEX)
+            .context("withdrawal_index CF missing")?;
+
+        match self.db.get_cf(cf, tx_hash)? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Record a withdrawal's L1 progress (bridge indexer)
+    pub fn put_l1_withdrawal(&self, tx_hash: &[u8; 32], record: &L1WithdrawalRecord) -> Result<()> {
+        let cf = self
+            .db
+            .cf_handle(CF_L1_WITHDRAWALS)
+            .context("l1_withdrawals CF missing")?;
+
+        self.db.put_cf(cf, tx_hash, serde_json::to_vec(record)?)?;
+        Ok(())
+    }
+
+    /// Get a withdrawal's L1 progress by tx hash
+    pub fn get_l1_withdrawal(&self, tx_hash: &[u8; 32]) -> Result<Option<L1WithdrawalRecord>> {
+        let cf = self
+            .db
+            .cf_handle(CF_L1_WITHDRAWALS)
+            .context("l1_withdrawals CF missing")?;
+
+        match self.db.get_cf(cf, tx_hash)? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
     // Delegation Methods (Split Proving)
 
     /// Store a delegation request for Swarm processing
@@ -716,6 +828,69 @@ impl RocksDbStore {
         Ok(())
     }
 
+    /// Atomically undo a set of committed operations (executor rollback)
+    pub fn revert_batch(&self, operations: DbRevert) -> Result<()> {
+        let mut batch = WriteBatch::default();
+
+        let cf_accounts = self
+            .db
+            .cf_handle(CF_ACCOUNTS)
+            .context("accounts CF missing")?;
+        let cf_nullifiers = self
+            .db
+            .cf_handle(CF_NULLIFIERS)
+            .context("nullifiers CF missing")?;
+        let cf_commitments = self
+            .db
+            .cf_handle(CF_COMMITMENTS)
+            .context("commitments CF missing")?;
+        let cf_enc_notes = self
+            .db
+            .cf_handle(CF_ENCRYPTED_NOTES)
+            .context("encrypted_notes CF missing")?;
+        let cf_withdrawals = self
+            .db
+            .cf_handle(CF_WITHDRAWALS)
+            .context("withdrawals CF missing")?;
+
+        // Restore prior account states, deleting accounts that did not exist
+        for (id, state) in &operations.accounts {
+            match state {
+                Some(state) => batch.put_cf(cf_accounts, id.0, wincode::serialize(state)?),
+                None => batch.delete_cf(cf_accounts, id.0),
+            }
+        }
+
+        for nullifier in &operations.nullifiers {
+            batch.delete_cf(cf_nullifiers, nullifier.0);
+        }
+
+        for (position, commitment) in &operations.commitments {
+            batch.delete_cf(cf_commitments, position.to_be_bytes());
+            batch.delete_cf(cf_enc_notes, commitment);
+        }
+
+        for tx_hash in &operations.withdrawals {
+            batch.delete_cf(cf_withdrawals, tx_hash);
+        }
+
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Get an account's state, or `None` if it has never been written
+    pub fn get_account(&self, id: &AccountId) -> Result<Option<AccountState>> {
+        let cf = self
+            .db
+            .cf_handle(CF_ACCOUNTS)
+            .context("accounts CF missing")?;
+
+        match self.db.get_cf(cf, id.0)? {
+            Some(bytes) => Ok(Some(wincode::deserialize(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
     /// Get all accounts (for Merkle tree reconstruction on startup)
     pub fn get_all_accounts(&self) -> Result<Vec<(AccountId, AccountState)>> {
         let cf = self
@@ -822,6 +997,60 @@ impl RocksDbStore {
         Ok(None)
     }
 
+    /// Find the first missing id in the stored batch sequence (1, 2, 3, ...)
+    pub fn find_batch_id_gap(&self) -> Result<Option<u64>> {
+        let cf = self
+            .db
+            .cf_handle(CF_BATCHES)
+            .context("batches CF missing")?;
+
+        let mut expected = 1u64;
+        for item in self.db.iterator_cf(cf, rocksdb::IteratorMode::Start) {
+            let (key, _) = item?;
+            let arr: [u8; 8] = key
+                .as_ref()
+                .try_into()
+                .context("invalid batch_id key length")?;
+            if u64::from_be_bytes(arr) != expected {
+                return Ok(Some(expected));
+            }
+            expected += 1;
+        }
+
+        Ok(None)
+    }
+
+    /// Get the next batch id the allocator will hand out
+    pub fn get_next_batch_id(&self) -> Result<Option<u64>> {
+        let cf = self
+            .db
+            .cf_handle(CF_BATCH_META)
+            .context("batch_meta CF missing")?;
+
+        match self.db.get_cf(cf, b"next_batch_id")? {
+            Some(bytes) => {
+                let arr: [u8; 8] = bytes
+                    .as_slice()
+                    .try_into()
+                    .context("invalid batch_id length")?;
+                Ok(Some(u64::from_be_bytes(arr)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Persist the next batch id the allocator will hand out
+    pub fn set_next_batch_id(&self, batch_id: u64) -> Result<()> {
+        let cf = self
+            .db
+            .cf_handle(CF_BATCH_META)
+            .context("batch_meta CF missing")?;
+
+        self.db
+            .put_cf(cf, b"next_batch_id", batch_id.to_be_bytes())?;
+        Ok(())
+    }
+
     /// Count total batches
     pub fn count_batches(&self) -> Result<u64> {
         let cf = self
@@ -840,18 +1069,148 @@ impl RocksDbStore {
 
     // Transaction Index Methods
 
-    /// Store a transaction summary
+    /// Store a transaction summary and index it under the accounts it touches
     pub fn store_tx_summary(&self, tx_hash: &[u8; 32], summary: &TxSummary) -> Result<()> {
         let cf = self
             .db
             .cf_handle(CF_TX_INDEX)
             .context("tx_index CF missing")?;
+        let cf_account = self
+            .db
+            .cf_handle(CF_ACCOUNT_TX_INDEX)
+            .context("account_tx_index CF missing")?;
 
-        let value = serde_json::to_vec(summary)?;
-        self.db.put_cf(cf, tx_hash, value)?;
+        let mut batch = WriteBatch::default();
+        batch.put_cf(cf, tx_hash, serde_json::to_vec(summary)?);
+        for key in account_tx_keys(tx_hash, summary) {
+            batch.put_cf(cf_account, key, tx_hash);
+        }
+
+        self.db.write(batch)?;
         Ok(())
     }
 
+    /// Delete summaries (and their account index entries) of transactions
+    /// included before `before_batch`, returning how many were removed.
+    ///
+    /// Transactions not yet assigned to a batch are kept, as are records that
+    /// fail to parse (logged and skipped). Deletes are written in chunks of
+    /// `PRUNE_CHUNK` records, and the horizon is recorded under
+    /// `tx_index_pruned_before` once the pass completes.
+    pub fn prune_tx_summaries(&self, before_batch: u64) -> Result<usize> {
+        const PRUNE_CHUNK: usize = 1024;
+
+        let cf = self
+            .db
+            .cf_handle(CF_TX_INDEX)
+            .context("tx_index CF missing")?;
+        let cf_account = self
+            .db
+            .cf_handle(CF_ACCOUNT_TX_INDEX)
+            .context("account_tx_index CF missing")?;
+
+        let mut batch = WriteBatch::default();
+        let mut chunk = 0;
+        let mut pruned = 0;
+        for item in self.db.iterator_cf(cf, rocksdb::IteratorMode::Start) {
+            let (key, value) = item?;
+            let Ok(tx_hash) = <[u8; 32]>::try_from(key.as_ref()) else {
+                log::warn!(
+                    "Skipping tx_index entry with invalid key {}",
+                    hex::encode(&key)
+                );
+                continue;
+            };
+            let summary: TxSummary = match serde_json::from_slice(&value) {
+                Ok(summary) => summary,
+                Err(e) => {
+                    log::warn!(
+                        "Skipping unreadable tx_index entry {}: {}",
+                        hex::encode(tx_hash),
+                        e
+                    );
+                    continue;
+                }
+            };
+            if !summary.batch_id.is_some_and(|id| id < before_batch) {
+                continue;
+            }
+            for account_key in account_tx_keys(&tx_hash, &summary) {
+                batch.delete_cf(cf_account, account_key);
+            }
+            batch.delete_cf(cf, tx_hash);
+            pruned += 1;
+
+            chunk += 1;
+            if chunk == PRUNE_CHUNK {
+                self.db.write(std::mem::take(&mut batch))?;
+                chunk = 0;
+            }
+        }
+        self.db.write(batch)?;
+
+        if self.get_tx_index_pruned_before()? < Some(before_batch) {
+            let cf_meta = self
+                .db
+                .cf_handle(CF_BATCH_META)
+                .context("batch_meta CF missing")?;
+            self.db.put_cf(
+                cf_meta,
+                b"tx_index_pruned_before",
+                before_batch.to_be_bytes(),
+            )?;
+        }
+        Ok(pruned)
+    }
+
+    /// First batch whose transaction summaries are still complete, if
+    /// `prune_tx_summaries` has ever run
+    pub fn get_tx_index_pruned_before(&self) -> Result<Option<u64>> {
+        let cf = self
+            .db
+            .cf_handle(CF_BATCH_META)
+            .context("batch_meta CF missing")?;
+
+        match self.db.get_cf(cf, b"tx_index_pruned_before")? {
+            Some(bytes) => {
+                let arr: [u8; 8] = bytes
+                    .as_slice()
+                    .try_into()
+                    .context("invalid tx_index_pruned_before length")?;
+                Ok(Some(u64::from_be_bytes(arr)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Transaction hashes touching an account, oldest first
+    pub fn list_account_tx_hashes(
+        &self,
+        account: &AccountId,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<[u8; 32]>> {
+        let cf = self
+            .db
+            .cf_handle(CF_ACCOUNT_TX_INDEX)
+            .context("account_tx_index CF missing")?;
+
+        let mut hashes = Vec::new();
+        for item in self.db.prefix_iterator_cf(cf, account.0) {
+            let (key, value) = item?;
+            if !key.starts_with(&account.0) {
+                break;
+            }
+            let hash: [u8; 32] = value
+                .as_ref()
+                .try_into()
+                .context("invalid account_tx_index entry")?;
+            hashes.push(hash);
+        }
+
+        Ok(hashes.into_iter().skip(offset).take(limit).collect())
+    }
+
     /// Get a transaction summary by hash
     pub fn get_tx_summary(&self, tx_hash: &[u8; 32]) -> Result<Option<TxSummary>> {
         let cf = self
@@ -1078,6 +1437,32 @@ impl RocksDbStore {
     }
 }
 
+/// `account_tx_index` keys for the accounts a transaction touches
+fn account_tx_keys(tx_hash: &[u8; 32], summary: &TxSummary) -> Vec<Vec<u8>> {
+    // A withdrawal's `to` is an L1 address, not an L2 account
+    let to = match summary.tx_type {
+        TxType::Withdrawal => None,
+        _ => summary.to.as_deref(),
+    };
+    let mut accounts: Vec<[u8; 32]> = [summary.from.as_deref(), to]
+        .into_iter()
+        .flatten()
+        .filter_map(|id| hex::decode(id).ok()?.try_into().ok())
+        .collect();
+    accounts.dedup();
+
+    accounts
+        .into_iter()
+        .map(|account| {
+            let mut key = Vec::with_capacity(72);
+            key.extend_from_slice(&account);
+            key.extend_from_slice(&summary.received_at.to_be_bytes());
+            key.extend_from_slice(tx_hash);
+            key
+        })
+        .collect()
+}
+
 /// Batch of database operations for atomic commit
 #[derive(Default)]
 pub struct DbBatch {
@@ -1087,6 +1472,18 @@ pub struct DbBatch {
     pub encrypted_notes: Vec<([u8; 32], EncryptedNote)>,
 }
 
+/// Operations undone by `revert_batch`
+#[derive(Default)]
+pub struct DbRevert {
+    /// Prior account states (`None` deletes the account)
+    pub accounts: Vec<(AccountId, Option<AccountState>)>,
+    pub nullifiers: Vec<Nullifier>,
+    /// Commitments (and their encrypted notes) to remove, by position
+    pub commitments: Vec<(u32, [u8; 32])>,
+    /// Queued withdrawals to remove, by tx hash
+    pub withdrawals: Vec<[u8; 32]>,
+}
+
 impl StateStore for RocksDbStore {
     fn get_account_state(&self, id: &AccountId) -> Result<AccountState> {
         let cf = self
@@ -1116,3 +1513,83 @@ impl StateStore for RocksDbStore {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn summary(tx_hash: u8, received_at: u64, from: [u8; 32], to: [u8; 32]) -> TxSummary {
+        TxSummary {
+            tx_hash: hex::encode([tx_hash; 32]),
+            tx_type: TxType::Transfer,
+            batch_id: Some(1),
+            batch_index: None,
+            status: TxStatus::Executed,
+            received_at,
+            executed_at: Some(received_at),
+            amount: Some(1),
+            from: Some(hex::encode(from)),
+            to: Some(hex::encode(to)),
+            shield_amount: None,
+            unshield_amount: None,
+        }
+    }
+
+    #[test]
+    fn test_account_tx_index_returns_account_history_in_time_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDbStore::open(temp_dir.path()).unwrap();
+        let (alice, bob, carol) = ([0xaa; 32], [0xbb; 32], [0xcc; 32]);
+
+        // Stored out of time order, with two transactions in the same second
+        for (hash, received_at, from, to) in [
+            (1, 300, alice, bob),
+            (2, 100, bob, alice),
+            (3, 200, bob, carol),
+            (4, 100, carol, alice),
+        ] {
+            db.store_tx_summary(&[hash; 32], &summary(hash, received_at, from, to))
+                .unwrap();
+        }
+        // Re-storing on a status update must not duplicate index entries
+        db.update_tx_status(&[1; 32], TxStatus::Settled, Some(1))
+            .unwrap();
+
+        let alice_history = db.list_account_tx_hashes(&AccountId(alice), 0, 10).unwrap();
+        assert_eq!(alice_history, vec![[2; 32], [4; 32], [1; 32]]);
+
+        let carol_history = db.list_account_tx_hashes(&AccountId(carol), 0, 10).unwrap();
+        assert_eq!(carol_history, vec![[4; 32], [3; 32]]);
+
+        let paged = db.list_account_tx_hashes(&AccountId(alice), 1, 1).unwrap();
+        assert_eq!(paged, vec![[4; 32]]);
+
+        assert!(
+            db.list_account_tx_hashes(&AccountId([0xdd; 32]), 0, 10)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_prune_tx_summaries_skips_unreadable_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDbStore::open(temp_dir.path()).unwrap();
+        let (alice, bob) = ([0xaa; 32], [0xbb; 32]);
+
+        db.store_tx_summary(&[1; 32], &summary(1, 100, alice, bob))
+            .unwrap();
+        let cf = db.db.cf_handle(CF_TX_INDEX).unwrap();
+        db.db.put_cf(cf, [2; 32], b"not json").unwrap();
+
+        assert_eq!(db.prune_tx_summaries(2).unwrap(), 1);
+        assert!(db.get_tx_summary(&[1; 32]).unwrap().is_none());
+        assert!(
+            db.list_account_tx_hashes(&AccountId(alice), 0, 10)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(db.get_tx_index_pruned_before().unwrap(), Some(2));
+    }
+}
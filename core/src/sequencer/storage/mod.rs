@@ -3,4 +3,4 @@ pub mod db;
 pub mod shielded_state;
 
 // Re-export MiMC hash utilities for circuit-compatible computations
-pub use account_tree::{compute_batch_hash_mimc, compute_withdrawal_root_mimc};
+pub use account_tree::compute_batch_hash_mimc;
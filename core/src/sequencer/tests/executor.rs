@@ -27,6 +27,7 @@ fn signed_transfer(from: AccountId, to: AccountId, amount: u64, nonce: u64) -> S
             amount,
             nonce,
             chain_id: 1,
+            valid_until_batch: None,
         },
         signature: vec![9u8; 64],
         signer_pubkey: from.0,
@@ -29,6 +29,7 @@ fn signed_transfer(from: AccountId, to: AccountId) -> SignedTransaction {
             amount: 25,
             nonce: 0,
             chain_id: 1,
+            valid_until_batch: None,
         },
         signature: vec![1u8; 64],
         signer_pubkey: from.0,
@@ -470,6 +470,7 @@ async fn test_pipeline_end_to_end_with_deposits_and_transfers() {
             amount: 1000,
             nonce: 0,
             chain_id: 1,
+            valid_until_batch: None,
         },
         signature: vec![0u8; 64], // Mock signature for test
         signer_pubkey: [0u8; 32],
@@ -773,7 +774,7 @@ async fn test_pipeline_multiple_withdrawals_in_batch() {
 
 #[test]
 fn test_withdrawal_merkle_root_computation() {
-    use crate::sequencer::{TrackedWithdrawal, WithdrawalState, build_withdrawal_merkle_root};
+    use crate::sequencer::{TrackedWithdrawal, WithdrawalState, build_withdrawal_tree};
 
     // Test that multiple withdrawals produce a non-zero merkle root
     let withdrawals: Vec<TrackedWithdrawal> = (0..3)
@@ -789,17 +790,17 @@ fn test_withdrawal_merkle_root_computation() {
         })
         .collect();
 
-    let root = build_withdrawal_merkle_root(&withdrawals);
+    let root = build_withdrawal_tree(&withdrawals).unwrap().root();
     assert_ne!(root, [0u8; 32], "withdrawal root should not be zero");
 
     // Same withdrawals should produce same root
-    let root2 = build_withdrawal_merkle_root(&withdrawals);
+    let root2 = build_withdrawal_tree(&withdrawals).unwrap().root();
     assert_eq!(root, root2, "withdrawal root should be deterministic");
 
     // Different withdrawals should produce different root
     let mut different = withdrawals.clone();
     different[0].amount = 9999;
-    let root3 = build_withdrawal_merkle_root(&different);
+    let root3 = build_withdrawal_tree(&different).unwrap().root();
     assert_ne!(
         root, root3,
         "different withdrawals should produce different root"
@@ -1172,6 +1173,7 @@ async fn test_pipeline_full_l2_cycle_with_shielded() {
         amount: 3_000,
         nonce: 0,
         chain_id: 1,
+        valid_until_batch: None,
     };
     let signed_transfer = alice_kp.sign_transaction(transfer_data);
 
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use zelana_account::AccountState;
+use zelana_privacy::Nullifier;
 use zelana_transaction::Transaction;
 
 use crate::db;
@@ -226,11 +227,16 @@ impl App {
             .map(|(i, _)| i)
             .collect();
 
+        // A pasted full nullifier (any case, optional 0x) matches exactly
+        let pasted = query.parse::<Nullifier>().ok().map(|nf| nf.to_string());
         self.filtered_nullifiers = self
             .nullifiers
             .iter()
             .enumerate()
-            .filter(|(_, nf)| nf.to_lowercase().contains(&query))
+            .filter(|(_, nf)| match &pasted {
+                Some(pasted) => *nf == pasted,
+                None => nf.contains(&query),
+            })
             .map(|(i, _)| i)
             .collect();
 
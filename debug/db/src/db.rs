@@ -3,6 +3,7 @@ use hex;
 use rocksdb::{ColumnFamilyDescriptor, DB, IteratorMode, Options};
 use std::path::PathBuf;
 use zelana_account::AccountState;
+use zelana_privacy::Nullifier;
 use zelana_transaction::Transaction;
 
 const CF_ACCOUNTS: &str = "accounts";
@@ -76,7 +77,9 @@ pub fn load_database(
     if let Some(cf) = db.cf_handle(CF_NULLIFIERS) {
         for entry in db.iterator_cf(&cf, IteratorMode::Start) {
             let (key_bytes, _) = entry?;
-            nullifiers.push(hex::encode(&key_bytes));
+            if let Ok(bytes) = <[u8; 32]>::try_from(key_bytes.as_ref()) {
+                nullifiers.push(Nullifier(bytes).to_string());
+            }
         }
     }
     nullifiers.reverse();
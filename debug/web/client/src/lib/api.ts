@@ -14,6 +14,7 @@ export interface PaginatedResponse<T> {
 export interface Stats {
   accounts: number;
   transactions: number;
+  transactions_by_type: Record<string, number>;
   batches: number;
   blocks: number;
   nullifiers: number;
@@ -132,6 +133,8 @@ export const api = {
       batch_id?: number;
       tx_type?: string;
       status?: string;
+      from_ts?: number;
+      to_ts?: number;
     }
   ) => {
     const params = new URLSearchParams({
@@ -141,6 +144,9 @@ export const api = {
     if (filters?.batch_id) params.set("batch_id", String(filters.batch_id));
     if (filters?.tx_type) params.set("tx_type", filters.tx_type);
     if (filters?.status) params.set("status", filters.status);
+    if (filters?.from_ts !== undefined)
+      params.set("from_ts", String(filters.from_ts));
+    if (filters?.to_ts !== undefined) params.set("to_ts", String(filters.to_ts));
     return fetchApi<PaginatedResponse<Transaction>>(
       `/transactions?${params.toString()}`
     );
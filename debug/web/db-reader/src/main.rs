@@ -4,9 +4,13 @@
 //! via a Unix socket or TCP. This allows the Bun.js frontend to query database
 //! state without needing native RocksDB bindings.
 
+mod replay;
+
 use anyhow::{Context, Result};
+use replay::{ReplayState, ReplayTx};
 use rocksdb::{ColumnFamilyDescriptor, DB, IteratorMode, Options};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::io::{BufRead, BufReader, Write};
 use std::net::TcpListener;
 use std::path::{Path, PathBuf};
@@ -14,7 +18,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use zelana_account::AccountState;
 use zelana_block::BlockHeader;
-use zelana_privacy::EncryptedNote;
+use zelana_privacy::{Commitment, EncryptedNote, Nullifier};
 
 // Column family names (must match core/src/sequencer/db.rs)
 const CF_ACCOUNTS: &str = "accounts";
@@ -29,6 +33,9 @@ const CF_PROCESSED_DEPOSITS: &str = "processed_deposits";
 const CF_BATCHES: &str = "batches";
 const CF_TX_INDEX: &str = "tx_index";
 const CF_INDEXER_META: &str = "indexer_meta";
+const CF_ACCOUNT_TX_INDEX: &str = "account_tx_index";
+const CF_WITHDRAWAL_INDEX: &str = "withdrawal_index";
+const CF_BATCH_META: &str = "batch_meta";
 
 /// Request from the Bun server
 #[derive(Debug, Deserialize)]
@@ -47,9 +54,19 @@ enum Request {
         batch_id: Option<u64>,
         tx_type: Option<String>,
         status: Option<String>,
+        /// Inclusive lower bound on `received_at` (unix seconds)
+        from_ts: Option<u64>,
+        /// Inclusive upper bound on `received_at` (unix seconds)
+        to_ts: Option<u64>,
     },
     #[serde(rename = "transaction")]
     Transaction { hash: String },
+    #[serde(rename = "account_transactions")]
+    AccountTransactions {
+        id: String,
+        offset: usize,
+        limit: usize,
+    },
     #[serde(rename = "batches")]
     Batches { offset: usize, limit: usize },
     #[serde(rename = "batch")]
@@ -68,8 +85,12 @@ enum Request {
     Deposits { offset: usize, limit: usize },
     #[serde(rename = "withdrawals")]
     Withdrawals { offset: usize, limit: usize },
+    #[serde(rename = "withdrawal_index")]
+    WithdrawalIndex { hash: String },
     #[serde(rename = "indexer_meta")]
     IndexerMeta,
+    #[serde(rename = "verify_state")]
+    VerifyState,
     #[serde(rename = "ping")]
     Ping,
 }
@@ -102,6 +123,45 @@ impl Response {
     }
 }
 
+/// Filters for the `transactions` query; unset fields match everything
+#[derive(Debug, Default)]
+struct TxFilter {
+    batch_id: Option<u64>,
+    tx_type: Option<String>,
+    status: Option<String>,
+    from_ts: Option<u64>,
+    to_ts: Option<u64>,
+}
+
+impl TxFilter {
+    fn matches(&self, summary: &serde_json::Value) -> bool {
+        if let Some(bid) = self.batch_id {
+            if summary.get("batch_id").and_then(|v| v.as_u64()) != Some(bid) {
+                return false;
+            }
+        }
+        if let Some(ref tt) = self.tx_type {
+            if summary.get("tx_type").and_then(|v| v.as_str()) != Some(tt) {
+                return false;
+            }
+        }
+        if let Some(ref st) = self.status {
+            if summary.get("status").and_then(|v| v.as_str()) != Some(st) {
+                return false;
+            }
+        }
+        if self.from_ts.is_some() || self.to_ts.is_some() {
+            let Some(ts) = summary.get("received_at").and_then(|v| v.as_u64()) else {
+                return false;
+            };
+            if self.from_ts.is_some_and(|from| ts < from) || self.to_ts.is_some_and(|to| ts > to) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 /// Database reader with cached handles
 struct DbReader {
     db: Arc<DB>,
@@ -126,6 +186,9 @@ impl DbReader {
             CF_BATCHES,
             CF_TX_INDEX,
             CF_INDEXER_META,
+            CF_ACCOUNT_TX_INDEX,
+            CF_WITHDRAWAL_INDEX,
+            CF_BATCH_META,
         ];
 
         // Helper to create descriptors (since they don't implement Clone)
@@ -161,8 +224,23 @@ impl DbReader {
                 batch_id,
                 tx_type,
                 status,
-            } => self.get_transactions(offset, limit, batch_id, tx_type, status),
+                from_ts,
+                to_ts,
+            } => self.get_transactions(
+                offset,
+                limit,
+                &TxFilter {
+                    batch_id,
+                    tx_type,
+                    status,
+                    from_ts,
+                    to_ts,
+                },
+            ),
             Request::Transaction { hash } => self.get_transaction(&hash),
+            Request::AccountTransactions { id, offset, limit } => {
+                self.get_account_transactions(&id, offset, limit)
+            }
             Request::Batches { offset, limit } => self.get_batches(offset, limit),
             Request::Batch { id } => self.get_batch(id),
             Request::Blocks { offset, limit } => self.get_blocks(offset, limit),
@@ -172,13 +250,15 @@ impl DbReader {
             Request::TreeMeta => self.get_tree_meta(),
             Request::Deposits { offset, limit } => self.get_deposits(offset, limit),
             Request::Withdrawals { offset, limit } => self.get_withdrawals(offset, limit),
+            Request::WithdrawalIndex { hash } => self.get_withdrawal_index(&hash),
             Request::IndexerMeta => self.get_indexer_meta(),
+            Request::VerifyState => self.verify_state(),
         }
     }
 
     fn get_stats(&self) -> Response {
         let accounts_count = self.count_cf(CF_ACCOUNTS).unwrap_or(0);
-        let transactions_count = self.count_cf(CF_TX_INDEX).unwrap_or(0);
+        let (transactions_count, transactions_by_type) = self.count_tx_types().unwrap_or_default();
         let batches_count = self.count_cf(CF_BATCHES).unwrap_or(0);
         let blocks_count = self.count_cf(CF_BLOCKS).unwrap_or(0);
         let nullifiers_count = self.count_cf(CF_NULLIFIERS).unwrap_or(0);
@@ -187,7 +267,7 @@ impl DbReader {
         let withdrawals_count = self.count_cf(CF_WITHDRAWALS).unwrap_or(0);
         let deposits_count = self.count_cf(CF_PROCESSED_DEPOSITS).unwrap_or(0);
 
-        // Get latest state root from blocks
+        // Get latest state root from batch summaries (or legacy blocks)
         let latest_state_root = self
             .get_latest_state_root()
             .unwrap_or_else(|| "0".repeat(64));
@@ -196,6 +276,7 @@ impl DbReader {
         Response::ok(serde_json::json!({
             "accounts": accounts_count,
             "transactions": transactions_count,
+            "transactions_by_type": transactions_by_type,
             "batches": batches_count,
             "blocks": blocks_count,
             "nullifiers": nullifiers_count,
@@ -285,14 +366,7 @@ impl DbReader {
         }
     }
 
-    fn get_transactions(
-        &self,
-        offset: usize,
-        limit: usize,
-        batch_id: Option<u64>,
-        tx_type: Option<String>,
-        status: Option<String>,
-    ) -> Response {
+    fn get_transactions(&self, offset: usize, limit: usize, filter: &TxFilter) -> Response {
         let cf = match self.db.cf_handle(CF_TX_INDEX) {
             Some(cf) => cf,
             None => return Response::err("tx_index CF not found"),
@@ -309,21 +383,9 @@ impl DbReader {
 
             let tx_hash = hex::encode(&key);
             if let Ok(summary) = serde_json::from_slice::<serde_json::Value>(&value) {
-                // Apply filters
-                if let Some(bid) = batch_id {
-                    if summary.get("batch_id").and_then(|v| v.as_u64()) != Some(bid) {
-                        continue;
-                    }
-                }
-                if let Some(ref tt) = tx_type {
-                    if summary.get("tx_type").and_then(|v| v.as_str()) != Some(tt) {
-                        continue;
-                    }
-                }
-                if let Some(ref st) = status {
-                    if summary.get("status").and_then(|v| v.as_str()) != Some(st) {
-                        continue;
-                    }
+                // No time index exists, so the range is checked in the same pass
+                if !filter.matches(&summary) {
+                    continue;
                 }
 
                 let mut tx = summary.clone();
@@ -379,6 +441,61 @@ impl DbReader {
         }
     }
 
+    /// An account's transactions, oldest first, via the account_tx_index range
+    fn get_account_transactions(&self, id: &str, offset: usize, limit: usize) -> Response {
+        let (cf_account, cf_tx) = match (
+            self.db.cf_handle(CF_ACCOUNT_TX_INDEX),
+            self.db.cf_handle(CF_TX_INDEX),
+        ) {
+            (Some(a), Some(t)) => (a, t),
+            _ => return Response::err("account_tx_index or tx_index CF not found"),
+        };
+
+        let prefix = match hex::decode(id) {
+            Ok(k) if k.len() == 32 => k,
+            _ => return Response::err("Invalid hex ID"),
+        };
+
+        let mut hashes = Vec::new();
+        for item in self.db.prefix_iterator_cf(&cf_account, &prefix) {
+            let (key, value) = match item {
+                Ok(kv) => kv,
+                Err(e) => return Response::err(format!("Iterator error: {}", e)),
+            };
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            hashes.push(value);
+        }
+
+        let total = hashes.len();
+        let mut items = Vec::new();
+        for hash in hashes.into_iter().skip(offset).take(limit) {
+            match self.db.get_cf(&cf_tx, &hash) {
+                Ok(Some(value)) => {
+                    if let Ok(mut summary) = serde_json::from_slice::<serde_json::Value>(&value) {
+                        if let serde_json::Value::Object(ref mut map) = summary {
+                            map.insert(
+                                "tx_hash".to_string(),
+                                serde_json::json!(hex::encode(&hash)),
+                            );
+                        }
+                        items.push(summary);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => return Response::err(format!("DB error: {}", e)),
+            }
+        }
+
+        Response::ok(serde_json::json!({
+            "items": items,
+            "total": total,
+            "offset": offset,
+            "limit": limit,
+        }))
+    }
+
     fn get_batches(&self, offset: usize, limit: usize) -> Response {
         let cf = match self.db.cf_handle(CF_BATCHES) {
             Some(cf) => cf,
@@ -494,9 +611,11 @@ impl DbReader {
                 Err(e) => return Response::err(format!("Iterator error: {}", e)),
             };
 
-            nullifiers.push(serde_json::json!({
-                "nullifier": hex::encode(&key),
-            }));
+            if let Ok(bytes) = <[u8; 32]>::try_from(key.as_ref()) {
+                nullifiers.push(serde_json::json!({
+                    "nullifier": Nullifier(bytes).to_string(),
+                }));
+            }
         }
 
         let total = nullifiers.len();
@@ -525,11 +644,13 @@ impl DbReader {
                 Err(e) => return Response::err(format!("Iterator error: {}", e)),
             };
 
-            if key.len() == 4 && value.len() == 32 {
-                let position = u32::from_be_bytes(key.as_ref().try_into().unwrap_or([0; 4]));
+            if let (Ok(position), Ok(commitment)) = (
+                <[u8; 4]>::try_from(key.as_ref()),
+                <[u8; 32]>::try_from(value.as_ref()),
+            ) {
                 commitments.push(serde_json::json!({
-                    "position": position,
-                    "commitment": hex::encode(&value),
+                    "position": u32::from_be_bytes(position),
+                    "commitment": Commitment(commitment).to_string(),
                 }));
             }
         }
@@ -682,6 +803,34 @@ impl DbReader {
         }))
     }
 
+    /// Batch and committed withdrawal root needed to claim a withdrawal
+    fn get_withdrawal_index(&self, hash: &str) -> Response {
+        let cf = match self.db.cf_handle(CF_WITHDRAWAL_INDEX) {
+            Some(cf) => cf,
+            None => return Response::err("withdrawal_index CF not found"),
+        };
+
+        let key = match hex::decode(hash) {
+            Ok(k) => k,
+            Err(_) => return Response::err("Invalid hex hash"),
+        };
+
+        match self.db.get_cf(&cf, &key) {
+            Ok(Some(value)) => {
+                if let Ok(mut entry) = serde_json::from_slice::<serde_json::Value>(&value) {
+                    if let serde_json::Value::Object(ref mut map) = entry {
+                        map.insert("tx_hash".to_string(), serde_json::json!(hash));
+                    }
+                    Response::ok(entry)
+                } else {
+                    Response::err("Failed to deserialize withdrawal index entry")
+                }
+            }
+            Ok(None) => Response::err("Withdrawal not indexed"),
+            Err(e) => Response::err(format!("DB error: {}", e)),
+        }
+    }
+
     fn get_indexer_meta(&self) -> Response {
         let cf = match self.db.cf_handle(CF_INDEXER_META) {
             Some(cf) => cf,
@@ -700,8 +849,213 @@ impl DbReader {
         }))
     }
 
+    /// Replay every recorded batch from genesis and check the result against
+    /// the stored accounts and state root
+    ///
+    /// Batches come from the `batches` column family and their transactions
+    /// from `tx_index`, applied in `batch_index` order. A batch diverges when a
+    /// transaction fails to apply or when `tx_index` no longer holds all
+    /// `tx_count` of its records. Transactions of batches without a summary
+    /// yet (executed, not settled) are applied afterwards.
+    ///
+    /// The latest batch's `state_root` is recomputed as the sealer does: the
+    /// account tree (leaf position derived from the account id, so only final
+    /// states matter) over the state before the batch plus its transfers and
+    /// withdrawals. Its deposits and shielded legs are left out.
+    ///
+    /// Refused once the sequencer has compacted `tx_index`, since history
+    /// before the retention window is gone.
+    fn verify_state(&self) -> Response {
+        match self.get_tx_index_pruned_before() {
+            Ok(None) => {}
+            Ok(Some(batch_id)) => {
+                return Response::err(format!(
+                    "tx_index was compacted before batch {}; replay from genesis is unavailable",
+                    batch_id
+                ));
+            }
+            Err(e) => return Response::err(format!("Failed to read compaction horizon: {}", e)),
+        }
+
+        let mut txs_by_batch = match self.load_replay_txs() {
+            Ok(txs) => txs,
+            Err(e) => return Response::err(format!("Failed to load transactions: {}", e)),
+        };
+
+        let cf = match self.db.cf_handle(CF_BATCHES) {
+            Some(cf) => cf,
+            None => return Response::err("batches CF not found"),
+        };
+
+        let mut state = ReplayState::new();
+        let mut batches_replayed = 0u64;
+        let mut txs_applied = 0u64;
+        let mut first_divergence = None;
+        let mut state_root = None;
+
+        let mut replay = |batch_id: u64,
+                          txs: Vec<ReplayTx>,
+                          tx_count: Option<u64>,
+                          stored_root: Option<String>| {
+            if let Some(stored) = stored_root {
+                // Failures are reported by the full replay below
+                let mut sealed = state.clone();
+                for tx in txs.iter().filter(|tx| tx.in_state_root()) {
+                    let _ = sealed.apply(tx);
+                }
+                let replayed = hex::encode(sealed.state_root());
+                state_root = Some(serde_json::json!({
+                    "batch_id": batch_id,
+                    "stored": stored,
+                    "replayed": replayed,
+                    "matches": stored == replayed,
+                }));
+            }
+
+            let mut reason = tx_count
+                .filter(|&expected| expected != txs.len() as u64)
+                .map(|expected| {
+                    format!(
+                        "tx_index holds {} of {} transactions (pruned or missing)",
+                        txs.len(),
+                        expected
+                    )
+                });
+            for tx in txs.iter().filter(|tx| tx.is_applied()) {
+                match state.apply(tx) {
+                    Ok(()) => txs_applied += 1,
+                    Err(e) => {
+                        reason.get_or_insert(e);
+                    }
+                }
+            }
+            batches_replayed += 1;
+
+            if let Some(reason) = reason.filter(|_| first_divergence.is_none()) {
+                first_divergence = Some(serde_json::json!({
+                    "batch_id": batch_id,
+                    "reason": reason,
+                }));
+            }
+        };
+
+        let mut summaries = Vec::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (key, value) = match item {
+                Ok(kv) => kv,
+                Err(e) => return Response::err(format!("Iterator error: {}", e)),
+            };
+            let Ok(key) = <[u8; 8]>::try_from(key.as_ref()) else {
+                continue;
+            };
+            let batch_id = u64::from_be_bytes(key);
+            match serde_json::from_slice::<serde_json::Value>(&value) {
+                Ok(summary) => summaries.push((batch_id, summary)),
+                Err(e) => {
+                    return Response::err(format!("Failed to parse batch {}: {}", batch_id, e));
+                }
+            }
+        }
+
+        let latest = summaries.len().saturating_sub(1);
+        for (i, (batch_id, summary)) in summaries.into_iter().enumerate() {
+            let txs = txs_by_batch.remove(&batch_id).unwrap_or_default();
+            let stored_root = summary["state_root"]
+                .as_str()
+                .filter(|root| i == latest && !root.is_empty())
+                .map(str::to_owned);
+            replay(batch_id, txs, summary["tx_count"].as_u64(), stored_root);
+        }
+        for (batch_id, txs) in txs_by_batch {
+            replay(batch_id, txs, None, None);
+        }
+
+        let account_mismatches = match self.diff_accounts(&state) {
+            Ok(m) => m,
+            Err(e) => return Response::err(format!("Failed to compare accounts: {}", e)),
+        };
+
+        let root_matches = state_root
+            .as_ref()
+            .is_none_or(|check| check["matches"] == true);
+        let matches = first_divergence.is_none() && account_mismatches.is_empty() && root_matches;
+
+        Response::ok(serde_json::json!({
+            "matches": matches,
+            "batches_replayed": batches_replayed,
+            "transactions_applied": txs_applied,
+            "first_divergent_batch": first_divergence,
+            "account_mismatches": account_mismatches,
+            "state_root": state_root,
+        }))
+    }
+
     // Helper methods
 
+    /// Transaction records from `tx_index` (failed ones included), grouped by
+    /// batch in execution order
+    fn load_replay_txs(&self) -> Result<BTreeMap<u64, Vec<ReplayTx>>> {
+        let cf = self
+            .db
+            .cf_handle(CF_TX_INDEX)
+            .context("tx_index CF not found")?;
+
+        let mut by_batch: BTreeMap<u64, Vec<ReplayTx>> = BTreeMap::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (_, value) = item?;
+            let tx: ReplayTx = serde_json::from_slice(&value)?;
+            if let Some(batch_id) = tx.batch_id {
+                by_batch.entry(batch_id).or_default().push(tx);
+            }
+        }
+
+        // A batch's summaries share one timestamp, so `batch_index` restores the
+        // sequencer's canonical order; records without it keep tx_hash order
+        for txs in by_batch.values_mut() {
+            txs.sort_by_key(|tx| (tx.received_at, tx.batch_index));
+        }
+        Ok(by_batch)
+    }
+
+    /// Stored accounts that disagree with the replayed state
+    fn diff_accounts(&self, state: &ReplayState) -> Result<Vec<serde_json::Value>> {
+        let cf = self
+            .db
+            .cf_handle(CF_ACCOUNTS)
+            .context("accounts CF not found")?;
+
+        let mut stored = BTreeMap::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (key, value) = item?;
+            if let Ok(id) = <[u8; 32]>::try_from(key.as_ref()) {
+                stored.insert(id, wincode::deserialize::<AccountState>(&value)?);
+            }
+        }
+
+        let mut ids: Vec<_> = stored.keys().chain(state.accounts().keys()).collect();
+        ids.sort();
+        ids.dedup();
+
+        let account_json = |s: Option<&AccountState>| {
+            s.map(|s| serde_json::json!({"balance": s.balance, "nonce": s.nonce}))
+        };
+        Ok(ids
+            .into_iter()
+            .filter(|id| {
+                // Missing accounts read as default, same as the sequencer
+                stored.get(*id).copied().unwrap_or_default()
+                    != state.accounts().get(*id).copied().unwrap_or_default()
+            })
+            .map(|id| {
+                serde_json::json!({
+                    "id": hex::encode(id),
+                    "stored": account_json(stored.get(id)),
+                    "replayed": account_json(state.accounts().get(id)),
+                })
+            })
+            .collect())
+    }
+
     fn count_cf(&self, cf_name: &str) -> Option<u64> {
         let cf = self.db.cf_handle(cf_name)?;
         let mut count = 0u64;
@@ -712,7 +1066,44 @@ impl DbReader {
         Some(count)
     }
 
+    /// Total tx_index entries and a per-`tx_type` tally, in one pass
+    fn count_tx_types(&self) -> Option<(u64, BTreeMap<String, u64>)> {
+        let cf = self.db.cf_handle(CF_TX_INDEX)?;
+        let mut by_type: BTreeMap<String, u64> = ["deposit", "transfer", "shielded", "withdrawal"]
+            .into_iter()
+            .map(|t| (t.to_string(), 0))
+            .collect();
+        let mut total = 0u64;
+
+        for (_, value) in self.db.iterator_cf(&cf, IteratorMode::Start).flatten() {
+            total += 1;
+            let Ok(summary) = serde_json::from_slice::<serde_json::Value>(&value) else {
+                continue;
+            };
+            if let Some(tx_type) = summary.get("tx_type").and_then(|v| v.as_str()) {
+                *by_type.entry(tx_type.to_string()).or_default() += 1;
+            }
+        }
+        Some((total, by_type))
+    }
+
+    /// Latest batch summary's `state_root`, falling back to the legacy
+    /// block headers (same order as the sequencer's lookup)
     fn get_latest_state_root(&self) -> Option<String> {
+        let latest_summary = self
+            .db
+            .cf_handle(CF_BATCHES)
+            .and_then(|cf| self.db.iterator_cf(&cf, IteratorMode::End).next())
+            .and_then(|item| item.ok())
+            .and_then(|(_, value)| serde_json::from_slice::<serde_json::Value>(&value).ok());
+        if let Some(root) = latest_summary
+            .as_ref()
+            .and_then(|summary| summary["state_root"].as_str())
+            .filter(|root| !root.is_empty())
+        {
+            return Some(root.to_string());
+        }
+
         let cf = self.db.cf_handle(CF_BLOCKS)?;
         let mut iter = self.db.iterator_cf(&cf, IteratorMode::End);
         if let Some(Ok((_, value))) = iter.next() {
@@ -723,6 +1114,24 @@ impl DbReader {
         None
     }
 
+    /// Horizon recorded by the sequencer's tx_index compaction, if it ever ran
+    fn get_tx_index_pruned_before(&self) -> Result<Option<u64>> {
+        let cf = self
+            .db
+            .cf_handle(CF_BATCH_META)
+            .context("batch_meta CF not found")?;
+        match self.db.get_cf(&cf, b"tx_index_pruned_before")? {
+            Some(bytes) => {
+                let arr: [u8; 8] = bytes
+                    .as_slice()
+                    .try_into()
+                    .context("invalid tx_index_pruned_before length")?;
+                Ok(Some(u64::from_be_bytes(arr)))
+            }
+            None => Ok(None),
+        }
+    }
+
     fn get_latest_batch_id(&self) -> Option<u64> {
         let cf = self.db.cf_handle(CF_BATCHES)?;
         let mut iter = self.db.iterator_cf(&cf, IteratorMode::End);
@@ -813,3 +1222,394 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocksdb::WriteBatch;
+
+    const ALICE: [u8; 32] = [0xaa; 32];
+    const BOB: [u8; 32] = [0xbb; 32];
+
+    fn summary(
+        batch_id: u64,
+        tx_type: &str,
+        amount: u64,
+        from: Option<[u8; 32]>,
+        to: [u8; 32],
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "tx_type": tx_type,
+            "batch_id": batch_id,
+            "status": "executed",
+            "received_at": batch_id,
+            "amount": amount,
+            "from": from.map(hex::encode),
+            "to": hex::encode(to),
+        })
+    }
+
+    /// Two-batch chain: deposits, then a transfer and a withdrawal.
+    ///
+    /// With `inflate_alice` the stored account of Alice carries a credit no
+    /// recorded transaction explains.
+    fn seed_chain(name: &str, inflate_alice: bool) -> DbReader {
+        let path = std::env::temp_dir().join(format!("db_reader_{}_{}", name, std::process::id()));
+        let _ = DB::destroy(&Options::default(), &path);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(
+            &opts,
+            &path,
+            [
+                CF_ACCOUNTS,
+                CF_BLOCKS,
+                CF_TX_INDEX,
+                CF_BATCHES,
+                CF_ACCOUNT_TX_INDEX,
+                CF_BATCH_META,
+            ],
+        )
+        .unwrap();
+
+        let batches = [
+            vec![
+                summary(1, "deposit", 100, None, ALICE),
+                summary(1, "deposit", 50, None, BOB),
+            ],
+            vec![
+                summary(2, "transfer", 30, Some(ALICE), BOB),
+                summary(2, "withdrawal", 20, Some(BOB), BOB),
+            ],
+        ];
+
+        let mut state = ReplayState::new();
+        let mut batch = WriteBatch::default();
+        let mut tx_hash = 0u8;
+        for (i, txs) in batches.iter().enumerate() {
+            let batch_id = i as u64 + 1;
+            // The sealer's root leaves the batch's deposits to the next one
+            let mut sealed = state.clone();
+            for tx in txs {
+                tx_hash += 1;
+                let cf = db.cf_handle(CF_TX_INDEX).unwrap();
+                batch.put_cf(&cf, [tx_hash; 32], serde_json::to_vec(tx).unwrap());
+                let tx: ReplayTx = serde_json::from_value(tx.clone()).unwrap();
+                if tx.tx_type != "deposit" {
+                    sealed.apply(&tx).unwrap();
+                }
+                state.apply(&tx).unwrap();
+            }
+
+            let cf = db.cf_handle(CF_BATCHES).unwrap();
+            let batch_summary = serde_json::json!({
+                "batch_id": batch_id,
+                "tx_count": txs.len(),
+                "state_root": hex::encode(sealed.state_root()),
+                "status": "settled",
+            });
+            batch.put_cf(
+                &cf,
+                batch_id.to_be_bytes(),
+                serde_json::to_vec(&batch_summary).unwrap(),
+            );
+        }
+        if inflate_alice {
+            // Unrecorded credit: only the stored account knows about it
+            state
+                .apply(&serde_json::from_value(summary(2, "deposit", 1000, None, ALICE)).unwrap())
+                .unwrap();
+        }
+
+        let cf = db.cf_handle(CF_ACCOUNTS).unwrap();
+        for (id, account) in state.accounts() {
+            batch.put_cf(&cf, id, wincode::serialize(account).unwrap());
+        }
+        db.write(batch).unwrap();
+
+        DbReader { db: Arc::new(db) }
+    }
+
+    fn verify(reader: &DbReader) -> serde_json::Value {
+        let response =
+            reader.handle_request(serde_json::from_str(r#"{"cmd":"verify_state"}"#).unwrap());
+        assert!(response.success, "{:?}", response.error);
+        response.data.unwrap()
+    }
+
+    #[test]
+    fn test_verify_state_matches_clean_chain() {
+        let report = verify(&seed_chain("clean", false));
+
+        assert_eq!(report["matches"], true);
+        assert_eq!(report["batches_replayed"], 2);
+        assert_eq!(report["transactions_applied"], 4);
+        assert!(report["first_divergent_batch"].is_null());
+        assert_eq!(report["account_mismatches"], serde_json::json!([]));
+        assert_eq!(report["state_root"]["batch_id"], 2);
+        assert_eq!(report["state_root"]["matches"], true);
+    }
+
+    #[test]
+    fn test_verify_state_flags_state_root_mismatch() {
+        let reader = seed_chain("root", false);
+        let cf = reader.db.cf_handle(CF_BATCHES).unwrap();
+        let key = 2u64.to_be_bytes();
+        let mut summary: serde_json::Value =
+            serde_json::from_slice(&reader.db.get_cf(&cf, key).unwrap().unwrap()).unwrap();
+        let expected = summary["state_root"].clone();
+
+        summary["state_root"] = hex::encode([7u8; 32]).into();
+        reader
+            .db
+            .put_cf(&cf, key, serde_json::to_vec(&summary).unwrap())
+            .unwrap();
+
+        let report = verify(&reader);
+        assert_eq!(report["matches"], false);
+        assert!(report["first_divergent_batch"].is_null());
+        assert_eq!(report["account_mismatches"], serde_json::json!([]));
+        assert_eq!(report["state_root"]["stored"], hex::encode([7u8; 32]));
+        assert_eq!(report["state_root"]["replayed"], expected);
+        assert_eq!(report["state_root"]["matches"], false);
+    }
+
+    #[test]
+    fn test_verify_state_root_leaves_out_latest_deposits() {
+        let reader = seed_chain("root_deposit", false);
+
+        // A deposit lands in the latest batch after its root was sealed
+        let cf = reader.db.cf_handle(CF_TX_INDEX).unwrap();
+        let deposit = summary(2, "deposit", 5, None, ALICE);
+        reader
+            .db
+            .put_cf(&cf, [0xd1; 32], serde_json::to_vec(&deposit).unwrap())
+            .unwrap();
+        let cf = reader.db.cf_handle(CF_BATCHES).unwrap();
+        let key = 2u64.to_be_bytes();
+        let mut summary: serde_json::Value =
+            serde_json::from_slice(&reader.db.get_cf(&cf, key).unwrap().unwrap()).unwrap();
+        summary["tx_count"] = 3.into();
+        reader
+            .db
+            .put_cf(&cf, key, serde_json::to_vec(&summary).unwrap())
+            .unwrap();
+        let cf = reader.db.cf_handle(CF_ACCOUNTS).unwrap();
+        let alice = AccountState {
+            balance: 75,
+            nonce: 1,
+        };
+        reader
+            .db
+            .put_cf(&cf, ALICE, wincode::serialize(&alice).unwrap())
+            .unwrap();
+
+        let report = verify(&reader);
+        assert_eq!(report["matches"], true);
+        assert_eq!(report["state_root"]["matches"], true);
+    }
+
+    #[test]
+    fn test_verify_state_applies_shield_and_unshield() {
+        let reader = seed_chain("shielded", false);
+        let cf = reader.db.cf_handle(CF_TX_INDEX).unwrap();
+        let mut shield = summary(3, "shielded", 0, Some(ALICE), ALICE);
+        shield["to"] = serde_json::Value::Null;
+        shield["shield_amount"] = 40.into();
+        let mut unshield = summary(3, "shielded", 0, None, BOB);
+        unshield["unshield_amount"] = 25.into();
+        for (hash, tx) in [(0xd1u8, shield), (0xd2, unshield)] {
+            reader
+                .db
+                .put_cf(&cf, [hash; 32], serde_json::to_vec(&tx).unwrap())
+                .unwrap();
+        }
+
+        let report = verify(&reader);
+
+        // Stored accounts predate batch 3, so both legs show up as drift
+        assert_eq!(report["batches_replayed"], 3);
+        assert!(report["first_divergent_batch"].is_null());
+        let mismatches = report["account_mismatches"].as_array().unwrap();
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(mismatches[0]["id"], hex::encode(ALICE));
+        assert_eq!(mismatches[0]["replayed"]["balance"], 30);
+        assert_eq!(mismatches[1]["id"], hex::encode(BOB));
+        assert_eq!(mismatches[1]["replayed"]["balance"], 125);
+    }
+
+    #[test]
+    fn test_verify_state_flags_pruned_batch() {
+        let reader = seed_chain("pruned", false);
+        let cf = reader.db.cf_handle(CF_TX_INDEX).unwrap();
+        reader.db.delete_cf(&cf, [1u8; 32]).unwrap();
+
+        let report = verify(&reader);
+
+        assert_eq!(report["matches"], false);
+        assert_eq!(report["first_divergent_batch"]["batch_id"], 1);
+        assert_eq!(
+            report["first_divergent_batch"]["reason"],
+            "tx_index holds 1 of 2 transactions (pruned or missing)"
+        );
+    }
+
+    #[test]
+    fn test_verify_state_refuses_compacted_history() {
+        let reader = seed_chain("compacted", false);
+        let cf = reader.db.cf_handle(CF_BATCH_META).unwrap();
+        reader
+            .db
+            .put_cf(&cf, b"tx_index_pruned_before", 2u64.to_be_bytes())
+            .unwrap();
+
+        let response =
+            reader.handle_request(serde_json::from_str(r#"{"cmd":"verify_state"}"#).unwrap());
+        assert!(!response.success);
+        assert!(response.error.unwrap().contains("compacted before batch 2"));
+    }
+
+    #[test]
+    fn test_stats_counts_transactions_by_type() {
+        let reader = seed_chain("stats", false);
+        let cf = reader.db.cf_handle(CF_TX_INDEX).unwrap();
+        for (i, tx_type) in ["transfer", "shielded", "shielded"].iter().enumerate() {
+            let tx = summary(3, tx_type, 1, Some(ALICE), BOB);
+            reader
+                .db
+                .put_cf(&cf, [0xf0 + i as u8; 32], serde_json::to_vec(&tx).unwrap())
+                .unwrap();
+        }
+
+        let response = reader.handle_request(serde_json::from_str(r#"{"cmd":"stats"}"#).unwrap());
+        assert!(response.success, "{:?}", response.error);
+        let stats = response.data.unwrap();
+
+        assert_eq!(stats["transactions"], 7);
+        assert_eq!(
+            stats["transactions_by_type"],
+            serde_json::json!({
+                "deposit": 2,
+                "transfer": 2,
+                "shielded": 2,
+                "withdrawal": 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_transactions_time_range_filter() {
+        let reader = seed_chain("time_range", false);
+        let cf = reader.db.cf_handle(CF_TX_INDEX).unwrap();
+        for received_at in [100u64, 200, 300] {
+            let mut tx = summary(3, "transfer", 1, Some(ALICE), BOB);
+            tx["received_at"] = received_at.into();
+            reader
+                .db
+                .put_cf(
+                    &cf,
+                    [received_at as u8; 32],
+                    serde_json::to_vec(&tx).unwrap(),
+                )
+                .unwrap();
+        }
+
+        let query = |range: &str| {
+            let req = format!(r#"{{"cmd":"transactions","offset":0,"limit":50{}}}"#, range);
+            let response = reader.handle_request(serde_json::from_str(&req).unwrap());
+            assert!(response.success, "{:?}", response.error);
+            let data = response.data.unwrap();
+            data["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|tx| tx["received_at"].as_u64().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(query(r#","from_ts":100,"to_ts":200"#), vec![200, 100]);
+        assert_eq!(query(r#","from_ts":150"#), vec![300, 200]);
+        assert_eq!(query(r#","to_ts":1"#), vec![1, 1]);
+        assert_eq!(query("").len(), 7);
+    }
+
+    #[test]
+    fn test_replay_txs_follow_batch_index() {
+        let reader = seed_chain("replay_order", false);
+        let cf = reader.db.cf_handle(CF_TX_INDEX).unwrap();
+        // tx_hash order is the reverse of execution order
+        for (hash, batch_index, amount) in [(1u8, 1u32, 20u64), (2, 0, 10)] {
+            let mut tx = summary(9, "deposit", amount, None, BOB);
+            tx["batch_index"] = batch_index.into();
+            reader
+                .db
+                .put_cf(&cf, [hash; 32], serde_json::to_vec(&tx).unwrap())
+                .unwrap();
+        }
+
+        let batches = reader.load_replay_txs().unwrap();
+        let amounts: Vec<_> = batches[&9].iter().map(|tx| tx.amount.unwrap()).collect();
+        assert_eq!(amounts, vec![10, 20]);
+    }
+
+    #[test]
+    fn test_account_transactions_range_scans_index() {
+        let reader = seed_chain("account_txs", false);
+        let cf_tx = reader.db.cf_handle(CF_TX_INDEX).unwrap();
+        let cf_account = reader.db.cf_handle(CF_ACCOUNT_TX_INDEX).unwrap();
+        let index = |account: [u8; 32], received_at: u64, hash: [u8; 32]| {
+            let key = [&account[..], &received_at.to_be_bytes(), &hash].concat();
+            reader.db.put_cf(&cf_account, key, hash).unwrap();
+        };
+        for (hash, received_at, from, to) in [
+            ([0xe1; 32], 30u64, ALICE, BOB),
+            ([0xe2; 32], 10, BOB, ALICE),
+            ([0xe3; 32], 20, BOB, [0xcc; 32]),
+        ] {
+            let mut tx = summary(3, "transfer", 1, Some(from), to);
+            tx["received_at"] = received_at.into();
+            reader
+                .db
+                .put_cf(&cf_tx, hash, serde_json::to_vec(&tx).unwrap())
+                .unwrap();
+            index(from, received_at, hash);
+            index(to, received_at, hash);
+        }
+
+        let req = format!(
+            r#"{{"cmd":"account_transactions","id":"{}","offset":0,"limit":10}}"#,
+            hex::encode(ALICE)
+        );
+        let response = reader.handle_request(serde_json::from_str(&req).unwrap());
+        assert!(response.success, "{:?}", response.error);
+        let data = response.data.unwrap();
+
+        assert_eq!(data["total"], 2);
+        let hashes: Vec<_> = data["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tx| tx["tx_hash"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            hashes,
+            vec![hex::encode([0xe2; 32]), hex::encode([0xe1; 32])]
+        );
+    }
+
+    #[test]
+    fn test_verify_state_flags_corrupted_account() {
+        let report = verify(&seed_chain("corrupt", true));
+
+        assert_eq!(report["matches"], false);
+        assert!(report["first_divergent_batch"].is_null());
+
+        let mismatches = report["account_mismatches"].as_array().unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0]["id"], hex::encode(ALICE));
+        assert_eq!(mismatches[0]["stored"]["balance"], 1070);
+        assert_eq!(mismatches[0]["replayed"]["balance"], 70);
+    }
+}
@@ -0,0 +1,129 @@
+//! State Replay
+//!
+//! Pure transparent-state machine used by `verify_state` to rebuild account
+//! state from the persisted batch and transaction records without trusting
+//! the `accounts` column family. Roots come from the sequencer's own
+//! `AccountTree`.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use zelana_account::{AccountId, AccountState};
+use zelana_core::sequencer::storage::account_tree::AccountTree;
+
+/// Transaction as recorded in the `tx_index` column family (subset of core's TxSummary)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplayTx {
+    pub tx_type: String,
+    pub batch_id: Option<u64>,
+    /// Position in the batch's canonical execution order (absent on old records)
+    #[serde(default)]
+    pub batch_index: Option<u32>,
+    pub status: String,
+    #[serde(default)]
+    pub received_at: u64,
+    pub amount: Option<u64>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// SHIELD debit of `from` (shielded txs)
+    #[serde(default)]
+    pub shield_amount: Option<u64>,
+    /// UNSHIELD credit to `to` (shielded txs)
+    #[serde(default)]
+    pub unshield_amount: Option<u64>,
+}
+
+impl ReplayTx {
+    /// Only executed transactions touched state; failed or pending ones are skipped
+    pub fn is_applied(&self) -> bool {
+        matches!(self.status.as_str(), "executed" | "settled")
+    }
+
+    /// Whether the batch's own post-state root covers this transaction: the
+    /// sealer leaves deposits and shielded legs to the next batch's root
+    pub fn in_state_root(&self) -> bool {
+        self.is_applied() && matches!(self.tx_type.as_str(), "transfer" | "withdrawal")
+    }
+}
+
+/// Transparent account state rebuilt from genesis
+#[derive(Debug, Default, Clone)]
+pub struct ReplayState {
+    accounts: BTreeMap<[u8; 32], AccountState>,
+}
+
+impl ReplayState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn accounts(&self) -> &BTreeMap<[u8; 32], AccountState> {
+        &self.accounts
+    }
+
+    /// Root of the sequencer's account tree over the replayed accounts
+    pub fn state_root(&self) -> [u8; 32] {
+        let mut tree = AccountTree::new();
+        for (id, state) in &self.accounts {
+            tree.insert(&AccountId(*id), state);
+        }
+        tree.root()
+    }
+
+    /// Apply one transaction, mirroring the sequencer's transition rules
+    pub fn apply(&mut self, tx: &ReplayTx) -> Result<(), String> {
+        match tx.tx_type.as_str() {
+            "deposit" => {
+                let to = parse_account(tx.to.as_deref(), "to")?;
+                let amount = tx.amount.ok_or("deposit without amount")?;
+                self.credit(to, amount)
+            }
+            "transfer" => {
+                let from = parse_account(tx.from.as_deref(), "from")?;
+                let to = parse_account(tx.to.as_deref(), "to")?;
+                let amount = tx.amount.ok_or("transfer without amount")?;
+                self.debit(from, amount)?;
+                self.credit(to, amount)
+            }
+            "withdrawal" => {
+                let from = parse_account(tx.from.as_deref(), "from")?;
+                let amount = tx.amount.ok_or("withdrawal without amount")?;
+                self.debit(from, amount)
+            }
+            // Only the SHIELD/UNSHIELD legs touch transparent accounts
+            "shielded" => {
+                if let Some(amount) = tx.shield_amount {
+                    self.debit(parse_account(tx.from.as_deref(), "from")?, amount)?;
+                }
+                if let Some(amount) = tx.unshield_amount {
+                    self.credit(parse_account(tx.to.as_deref(), "to")?, amount)?;
+                }
+                Ok(())
+            }
+            other => Err(format!("unknown tx_type '{}'", other)),
+        }
+    }
+
+    fn debit(&mut self, id: [u8; 32], amount: u64) -> Result<(), String> {
+        self.accounts
+            .entry(id)
+            .or_default()
+            .apply_transfer(amount, 0)
+            .map_err(|e| format!("{} ({})", e, hex::encode(id)))
+    }
+
+    fn credit(&mut self, id: [u8; 32], amount: u64) -> Result<(), String> {
+        self.accounts
+            .entry(id)
+            .or_default()
+            .apply_credit(amount)
+            .map_err(|e| format!("{} ({})", e, hex::encode(id)))
+    }
+}
+
+fn parse_account(hex_id: Option<&str>, field: &str) -> Result<[u8; 32], String> {
+    let hex_id = hex_id.ok_or_else(|| format!("missing '{}' account", field))?;
+    let bytes = hex::decode(hex_id).map_err(|_| format!("invalid '{}' account", field))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("'{}' account is not 32 bytes", field))
+}
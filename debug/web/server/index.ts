@@ -110,6 +110,24 @@ app.get("/api/accounts/:id", async (c) => {
   }
 });
 
+app.get("/api/accounts/:id/transactions", async (c) => {
+  const id = c.req.param("id");
+  const offset = parseInt(c.req.query("offset") || "0");
+  const limit = parseInt(c.req.query("limit") || "50");
+
+  try {
+    const result = await dbClient.request({
+      cmd: "account_transactions",
+      id,
+      offset,
+      limit,
+    });
+    return c.json(result);
+  } catch (e) {
+    return c.json({ error: String(e) }, 500);
+  }
+});
+
 // Transactions
 app.get("/api/transactions", async (c) => {
   const offset = parseInt(c.req.query("offset") || "0");
@@ -119,6 +137,12 @@ app.get("/api/transactions", async (c) => {
     : undefined;
   const tx_type = c.req.query("tx_type") || undefined;
   const status = c.req.query("status") || undefined;
+  const from_ts = c.req.query("from_ts")
+    ? parseInt(c.req.query("from_ts")!)
+    : undefined;
+  const to_ts = c.req.query("to_ts")
+    ? parseInt(c.req.query("to_ts")!)
+    : undefined;
 
   try {
     const result = await dbClient.request({
@@ -128,6 +152,8 @@ app.get("/api/transactions", async (c) => {
       batch_id,
       tx_type,
       status,
+      from_ts,
+      to_ts,
     });
     return c.json(result);
   } catch (e) {
@@ -282,6 +308,17 @@ app.get("/api/bridge/withdrawals", async (c) => {
   }
 });
 
+app.get("/api/bridge/withdrawals/:hash", async (c) => {
+  const hash = c.req.param("hash");
+
+  try {
+    const result = await dbClient.request({ cmd: "withdrawal_index", hash });
+    return c.json(result);
+  } catch (e) {
+    return c.json({ error: String(e) }, 500);
+  }
+});
+
 // Indexer
 app.get("/api/indexer", async (c) => {
   try {
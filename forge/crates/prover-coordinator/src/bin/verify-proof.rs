@@ -5,7 +5,9 @@
 use clap::Parser;
 use std::path::PathBuf;
 
-use prover_coordinator::{ProofData, SolanaVerifierClient, SolanaVerifierConfig};
+use prover_coordinator::{
+    ProofData, SolanaVerifierClient, SolanaVerifierConfig, VerificationCache,
+};
 
 #[derive(Parser, Debug)]
 #[command(name = "verify-proof")]
@@ -111,6 +113,8 @@ async fn main() -> anyhow::Result<()> {
         keypair_path: args.keypair,
         compute_units: args.compute_units,
         priority_fee_micro_lamports: 1000,
+        verification_cache: VerificationCache::default(),
+        preflight: true,
     };
 
     let client = SolanaVerifierClient::new(config)?;
@@ -6,14 +6,17 @@
 //! ## Endpoints
 //!
 //! - `POST /v2/batch/prove` - Submit batch for proving, returns job_id
-//! - `GET /v2/batch/:job_id/status` - Get proof job status (SSE stream)
+//! - `GET /v2/batch/:job_id/status` - Get proof job status (SSE stream,
+//!   resumable via `Last-Event-ID`)
 //! - `GET /v2/batch/:job_id/proof` - Get completed proof
-//! - `DELETE /v2/batch/:job_id` - Cancel proof job
+//! - `DELETE /v2/batch/:job_id` - Cancel proof job (kills nargo/sunspot if proving)
+//! - `GET /cache` - Proof cache statistics
+//! - `DELETE /cache` - Flush the proof cache (requires `Authorization: Bearer <admin token>`)
 
 use axum::{
     Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{DefaultBodyLimit, Path, State},
+    http::{HeaderMap, StatusCode},
     response::{
         IntoResponse, Response,
         sse::{Event, Sse},
@@ -21,15 +24,17 @@ use axum::{
     routing::{delete, get, post},
 };
 use futures::stream::Stream;
+use prover_network::admin::check_admin_token;
+use prover_network::limits::{CONTROL_BODY_LIMIT, PROVE_BODY_LIMIT};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     convert::Infallible,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use tokio::sync::{RwLock, broadcast};
+use tokio::sync::{RwLock, broadcast, watch};
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::BroadcastStream;
 use tracing::{error, info, warn};
@@ -57,6 +62,162 @@ pub struct CoreBatchProveRequest {
     pub shielded: Vec<CoreShieldedWitness>,
 }
 
+impl CoreBatchProveRequest {
+    /// Hex SHA-256 of the request's JSON encoding, addressing proofs in the
+    /// disk cache tier
+    pub fn witness_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let json = serde_json::to_vec(self).expect("request serializes");
+        hex::encode(Sha256::digest(json))
+    }
+
+    /// Reject requests that could never produce a valid witness, before any
+    /// proving work is scheduled.
+    ///
+    /// Checks per-kind counts against the circuit capacity, that every field
+    /// element parses and is in range, Merkle path shapes, balances covering
+    /// amounts, and that the shielded root is unchanged when the batch has no
+    /// shielded transactions.
+    pub fn validate(&self) -> Result<(), String> {
+        use prover_worker::{
+            MAX_SHIELDED, MAX_TRANSFERS, MAX_WITHDRAWALS, ShieldedData, TransferData,
+            WithdrawalData, parse_field_checked,
+        };
+
+        check_count("transfers", self.transfers.len(), MAX_TRANSFERS)?;
+        check_count("withdrawals", self.withdrawals.len(), MAX_WITHDRAWALS)?;
+        check_count("shielded", self.shielded.len(), MAX_SHIELDED)?;
+
+        let field = |name: &'static str, value: &str| {
+            parse_field_checked(name, value).map_err(|e| e.to_string())
+        };
+        field("pre_state_root", &self.pre_state_root)?;
+        field("post_state_root", &self.post_state_root)?;
+        let pre_shielded = field("pre_shielded_root", &self.pre_shielded_root)?;
+        let post_shielded = field("post_shielded_root", &self.post_shielded_root)?;
+        if self.shielded.is_empty() && pre_shielded != post_shielded {
+            return Err(
+                "post_shielded_root differs from pre_shielded_root with no shielded transactions"
+                    .to_string(),
+            );
+        }
+
+        for (i, tx) in self.transfers.iter().enumerate() {
+            let context = |e: String| format!("transfer {}: {}", i, e);
+            let data = TransferData {
+                sender_pubkey: field("sender_pubkey", &tx.sender_pubkey).map_err(context)?,
+                receiver_pubkey: field("receiver_pubkey", &tx.receiver_pubkey).map_err(context)?,
+                amount: tx.amount.into(),
+                sender_nonce: tx.sender_nonce.into(),
+            };
+            data.validate().map_err(|e| context(e.to_string()))?;
+            check_merkle_path("sender", &tx.sender_merkle_path, &tx.sender_path_indices)
+                .map_err(context)?;
+            check_merkle_path(
+                "receiver",
+                &tx.receiver_merkle_path,
+                &tx.receiver_path_indices,
+            )
+            .map_err(context)?;
+            if tx.sender_balance < tx.amount {
+                return Err(context(format!(
+                    "amount {} exceeds sender_balance {}",
+                    tx.amount, tx.sender_balance
+                )));
+            }
+        }
+
+        for (i, wd) in self.withdrawals.iter().enumerate() {
+            let context = |e: String| format!("withdrawal {}: {}", i, e);
+            let data = WithdrawalData {
+                sender_pubkey: field("sender_pubkey", &wd.sender_pubkey).map_err(context)?,
+                l1_recipient: field("l1_recipient", &wd.l1_recipient).map_err(context)?,
+                amount: wd.amount.into(),
+            };
+            data.validate().map_err(|e| context(e.to_string()))?;
+            // Both enter the withdrawal leaf byte for byte
+            parse_bytes32("l1_recipient", &wd.l1_recipient).map_err(context)?;
+            parse_bytes32("tx_hash", &wd.tx_hash).map_err(context)?;
+            check_merkle_path("sender", &wd.sender_merkle_path, &wd.sender_path_indices)
+                .map_err(context)?;
+            if wd.sender_balance < wd.amount {
+                return Err(context(format!(
+                    "amount {} exceeds sender_balance {}",
+                    wd.amount, wd.sender_balance
+                )));
+            }
+        }
+
+        for (i, sh) in self.shielded.iter().enumerate() {
+            let context = |e: String| format!("shielded {}: {}", i, e);
+            field("input_commitment", &sh.input_commitment).map_err(context)?;
+            field("input_blinding", &sh.input_blinding).map_err(context)?;
+            field("spending_key", &sh.spending_key).map_err(context)?;
+            field("output_owner", &sh.output_owner).map_err(context)?;
+            field("output_blinding", &sh.output_blinding).map_err(context)?;
+            let data = ShieldedData {
+                nullifier: field("nullifier", &sh.nullifier).map_err(context)?,
+                output_commitment: field("output_commitment", &sh.output_commitment)
+                    .map_err(context)?,
+            };
+            data.validate().map_err(|e| context(e.to_string()))?;
+            check_merkle_path("input", &sh.input_merkle_path, &sh.input_path_indices)
+                .map_err(context)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn check_count(kind: &str, count: usize, max: usize) -> Result<(), String> {
+    if count > max {
+        return Err(format!(
+            "{} {} exceeds circuit capacity of {}",
+            count, kind, max
+        ));
+    }
+    Ok(())
+}
+
+/// Exactly 32 bytes of hex (`0x` optional)
+fn parse_bytes32(name: &str, value: &str) -> Result<[u8; 32], String> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| format!("{}: expected 32 bytes of hex", name))
+}
+
+/// Paths may be shorter than the tree depth (zero-padded), but never longer,
+/// and every sibling needs a 0/1 direction bit.
+fn check_merkle_path(owner: &str, path: &[String], indices: &[u8]) -> Result<(), String> {
+    use prover_worker::{MERKLE_DEPTH, parse_field_checked};
+
+    if path.len() > MERKLE_DEPTH {
+        return Err(format!(
+            "{} merkle path has {} nodes, tree depth is {}",
+            owner,
+            path.len(),
+            MERKLE_DEPTH
+        ));
+    }
+    if indices.len() != path.len() {
+        return Err(format!(
+            "{} merkle path has {} nodes but {} path indices",
+            owner,
+            path.len(),
+            indices.len()
+        ));
+    }
+    if indices.iter().any(|&bit| bit > 1) {
+        return Err(format!("{} path indices must be 0 or 1", owner));
+    }
+    for node in path {
+        parse_field_checked("merkle_path", node).map_err(|e| format!("{} {}", owner, e))?;
+    }
+    Ok(())
+}
+
 /// Transfer witness from core sequencer
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoreTransferWitness {
@@ -83,6 +244,8 @@ pub struct CoreWithdrawalWitness {
     pub sender_merkle_path: Vec<String>,
     pub sender_path_indices: Vec<u8>,
     pub l1_recipient: String, // 32-byte Solana address
+    /// L2 withdrawal tx hash (32-byte hex), the claim's nullifier on L1
+    pub tx_hash: String,
     pub amount: u64,
     pub signature: String,
 }
@@ -130,7 +293,7 @@ pub struct CoreProofResult {
     pub public_witness_bytes: String,
     /// Circuit-computed batch hash (MiMC)
     pub batch_hash: String,
-    /// Circuit-computed withdrawal root (MiMC)
+    /// Circuit-computed withdrawal root (Poseidon Merkle root)
     pub withdrawal_root: String,
     /// Proving time in milliseconds
     pub proving_time_ms: u64,
@@ -176,6 +339,105 @@ pub enum ProofStatusEvent {
         job_id: String,
         error: String,
     },
+    Cancelled {
+        job_id: String,
+    },
+}
+
+impl ProofStatusEvent {
+    /// SSE `event:` name
+    fn event_type(&self) -> &'static str {
+        match self {
+            ProofStatusEvent::Status(_) => "status",
+            ProofStatusEvent::Progress { .. } => "progress",
+            ProofStatusEvent::Completed(_) => "completed",
+            ProofStatusEvent::Failed { .. } => "failed",
+            ProofStatusEvent::Cancelled { .. } => "cancelled",
+        }
+    }
+}
+
+/// A status event tagged with its per-job sequence number.
+///
+/// `seq` starts at 1 and increases by one per event, and is also sent as the
+/// SSE `id:` so clients can resume with `Last-Event-ID`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequencedEvent {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub event: ProofStatusEvent,
+}
+
+/// Number of recent events kept per job for `Last-Event-ID` resume
+pub const EVENT_REPLAY_BUFFER: usize = 64;
+
+/// Per-job event stream: assigns sequence numbers, keeps the most recent
+/// events for replay and fans them out to live SSE subscribers.
+///
+/// Publishing and subscribing happen under one lock, so a subscriber sees
+/// every event exactly once and in order across the replay/live boundary.
+#[derive(Debug, Clone)]
+pub struct JobEventLog {
+    inner: Arc<std::sync::Mutex<EventLogInner>>,
+}
+
+#[derive(Debug)]
+struct EventLogInner {
+    next_seq: u64,
+    recent: VecDeque<SequencedEvent>,
+    tx: broadcast::Sender<SequencedEvent>,
+}
+
+impl JobEventLog {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(16);
+        Self {
+            inner: Arc::new(std::sync::Mutex::new(EventLogInner {
+                next_seq: 1,
+                recent: VecDeque::with_capacity(EVENT_REPLAY_BUFFER),
+                tx,
+            })),
+        }
+    }
+
+    /// Assign the next sequence number, buffer and broadcast the event
+    pub fn publish(&self, event: ProofStatusEvent) -> u64 {
+        let mut inner = self.inner.lock().unwrap();
+        let sequenced = SequencedEvent {
+            seq: inner.next_seq,
+            event,
+        };
+        inner.next_seq += 1;
+        if inner.recent.len() == EVENT_REPLAY_BUFFER {
+            inner.recent.pop_front();
+        }
+        inner.recent.push_back(sequenced.clone());
+        let _ = inner.tx.send(sequenced.clone());
+        sequenced.seq
+    }
+
+    /// Buffered events after `last_seen` (all buffered events if `None`),
+    /// plus a receiver for everything published afterwards.
+    pub fn subscribe_after(
+        &self,
+        last_seen: Option<u64>,
+    ) -> (Vec<SequencedEvent>, broadcast::Receiver<SequencedEvent>) {
+        let inner = self.inner.lock().unwrap();
+        let after = last_seen.unwrap_or(0);
+        let replay = inner
+            .recent
+            .iter()
+            .filter(|e| e.seq > after)
+            .cloned()
+            .collect();
+        (replay, inner.tx.subscribe())
+    }
+}
+
+impl Default for JobEventLog {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // API Response Wrapper
@@ -215,7 +477,7 @@ impl<T> ApiResponse<T> {
 // Proof Cache
 
 /// Cached proof entry
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedProof {
     pub result: CoreProofResult,
     pub cached_at: u64,
@@ -233,42 +495,75 @@ impl CachedProof {
     }
 }
 
-/// Proof cache with TTL
-#[derive(Debug, Clone, Default)]
+/// Snapshot of what the proof cache holds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofCacheStats {
+    /// Proofs held in memory
+    pub entries: usize,
+    /// Proof and public witness bytes held in memory
+    pub size_bytes: usize,
+    /// `cached_at` of the oldest and newest in-memory entry
+    pub oldest_cached_at: Option<u64>,
+    pub newest_cached_at: Option<u64>,
+    /// Proofs in the disk tier, if enabled
+    pub disk_entries: Option<usize>,
+}
+
+/// In-memory proof cache with TTL and an entry cap
+///
+/// Never touches the filesystem, so it is safe to use under the state lock;
+/// the optional disk tier is [`ProofDiskCache`].
+#[derive(Debug, Clone)]
 pub struct ProofCache {
     /// Map of batch_id -> cached proof
     proofs: HashMap<u64, CachedProof>,
     /// Map of job_id -> batch_id (for lookup)
     job_to_batch: HashMap<String, u64>,
+    /// Proofs kept before the oldest are evicted
+    max_entries: usize,
 }
 
 impl ProofCache {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            proofs: HashMap::new(),
+            job_to_batch: HashMap::new(),
+            max_entries,
+        }
     }
 
-    /// Insert a proof into the cache
+    /// Insert a proof into the cache, returning the entry for the disk tier
     pub fn insert(
         &mut self,
         job_id: String,
         batch_id: u64,
         result: CoreProofResult,
         ttl_secs: u64,
-    ) {
+    ) -> CachedProof {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
+        let cached = CachedProof {
+            result,
+            cached_at: now,
+            ttl_secs,
+        };
 
-        self.proofs.insert(
-            batch_id,
-            CachedProof {
-                result,
-                cached_at: now,
-                ttl_secs,
-            },
-        );
+        self.proofs.insert(batch_id, cached.clone());
         self.job_to_batch.insert(job_id, batch_id);
+        self.evict_over_cap();
+        cached
+    }
+
+    /// Adopt an entry read back from the disk tier, so later `get_by_job`
+    /// lookups for its original job_id succeed too
+    pub fn promote(&mut self, cached: CachedProof) {
+        let result = &cached.result;
+        self.job_to_batch
+            .insert(result.job_id.clone(), result.batch_id);
+        self.proofs.insert(result.batch_id, cached);
+        self.evict_over_cap();
     }
 
     /// Get a proof by job_id
@@ -287,8 +582,8 @@ impl ProofCache {
         }
     }
 
-    /// Clean up expired entries
-    pub fn cleanup_expired(&mut self) {
+    /// Clean up expired entries, returning how many were removed
+    pub fn cleanup_expired(&mut self) -> usize {
         let expired_batches: Vec<u64> = self
             .proofs
             .iter()
@@ -296,13 +591,178 @@ impl ProofCache {
             .map(|(k, _)| *k)
             .collect();
 
-        for batch_id in expired_batches {
-            self.proofs.remove(&batch_id);
+        for batch_id in &expired_batches {
+            self.proofs.remove(batch_id);
         }
 
         // Also clean up job mappings
         self.job_to_batch
             .retain(|_, batch_id| self.proofs.contains_key(batch_id));
+        expired_batches.len()
+    }
+
+    /// Evict the oldest proofs beyond `max_entries`
+    fn evict_over_cap(&mut self) {
+        while self.proofs.len() > self.max_entries {
+            let Some(oldest) = self
+                .proofs
+                .iter()
+                .min_by_key(|(batch_id, cached)| (cached.cached_at, **batch_id))
+                .map(|(batch_id, _)| *batch_id)
+            else {
+                break;
+            };
+            self.proofs.remove(&oldest);
+        }
+        self.job_to_batch
+            .retain(|_, batch_id| self.proofs.contains_key(batch_id));
+    }
+
+    /// Current contents summary, expired-but-unswept entries included.
+    /// `disk_entries` is left for the caller to fill from the disk tier.
+    pub fn stats(&self) -> ProofCacheStats {
+        let cached_at = || self.proofs.values().map(|c| c.cached_at);
+        ProofCacheStats {
+            entries: self.proofs.len(),
+            size_bytes: self
+                .proofs
+                .values()
+                .map(|c| (c.result.proof_bytes.len() + c.result.public_witness_bytes.len()) / 2)
+                .sum(),
+            oldest_cached_at: cached_at().min(),
+            newest_cached_at: cached_at().max(),
+            disk_entries: None,
+        }
+    }
+
+    /// Drop every in-memory proof, returning how many were removed
+    pub fn clear(&mut self) -> usize {
+        let removed = self.proofs.len();
+        self.proofs.clear();
+        self.job_to_batch.clear();
+        removed
+    }
+}
+
+/// On-disk proof cache tier
+///
+/// Proofs are written as `<witness_hash>.json` so they survive coordinator
+/// restarts, and consulted after an in-memory miss. The tier follows the
+/// in-memory policy: expired proofs are swept and the oldest are evicted
+/// beyond `max_entries`. All I/O is async: clone the tier out of the shared
+/// state and use it after releasing the lock.
+#[derive(Debug, Clone)]
+pub struct ProofDiskCache {
+    dir: PathBuf,
+    max_entries: usize,
+}
+
+impl ProofDiskCache {
+    /// Disk tier in `dir` (created on first write)
+    pub fn new(dir: PathBuf, max_entries: usize) -> Self {
+        Self { dir, max_entries }
+    }
+
+    /// Persist a proof; a crash mid-write never leaves a truncated entry
+    pub async fn write(&self, witness_hash: &str, cached: &CachedProof) -> std::io::Result<()> {
+        let Some(path) = self.path(witness_hash) else {
+            return Ok(());
+        };
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec(cached)?).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        self.sweep().await;
+        Ok(())
+    }
+
+    /// Read back an unexpired proof, removing it if it has expired
+    pub async fn read(&self, witness_hash: &str) -> Option<CachedProof> {
+        let path = self.path(witness_hash)?;
+        let bytes = tokio::fs::read(&path).await.ok()?;
+        match serde_json::from_slice::<CachedProof>(&bytes) {
+            Ok(cached) if !cached.is_expired() => Some(cached),
+            Ok(_) => {
+                let _ = tokio::fs::remove_file(&path).await;
+                None
+            }
+            Err(e) => {
+                warn!("Ignoring unreadable cached proof {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Number of proofs on disk
+    pub async fn count(&self) -> usize {
+        self.entries().await.len()
+    }
+
+    /// Remove every proof on disk
+    pub async fn clear(&self) {
+        for path in self.entries().await {
+            Self::remove(&path).await;
+        }
+    }
+
+    /// Remove expired and unreadable proofs, then the oldest beyond
+    /// `max_entries`, returning how many were removed
+    pub async fn sweep(&self) -> usize {
+        let mut removed = 0;
+        let mut live = Vec::new();
+        for path in self.entries().await {
+            let cached = tokio::fs::read(&path)
+                .await
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<CachedProof>(&bytes).ok());
+            match cached {
+                Some(cached) if !cached.is_expired() => live.push((cached.cached_at, path)),
+                // Never served again, so not worth keeping
+                _ => removed += usize::from(Self::remove(&path).await),
+            }
+        }
+
+        if live.len() > self.max_entries {
+            live.sort();
+            let excess = live.len() - self.max_entries;
+            for (_, path) in live.drain(..excess) {
+                removed += usize::from(Self::remove(&path).await);
+            }
+        }
+        removed
+    }
+
+    async fn remove(path: &Path) -> bool {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Failed to remove cached proof {:?}: {}", path, e);
+                false
+            }
+        }
+    }
+
+    /// Entry files in the directory
+    async fn entries(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        let Ok(mut entries) = tokio::fs::read_dir(&self.dir).await else {
+            return paths;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                paths.push(path);
+            }
+        }
+        paths
+    }
+
+    fn path(&self, witness_hash: &str) -> Option<PathBuf> {
+        // Hashes are hex; anything else must not escape the directory
+        if witness_hash.is_empty() || !witness_hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        Some(self.dir.join(format!("{}.json", witness_hash)))
     }
 }
 
@@ -319,6 +779,12 @@ pub struct CoreApiConfig {
     pub mock_delay_ms: u64,
     /// Proof cache TTL (seconds)
     pub cache_ttl_secs: u64,
+    /// Directory for the on-disk proof cache tier (memory only when unset)
+    pub proof_cache_dir: Option<PathBuf>,
+    /// Proofs each cache tier keeps before evicting the oldest
+    pub max_cached_proofs: usize,
+    /// Bearer token for admin endpoints (`DELETE /cache`); disabled when unset
+    pub admin_token: Option<String>,
     /// Maximum concurrent proving jobs
     pub max_concurrent_jobs: usize,
 }
@@ -329,6 +795,9 @@ impl Default for CoreApiConfig {
             circuit_path: PathBuf::from("../../circuits/zelana_batch"),
             mock_prover: true,
             cache_ttl_secs: 3600, // 1 hour
+            proof_cache_dir: None,
+            max_cached_proofs: 256,
+            admin_token: None,
             max_concurrent_jobs: 4,
             mock_delay_ms: 1000,
         }
@@ -340,8 +809,10 @@ impl Default for CoreApiConfig {
 pub struct ProofJob {
     pub status: ProofJobStatus,
     pub request: CoreBatchProveRequest,
-    /// Channel to broadcast status updates
-    pub status_tx: broadcast::Sender<ProofStatusEvent>,
+    /// Sequenced status updates for SSE subscribers
+    pub events: JobEventLog,
+    /// Raised by the cancel endpoint; aborts the proving task
+    pub cancel_tx: watch::Sender<bool>,
 }
 
 /// Core API shared state
@@ -351,18 +822,55 @@ pub struct CoreApiState {
     pub jobs: HashMap<String, ProofJob>,
     /// Completed proof cache
     pub cache: ProofCache,
+    /// Disk tier of the proof cache, if enabled
+    pub disk_cache: Option<ProofDiskCache>,
     /// Current active job count
     pub active_jobs: usize,
 }
 
 pub type SharedCoreApiState = Arc<RwLock<CoreApiState>>;
 
+/// How often expired proofs are swept from both cache tiers
+const CACHE_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically drop expired proofs from both cache tiers
+pub fn spawn_cache_sweeper(state: SharedCoreApiState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CACHE_SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let (removed, disk_cache) = {
+                let mut api_state = state.write().await;
+                (
+                    api_state.cache.cleanup_expired(),
+                    api_state.disk_cache.clone(),
+                )
+            };
+            let disk_removed = match disk_cache {
+                Some(disk) => disk.sweep().await,
+                None => 0,
+            };
+            if removed + disk_removed > 0 {
+                info!(
+                    "Swept {} cached proofs from memory, {} from disk",
+                    removed, disk_removed
+                );
+            }
+        }
+    });
+}
+
 impl CoreApiState {
     pub fn new(config: CoreApiConfig) -> Self {
+        let disk_cache = config
+            .proof_cache_dir
+            .clone()
+            .map(|dir| ProofDiskCache::new(dir, config.max_cached_proofs));
         Self {
+            cache: ProofCache::new(config.max_cached_proofs),
             config,
             jobs: HashMap::new(),
-            cache: ProofCache::new(),
+            disk_cache,
             active_jobs: 0,
         }
     }
@@ -373,11 +881,19 @@ impl CoreApiState {
 /// Create the Core API router
 pub fn core_api_router(state: SharedCoreApiState) -> Router {
     Router::new()
-        .route("/v2/batch/prove", post(prove_handler))
+        .route(
+            "/v2/batch/prove",
+            post(prove_handler).layer(DefaultBodyLimit::max(PROVE_BODY_LIMIT)),
+        )
         .route("/v2/batch/:job_id/status", get(status_sse_handler))
         .route("/v2/batch/:job_id/proof", get(get_proof_handler))
         .route("/v2/batch/:job_id", delete(cancel_handler))
         .route("/v2/health", get(health_handler))
+        .route(
+            "/cache",
+            get(cache_stats_handler).delete(cache_flush_handler),
+        )
+        .layer(DefaultBodyLimit::max(CONTROL_BODY_LIMIT))
         .with_state(state)
 }
 
@@ -404,20 +920,48 @@ async fn health_handler(
 async fn prove_handler(
     State(state): State<SharedCoreApiState>,
     Json(request): Json<CoreBatchProveRequest>,
-) -> Result<Json<ApiResponse<CoreBatchProveResponse>>, StatusCode> {
-    // Check if we have a cached proof for this batch
-    {
+) -> Result<
+    Json<ApiResponse<CoreBatchProveResponse>>,
+    (StatusCode, Json<ApiResponse<CoreBatchProveResponse>>),
+> {
+    // Malformed witnesses would otherwise only fail inside nargo
+    if let Err(e) = request.validate() {
+        warn!("Rejecting batch {}: {}", request.batch_id, e);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error_with_code(e, "INVALID_REQUEST")),
+        ));
+    }
+
+    // Check if we have a cached proof for this batch (memory, then disk)
+    let (cached_job_id, disk_cache) = {
         let api_state = state.read().await;
-        if let Some(cached) = api_state.cache.get_by_batch(request.batch_id) {
-            info!("Returning cached proof for batch {}", request.batch_id);
-            // Return existing job_id if we have it
-            return Ok(Json(ApiResponse::success(CoreBatchProveResponse {
-                job_id: cached.job_id.clone(),
-                batch_id: request.batch_id,
-                estimated_time_ms: 0,
-                status_url: format!("/v2/batch/{}/status", cached.job_id),
-            })));
-        }
+        let job_id = api_state
+            .cache
+            .get_by_batch(request.batch_id)
+            .map(|cached| cached.job_id.clone());
+        (job_id, api_state.disk_cache.clone())
+    };
+    let cached_job_id = match (cached_job_id, disk_cache) {
+        (None, Some(disk)) => match disk.read(&request.witness_hash()).await {
+            Some(cached) => {
+                let job_id = cached.result.job_id.clone();
+                state.write().await.cache.promote(cached);
+                Some(job_id)
+            }
+            None => None,
+        },
+        (job_id, _) => job_id,
+    };
+    if let Some(job_id) = cached_job_id {
+        info!("Returning cached proof for batch {}", request.batch_id);
+        // Return existing job_id if we have it
+        return Ok(Json(ApiResponse::success(CoreBatchProveResponse {
+            status_url: format!("/v2/batch/{}/status", job_id),
+            job_id,
+            batch_id: request.batch_id,
+            estimated_time_ms: 0,
+        })));
     }
 
     // Check capacity
@@ -441,8 +985,9 @@ async fn prove_handler(
         .unwrap()
         .as_secs();
 
-    // Create broadcast channel for status updates
-    let (status_tx, _) = broadcast::channel::<ProofStatusEvent>(16);
+    // Create event log for status updates
+    let events = JobEventLog::new();
+    let (cancel_tx, cancel_rx) = watch::channel(false);
 
     // Create job
     let job = ProofJob {
@@ -458,7 +1003,8 @@ async fn prove_handler(
             error: None,
         },
         request: request.clone(),
-        status_tx: status_tx.clone(),
+        events: events.clone(),
+        cancel_tx,
     };
 
     // Store job and increment active count
@@ -499,7 +1045,8 @@ async fn prove_handler(
             job_id_clone,
             batch_id,
             request,
-            status_tx,
+            events,
+            cancel_rx,
             mock_prover,
             mock_delay,
             circuit_path,
@@ -517,15 +1064,24 @@ async fn prove_handler(
 }
 
 /// SSE endpoint for proof status updates
+///
+/// A reconnecting client sends `Last-Event-ID: <seq>` and gets the buffered
+/// events after that seq replayed before live updates continue.
 async fn status_sse_handler(
     State(state): State<SharedCoreApiState>,
     Path(job_id): Path<String>,
+    headers: HeaderMap,
 ) -> Response {
-    // Get the broadcast receiver for this job
-    let rx = {
+    let last_seen = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+
+    // Get buffered events and a live receiver for this job
+    let (replay, rx) = {
         let api_state = state.read().await;
         match api_state.jobs.get(&job_id) {
-            Some(job) => job.status_tx.subscribe(),
+            Some(job) => job.events.subscribe_after(last_seen),
             None => {
                 // Job not found - check cache
                 if let Some(cached) = api_state.cache.get_by_job(&job_id) {
@@ -552,26 +1108,21 @@ async fn status_sse_handler(
         }
     };
 
-    // Convert broadcast receiver to SSE stream
-    let stream = BroadcastStream::new(rx).filter_map(|result| {
-        match result {
-            Ok(event) => {
-                let event_type = match &event {
-                    ProofStatusEvent::Status(_) => "status",
-                    ProofStatusEvent::Progress { .. } => "progress",
-                    ProofStatusEvent::Completed(_) => "completed",
-                    ProofStatusEvent::Failed { .. } => "failed",
-                };
-                Some(Ok::<_, Infallible>(
+    // Replay missed events, then continue with the live broadcast
+    let stream = tokio_stream::iter(replay.into_iter().map(Ok))
+        .chain(BroadcastStream::new(rx))
+        .filter_map(|result| {
+            match result {
+                Ok(sequenced) => Some(Ok::<_, Infallible>(
                     Event::default()
-                        .event(event_type)
-                        .json_data(&event)
+                        .id(sequenced.seq.to_string())
+                        .event(sequenced.event.event_type())
+                        .json_data(&sequenced)
                         .unwrap(),
-                ))
+                )),
+                Err(_) => None, // Channel lagged, skip
             }
-            Err(_) => None, // Channel lagged, skip
-        }
-    });
+        });
 
     Sse::new(stream)
         .keep_alive(
@@ -636,10 +1187,10 @@ async fn cancel_handler(
     let mut api_state = state.write().await;
 
     if let Some(job) = api_state.jobs.get_mut(&job_id) {
-        // Can only cancel pending or preparing jobs
+        // Terminal jobs can't be cancelled
         if matches!(
             job.status.state,
-            ProofJobState::Pending | ProofJobState::Preparing
+            ProofJobState::Pending | ProofJobState::Preparing | ProofJobState::Proving
         ) {
             job.status.state = ProofJobState::Cancelled;
             job.status.message = "Cancelled by user".to_string();
@@ -648,10 +1199,12 @@ async fn cancel_handler(
                 .unwrap()
                 .as_secs();
 
+            // Stop the proving task (dropping it kills nargo/sunspot)
+            let _ = job.cancel_tx.send(true);
+
             // Notify subscribers
-            let _ = job.status_tx.send(ProofStatusEvent::Failed {
+            job.events.publish(ProofStatusEvent::Cancelled {
                 job_id: job_id.clone(),
-                error: "Cancelled by user".to_string(),
             });
 
             api_state.active_jobs = api_state.active_jobs.saturating_sub(1);
@@ -674,6 +1227,48 @@ async fn cancel_handler(
     )))
 }
 
+/// Proof cache statistics
+async fn cache_stats_handler(
+    State(state): State<SharedCoreApiState>,
+) -> Json<ApiResponse<ProofCacheStats>> {
+    let (mut stats, disk_cache) = {
+        let api_state = state.read().await;
+        (api_state.cache.stats(), api_state.disk_cache.clone())
+    };
+    if let Some(disk) = disk_cache {
+        stats.disk_entries = Some(disk.count().await);
+    }
+    Json(ApiResponse::success(stats))
+}
+
+/// Flush the proof cache, e.g. after a bad circuit deploy
+async fn cache_flush_handler(
+    State(state): State<SharedCoreApiState>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<serde_json::Value>>, (StatusCode, Json<ApiResponse<serde_json::Value>>)>
+{
+    let admin_token = state.read().await.config.admin_token.clone();
+    if let Err(e) = check_admin_token(admin_token.as_deref(), &headers) {
+        return Err((
+            e.status(),
+            Json(ApiResponse::error_with_code(e.message(), e.code())),
+        ));
+    }
+
+    let mut api_state = state.write().await;
+    let removed = api_state.cache.clear();
+    let disk_cache = api_state.disk_cache.clone();
+    drop(api_state);
+    if let Some(disk) = disk_cache {
+        disk.clear().await;
+    }
+    warn!("Proof cache flushed ({} entries)", removed);
+
+    Ok(Json(ApiResponse::success(serde_json::json!({
+        "removed": removed
+    }))))
+}
+
 // Proof Execution
 
 /// Execute the proof job (runs in background task)
@@ -682,7 +1277,8 @@ async fn execute_proof_job(
     job_id: String,
     batch_id: u64,
     request: CoreBatchProveRequest,
-    status_tx: broadcast::Sender<ProofStatusEvent>,
+    events: JobEventLog,
+    mut cancel_rx: watch::Receiver<bool>,
     mock_prover: bool,
     mock_delay: u64,
     circuit_path: PathBuf,
@@ -699,7 +1295,7 @@ async fn execute_proof_job(
         "Preparing witness",
     )
     .await;
-    let _ = status_tx.send(ProofStatusEvent::Progress {
+    events.publish(ProofStatusEvent::Progress {
         job_id: job_id.clone(),
         progress_pct: 10,
         message: "Preparing witness".to_string(),
@@ -722,77 +1318,92 @@ async fn execute_proof_job(
         "Generating proof",
     )
     .await;
-    let _ = status_tx.send(ProofStatusEvent::Progress {
+    events.publish(ProofStatusEvent::Progress {
         job_id: job_id.clone(),
         progress_pct: 30,
         message: "Generating proof".to_string(),
     });
 
-    // Execute proof
-    let proof_result = if mock_prover {
-        // Mock proving
-        tokio::time::sleep(Duration::from_millis(mock_delay / 2)).await;
+    // Execute proof. Dropping this future (on cancel) kills nargo/sunspot.
+    let proving = async {
+        if mock_prover {
+            // Mock proving
+            tokio::time::sleep(Duration::from_millis(mock_delay / 2)).await;
 
-        // Send progress update
-        let _ = status_tx.send(ProofStatusEvent::Progress {
-            job_id: job_id.clone(),
-            progress_pct: 60,
-            message: "Proof computation in progress".to_string(),
-        });
+            // Send progress update
+            events.publish(ProofStatusEvent::Progress {
+                job_id: job_id.clone(),
+                progress_pct: 60,
+                message: "Proof computation in progress".to_string(),
+            });
 
-        tokio::time::sleep(Duration::from_millis(mock_delay / 2)).await;
+            tokio::time::sleep(Duration::from_millis(mock_delay / 2)).await;
 
-        // Generate mock proof
-        generate_mock_proof(&job_id, batch_id, &request)
-    } else {
-        // Real Noir proving using prover-worker
-        info!(
-            "Using real NoirProver with circuit_path: {:?}",
-            circuit_path
-        );
+            // Generate mock proof
+            generate_mock_proof(&job_id, batch_id, &request)
+        } else {
+            // Real Noir proving using prover-worker
+            info!(
+                "Using real NoirProver with circuit_path: {:?}",
+                circuit_path
+            );
+
+            // Send progress update for witness preparation
+            events.publish(ProofStatusEvent::Progress {
+                job_id: job_id.clone(),
+                progress_pct: 40,
+                message: "Executing nargo witness generation".to_string(),
+            });
 
-        // Send progress update for witness preparation
-        let _ = status_tx.send(ProofStatusEvent::Progress {
-            job_id: job_id.clone(),
-            progress_pct: 40,
-            message: "Executing nargo witness generation".to_string(),
-        });
+            let prover = prover_worker::NoirProver::new(circuit_path.clone());
+            match prover.generate_batch_proof(batch_inputs).await {
+                Ok(proof_result) => {
+                    // Send progress update for proof completion
+                    events.publish(ProofStatusEvent::Progress {
+                        job_id: job_id.clone(),
+                        progress_pct: 90,
+                        message: "Proof generated, finalizing".to_string(),
+                    });
 
-        let prover = prover_worker::NoirProver::new(circuit_path.clone());
-        match prover.generate_batch_proof(batch_inputs).await {
-            Ok(proof_result) => {
-                // Send progress update for proof completion
-                let _ = status_tx.send(ProofStatusEvent::Progress {
-                    job_id: job_id.clone(),
-                    progress_pct: 90,
-                    message: "Proof generated, finalizing".to_string(),
-                });
-
-                // Extract batch_hash and withdrawal_root from public witness
-                // The public witness contains 7 field elements after a 12-byte header:
-                // [0-3]: count, [4-11]: padding, [12-43]: input 0, ..., [204-235]: input 6
-                // Typically: pre_state, post_state, pre_shielded, post_shielded, withdrawal_root, batch_hash, batch_id
-                let (batch_hash, withdrawal_root) =
-                    extract_hashes_from_witness(&proof_result.public_witness_bytes);
-
-                // Convert prover-worker result to CoreProofResult
-                Ok(CoreProofResult {
-                    job_id: job_id.clone(),
-                    batch_id,
-                    proof_bytes: hex::encode(&proof_result.proof_bytes),
-                    public_witness_bytes: hex::encode(&proof_result.public_witness_bytes),
-                    batch_hash,
-                    withdrawal_root,
-                    proving_time_ms: 0, // Will be set later
-                })
-            }
-            Err(e) => {
-                error!("NoirProver failed: {:?}", e);
-                Err(format!("Noir proving failed: {}", e))
+                    // Extract batch_hash and withdrawal_root from public witness
+                    // The public witness contains 7 field elements after a 12-byte header:
+                    // [0-3]: count, [4-11]: padding, [12-43]: input 0, ..., [204-235]: input 6
+                    // Typically: pre_state, post_state, pre_shielded, post_shielded, withdrawal_root, batch_hash, batch_id
+                    let (batch_hash, withdrawal_root) =
+                        extract_hashes_from_witness(&proof_result.public_witness_bytes);
+
+                    // Convert prover-worker result to CoreProofResult
+                    Ok(CoreProofResult {
+                        job_id: job_id.clone(),
+                        batch_id,
+                        proof_bytes: hex::encode(&proof_result.proof_bytes),
+                        public_witness_bytes: hex::encode(&proof_result.public_witness_bytes),
+                        batch_hash,
+                        withdrawal_root,
+                        proving_time_ms: 0, // Will be set later
+                    })
+                }
+                Err(e) => {
+                    error!("NoirProver failed: {:?}", e);
+                    Err(format!("Noir proving failed: {}", e))
+                }
             }
         }
     };
 
+    let proof_result = tokio::select! {
+        result = proving => result,
+        _ = job_cancelled(&mut cancel_rx) => {
+            info!("Proof job {} cancelled while proving", job_id);
+            return;
+        }
+    };
+
+    // Cancelled after proving finished; the cancel handler already released the job
+    if is_job_cancelled(&state, &job_id).await {
+        return;
+    }
+
     let proving_time_ms = start.elapsed().as_millis() as u64;
 
     match proof_result {
@@ -800,11 +1411,12 @@ async fn execute_proof_job(
             result.proving_time_ms = proving_time_ms;
 
             // Cache the result
-            {
+            let (cached, disk_cache) = {
                 let mut api_state = state.write().await;
-                api_state
-                    .cache
-                    .insert(job_id.clone(), batch_id, result.clone(), cache_ttl);
+                let cached =
+                    api_state
+                        .cache
+                        .insert(job_id.clone(), batch_id, result.clone(), cache_ttl);
                 api_state.active_jobs = api_state.active_jobs.saturating_sub(1);
 
                 // Update job status
@@ -820,12 +1432,22 @@ async fn execute_proof_job(
                     );
                     job.status.updated_at = job.status.completed_at.unwrap();
                 }
+                (cached, api_state.disk_cache.clone())
+            };
+
+            // The disk tier is best-effort; the proof is still served from memory
+            let persisted = match disk_cache {
+                Some(disk) => disk.write(&request.witness_hash(), &cached).await,
+                None => Ok(()),
+            };
+            if let Err(e) = persisted {
+                warn!("Failed to persist proof for batch {}: {}", batch_id, e);
             }
 
             info!("Proof job {} completed in {}ms", job_id, proving_time_ms);
 
             // Send completion event
-            let _ = status_tx.send(ProofStatusEvent::Completed(result));
+            events.publish(ProofStatusEvent::Completed(result));
         }
         Err(e) => {
             error!("Proof job {} failed: {}", job_id, e);
@@ -846,7 +1468,7 @@ async fn execute_proof_job(
             }
 
             // Send failure event
-            let _ = status_tx.send(ProofStatusEvent::Failed {
+            events.publish(ProofStatusEvent::Failed {
                 job_id: job_id.clone(),
                 error: e.to_string(),
             });
@@ -864,6 +1486,10 @@ async fn update_job_status(
 ) {
     let mut api_state = state.write().await;
     if let Some(job) = api_state.jobs.get_mut(job_id) {
+        // Never resurrect a cancelled job
+        if job.status.state == ProofJobState::Cancelled {
+            return;
+        }
         job.status.state = new_state;
         job.status.progress_pct = progress;
         job.status.message = message.to_string();
@@ -873,9 +1499,16 @@ async fn update_job_status(
             .as_secs();
 
         // Also broadcast status update
-        let _ = job
-            .status_tx
-            .send(ProofStatusEvent::Status(job.status.clone()));
+        job.events
+            .publish(ProofStatusEvent::Status(job.status.clone()));
+    }
+}
+
+/// Resolves once the cancel endpoint has flagged the job
+async fn job_cancelled(cancel_rx: &mut watch::Receiver<bool>) {
+    if cancel_rx.wait_for(|&cancelled| cancelled).await.is_err() {
+        // Sender dropped without cancelling, never resolve
+        std::future::pending::<()>().await;
     }
 }
 
@@ -888,15 +1521,20 @@ async fn is_job_cancelled(state: &SharedCoreApiState, job_id: &str) -> bool {
     false
 }
 
+// The circuit's withdrawal slots are the leaves of the bridge's tree
+const _: () =
+    assert!(prover_worker::MAX_WITHDRAWALS == zelana_bridge_events::merkle::MAX_BATCH_WITHDRAWALS);
+
 /// Convert CoreBatchProveRequest to Noir BatchInputs
 fn convert_to_noir_inputs(request: &CoreBatchProveRequest) -> prover_worker::BatchInputs {
     use prover_worker::{
         BatchInputs, Fr, MERKLE_DEPTH, MiMC, ShieldedData, ShieldedWitness, TransferData,
-        TransferWitness, WithdrawalData, WithdrawalWitness, compute_batch_hash,
-        compute_withdrawal_root, field_to_hex, hex_to_field,
+        TransferWitness, WithdrawalData, WithdrawalWitness, compute_batch_hash, field_to_hex,
+        hex_to_field, u128_halves,
     };
+    use zelana_bridge_events::merkle::{WithdrawalTree, withdrawal_leaf};
 
-    // Create MiMC hasher for computing batch_hash and withdrawal_root
+    // Create MiMC hasher for computing batch_hash
     let mimc = MiMC::new();
     let batch_id_fr = Fr::from(request.batch_id);
 
@@ -933,7 +1571,7 @@ fn convert_to_noir_inputs(request: &CoreBatchProveRequest) -> prover_worker::Bat
         })
         .collect();
 
-    // Compute batch_hash and withdrawal_root using MiMC (matching circuit)
+    // Compute batch_hash using MiMC (matching circuit)
     let batch_hash = compute_batch_hash(
         &mimc,
         batch_id_fr,
@@ -941,24 +1579,31 @@ fn convert_to_noir_inputs(request: &CoreBatchProveRequest) -> prover_worker::Bat
         &withdrawal_data,
         &shielded_data,
     );
-    let withdrawal_root = compute_withdrawal_root(&mimc, batch_id_fr, &withdrawal_data);
 
-    // Convert to hex strings for BatchInputs
-    let batch_hash_hex = field_to_hex(batch_hash);
-    let withdrawal_root_hex = field_to_hex(withdrawal_root);
-
-    let mut batch = BatchInputs::empty_batch(
-        &request.pre_state_root,
-        &request.pre_shielded_root,
-        request.batch_id,
-        &batch_hash_hex,
-        &withdrawal_root_hex,
-    );
-    let withdrawal_root = compute_withdrawal_root(&mimc, batch_id_fr, &withdrawal_data);
+    // Withdrawal leaves in slot order, over the exact recipient and tx hash
+    // bytes (validated as 32-byte hex)
+    let withdrawal_bytes: Vec<([u8; 32], [u8; 32])> = request
+        .withdrawals
+        .iter()
+        .map(|wd| {
+            (
+                parse_bytes32("tx_hash", &wd.tx_hash).unwrap_or_default(),
+                parse_bytes32("l1_recipient", &wd.l1_recipient).unwrap_or_default(),
+            )
+        })
+        .collect();
+    let leaves: Vec<[u8; 32]> = withdrawal_bytes
+        .iter()
+        .zip(&request.withdrawals)
+        .map(|((tx_hash, recipient), wd)| withdrawal_leaf(tx_hash, recipient, wd.amount))
+        .collect();
+    let withdrawal_root = WithdrawalTree::new(&leaves)
+        .map(|tree| tree.root())
+        .unwrap_or_default();
 
     // Convert to hex strings for BatchInputs
     let batch_hash_hex = field_to_hex(batch_hash);
-    let withdrawal_root_hex = field_to_hex(withdrawal_root);
+    let withdrawal_root_hex = format!("0x{}", hex::encode(withdrawal_root));
 
     let mut batch = BatchInputs::empty_batch(
         &request.pre_state_root,
@@ -992,7 +1637,15 @@ fn convert_to_noir_inputs(request: &CoreBatchProveRequest) -> prover_worker::Bat
     batch.num_transfers = request.transfers.len().min(8).to_string();
 
     // Convert withdrawals
-    for (i, wd) in request.withdrawals.iter().take(4).enumerate() {
+    for (i, (wd, (tx_hash, recipient))) in request
+        .withdrawals
+        .iter()
+        .zip(&withdrawal_bytes)
+        .take(4)
+        .enumerate()
+    {
+        let (l1_recipient_hi, l1_recipient_lo) = u128_halves(recipient);
+        let (tx_hash_hi, tx_hash_lo) = u128_halves(tx_hash);
         batch.withdrawals[i] = WithdrawalWitness {
             sender_pubkey: wd.sender_pubkey.clone(),
             sender_balance: wd.sender_balance.to_string(),
@@ -1000,6 +1653,10 @@ fn convert_to_noir_inputs(request: &CoreBatchProveRequest) -> prover_worker::Bat
             sender_path: array_from_vec(&wd.sender_merkle_path, MERKLE_DEPTH),
             sender_path_indices: array_from_indices(&wd.sender_path_indices, MERKLE_DEPTH),
             l1_recipient: wd.l1_recipient.clone(),
+            l1_recipient_hi,
+            l1_recipient_lo,
+            tx_hash_hi,
+            tx_hash_lo,
             amount: wd.amount.to_string(),
             signature: wd.signature.clone(),
             is_valid: true,
@@ -1138,10 +1795,11 @@ fn extract_hashes_from_witness(pw_bytes: &[u8]) -> (String, String) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::http::header::AUTHORIZATION;
 
     #[test]
     fn test_proof_cache() {
-        let mut cache = ProofCache::new();
+        let mut cache = ProofCache::new(16);
 
         let result = CoreProofResult {
             job_id: "job1".to_string(),
@@ -1161,6 +1819,411 @@ mod tests {
         assert!(cache.get_by_batch(2).is_none());
     }
 
+    #[tokio::test]
+    async fn test_proof_cache_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = CoreProofResult {
+            job_id: "job1".to_string(),
+            batch_id: 1,
+            proof_bytes: "abcd".to_string(),
+            public_witness_bytes: "1234".to_string(),
+            batch_hash: "0x0".to_string(),
+            withdrawal_root: "0x0".to_string(),
+            proving_time_ms: 100,
+        };
+
+        let disk = ProofDiskCache::new(dir.path().to_path_buf(), 16);
+        let mut cache = ProofCache::new(16);
+        let cached = cache.insert("job1".to_string(), 1, result.clone(), 3600);
+        disk.write("aa", &cached).await.unwrap();
+        drop(cache);
+        let expired = CachedProof {
+            result: result.clone(),
+            cached_at: 0,
+            ttl_secs: 3600,
+        };
+        std::fs::write(
+            dir.path().join("bb.json"),
+            serde_json::to_vec(&expired).unwrap(),
+        )
+        .unwrap();
+
+        // Restart: memory is empty, the disk tier still has the proof
+        let mut cache = ProofCache::new(16);
+        assert!(cache.get_by_batch(1).is_none());
+        assert!(disk.read("cc").await.is_none());
+        cache.promote(disk.read("aa").await.unwrap());
+        let cached = cache.get_by_batch(1).unwrap();
+        assert_eq!(cached.proof_bytes, result.proof_bytes);
+        assert!(cache.get_by_job("job1").is_some());
+
+        // Expired entries are neither served nor kept
+        assert!(disk.read("bb").await.is_none());
+        assert!(!dir.path().join("bb.json").exists());
+
+        // Non-hex hashes never reach the filesystem
+        assert!(disk.read("../aa").await.is_none());
+    }
+
+    fn cached_result(job_id: &str, batch_id: u64) -> CoreProofResult {
+        CoreProofResult {
+            job_id: job_id.to_string(),
+            batch_id,
+            proof_bytes: "ab".repeat(388),
+            public_witness_bytes: "cd".repeat(236),
+            batch_hash: "0x0".to_string(),
+            withdrawal_root: "0x0".to_string(),
+            proving_time_ms: 100,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_proof_cache_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk = ProofDiskCache::new(dir.path().to_path_buf(), 16);
+        let mut cache = ProofCache::new(16);
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 0);
+        assert_eq!(stats.oldest_cached_at, None);
+        assert_eq!(stats.disk_entries, None);
+        assert_eq!(disk.count().await, 0);
+
+        let cached = cache.insert("job1".to_string(), 1, cached_result("job1", 1), 3600);
+        disk.write("aa", &cached).await.unwrap();
+        let cached = cache.insert("job2".to_string(), 2, cached_result("job2", 2), 3600);
+        disk.write("bb", &cached).await.unwrap();
+        cache.proofs.get_mut(&1).unwrap().cached_at = 100;
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.size_bytes, 2 * (388 + 236));
+        assert_eq!(stats.oldest_cached_at, Some(100));
+        assert!(stats.newest_cached_at.unwrap() > 100);
+        assert_eq!(disk.count().await, 2);
+
+        assert_eq!(cache.clear(), 2);
+        disk.clear().await;
+        assert_eq!(cache.stats().entries, 0);
+        assert_eq!(disk.count().await, 0);
+        assert!(disk.read("aa").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_proof_cache_eviction() {
+        // Memory: the oldest proof goes once the cap is reached
+        let mut cache = ProofCache::new(2);
+        cache.insert("job1".to_string(), 1, cached_result("job1", 1), 3600);
+        cache.insert("job2".to_string(), 2, cached_result("job2", 2), 3600);
+        cache.proofs.get_mut(&1).unwrap().cached_at -= 10;
+        cache.insert("job3".to_string(), 3, cached_result("job3", 3), 3600);
+        assert!(cache.get_by_job("job1").is_none());
+        assert!(cache.get_by_batch(2).is_some());
+        assert!(cache.get_by_batch(3).is_some());
+
+        // The sweep drops expired proofs and their job mappings
+        cache.proofs.get_mut(&2).unwrap().cached_at = 0;
+        assert_eq!(cache.cleanup_expired(), 1);
+        assert_eq!(cache.stats().entries, 1);
+        assert!(cache.get_by_job("job2").is_none());
+
+        // Disk: the same cap, oldest first, and expired entries are swept
+        let dir = tempfile::tempdir().unwrap();
+        let disk = ProofDiskCache::new(dir.path().to_path_buf(), 2);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let entry = |batch_id: u64, age: u64| CachedProof {
+            result: cached_result("job", batch_id),
+            cached_at: now - age,
+            ttl_secs: 3600,
+        };
+        disk.write("aa", &entry(1, 30)).await.unwrap();
+        disk.write("bb", &entry(2, 20)).await.unwrap();
+        disk.write("cc", &entry(3, 10)).await.unwrap();
+        assert_eq!(disk.count().await, 2);
+        assert!(disk.read("aa").await.is_none());
+        assert!(disk.read("bb").await.is_some());
+
+        std::fs::write(
+            dir.path().join("dd.json"),
+            serde_json::to_vec(&entry(4, 7200)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(disk.sweep().await, 1);
+        assert_eq!(disk.count().await, 2);
+        assert!(disk.read("cc").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cache_flush_requires_admin_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let state: SharedCoreApiState = Arc::new(RwLock::new(CoreApiState::new(CoreApiConfig {
+            admin_token: Some("secret".to_string()),
+            proof_cache_dir: Some(dir.path().to_path_buf()),
+            ..CoreApiConfig::default()
+        })));
+        let cached = {
+            let mut api_state = state.write().await;
+            api_state
+                .cache
+                .insert("job1".to_string(), 1, cached_result("job1", 1), 3600)
+        };
+        let disk = state.read().await.disk_cache.clone().unwrap();
+        disk.write("aa", &cached).await.unwrap();
+
+        let flush = |auth: Option<&str>| {
+            let mut headers = HeaderMap::new();
+            if let Some(auth) = auth {
+                headers.insert(AUTHORIZATION, auth.parse().unwrap());
+            }
+            cache_flush_handler(State(state.clone()), headers)
+        };
+
+        assert_eq!(flush(None).await.unwrap_err().0, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            flush(Some("Bearer wrong")).await.unwrap_err().0,
+            StatusCode::UNAUTHORIZED
+        );
+        let Json(stats) = cache_stats_handler(State(state.clone())).await;
+        let ApiResponse::Success { data } = stats else {
+            panic!("stats failed");
+        };
+        assert_eq!(data.entries, 1);
+        assert_eq!(data.disk_entries, Some(1));
+
+        assert!(flush(Some("Bearer secret")).await.is_ok());
+        assert_eq!(state.read().await.cache.stats().entries, 0);
+        assert_eq!(disk.count().await, 0);
+
+        // Without a configured token the endpoint is disabled outright
+        state.write().await.config.admin_token = None;
+        assert_eq!(
+            flush(Some("Bearer secret")).await.unwrap_err().0,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    fn progress(pct: u8) -> ProofStatusEvent {
+        ProofStatusEvent::Progress {
+            job_id: "job1".to_string(),
+            progress_pct: pct,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_event_log_resume_replays_newer_events() {
+        let log = JobEventLog::new();
+        assert_eq!(log.publish(progress(10)), 1);
+        assert_eq!(log.publish(progress(30)), 2);
+        assert_eq!(log.publish(progress(60)), 3);
+
+        // Reconnect having seen seq 1: only 2 and 3 are replayed
+        let (replay, mut rx) = log.subscribe_after(Some(1));
+        let seqs: Vec<u64> = replay.iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![2, 3]);
+
+        // Live events continue the sequence with no gap or duplicate
+        log.publish(progress(90));
+        assert_eq!(rx.try_recv().unwrap().seq, 4);
+
+        // Fresh subscriber gets the whole buffer, caught-up one gets nothing
+        assert_eq!(log.subscribe_after(None).0.len(), 4);
+        assert!(log.subscribe_after(Some(4)).0.is_empty());
+    }
+
+    #[test]
+    fn test_event_log_buffer_is_bounded() {
+        let log = JobEventLog::new();
+        for _ in 0..EVENT_REPLAY_BUFFER + 5 {
+            log.publish(progress(50));
+        }
+        let (replay, _) = log.subscribe_after(None);
+        assert_eq!(replay.len(), EVENT_REPLAY_BUFFER);
+        assert_eq!(replay[0].seq, 6);
+    }
+
+    fn empty_request() -> CoreBatchProveRequest {
+        CoreBatchProveRequest {
+            batch_id: 1,
+            pre_state_root: "0x1234".to_string(),
+            post_state_root: "0x5678".to_string(),
+            pre_shielded_root: "0xaaaa".to_string(),
+            post_shielded_root: "0xaaaa".to_string(),
+            transfers: vec![],
+            withdrawals: vec![],
+            shielded: vec![],
+        }
+    }
+
+    fn transfer() -> CoreTransferWitness {
+        CoreTransferWitness {
+            sender_pubkey: "0x01".to_string(),
+            sender_balance: 1_000,
+            sender_nonce: 0,
+            sender_merkle_path: vec!["0x00".to_string(); 4],
+            sender_path_indices: vec![0, 1, 0, 1],
+            receiver_pubkey: "0x02".to_string(),
+            receiver_balance: 0,
+            receiver_nonce: 0,
+            receiver_merkle_path: vec!["0x00".to_string(); 4],
+            receiver_path_indices: vec![1, 1, 0, 0],
+            amount: 100,
+            signature: "00".repeat(64),
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_over_capacity() {
+        let mut request = empty_request();
+        request.transfers = vec![transfer(); prover_worker::MAX_TRANSFERS];
+        request.validate().unwrap();
+
+        request.transfers.push(transfer());
+        let err = request.validate().unwrap_err();
+        assert!(err.contains("exceeds circuit capacity"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_fields() {
+        // BN254 modulus as a decimal string is not a canonical field element
+        let mut request = empty_request();
+        request.pre_state_root =
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+                .to_string();
+        assert!(request.validate().unwrap_err().contains("pre_state_root"));
+
+        let mut request = empty_request();
+        let mut tx = transfer();
+        tx.sender_path_indices[2] = 2;
+        request.transfers.push(tx);
+        assert!(request.validate().unwrap_err().contains("path indices"));
+
+        let mut request = empty_request();
+        let mut tx = transfer();
+        tx.amount = tx.sender_balance + 1;
+        request.transfers.push(tx);
+        assert!(request.validate().unwrap_err().contains("sender_balance"));
+
+        let mut request = empty_request();
+        request.post_shielded_root = "0xbbbb".to_string();
+        assert!(
+            request
+                .validate()
+                .unwrap_err()
+                .contains("post_shielded_root")
+        );
+    }
+
+    #[test]
+    fn test_withdrawals_commit_to_bridge_tree() {
+        use zelana_bridge_events::merkle::{WithdrawalTree, withdrawal_leaf};
+
+        let mut request = empty_request();
+        request.withdrawals.push(CoreWithdrawalWitness {
+            sender_pubkey: "0x01".to_string(),
+            sender_balance: 1_000,
+            sender_nonce: 0,
+            sender_merkle_path: vec!["0x00".to_string(); 4],
+            sender_path_indices: vec![0, 1, 0, 1],
+            l1_recipient: "ff".repeat(32),
+            tx_hash: "ab".repeat(32),
+            amount: 100,
+            signature: "00".repeat(64),
+        });
+        request.validate().unwrap();
+
+        // The public input is the bridge's root over the exact bytes, and
+        // the circuit's halves recombine to the recipient field element
+        let batch = convert_to_noir_inputs(&request);
+        let leaf = withdrawal_leaf(&[0xab; 32], &[0xff; 32], 100);
+        let root = WithdrawalTree::new(&[leaf]).unwrap().root();
+        assert_eq!(batch.withdrawal_root, format!("0x{}", hex::encode(root)));
+        assert_eq!(
+            batch.withdrawals[0].tx_hash_hi,
+            u128::from_be_bytes([0xab; 16]).to_string()
+        );
+        batch.validate().unwrap();
+
+        request.withdrawals[0].tx_hash = "ab".repeat(31);
+        assert!(request.validate().unwrap_err().contains("tx_hash"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancel_mock_job_mid_flight() {
+        let state: SharedCoreApiState = Arc::new(RwLock::new(CoreApiState::new(CoreApiConfig {
+            mock_delay_ms: 10_000,
+            ..CoreApiConfig::default()
+        })));
+
+        let Json(response) = prove_handler(State(state.clone()), Json(empty_request()))
+            .await
+            .unwrap();
+        let ApiResponse::Success { data } = response else {
+            panic!("prove request rejected");
+        };
+        let job_id = data.job_id;
+
+        // Let the job reach the mock proving sleep
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let (_, mut rx) = {
+            let api_state = state.read().await;
+            let job = &api_state.jobs[&job_id];
+            assert_eq!(job.status.state, ProofJobState::Proving);
+            job.events.subscribe_after(None)
+        };
+
+        let Json(response) = cancel_handler(State(state.clone()), Path(job_id.clone()))
+            .await
+            .unwrap();
+        assert!(matches!(response, ApiResponse::Success { .. }));
+
+        // Well past the mock delay: the job must stay cancelled and uncached
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        let api_state = state.read().await;
+        assert_eq!(
+            api_state.jobs[&job_id].status.state,
+            ProofJobState::Cancelled
+        );
+        assert_eq!(api_state.active_jobs, 0);
+        assert!(api_state.cache.get_by_job(&job_id).is_none());
+
+        // The cancel event is the last one published
+        let mut last = None;
+        while let Ok(event) = rx.try_recv() {
+            last = Some(event.event);
+        }
+        assert!(matches!(last, Some(ProofStatusEvent::Cancelled { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_rejected_before_buffering() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tower::ServiceExt;
+
+        let state = Arc::new(RwLock::new(CoreApiState::new(CoreApiConfig::default())));
+        let app = core_api_router(state);
+
+        // Stream 64x the limit and count how many chunks the server pulls
+        const CHUNK: usize = 64 * 1024;
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let counter = pulled.clone();
+        let body = tokio_stream::iter(0..PROVE_BODY_LIMIT * 64 / CHUNK).map(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, Infallible>(axum::body::Bytes::from(vec![b' '; CHUNK]))
+        });
+        let request = axum::http::Request::post("/v2/batch/prove")
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from_stream(body))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(pulled.load(Ordering::SeqCst) <= PROVE_BODY_LIMIT / CHUNK + 1);
+    }
+
     #[test]
     fn test_mock_proof_generation() {
         let request = CoreBatchProveRequest {
@@ -1,11 +1,20 @@
 //! Chunk Dispatcher Module
 //!
 //! Handles slicing batches into chunks and dispatching to workers.
+//!
+//! Chunks are assigned round-robin, but a worker never has more chunks in
+//! flight than the `max_concurrent_jobs` it advertises; extra chunks wait in
+//! [`WorkerSlots`] until one of its jobs finishes.
 
+use futures::stream::{FuturesUnordered, StreamExt};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
-use tracing::{error, info, warn};
+use tokio::sync::Notify;
+use tracing::{Instrument, debug, error, info, info_span, warn};
+use wincode::{SchemaRead, SchemaWrite};
 
 // Types
 
@@ -20,6 +29,27 @@ pub struct BatchTransaction {
     pub merkle_path: Vec<String>,
 }
 
+/// `Content-Type` of a binary (wincode) `/batch/submit` body
+pub const BATCH_BINARY_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Binary wire form of [`BatchTransaction`], hex fields as raw bytes
+#[derive(SchemaRead, SchemaWrite)]
+struct BatchTransactionWire {
+    sender_pubkey: [u8; 32],
+    receiver_pubkey: [u8; 32],
+    amount: u64,
+    signature: String,
+    merkle_path: Vec<[u8; 32]>,
+}
+
+/// Binary wire form of [`Batch`]
+#[derive(SchemaRead, SchemaWrite)]
+struct BatchWire {
+    batch_id: String,
+    initial_root: [u8; 32],
+    transactions: Vec<BatchTransactionWire>,
+}
+
 /// A batch of transactions to be proven
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Batch {
@@ -31,6 +61,171 @@ pub struct Batch {
     pub transactions: Vec<BatchTransaction>,
 }
 
+impl Batch {
+    /// Canonical SHA-256 of the batch as submitted.
+    ///
+    /// Hashes every field in declaration order with length prefixes, so it
+    /// is independent of JSON formatting and key order. Roots, keys and
+    /// Merkle nodes hash as their decoded 32 bytes, so `0x` prefixes and hex
+    /// case don't matter and the JSON and wincode forms agree; other strings
+    /// hash byte-for-byte. The coordinator echoes it in the submit response
+    /// for clients to compare with their own.
+    pub fn content_hash(&self) -> [u8; 32] {
+        fn put(hasher: &mut Sha256, bytes: &[u8]) {
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(bytes);
+        }
+        // Tagged so a malformed field can't collide with a decoded one
+        fn put_hex32(hasher: &mut Sha256, value: &str) {
+            match decode_hex32("", value) {
+                Ok(bytes) => {
+                    hasher.update([1]);
+                    hasher.update(bytes);
+                }
+                Err(_) => {
+                    hasher.update([0]);
+                    put(hasher, value.as_bytes());
+                }
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"zelana-batch-v2");
+        put(&mut hasher, self.batch_id.as_bytes());
+        put_hex32(&mut hasher, &self.initial_root);
+        hasher.update((self.transactions.len() as u64).to_le_bytes());
+        for tx in &self.transactions {
+            put_hex32(&mut hasher, &tx.sender_pubkey);
+            put_hex32(&mut hasher, &tx.receiver_pubkey);
+            hasher.update(tx.amount.to_le_bytes());
+            put(&mut hasher, tx.signature.as_bytes());
+            hasher.update((tx.merkle_path.len() as u64).to_le_bytes());
+            for node in &tx.merkle_path {
+                put_hex32(&mut hasher, node);
+            }
+        }
+        hasher.finalize().into()
+    }
+
+    /// Encode as a binary `/batch/submit` body, sent with
+    /// `Content-Type: BATCH_BINARY_CONTENT_TYPE`.
+    ///
+    /// Roots, keys and Merkle nodes travel as raw 32 bytes instead of hex
+    /// text, so a 100-transaction batch with depth-32 paths is about 124 KB
+    /// against 256 KB of compact JSON. Encoding wincode over the hex strings
+    /// as-is would be slightly larger than JSON. Fails if any of those
+    /// fields is not 32-byte hex.
+    pub fn to_wincode(&self) -> Result<Vec<u8>, String> {
+        let transactions = self
+            .transactions
+            .iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                let context = |e: String| format!("transaction {}: {}", i, e);
+                Ok(BatchTransactionWire {
+                    sender_pubkey: decode_hex32("sender_pubkey", &tx.sender_pubkey)
+                        .map_err(context)?,
+                    receiver_pubkey: decode_hex32("receiver_pubkey", &tx.receiver_pubkey)
+                        .map_err(context)?,
+                    amount: tx.amount,
+                    signature: tx.signature.clone(),
+                    merkle_path: tx
+                        .merkle_path
+                        .iter()
+                        .map(|node| decode_hex32("merkle_path node", node))
+                        .collect::<Result<_, _>>()
+                        .map_err(context)?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        let wire = BatchWire {
+            batch_id: self.batch_id.clone(),
+            initial_root: decode_hex32("initial_root", &self.initial_root)?,
+            transactions,
+        };
+        wincode::serialize(&wire).map_err(|e| e.to_string())
+    }
+
+    /// Decode a binary `/batch/submit` body.
+    ///
+    /// Hex fields come back `0x`-prefixed lowercase; `content_hash` hashes
+    /// their bytes, so it matches the JSON form however that was written.
+    pub fn from_wincode(bytes: &[u8]) -> Result<Self, String> {
+        let wire: BatchWire = wincode::deserialize(bytes).map_err(|e| e.to_string())?;
+        let hex32 = |bytes: &[u8; 32]| format!("0x{}", hex::encode(bytes));
+
+        Ok(Self {
+            batch_id: wire.batch_id,
+            initial_root: hex32(&wire.initial_root),
+            transactions: wire
+                .transactions
+                .into_iter()
+                .map(|tx| BatchTransaction {
+                    sender_pubkey: hex32(&tx.sender_pubkey),
+                    receiver_pubkey: hex32(&tx.receiver_pubkey),
+                    amount: tx.amount,
+                    signature: tx.signature,
+                    merkle_path: tx.merkle_path.iter().map(hex32).collect(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Reject structurally invalid batches before any chunk is dispatched.
+    ///
+    /// Checks the batch is non-empty, the initial root and every account key
+    /// and Merkle node are 32-byte hex, paths fit the tree depth, and each
+    /// transaction moves a non-zero amount with a signature attached.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.batch_id.trim().is_empty() {
+            return Err("batch_id is empty".to_string());
+        }
+        if self.transactions.is_empty() {
+            return Err("batch has no transactions".to_string());
+        }
+        check_hex32("initial_root", &self.initial_root)?;
+
+        for (i, tx) in self.transactions.iter().enumerate() {
+            let context = |e: String| format!("transaction {}: {}", i, e);
+            check_hex32("sender_pubkey", &tx.sender_pubkey).map_err(context)?;
+            check_hex32("receiver_pubkey", &tx.receiver_pubkey).map_err(context)?;
+            if tx.amount == 0 {
+                return Err(context("amount is zero".to_string()));
+            }
+            if tx.signature.trim().is_empty() {
+                return Err(context("signature is empty".to_string()));
+            }
+            if tx.merkle_path.len() > prover_worker::MERKLE_DEPTH {
+                return Err(context(format!(
+                    "merkle path has {} nodes, tree depth is {}",
+                    tx.merkle_path.len(),
+                    prover_worker::MERKLE_DEPTH
+                )));
+            }
+            for node in &tx.merkle_path {
+                check_hex32("merkle_path", node).map_err(context)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Roots and keys are 32-byte hex, optionally `0x`-prefixed
+fn check_hex32(name: &str, value: &str) -> Result<(), String> {
+    decode_hex32(name, value).map(|_| ())
+}
+
+fn decode_hex32(name: &str, value: &str) -> Result<[u8; 32], String> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    match hex::decode(digits) {
+        Ok(bytes) => <[u8; 32]>::try_from(bytes.as_slice())
+            .map_err(|_| format!("{} is {} bytes, expected 32", name, bytes.len())),
+        Err(_) => Err(format!("{} is not valid hex", name)),
+    }
+}
+
 /// A chunk is a subset of the batch assigned to one worker
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Chunk {
@@ -64,6 +259,9 @@ pub struct ChunkProof {
     pub proof: String,
     /// Public inputs
     pub public_inputs: Vec<String>,
+    /// Hash of the verifying key the proof was generated against
+    #[serde(default)]
+    pub vk_hash: String,
     /// Proving time in ms
     pub proving_time_ms: u64,
 }
@@ -90,8 +288,103 @@ pub struct DispatcherConfig {
     pub chunk_size: usize,
     /// HTTP client
     pub client: reqwest::Client,
-    /// Timeout for each proof request (ms)
+    /// Deadline for proving a single chunk (ms), counted from when the chunk
+    /// gets a worker slot
     pub proof_timeout_ms: u64,
+    /// Per-worker concurrency limits, shared across batches
+    pub slots: WorkerSlots,
+}
+
+// Worker Concurrency
+
+/// Capacity assumed for a worker that hasn't reported `max_concurrent_jobs`
+pub const DEFAULT_WORKER_CAPACITY: usize = 1;
+
+#[derive(Debug)]
+struct Slot {
+    capacity: usize,
+    in_flight: usize,
+}
+
+#[derive(Debug, Default)]
+struct SlotsInner {
+    slots: Mutex<HashMap<String, Slot>>,
+    freed: Notify,
+}
+
+/// In-flight chunk counts per worker, bounded by each worker's capacity
+#[derive(Debug, Clone, Default)]
+pub struct WorkerSlots {
+    inner: Arc<SlotsInner>,
+}
+
+impl WorkerSlots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the `max_concurrent_jobs` a worker advertised
+    pub fn set_capacity(&self, worker_url: &str, capacity: usize) {
+        {
+            let mut slots = self.inner.slots.lock().unwrap();
+            let slot = slots.entry(worker_url.to_string()).or_insert(Slot {
+                capacity: DEFAULT_WORKER_CAPACITY,
+                in_flight: 0,
+            });
+            slot.capacity = capacity.max(1);
+        }
+        // A raised limit may unblock waiting chunks
+        self.inner.freed.notify_waiters();
+    }
+
+    /// Chunks currently in flight on a worker
+    pub fn in_flight(&self, worker_url: &str) -> usize {
+        let slots = self.inner.slots.lock().unwrap();
+        slots.get(worker_url).map_or(0, |s| s.in_flight)
+    }
+
+    /// Wait for a free slot on a worker; the slot is released when the guard drops
+    pub async fn acquire(&self, worker_url: &str) -> SlotGuard {
+        loop {
+            // Registered before checking so a release in between isn't missed
+            let freed = self.inner.freed.notified();
+            {
+                let mut slots = self.inner.slots.lock().unwrap();
+                let slot = slots.entry(worker_url.to_string()).or_insert(Slot {
+                    capacity: DEFAULT_WORKER_CAPACITY,
+                    in_flight: 0,
+                });
+                if slot.in_flight < slot.capacity {
+                    slot.in_flight += 1;
+                    return SlotGuard {
+                        slots: self.clone(),
+                        worker_url: worker_url.to_string(),
+                    };
+                }
+            }
+            debug!("Worker {} at capacity, holding chunk", worker_url);
+            freed.await;
+        }
+    }
+}
+
+/// A reserved slot on a worker
+#[derive(Debug)]
+pub struct SlotGuard {
+    slots: WorkerSlots,
+    worker_url: String,
+}
+
+impl Drop for SlotGuard {
+    fn drop(&mut self) {
+        {
+            let mut slots = self.slots.inner.slots.lock().unwrap();
+            if let Some(slot) = slots.get_mut(&self.worker_url) {
+                slot.in_flight = slot.in_flight.saturating_sub(1);
+            }
+        }
+        self.slots.inner.freed.notify_waiters();
+    }
 }
 
 // State Computation
@@ -130,6 +423,48 @@ pub fn compute_intermediate_roots(
     roots
 }
 
+/// Number of chunks needed for `tx_count` transactions.
+///
+/// Returns `None` for a zero chunk size; never overflows, unlike the
+/// `(len + size - 1) / size` form.
+pub fn chunk_count(tx_count: usize, chunk_size: usize) -> Option<usize> {
+    if chunk_size == 0 {
+        return None;
+    }
+    Some(tx_count.div_ceil(chunk_size))
+}
+
+/// Estimated milliseconds to prove `chunks` chunks on workers with the given
+/// average proving times.
+///
+/// Mirrors the dispatcher's round-robin assignment, with any uneven share
+/// going to the slowest workers, so the slowest worker's queue sets the
+/// estimate. Workers without a recorded average (0) are assumed to run at the
+/// mean of the others; with no averages at all there is no estimate. Other
+/// batches sharing the workers are not accounted for.
+pub fn estimate_proving_ms(chunks: usize, worker_avg_ms: &[u64]) -> Option<u64> {
+    if chunks == 0 {
+        return Some(0);
+    }
+    let known: Vec<u64> = worker_avg_ms.iter().copied().filter(|&ms| ms > 0).collect();
+    if known.is_empty() {
+        return None;
+    }
+    let mean = known.iter().sum::<u64>() / known.len() as u64;
+
+    let mut avgs: Vec<u64> = worker_avg_ms
+        .iter()
+        .map(|&ms| if ms > 0 { ms } else { mean })
+        .collect();
+    avgs.sort_unstable_by(|a, b| b.cmp(a));
+
+    let (per_worker, extra) = (chunks / avgs.len(), chunks % avgs.len());
+    avgs.iter()
+        .enumerate()
+        .map(|(i, &ms)| (per_worker + usize::from(i < extra)) as u64 * ms)
+        .max()
+}
+
 /// Slice a batch into chunks with pre-computed state roots
 pub fn slice_batch(batch: &Batch, chunk_size: usize) -> Vec<Chunk> {
     let roots = compute_intermediate_roots(&batch.initial_root, &batch.transactions, chunk_size);
@@ -192,6 +527,9 @@ pub struct WorkerProveResponse {
     pub worker_id: u32,
     pub proof: String,
     pub public_inputs: Vec<String>,
+    /// Empty for workers that predate VK hash reporting
+    #[serde(default)]
+    pub vk_hash: String,
     pub proving_time_ms: u64,
 }
 
@@ -217,12 +555,24 @@ impl Dispatcher {
         Self { config }
     }
 
-    /// Dispatch a single chunk to a worker
+    /// Dispatch a single chunk to a worker, failing it once its own
+    /// `proof_timeout_ms` deadline passes
     pub async fn dispatch_chunk(
         &self,
         worker_url: &str,
         chunk: &Chunk,
     ) -> Result<ChunkProof, String> {
+        let deadline = std::time::Duration::from_millis(self.config.proof_timeout_ms);
+        match tokio::time::timeout(deadline, self.prove_on_worker(worker_url, chunk)).await {
+            Ok(result) => result,
+            Err(_) => Err(format!(
+                "Chunk {} timed out on worker {} after {}ms",
+                chunk.chunk_id, worker_url, self.config.proof_timeout_ms
+            )),
+        }
+    }
+
+    async fn prove_on_worker(&self, worker_url: &str, chunk: &Chunk) -> Result<ChunkProof, String> {
         let request = WorkerProveRequest {
             chunk_id: chunk.chunk_id,
             pre_root: chunk.pre_root.clone(),
@@ -246,18 +596,20 @@ impl Dispatcher {
             .client
             .post(format!("{}/prove", worker_url))
             .json(&request)
-            .timeout(std::time::Duration::from_millis(
-                self.config.proof_timeout_ms,
-            ))
             .send()
             .await
             .map_err(|e| format!("Failed to contact worker {}: {}", worker_url, e))?;
 
         if !response.status().is_success() {
+            let status = response.status();
+            // Rejected requests (400) carry the reason in the usual envelope
+            let detail = match response.json::<WorkerResponse<WorkerProveResponse>>().await {
+                Ok(WorkerResponse::Error { message }) => format!(": {}", message),
+                _ => String::new(),
+            };
             return Err(format!(
-                "Worker {} returned error status: {}",
-                worker_url,
-                response.status()
+                "Worker {} returned error status: {}{}",
+                worker_url, status, detail
             ));
         }
 
@@ -277,6 +629,7 @@ impl Dispatcher {
                     worker_id: data.worker_id,
                     proof: data.proof,
                     public_inputs: data.public_inputs,
+                    vk_hash: data.vk_hash,
                     proving_time_ms: data.proving_time_ms,
                 })
             }
@@ -291,6 +644,18 @@ impl Dispatcher {
         &self,
         batch: &Batch,
         chunk_size: usize,
+    ) -> Result<BatchProofs, String> {
+        self.dispatch_batch_with_progress(batch, chunk_size, |_| {})
+            .await
+    }
+
+    /// Like [`Dispatcher::dispatch_batch`], calling `on_proved` as each chunk
+    /// proof arrives
+    pub async fn dispatch_batch_with_progress(
+        &self,
+        batch: &Batch,
+        chunk_size: usize,
+        mut on_proved: impl FnMut(&ChunkProof),
     ) -> Result<BatchProofs, String> {
         let start = Instant::now();
 
@@ -319,33 +684,52 @@ impl Dispatcher {
             })
             .collect();
 
-        // Dispatch all chunks in parallel
+        // Dispatch all chunks in parallel, within each worker's capacity.
+        // A chunk that fails (or times out) is retried once on the next worker.
         let mut handles = Vec::new();
-        for (worker_url, chunk) in assignments {
-            let client = self.config.client.clone();
-            let timeout = self.config.proof_timeout_ms;
-
-            let handle = tokio::spawn(async move {
-                let dispatcher = Dispatcher {
-                    config: DispatcherConfig {
-                        worker_urls: vec![],
-                        chunk_size: 0,
-                        client,
-                        proof_timeout_ms: timeout,
-                    },
-                };
-                dispatcher.dispatch_chunk(&worker_url, &chunk).await
-            });
+        for (i, (worker_url, chunk)) in assignments.into_iter().enumerate() {
+            let dispatcher = Dispatcher {
+                config: self.config.clone(),
+            };
+            let workers = &self.config.worker_urls;
+            let fallback_url =
+                (workers.len() > 1).then(|| workers[(i + 1) % workers.len()].clone());
+
+            let span = info_span!("chunk", chunk_id = chunk.chunk_id);
+            let handle = tokio::spawn(
+                async move {
+                    let result = {
+                        let _slot = dispatcher.config.slots.acquire(&worker_url).await;
+                        dispatcher.dispatch_chunk(&worker_url, &chunk).await
+                    };
+                    match (result, fallback_url) {
+                        (Err(e), Some(fallback_url)) => {
+                            warn!(
+                                "{}; retrying chunk {} on {}",
+                                e, chunk.chunk_id, fallback_url
+                            );
+                            let _slot = dispatcher.config.slots.acquire(&fallback_url).await;
+                            dispatcher.dispatch_chunk(&fallback_url, &chunk).await
+                        }
+                        (result, _) => result,
+                    }
+                }
+                .instrument(span),
+            );
             handles.push(handle);
         }
 
-        // Collect results
+        // Collect results as they complete
         let mut proofs = Vec::new();
         let mut errors = Vec::new();
 
-        for handle in handles {
-            match handle.await {
-                Ok(Ok(proof)) => proofs.push(proof),
+        let mut pending: FuturesUnordered<_> = handles.into_iter().collect();
+        while let Some(joined) = pending.next().await {
+            match joined {
+                Ok(Ok(proof)) => {
+                    on_proved(&proof);
+                    proofs.push(proof);
+                }
                 Ok(Err(e)) => errors.push(e),
                 Err(e) => errors.push(format!("Task panicked: {}", e)),
             }
@@ -353,7 +737,6 @@ impl Dispatcher {
 
         if !errors.is_empty() {
             error!("Some chunks failed: {:?}", errors);
-            // For now, we fail if any chunk fails. Could implement retry logic.
             return Err(format!("Failed chunks: {}", errors.join(", ")));
         }
 
@@ -480,4 +863,368 @@ mod tests {
         // Roots should chain
         assert_eq!(chunks[0].post_root, chunks[1].pre_root);
     }
+
+    #[test]
+    fn test_chunk_count() {
+        assert_eq!(chunk_count(0, 25), Some(0));
+        assert_eq!(chunk_count(25, 25), Some(1));
+        assert_eq!(chunk_count(26, 25), Some(2));
+        assert_eq!(chunk_count(100, 0), None);
+
+        // Would overflow as (len + size - 1) / size
+        assert_eq!(chunk_count(usize::MAX, 25), Some(usize::MAX / 25 + 1));
+        assert_eq!(chunk_count(usize::MAX, usize::MAX), Some(1));
+    }
+
+    #[test]
+    fn test_estimate_proving_ms() {
+        // Proportional to chunk count
+        assert_eq!(estimate_proving_ms(4, &[100; 4]), Some(100));
+        assert_eq!(estimate_proving_ms(8, &[100; 4]), Some(200));
+        assert_eq!(estimate_proving_ms(16, &[100; 4]), Some(400));
+
+        // Inverse to worker count and speed
+        assert_eq!(estimate_proving_ms(8, &[100; 2]), Some(400));
+        assert_eq!(estimate_proving_ms(8, &[100; 8]), Some(100));
+        assert_eq!(estimate_proving_ms(8, &[50; 4]), Some(100));
+
+        // Uneven shares land on the slowest worker
+        assert_eq!(estimate_proving_ms(2, &[100, 300]), Some(300));
+        assert_eq!(estimate_proving_ms(3, &[100, 300]), Some(600));
+
+        // Unknown speeds take the mean of the known ones
+        assert_eq!(estimate_proving_ms(4, &[100, 0]), Some(200));
+        assert_eq!(estimate_proving_ms(4, &[100, 300, 0, 0]), Some(300));
+        assert_eq!(estimate_proving_ms(4, &[0, 0]), None);
+        assert_eq!(estimate_proving_ms(4, &[]), None);
+        assert_eq!(estimate_proving_ms(0, &[]), Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_worker_slots_respect_capacity() {
+        let slots = WorkerSlots::new();
+        let first = slots.acquire("w").await;
+        assert_eq!(slots.in_flight("w"), 1);
+
+        // Unknown workers default to one slot, so a second chunk waits
+        let waiting = tokio::spawn({
+            let slots = slots.clone();
+            async move { slots.acquire("w").await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiting.is_finished());
+
+        drop(first);
+        let second = waiting.await.unwrap();
+        assert_eq!(slots.in_flight("w"), 1);
+
+        // Raising the advertised capacity admits more chunks at once
+        slots.set_capacity("w", 2);
+        let third = slots.acquire("w").await;
+        assert_eq!(slots.in_flight("w"), 2);
+        drop((second, third));
+        assert_eq!(slots.in_flight("w"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_serializes_chunks_on_single_slot_worker() {
+        use axum::{Json, Router, routing::post};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Mock worker that records how many proofs it runs at once
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let app = Router::new().route(
+            "/prove",
+            post({
+                let (active, peak) = (active.clone(), peak.clone());
+                move |Json(req): Json<WorkerProveRequest>| {
+                    let (active, peak) = (active.clone(), peak.clone());
+                    async move {
+                        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        Json(WorkerResponse::Success {
+                            data: WorkerProveResponse {
+                                job_id: format!("job-{}", req.chunk_id),
+                                chunk_id: req.chunk_id,
+                                worker_id: 1,
+                                proof: String::new(),
+                                public_inputs: vec![],
+                                vk_hash: String::new(),
+                                proving_time_ms: 50,
+                            },
+                        })
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let worker_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let slots = WorkerSlots::new();
+        slots.set_capacity(&worker_url, 1);
+        let dispatcher = Dispatcher::new(DispatcherConfig {
+            worker_urls: vec![worker_url],
+            chunk_size: 1,
+            client: reqwest::Client::new(),
+            proof_timeout_ms: 5_000,
+            slots,
+        });
+
+        let mut batch = valid_batch();
+        batch.transactions = vec![batch.transactions[0].clone(); 3];
+        let proofs = dispatcher.dispatch_batch(&batch, 1).await.unwrap();
+
+        assert_eq!(proofs.proofs.len(), 3);
+        assert_eq!(peak.load(Ordering::SeqCst), 1, "chunks must not overlap");
+    }
+
+    /// Mock worker that takes `slow_ms` on `slow_chunk` and 10ms otherwise,
+    /// counting completed proofs
+    async fn spawn_mock_worker(
+        worker_id: u32,
+        slow_chunk: Option<u32>,
+        slow_ms: u64,
+        completed: Arc<std::sync::atomic::AtomicUsize>,
+    ) -> String {
+        use axum::{Json, Router, routing::post};
+        use std::sync::atomic::Ordering;
+
+        let app = Router::new().route(
+            "/prove",
+            post(move |Json(req): Json<WorkerProveRequest>| {
+                let completed = completed.clone();
+                async move {
+                    let delay = if Some(req.chunk_id) == slow_chunk {
+                        slow_ms
+                    } else {
+                        10
+                    };
+                    tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+                    completed.fetch_add(1, Ordering::SeqCst);
+                    Json(WorkerResponse::Success {
+                        data: WorkerProveResponse {
+                            job_id: format!("job-{}", req.chunk_id),
+                            chunk_id: req.chunk_id,
+                            worker_id,
+                            proof: String::new(),
+                            public_inputs: vec![],
+                            vk_hash: String::new(),
+                            proving_time_ms: delay,
+                        },
+                    })
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let worker_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        worker_url
+    }
+
+    #[tokio::test]
+    async fn test_chunk_timeout_is_per_chunk() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let completed = Arc::new(AtomicUsize::new(0));
+        let worker_url = spawn_mock_worker(1, Some(1), 5_000, completed.clone()).await;
+
+        // One slot: the later chunks queue behind the slow one, but the
+        // queueing doesn't count against their own deadlines
+        let slots = WorkerSlots::new();
+        slots.set_capacity(&worker_url, 1);
+        let dispatcher = Dispatcher::new(DispatcherConfig {
+            worker_urls: vec![worker_url],
+            chunk_size: 1,
+            client: reqwest::Client::new(),
+            proof_timeout_ms: 300,
+            slots,
+        });
+
+        let mut batch = valid_batch();
+        batch.transactions = vec![batch.transactions[0].clone(); 4];
+        let mut proved = Vec::new();
+        let err = dispatcher
+            .dispatch_batch_with_progress(&batch, 1, |proof| proved.push(proof.chunk_id))
+            .await
+            .unwrap_err();
+
+        assert!(err.contains("Chunk 1 timed out"), "{}", err);
+        for other in [0, 2, 3] {
+            assert!(!err.contains(&format!("Chunk {} ", other)), "{}", err);
+        }
+        assert_eq!(completed.load(Ordering::SeqCst), 3);
+        proved.sort();
+        assert_eq!(proved, vec![0, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_chunk_retries_on_another_worker() {
+        use std::sync::atomic::AtomicUsize;
+
+        let completed = Arc::new(AtomicUsize::new(0));
+        let slow = spawn_mock_worker(1, Some(0), 5_000, completed.clone()).await;
+        let fast = spawn_mock_worker(2, None, 0, completed.clone()).await;
+        let dispatcher = Dispatcher::new(DispatcherConfig {
+            worker_urls: vec![slow, fast],
+            chunk_size: 1,
+            client: reqwest::Client::new(),
+            proof_timeout_ms: 300,
+            slots: WorkerSlots::new(),
+        });
+
+        let mut batch = valid_batch();
+        batch.transactions = vec![batch.transactions[0].clone(); 2];
+        let proofs = dispatcher.dispatch_batch(&batch, 1).await.unwrap();
+
+        let workers: Vec<u32> = proofs.proofs.iter().map(|p| p.worker_id).collect();
+        assert_eq!(workers, vec![2, 2]);
+    }
+
+    fn valid_batch() -> Batch {
+        Batch {
+            batch_id: "batch-1".to_string(),
+            initial_root: format!("0x{}", "00".repeat(32)),
+            transactions: vec![BatchTransaction {
+                sender_pubkey: "11".repeat(32),
+                receiver_pubkey: format!("0x{}", "22".repeat(32)),
+                amount: 100,
+                signature: "0xsig".to_string(),
+                merkle_path: vec!["33".repeat(32); 4],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let batch = valid_batch();
+        let hash = batch.content_hash();
+
+        let json = serde_json::to_string_pretty(&batch).unwrap();
+        let round_tripped: Batch = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_tripped.content_hash(), hash);
+
+        type Mutation = fn(&mut Batch);
+        let mutations: Vec<Mutation> = vec![
+            |b| b.batch_id.push('x'),
+            |b| b.initial_root = format!("0x{}", "01".repeat(32)),
+            |b| b.transactions[0].sender_pubkey = "12".repeat(32),
+            |b| b.transactions[0].receiver_pubkey = "23".repeat(32),
+            |b| b.transactions[0].amount += 1,
+            |b| b.transactions[0].signature.push('0'),
+            |b| b.transactions[0].merkle_path[3] = "34".repeat(32),
+            |b| {
+                b.transactions[0].merkle_path.pop();
+            },
+            |b| b.transactions.push(b.transactions[0].clone()),
+            |b| {
+                // Same bytes, different field boundary
+                let tx = &mut b.transactions[0];
+                tx.sender_pubkey.push_str(&tx.receiver_pubkey[..2]);
+                tx.receiver_pubkey.drain(..2);
+            },
+        ];
+        for (i, mutate) in mutations.into_iter().enumerate() {
+            let mut changed = valid_batch();
+            mutate(&mut changed);
+            assert_ne!(changed.content_hash(), hash, "mutation {}", i);
+        }
+
+        // Hex fields hash as bytes: `0x` prefixes and case don't matter
+        let mut restyled = valid_batch();
+        restyled.initial_root = "00".repeat(32);
+        restyled.transactions[0].sender_pubkey = format!("0x{}", "11".repeat(32));
+        restyled.transactions[0].receiver_pubkey = "22".repeat(32);
+        restyled.transactions[0].merkle_path[0] = format!("0x{}", "33".repeat(32));
+        assert_eq!(restyled.content_hash(), hash);
+    }
+
+    #[test]
+    fn test_wincode_round_trip() {
+        let mut batch = valid_batch();
+        batch.transactions[0].merkle_path =
+            vec![format!("0x{}", "33".repeat(32)); prover_worker::MERKLE_DEPTH];
+        let bytes = batch.to_wincode().unwrap();
+        let decoded = Batch::from_wincode(&bytes).unwrap();
+
+        // Hex fields are normalized to 0x-prefixed form
+        assert_eq!(
+            decoded.transactions[0].sender_pubkey,
+            format!("0x{}", "11".repeat(32))
+        );
+        assert_eq!(decoded.transactions[0].signature, "0xsig");
+        assert_eq!(decoded.to_wincode().unwrap(), bytes);
+        assert_eq!(
+            Batch::from_wincode(&decoded.to_wincode().unwrap())
+                .unwrap()
+                .content_hash(),
+            decoded.content_hash()
+        );
+        assert!(bytes.len() * 2 < serde_json::to_vec(&batch).unwrap().len());
+
+        assert!(Batch::from_wincode(&bytes[..bytes.len() - 1]).is_err());
+        batch.initial_root = "0x00".to_string();
+        assert!(batch.to_wincode().unwrap_err().contains("initial_root"));
+    }
+
+    #[test]
+    fn test_validate_accepts_well_formed_batch() {
+        valid_batch().validate().unwrap();
+    }
+
+    #[test]
+    fn test_validate_rejects_malformed_batches() {
+        type Mutation = fn(&mut Batch);
+        let cases: Vec<(Mutation, &str)> = vec![
+            (|b| b.batch_id.clear(), "batch_id"),
+            (|b| b.transactions.clear(), "no transactions"),
+            (
+                |b| b.initial_root = "0x0000".to_string(),
+                "initial_root is 2 bytes",
+            ),
+            (
+                |b| b.initial_root = "0xzz".to_string(),
+                "initial_root is not valid hex",
+            ),
+            (
+                |b| b.transactions[0].sender_pubkey = "0xabc".to_string(),
+                "transaction 0: sender_pubkey",
+            ),
+            (
+                |b| b.transactions[0].receiver_pubkey.push('0'),
+                "transaction 0: receiver_pubkey",
+            ),
+            (|b| b.transactions[0].amount = 0, "amount is zero"),
+            (
+                |b| b.transactions[0].signature.clear(),
+                "signature is empty",
+            ),
+            (
+                |b| b.transactions[0].merkle_path[1] = "0x01".to_string(),
+                "merkle_path is 1 bytes",
+            ),
+            (
+                |b| {
+                    b.transactions[0].merkle_path =
+                        vec!["33".repeat(32); prover_worker::MERKLE_DEPTH + 1]
+                },
+                "tree depth",
+            ),
+        ];
+
+        for (mutate, expected) in cases {
+            let mut batch = valid_batch();
+            mutate(&mut batch);
+            let err = batch.validate().unwrap_err();
+            assert!(
+                err.contains(expected),
+                "{:?} should mention {:?}",
+                err,
+                expected
+            );
+        }
+    }
 }
@@ -9,25 +9,32 @@
 //! - `settler` - Settlement to Solana L1
 //! - `solana_client` - Solana RPC client for verification
 //! - `core_api` - HTTP API for Core Sequencer integration (SSE)
+//! - `shutdown` - Draining in-flight batches on shutdown
 
 pub mod core_api;
 pub mod dispatcher;
 pub mod settler;
+pub mod shutdown;
 pub mod solana_client;
 
 pub use dispatcher::{
-    Batch, BatchProofs, BatchTransaction, Chunk, ChunkProof, Dispatcher, DispatcherConfig,
+    BATCH_BINARY_CONTENT_TYPE, Batch, BatchProofs, BatchTransaction, Chunk, ChunkProof, Dispatcher,
+    DispatcherConfig, estimate_proving_ms,
 };
 pub use settler::{
     BatchSettlement, MockSettler, ProofSettlement, SettlementMode, Settler, SettlerConfig,
+    check_vk_hashes,
 };
+pub use shutdown::{BatchDrain, ShuttingDown};
 pub use solana_client::{
-    ProofData, SolanaClientError, SolanaVerifierClient, SolanaVerifierConfig, VerificationResult,
+    ProofData, SimulationResult, SolanaClientError, SolanaVerifierClient, SolanaVerifierConfig,
+    VerificationCache, VerificationResult,
 };
 
 // Core API types for integration with Zelana Core Sequencer
 pub use core_api::{
     CoreApiConfig, CoreApiState, CoreBatchProveRequest, CoreBatchProveResponse, CoreProofResult,
-    CoreShieldedWitness, CoreTransferWitness, CoreWithdrawalWitness, ProofCache, ProofJobState,
-    ProofJobStatus, ProofStatusEvent, SharedCoreApiState, core_api_router,
+    CoreShieldedWitness, CoreTransferWitness, CoreWithdrawalWitness, JobEventLog, ProofCache,
+    ProofCacheStats, ProofDiskCache, ProofJobState, ProofJobStatus, ProofStatusEvent,
+    SequencedEvent, SharedCoreApiState, core_api_router,
 };
@@ -31,8 +31,13 @@
 //! ## Endpoints
 //!
 //! ### Parallel Swarm (New)
-//! - `POST /batch/submit` - Submit a batch for parallel proving
+//! - `POST /batch/submit` - Submit a batch for parallel proving (JSON, or
+//!   wincode with `Content-Type: application/octet-stream`). An optional
+//!   `callback_url` on an allowed host receives the final `BatchStatus`.
 //! - `GET /batch/:id/status` - Check batch status
+//! - `POST /settle` - Settle a `BatchProofs` proved elsewhere, skipping
+//!   dispatch; progress is reported through `/batch/:id/status` (requires
+//!   `Authorization: Bearer <admin token>`)
 //! - `GET /workers` - List available workers and their status
 //!
 //! ### Legacy (Threshold Schnorr)
@@ -45,27 +50,43 @@ mod core_api;
 mod dispatcher;
 mod ownership_api;
 mod settler;
+mod shutdown;
 mod solana_client;
 
 use axum::{
     Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
+    body::Bytes,
+    extract::{DefaultBodyLimit, FromRequest, Path, Request, State},
+    http::{HeaderMap, StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 use clap::Parser;
-use core_api::{CoreApiConfig, CoreApiState, SharedCoreApiState, core_api_router};
-use dispatcher::{Batch, BatchProofs, Dispatcher, DispatcherConfig};
+use core_api::{
+    CoreApiConfig, CoreApiState, SharedCoreApiState, core_api_router, spawn_cache_sweeper,
+};
+use dispatcher::{
+    BATCH_BINARY_CONTENT_TYPE, Batch, BatchProofs, DEFAULT_WORKER_CAPACITY, Dispatcher,
+    DispatcherConfig, WorkerSlots, chunk_count, estimate_proving_ms,
+};
 use ownership_api::{
     OwnershipProverConfig, OwnershipProverState, SharedOwnershipState, ownership_api_router,
 };
+use prover_network::admin::check_admin_token;
+use prover_network::limits::{BATCH_BODY_LIMIT, CONTROL_BODY_LIMIT};
+use prover_network::logging::{LogFormat, init_tracing};
 use serde::{Deserialize, Serialize};
-use settler::{BatchSettlement, MockSettler, SettlementMode, Settler, SettlerConfig};
+use settler::{
+    BatchSettlement, DEFAULT_VK_PROGRAM_ID, MockSettler, SettlementMode, Settler, SettlerConfig,
+};
+use shutdown::BatchDrain;
+use solana_client::VerificationCache;
 use std::{collections::HashMap, sync::Arc, time::Instant};
 use tokio::sync::RwLock;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing::{error, info, warn};
+use zelana_config::Domain;
 
 // CLI Arguments
 
@@ -90,11 +111,16 @@ struct Args {
     )]
     workers: Vec<String>,
 
-    /// Transactions per chunk
-    #[arg(long, default_value = "25", env = "CHUNK_SIZE")]
+    /// Transactions per chunk (at least 1)
+    #[arg(
+        long,
+        default_value = "25",
+        env = "CHUNK_SIZE",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
     chunk_size: usize,
 
-    /// Proof timeout in milliseconds
+    /// Per-chunk proof timeout in milliseconds (a timed-out chunk is retried on another worker)
     #[arg(long, default_value = "300000", env = "PROOF_TIMEOUT_MS")]
     proof_timeout_ms: u64,
 
@@ -130,6 +156,29 @@ struct Args {
     #[arg(long, default_value = "500000", env = "COMPUTE_UNITS")]
     compute_units: u32,
 
+    /// Zelana verifier program holding the batch VK accounts
+    #[arg(long, default_value = DEFAULT_VK_PROGRAM_ID, env = "ZL_VERIFIER_PROGRAM_ID")]
+    vk_program_id: String,
+
+    /// Domain whose deployed batch VK proofs are checked against before settlement
+    #[arg(long, env = "ZL_DOMAIN")]
+    domain: Option<Domain>,
+
+    /// Canonical hash of the verifying key deployed on-chain (hex), skipping
+    /// the fetch. Without it or a domain, hashes zelana_batch.vk in the
+    /// circuit target directory.
+    #[arg(long, env = "EXPECTED_VK_HASH")]
+    expected_vk_hash: Option<String>,
+
+    /// Comma-separated hosts batch status callbacks may be sent to;
+    /// callbacks are refused when empty
+    #[arg(long, value_delimiter = ',', env = "CALLBACK_ALLOWED_HOSTS")]
+    callback_allowed_hosts: Vec<String>,
+
+    /// Simulate verification transactions before sending them
+    #[arg(long, default_value = "true", env = "SETTLEMENT_PREFLIGHT", action = clap::ArgAction::Set)]
+    preflight: bool,
+
 // Core API Configuration
     /// Enable Core API endpoints (/v2/batch/prove etc.)
     #[arg(long, default_value = "true", env = "ENABLE_CORE_API", action = clap::ArgAction::Set)]
@@ -147,6 +196,19 @@ struct Args {
     #[arg(long, default_value = "3600", env = "PROOF_CACHE_TTL_SECS")]
     proof_cache_ttl_secs: u64,
 
+    /// Directory persisting cached proofs across restarts (memory only when unset)
+    #[arg(long, env = "PROOF_CACHE_DIR")]
+    proof_cache_dir: Option<std::path::PathBuf>,
+
+    /// Proofs each cache tier (memory, disk) keeps before evicting the oldest
+    #[arg(long, default_value = "256", env = "PROOF_CACHE_MAX_ENTRIES")]
+    proof_cache_max_entries: usize,
+
+    /// Bearer token for admin endpoints (`DELETE /cache`, `POST /settle`);
+    /// disabled when unset
+    #[arg(long, env = "ADMIN_TOKEN")]
+    admin_token: Option<String>,
+
     /// Maximum concurrent proving jobs
     #[arg(long, default_value = "4", env = "MAX_CONCURRENT_JOBS")]
     max_concurrent_jobs: usize,
@@ -164,6 +226,18 @@ struct Args {
     /// Use this when running coordinator purely for Core API (sequencer integration)
     #[arg(long, default_value = "false", env = "CORE_API_ONLY", action = clap::ArgAction::Set)]
     core_api_only: bool,
+
+    /// Seconds to wait for in-flight batches on shutdown
+    #[arg(long, default_value = "120", env = "SHUTDOWN_TIMEOUT_SECS")]
+    shutdown_timeout_secs: u64,
+
+    /// File to write final batch statuses to on shutdown (JSON)
+    #[arg(long, env = "BATCH_STATUS_FILE")]
+    batch_status_file: Option<String>,
+
+    /// Log output: "pretty" (human-readable) or "json" (one object per line)
+    #[arg(long, default_value = "pretty", env = "LOG_FORMAT")]
+    log_format: LogFormat,
 }
 
 // State
@@ -179,9 +253,29 @@ pub struct BatchStatus {
     pub proving_started_at: Option<u64>,
     pub proving_completed_at: Option<u64>,
     pub settled_at: Option<u64>,
+    /// Unix time the remaining chunks should be proved by, refreshed as
+    /// chunks complete (None until the assigned workers report proving times)
+    #[serde(default)]
+    pub estimated_completion_at: Option<u64>,
     pub proofs: Option<BatchProofs>,
     pub settlement: Option<BatchSettlement>,
     pub error: Option<String>,
+    /// Where the terminal status is POSTed. Persisted to `BATCH_STATUS_FILE`;
+    /// API responses and callbacks carry a [`BatchStatusResponse`] instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
+}
+
+/// A `BatchStatus` as API responses and callbacks carry it, without the
+/// submitter's `callback_url`
+#[derive(Clone, Debug, Serialize)]
+pub struct BatchStatusResponse(BatchStatus);
+
+impl From<BatchStatus> for BatchStatusResponse {
+    fn from(mut status: BatchStatus) -> Self {
+        status.callback_url = None;
+        Self(status)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -202,9 +296,13 @@ pub struct WorkerStatus {
     pub worker_id: Option<u32>,
     pub ready: bool,
     pub active_jobs: usize,
+    pub max_concurrent_jobs: usize,
     pub total_proofs: u64,
     pub avg_proving_time_ms: u64,
     pub last_health_check: u64,
+    /// Circuit version the worker last reported loading
+    #[serde(default)]
+    pub circuit_version: Option<String>,
 }
 
 /// Coordinator state
@@ -214,6 +312,12 @@ struct CoordinatorState {
     batches: HashMap<String, BatchStatus>,
     workers: HashMap<String, WorkerStatus>,
     client: reqwest::Client,
+    /// In-flight batch tasks, drained on shutdown
+    drain: Arc<BatchDrain>,
+    /// In-flight chunks per worker, shared by every batch's dispatcher
+    slots: WorkerSlots,
+    /// On-chain verifications, shared by every batch's settler
+    verification_cache: VerificationCache,
 }
 
 type SharedState = Arc<RwLock<CoordinatorState>>;
@@ -253,15 +357,67 @@ pub struct HealthResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchSubmitRequest {
     pub batch: Batch,
+    /// URL the final `BatchStatus` is POSTed to once the batch completes or fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
+}
+
+/// Header carrying `callback_url` for binary submissions
+pub const CALLBACK_URL_HEADER: &str = "x-callback-url";
+
+/// `/batch/submit` body, decoded as wincode or JSON by `Content-Type`
+pub struct SubmittedBatch(pub BatchSubmitRequest);
+
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequest<S> for SubmittedBatch {
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let binary = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with(BATCH_BINARY_CONTENT_TYPE));
+
+        if !binary {
+            let Json(request) = Json::<BatchSubmitRequest>::from_request(req, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            return Ok(Self(request));
+        }
+
+        let callback_url = req
+            .headers()
+            .get(CALLBACK_URL_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        let batch = Batch::from_wincode(&bytes).map_err(|e| {
+            let body = ApiResponse::<BatchSubmitResponse>::error(format!("invalid batch: {}", e));
+            (StatusCode::BAD_REQUEST, Json(body)).into_response()
+        })?;
+        Ok(Self(BatchSubmitRequest {
+            batch,
+            callback_url,
+        }))
+    }
 }
 
 /// Batch submit response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchSubmitResponse {
     pub batch_id: String,
+    /// Hex `Batch::content_hash` of the batch as received
+    pub content_hash: String,
     pub chunks: usize,
     pub workers_assigned: usize,
     pub status: BatchState,
+    /// Unix time proving should finish by, from the ready workers' average
+    /// proving times
+    #[serde(default)]
+    pub estimated_completion_at: Option<u64>,
 }
 
 /// Workers list response
@@ -276,16 +432,11 @@ pub struct WorkersResponse {
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "prover_coordinator=debug,tower_http=debug".into()),
-        )
-        .init();
-
     let args = Args::parse();
 
+    // Initialize tracing
+    init_tracing(args.log_format, "prover_coordinator=debug,tower_http=debug");
+
     info!(
         "Starting Parallel Swarm Coordinator on {}:{}",
         args.host, args.port
@@ -313,9 +464,11 @@ async fn main() -> anyhow::Result<()> {
                         worker_id: None,
                         ready: false,
                         active_jobs: 0,
+                        max_concurrent_jobs: DEFAULT_WORKER_CAPACITY,
                         total_proofs: 0,
                         avg_proving_time_ms: 0,
                         last_health_check: 0,
+                        circuit_version: None,
                     },
                 )
             })
@@ -327,6 +480,9 @@ async fn main() -> anyhow::Result<()> {
         batches: HashMap::new(),
         workers,
         client: reqwest::Client::new(),
+        drain: Arc::new(BatchDrain::new()),
+        slots: WorkerSlots::new(),
+        verification_cache: VerificationCache::default(),
     }));
 
     // Spawn background task to check worker health (only in swarm mode)
@@ -351,10 +507,18 @@ async fn main() -> anyhow::Result<()> {
         // Health
         .route("/health", get(health_handler))
         // Parallel Swarm endpoints
-        .route("/batch/submit", post(batch_submit_handler))
+        .route(
+            "/batch/submit",
+            post(batch_submit_handler).layer(DefaultBodyLimit::max(BATCH_BODY_LIMIT)),
+        )
         .route("/batch/:batch_id/status", get(batch_status_handler))
+        .route(
+            "/settle",
+            post(settle_handler).layer(DefaultBodyLimit::max(BATCH_BODY_LIMIT)),
+        )
         .route("/workers", get(workers_handler))
-        .with_state(state);
+        .layer(DefaultBodyLimit::max(CONTROL_BODY_LIMIT))
+        .with_state(state.clone());
 
     // Create the final app, optionally merging Core API
     let app = if args.enable_core_api {
@@ -378,11 +542,15 @@ async fn main() -> anyhow::Result<()> {
             mock_prover: args.mock_prover,
             mock_delay_ms: args.mock_prover_delay_ms,
             cache_ttl_secs: args.proof_cache_ttl_secs,
+            proof_cache_dir: args.proof_cache_dir.clone(),
+            max_cached_proofs: args.proof_cache_max_entries,
+            admin_token: args.admin_token.clone(),
             max_concurrent_jobs: args.max_concurrent_jobs,
         };
 
         let core_api_state: SharedCoreApiState =
             Arc::new(tokio::sync::RwLock::new(CoreApiState::new(core_api_config)));
+        spawn_cache_sweeper(core_api_state.clone());
 
         info!(
             "Core API enabled with {} max concurrent jobs, cache TTL {}s",
@@ -437,11 +605,84 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Coordinator listening on {}", addr);
 
-    axum::serve(listener, app).await?;
+    // Keep serving status queries while in-flight batches drain
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            drain_batches(&state).await;
+        })
+        .await?;
 
+    info!("Coordinator stopped");
     Ok(())
 }
 
+/// Resolves on Ctrl+C or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to install SIGTERM handler: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    info!("Shutdown signal received, no longer accepting batches");
+}
+
+/// Wait for in-flight batches, fail any left over and persist final statuses
+async fn drain_batches(state: &SharedState) {
+    let (drain, timeout, status_file) = {
+        let coord_state = state.read().await;
+        (
+            coord_state.drain.clone(),
+            std::time::Duration::from_secs(coord_state.config.shutdown_timeout_secs),
+            coord_state.config.batch_status_file.clone(),
+        )
+    };
+
+    let remaining = drain.drain(timeout).await;
+
+    let mut coord_state = state.write().await;
+    if remaining > 0 {
+        for status in coord_state.batches.values_mut() {
+            if status.state != BatchState::Completed && status.state != BatchState::Failed {
+                status.state = BatchState::Failed;
+                status.error = Some("Coordinator shut down before batch finished".to_string());
+            }
+        }
+    }
+
+    if let Some(path) = status_file {
+        let result = serde_json::to_vec_pretty(&coord_state.batches)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| std::fs::write(&path, json).map_err(anyhow::Error::from));
+        match result {
+            Ok(()) => info!(
+                "Persisted {} batch status(es) to {}",
+                coord_state.batches.len(),
+                path
+            ),
+            Err(e) => error!("Failed to persist batch statuses to {}: {}", path, e),
+        }
+    }
+}
+
 // Handlers
 
 /// Health check
@@ -472,10 +713,31 @@ async fn health_handler(State(state): State<SharedState>) -> Json<ApiResponse<He
 /// Submit a batch for parallel proving
 async fn batch_submit_handler(
     State(state): State<SharedState>,
-    Json(request): Json<BatchSubmitRequest>,
-) -> Result<Json<ApiResponse<BatchSubmitResponse>>, StatusCode> {
-    let batch = request.batch;
+    SubmittedBatch(request): SubmittedBatch,
+) -> Result<
+    Json<ApiResponse<BatchSubmitResponse>>,
+    (StatusCode, Json<ApiResponse<BatchSubmitResponse>>),
+> {
+    let BatchSubmitRequest {
+        batch,
+        callback_url,
+    } = request;
     let batch_id = batch.batch_id.clone();
+    let content_hash = batch.content_hash();
+
+    // Malformed batches would otherwise only fail inside a worker
+    if let Err(e) = batch.validate() {
+        warn!("Rejecting batch {}: {}", batch_id, e);
+        return Err((StatusCode::BAD_REQUEST, Json(ApiResponse::error(e))));
+    }
+    let allowed_hosts = state.read().await.config.callback_allowed_hosts.clone();
+    let checked = callback_url
+        .as_deref()
+        .map(|url| check_callback_url(url, &allowed_hosts));
+    if let Some(Err(e)) = checked {
+        warn!("Rejecting batch {}: {}", batch_id, e);
+        return Err((StatusCode::BAD_REQUEST, Json(ApiResponse::error(e))));
+    }
 
     info!(
         "Received batch {} with {} transactions",
@@ -484,8 +746,13 @@ async fn batch_submit_handler(
     );
 
     // Get config
-    let (config, workers, client) = {
+    let (config, workers, client, drain) = {
         let coord_state = state.read().await;
+        if !coord_state.drain.is_accepting() {
+            warn!("Rejecting batch {}: coordinator is shutting down", batch_id);
+            return Err(shutting_down());
+        }
+
         let ready_workers: Vec<String> = coord_state
             .workers
             .values()
@@ -501,17 +768,28 @@ async fn batch_submit_handler(
             coord_state.config.clone(),
             ready_workers,
             coord_state.client.clone(),
+            coord_state.drain.clone(),
         )
     };
 
     // Calculate chunks
-    let num_chunks = (batch.transactions.len() + config.chunk_size - 1) / config.chunk_size;
+    let Some(num_chunks) = chunk_count(batch.transactions.len(), config.chunk_size) else {
+        error!("Invalid chunk size {}", config.chunk_size);
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error("Invalid chunk size")),
+        ));
+    };
 
     // Create batch status
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_secs();
+    let estimated_completion_at = {
+        let coord_state = state.read().await;
+        estimate_completion_at(&coord_state.workers, &workers, num_chunks)
+    };
 
     let status = BatchStatus {
         batch_id: batch_id.clone(),
@@ -522,9 +800,11 @@ async fn batch_submit_handler(
         proving_started_at: None,
         proving_completed_at: None,
         settled_at: None,
+        estimated_completion_at,
         proofs: None,
         settlement: None,
         error: None,
+        callback_url,
     };
 
     // Store batch status
@@ -536,30 +816,190 @@ async fn batch_submit_handler(
     // Calculate workers assigned before moving
     let workers_assigned = std::cmp::min(num_chunks, workers.len());
 
-    // Spawn async task to process the batch
+    // Spawn async task to process the batch (tracked for shutdown drain)
     let state_clone = state.clone();
     let batch_clone = batch.clone();
-    tokio::spawn(async move {
+    let spawned = drain.spawn(async move {
         process_batch(state_clone, batch_clone, config, workers, client).await;
     });
+    if spawned.is_err() {
+        // Shutdown began after the check above
+        state.write().await.batches.remove(&batch_id);
+        return Err(shutting_down());
+    }
 
     Ok(Json(ApiResponse::success(BatchSubmitResponse {
         batch_id,
+        content_hash: hex::encode(content_hash),
         chunks: num_chunks,
         workers_assigned,
         status: BatchState::Pending,
+        estimated_completion_at,
     })))
 }
 
+/// Settle proofs generated elsewhere (e.g. an offline GPU prover)
+///
+/// Skips slicing and dispatch: no worker is contacted. The batch is tracked
+/// like a submitted one, so `/batch/:id/status` reports the settlement, and
+/// the terminal status goes to the `x-callback-url` header's URL (or the one
+/// the batch was first submitted with). Requires the admin token, since it
+/// spends the settler's funds.
+async fn settle_handler(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(proofs): Json<BatchProofs>,
+) -> Result<
+    Json<ApiResponse<BatchStatusResponse>>,
+    (StatusCode, Json<ApiResponse<BatchStatusResponse>>),
+> {
+    let admin_token = state.read().await.config.admin_token.clone();
+    if let Err(e) = check_admin_token(admin_token.as_deref(), &headers) {
+        warn!(
+            "Rejecting settlement of batch {}: {}",
+            proofs.batch_id,
+            e.message()
+        );
+        return Err((e.status(), Json(ApiResponse::error(e.message()))));
+    }
+
+    let batch_id = proofs.batch_id.clone();
+
+    if proofs.proofs.is_empty() {
+        warn!("Rejecting settlement of batch {}: no proofs", batch_id);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error("No proofs to settle")),
+        ));
+    }
+
+    let (config, drain) = {
+        let coord_state = state.read().await;
+        if !coord_state.drain.is_accepting() {
+            warn!(
+                "Rejecting settlement of batch {}: coordinator is shutting down",
+                batch_id
+            );
+            return Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiResponse::error("Coordinator is shutting down")),
+            ));
+        }
+        (coord_state.config.clone(), coord_state.drain.clone())
+    };
+
+    let callback_url = headers
+        .get(CALLBACK_URL_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let checked = callback_url
+        .as_deref()
+        .map(|url| check_callback_url(url, &config.callback_allowed_hosts));
+    if let Some(Err(e)) = checked {
+        warn!("Rejecting settlement of batch {}: {}", batch_id, e);
+        return Err((StatusCode::BAD_REQUEST, Json(ApiResponse::error(e))));
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut status = BatchStatus {
+        batch_id: batch_id.clone(),
+        state: BatchState::Settling,
+        chunks_total: proofs.proofs.len(),
+        chunks_proved: proofs.proofs.len(),
+        submitted_at: now,
+        proving_started_at: None,
+        proving_completed_at: None,
+        settled_at: None,
+        estimated_completion_at: None,
+        proofs: Some(proofs.clone()),
+        settlement: None,
+        error: None,
+        callback_url,
+    };
+
+    {
+        let mut coord_state = state.write().await;
+        let previous = coord_state.batches.get(&batch_id);
+        let in_flight = previous
+            .is_some_and(|b| b.state != BatchState::Completed && b.state != BatchState::Failed);
+        if status.callback_url.is_none() {
+            status.callback_url = previous.and_then(|b| b.callback_url.clone());
+        }
+        if in_flight {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(format!(
+                    "Batch {} is already in flight",
+                    batch_id
+                ))),
+            ));
+        }
+        coord_state.batches.insert(batch_id.clone(), status.clone());
+    }
+
+    info!(
+        "Settling pre-proved batch {} ({} proofs)",
+        batch_id,
+        proofs.proofs.len()
+    );
+
+    let state_clone = state.clone();
+    let task_batch_id = batch_id.clone();
+    let spawned = drain.spawn(async move {
+        let result = settle_proofs(&state_clone, &config, &proofs).await;
+        record_settlement(&state_clone, &task_batch_id, result).await;
+        notify_callback(&state_clone, &task_batch_id).await;
+    });
+    if spawned.is_err() {
+        // Shutdown began after the check above
+        state.write().await.batches.remove(&batch_id);
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::error("Coordinator is shutting down")),
+        ));
+    }
+
+    Ok(Json(ApiResponse::success(status.into())))
+}
+
+/// Unix time by which `chunks` more chunks should be proved on `worker_urls`,
+/// given the workers' current average proving times
+fn estimate_completion_at(
+    workers: &HashMap<String, WorkerStatus>,
+    worker_urls: &[String],
+    chunks: usize,
+) -> Option<u64> {
+    let avgs: Vec<u64> = worker_urls
+        .iter()
+        .map(|url| workers.get(url).map_or(0, |w| w.avg_proving_time_ms))
+        .collect();
+    let ms = estimate_proving_ms(chunks, &avgs)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    Some(now + ms.div_ceil(1000))
+}
+
+fn shutting_down() -> (StatusCode, Json<ApiResponse<BatchSubmitResponse>>) {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(ApiResponse::error("Coordinator is shutting down")),
+    )
+}
+
 /// Get batch status
 async fn batch_status_handler(
     State(state): State<SharedState>,
     Path(batch_id): Path<String>,
-) -> Json<ApiResponse<BatchStatus>> {
+) -> Json<ApiResponse<BatchStatusResponse>> {
     let coord_state = state.read().await;
 
     match coord_state.batches.get(&batch_id) {
-        Some(status) => Json(ApiResponse::success(status.clone())),
+        Some(status) => Json(ApiResponse::success(status.clone().into())),
         None => Json(ApiResponse::error(format!("Batch {} not found", batch_id))),
     }
 }
@@ -580,7 +1020,9 @@ async fn workers_handler(State(state): State<SharedState>) -> Json<ApiResponse<W
 
 // Background Tasks
 
-/// Process a batch: slice, dispatch, collect, settle
+/// Process a batch: slice, dispatch, collect, settle, then report the final
+/// status to the batch's `callback_url` if one was given
+#[tracing::instrument(skip_all, fields(batch_id = %batch.batch_id))]
 async fn process_batch(
     state: SharedState,
     batch: Batch,
@@ -602,11 +1044,13 @@ async fn process_batch(
     info!("Processing batch {}: slicing into chunks", batch_id);
 
     // Create dispatcher
+    let slots = state.read().await.slots.clone();
     let dispatcher = Dispatcher::new(DispatcherConfig {
         worker_urls: workers.clone(),
         chunk_size: config.chunk_size,
         client: client.clone(),
         proof_timeout_ms: config.proof_timeout_ms,
+        slots,
     });
 
     // Update state: Proving
@@ -623,8 +1067,31 @@ async fn process_batch(
         }
     }
 
-    // Dispatch batch and collect proofs
-    match dispatcher.dispatch_batch(&batch, config.chunk_size).await {
+    // Dispatch batch and collect proofs, refreshing progress and the
+    // estimate as each chunk lands
+    let (proved_tx, mut proved_rx) = tokio::sync::mpsc::unbounded_channel();
+    let dispatch = dispatcher.dispatch_batch_with_progress(&batch, config.chunk_size, move |_| {
+        let _ = proved_tx.send(());
+    });
+    let track = async {
+        while proved_rx.recv().await.is_some() {
+            let mut coord_state = state.write().await;
+            let CoordinatorState {
+                batches,
+                workers: worker_stats,
+                ..
+            } = &mut *coord_state;
+            if let Some(status) = batches.get_mut(&batch_id) {
+                status.chunks_proved += 1;
+                let remaining = status.chunks_total.saturating_sub(status.chunks_proved);
+                status.estimated_completion_at =
+                    estimate_completion_at(worker_stats, &workers, remaining);
+            }
+        }
+    };
+    let (result, ()) = tokio::join!(dispatch, track);
+
+    match result {
         Ok(proofs) => {
             info!(
                 "Batch {} proved: {} chunks in {}ms",
@@ -650,54 +1117,8 @@ async fn process_batch(
             }
 
             // Settle on Solana
-            let settlement_result = if config.mock_settlement {
-                let settler = MockSettler::new(100);
-                settler.settle_batch(&proofs).await
-            } else {
-                let mut settler = Settler::new(
-                    SettlerConfig {
-                        rpc_url: config.solana_rpc.clone(),
-                        program_id: config.program_id.clone(),
-                        keypair_path: config.keypair_path.clone(),
-                        circuit_target_path: config
-                            .circuit_target_path
-                            .as_ref()
-                            .map(|p| std::path::PathBuf::from(p)),
-                        compute_units: config.compute_units,
-                    },
-                    SettlementMode::Batched,
-                );
-                settler.settle_batch(&proofs).await
-            };
-
-            match settlement_result {
-                Ok(settlement) => {
-                    info!(
-                        "Batch {} settled: {}ms, tx: {:?}",
-                        batch_id, settlement.settlement_time_ms, settlement.batched_tx_signature
-                    );
-
-                    let mut coord_state = state.write().await;
-                    if let Some(status) = coord_state.batches.get_mut(&batch_id) {
-                        status.settlement = Some(settlement);
-                        status.state = BatchState::Completed;
-                        status.settled_at = Some(
-                            std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs(),
-                        );
-                    }
-                }
-                Err(e) => {
-                    error!("Batch {} settlement failed: {}", batch_id, e);
-                    let mut coord_state = state.write().await;
-                    if let Some(status) = coord_state.batches.get_mut(&batch_id) {
-                        status.state = BatchState::Failed;
-                        status.error = Some(format!("Settlement failed: {}", e));
-                    }
-                }
-            }
+            let settlement_result = settle_proofs(&state, &config, &proofs).await;
+            record_settlement(&state, &batch_id, settlement_result).await;
         }
         Err(e) => {
             error!("Batch {} proving failed: {}", batch_id, e);
@@ -711,6 +1132,158 @@ async fn process_batch(
 
     let total_time = start.elapsed();
     info!("Batch {} processing complete in {:?}", batch_id, total_time);
+
+    notify_callback(&state, &batch_id).await;
+}
+
+/// Settle proofs with the mock or Solana settler, as configured
+async fn settle_proofs(
+    state: &SharedState,
+    config: &Args,
+    proofs: &BatchProofs,
+) -> Result<BatchSettlement, String> {
+    if config.mock_settlement {
+        let settler = MockSettler::new(100);
+        settler.settle_batch(proofs).await
+    } else {
+        let verification_cache = state.read().await.verification_cache.clone();
+        let mut settler = Settler::new(
+            SettlerConfig {
+                rpc_url: config.solana_rpc.clone(),
+                program_id: config.program_id.clone(),
+                keypair_path: config.keypair_path.clone(),
+                circuit_target_path: config
+                    .circuit_target_path
+                    .as_ref()
+                    .map(|p| std::path::PathBuf::from(p)),
+                compute_units: config.compute_units,
+                vk_program_id: config.vk_program_id.clone(),
+                domain: config.domain,
+                expected_vk_hash: config.expected_vk_hash.clone(),
+                verification_cache,
+                preflight: config.preflight,
+            },
+            SettlementMode::Batched,
+        );
+        settler.settle_batch(proofs).await
+    }
+}
+
+/// Move a settling batch to its terminal state
+async fn record_settlement(
+    state: &SharedState,
+    batch_id: &str,
+    settlement_result: Result<BatchSettlement, String>,
+) {
+    match settlement_result {
+        Ok(settlement) => {
+            info!(
+                "Batch {} settled: {}ms, tx: {:?}",
+                batch_id, settlement.settlement_time_ms, settlement.batched_tx_signature
+            );
+
+            let mut coord_state = state.write().await;
+            if let Some(status) = coord_state.batches.get_mut(batch_id) {
+                status.settlement = Some(settlement);
+                status.state = BatchState::Completed;
+                status.settled_at = Some(
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs(),
+                );
+            }
+        }
+        Err(e) => {
+            error!("Batch {} settlement failed: {}", batch_id, e);
+            let mut coord_state = state.write().await;
+            if let Some(status) = coord_state.batches.get_mut(batch_id) {
+                status.state = BatchState::Failed;
+                status.error = Some(format!("Settlement failed: {}", e));
+            }
+        }
+    }
+}
+
+/// Attempts at delivering a batch status callback
+const CALLBACK_ATTEMPTS: u32 = 5;
+
+/// Delay before the first callback retry, doubled on each further one
+const CALLBACK_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Check a callback URL is http(s) on one of the allowed hosts
+///
+/// Callbacks are sent from inside the coordinator's network, so an open
+/// target would let any submitter probe internal services.
+fn check_callback_url(url: &str, allowed_hosts: &[String]) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("invalid callback_url: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!(
+            "callback_url scheme {} is not http(s)",
+            parsed.scheme()
+        ));
+    }
+    let host = parsed.host_str().unwrap_or_default();
+    if !allowed_hosts.iter().any(|h| h.eq_ignore_ascii_case(host)) {
+        return Err(format!("callback_url host {} is not allowed", host));
+    }
+    Ok(())
+}
+
+/// Send a batch's terminal status to its callback URL, if it has one
+async fn notify_callback(state: &SharedState, batch_id: &str) {
+    let status = state.read().await.batches.get(batch_id).cloned();
+    let Some(status) = status else { return };
+    if let Some(url) = &status.callback_url {
+        deliver_callback(url, &status).await;
+    }
+}
+
+/// POST a terminal batch status to the submitter, retrying failed deliveries
+///
+/// Redirects are not followed, so an allowed host can't bounce the request
+/// elsewhere. Returns whether the callback was acknowledged with a 2xx.
+async fn deliver_callback(url: &str, status: &BatchStatus) -> bool {
+    let client = match reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Batch {} callback client failed: {}", status.batch_id, e);
+            return false;
+        }
+    };
+    let body = BatchStatusResponse::from(status.clone());
+    let mut delay = CALLBACK_RETRY_DELAY;
+    for attempt in 1..=CALLBACK_ATTEMPTS {
+        let result = client.post(url).json(&body).send().await;
+        match result {
+            Ok(response) if response.status().is_success() => {
+                info!("Batch {} status delivered to {}", status.batch_id, url);
+                return true;
+            }
+            Ok(response) => warn!(
+                "Batch {} callback to {} returned {} (attempt {}/{})",
+                status.batch_id,
+                url,
+                response.status(),
+                attempt,
+                CALLBACK_ATTEMPTS
+            ),
+            Err(e) => warn!(
+                "Batch {} callback to {} failed: {} (attempt {}/{})",
+                status.batch_id, url, e, attempt, CALLBACK_ATTEMPTS
+            ),
+        }
+        if attempt < CALLBACK_ATTEMPTS {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+    error!("Giving up on batch {} callback to {}", status.batch_id, url);
+    false
 }
 
 /// Check health of all workers
@@ -737,12 +1310,23 @@ async fn check_worker_health(state: SharedState) {
                     if let Ok(health) = response.json::<ApiResponse<WorkerHealthResponse>>().await {
                         if let ApiResponse::Success { data } = health {
                             let mut coord_state = state.write().await;
+                            coord_state
+                                .slots
+                                .set_capacity(&worker_url, data.max_concurrent_jobs);
                             if let Some(worker) = coord_state.workers.get_mut(&worker_url) {
                                 worker.ready = data.ready;
                                 worker.worker_id = Some(data.worker_id);
                                 worker.active_jobs = data.active_jobs;
+                                worker.max_concurrent_jobs = data.max_concurrent_jobs;
                                 worker.total_proofs = data.total_proofs;
                                 worker.avg_proving_time_ms = data.avg_proving_time_ms;
+                                if worker.circuit_version != data.circuit_version {
+                                    info!(
+                                        "Worker {} circuit version: {:?} -> {:?}",
+                                        worker_url, worker.circuit_version, data.circuit_version
+                                    );
+                                    worker.circuit_version = data.circuit_version;
+                                }
                                 worker.last_health_check = std::time::SystemTime::now()
                                     .duration_since(std::time::UNIX_EPOCH)
                                     .unwrap()
@@ -778,4 +1362,407 @@ struct WorkerHealthResponse {
     max_concurrent_jobs: usize,
     total_proofs: u64,
     avg_proving_time_ms: u64,
+    #[serde(default)]
+    circuit_version: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_size_must_be_positive() {
+        let args = Args::try_parse_from(["prover-coordinator"]).unwrap();
+        assert_eq!(args.chunk_size, 25);
+
+        assert!(Args::try_parse_from(["prover-coordinator", "--chunk-size", "0"]).is_err());
+        let args = Args::try_parse_from(["prover-coordinator", "--chunk-size", "1"]).unwrap();
+        assert_eq!(args.chunk_size, 1);
+    }
+
+    async fn extract(content_type: &str, body: Vec<u8>) -> Result<Batch, StatusCode> {
+        let req = Request::builder()
+            .header(CONTENT_TYPE, content_type)
+            .body(axum::body::Body::from(body))
+            .unwrap();
+        SubmittedBatch::from_request(req, &())
+            .await
+            .map(|SubmittedBatch(request)| request.batch)
+            .map_err(|r| r.status())
+    }
+
+    /// One-transaction batch with hex fields in canonical `0x` form
+    fn test_batch() -> Batch {
+        let hex32 = |b: &str| format!("0x{}", b.repeat(32));
+        Batch {
+            batch_id: "batch-1".to_string(),
+            initial_root: hex32("00"),
+            transactions: vec![dispatcher::BatchTransaction {
+                sender_pubkey: hex32("11"),
+                receiver_pubkey: hex32("22"),
+                amount: 100,
+                signature: "0xsig".to_string(),
+                merkle_path: vec![hex32("33"); 4],
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_submit_json_and_wincode_agree() {
+        let batch = test_batch();
+
+        let json = serde_json::to_vec(&BatchSubmitRequest {
+            batch: batch.clone(),
+            callback_url: None,
+        })
+        .unwrap();
+        let from_json = extract("application/json", json).await.unwrap();
+        let from_wincode = extract(BATCH_BINARY_CONTENT_TYPE, batch.to_wincode().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(from_json.content_hash(), batch.content_hash());
+        assert_eq!(from_wincode.content_hash(), batch.content_hash());
+
+        // A client writing hex without `0x` gets the same hash either way
+        let mut unprefixed = batch.clone();
+        unprefixed.initial_root = "00".repeat(32);
+        unprefixed.transactions[0].sender_pubkey = "11".repeat(32);
+        let json = serde_json::to_vec(&BatchSubmitRequest {
+            batch: unprefixed.clone(),
+            callback_url: None,
+        })
+        .unwrap();
+        let from_json = extract("application/json", json).await.unwrap();
+        let from_wincode = extract(BATCH_BINARY_CONTENT_TYPE, unprefixed.to_wincode().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(from_json.content_hash(), from_wincode.content_hash());
+        assert_eq!(from_wincode.content_hash(), batch.content_hash());
+        assert_eq!(
+            extract(BATCH_BINARY_CONTENT_TYPE, b"garbage".to_vec())
+                .await
+                .unwrap_err(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn test_callback_receives_terminal_status() {
+        use std::sync::Mutex;
+
+        // Mock callback server that rejects the first delivery
+        let received: Arc<Mutex<Vec<BatchStatus>>> = Arc::default();
+        let app = Router::new().route(
+            "/callback",
+            post({
+                let received = received.clone();
+                move |Json(status): Json<BatchStatus>| {
+                    let received = received.clone();
+                    async move {
+                        let mut received = received.lock().unwrap();
+                        received.push(status);
+                        if received.len() == 1 {
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        } else {
+                            StatusCode::OK
+                        }
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let callback_url = format!("http://{}/callback", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = Args::try_parse_from([
+            "prover-coordinator",
+            "--callback-allowed-hosts",
+            "127.0.0.1",
+        ])
+        .unwrap();
+        assert!(check_callback_url(&callback_url, &config.callback_allowed_hosts).is_ok());
+        let batch = test_batch();
+        let state: SharedState = Arc::new(RwLock::new(CoordinatorState {
+            config: config.clone(),
+            batches: HashMap::new(),
+            workers: HashMap::new(),
+            client: reqwest::Client::new(),
+            drain: Arc::new(BatchDrain::new()),
+            slots: WorkerSlots::new(),
+            verification_cache: VerificationCache::default(),
+        }));
+        state.write().await.batches.insert(
+            batch.batch_id.clone(),
+            BatchStatus {
+                batch_id: batch.batch_id.clone(),
+                state: BatchState::Pending,
+                chunks_total: 1,
+                chunks_proved: 0,
+                submitted_at: 0,
+                proving_started_at: None,
+                proving_completed_at: None,
+                settled_at: None,
+                estimated_completion_at: None,
+                proofs: None,
+                settlement: None,
+                error: None,
+                callback_url: Some(callback_url),
+            },
+        );
+
+        // Nothing listens on the worker port, so proving fails
+        process_batch(
+            state,
+            batch,
+            config,
+            vec!["http://127.0.0.1:1".to_string()],
+            reqwest::Client::new(),
+        )
+        .await;
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2, "rejected delivery should be retried");
+        assert_eq!(received[1].state, BatchState::Failed);
+        assert!(
+            received[1]
+                .error
+                .as_deref()
+                .unwrap()
+                .starts_with("Proving failed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_file_keeps_callback_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("batches.json");
+        let config = Args::try_parse_from([
+            "prover-coordinator",
+            "--batch-status-file",
+            path.to_str().unwrap(),
+        ])
+        .unwrap();
+        let status = BatchStatus {
+            batch_id: "batch-1".to_string(),
+            state: BatchState::Completed,
+            chunks_total: 1,
+            chunks_proved: 1,
+            submitted_at: 0,
+            proving_started_at: None,
+            proving_completed_at: None,
+            settled_at: None,
+            estimated_completion_at: None,
+            proofs: None,
+            settlement: None,
+            error: None,
+            callback_url: Some("http://127.0.0.1:9/callback".to_string()),
+        };
+        let state: SharedState = Arc::new(RwLock::new(CoordinatorState {
+            config,
+            batches: HashMap::from([(status.batch_id.clone(), status.clone())]),
+            workers: HashMap::new(),
+            client: reqwest::Client::new(),
+            drain: Arc::new(BatchDrain::new()),
+            slots: WorkerSlots::new(),
+            verification_cache: VerificationCache::default(),
+        }));
+
+        drain_batches(&state).await;
+
+        let persisted: HashMap<String, BatchStatus> =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(persisted["batch-1"].callback_url, status.callback_url);
+
+        // Responses never echo it back
+        let response = serde_json::to_value(BatchStatusResponse::from(status)).unwrap();
+        assert_eq!(response["batch_id"], "batch-1");
+        assert!(response.get("callback_url").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_settle_endpoint_bypasses_workers() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Mock worker that counts every request it receives
+        let worker_hits = Arc::new(AtomicUsize::new(0));
+        let worker_app = Router::new().fallback({
+            let worker_hits = worker_hits.clone();
+            move || {
+                let worker_hits = worker_hits.clone();
+                async move {
+                    worker_hits.fetch_add(1, Ordering::SeqCst);
+                    StatusCode::OK
+                }
+            }
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let worker_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, worker_app).await.unwrap() });
+
+        // Mock callback server recording terminal statuses
+        let callbacks: Arc<std::sync::Mutex<Vec<BatchStatus>>> = Arc::default();
+        let callback_app = Router::new().route(
+            "/callback",
+            post({
+                let callbacks = callbacks.clone();
+                move |Json(status): Json<BatchStatus>| {
+                    let callbacks = callbacks.clone();
+                    async move {
+                        callbacks.lock().unwrap().push(status);
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let callback_url = format!("http://{}/callback", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, callback_app).await.unwrap() });
+
+        let config = Args::try_parse_from([
+            "prover-coordinator",
+            "--admin-token",
+            "secret",
+            "--callback-allowed-hosts",
+            "127.0.0.1",
+        ])
+        .unwrap();
+        assert!(config.mock_settlement);
+        let worker = WorkerStatus {
+            url: worker_url.clone(),
+            worker_id: Some(1),
+            ready: true,
+            active_jobs: 0,
+            max_concurrent_jobs: DEFAULT_WORKER_CAPACITY,
+            total_proofs: 0,
+            avg_proving_time_ms: 0,
+            last_health_check: 0,
+            circuit_version: None,
+        };
+        let state: SharedState = Arc::new(RwLock::new(CoordinatorState {
+            config,
+            batches: HashMap::new(),
+            workers: HashMap::from([(worker_url, worker)]),
+            client: reqwest::Client::new(),
+            drain: Arc::new(BatchDrain::new()),
+            slots: WorkerSlots::new(),
+            verification_cache: VerificationCache::default(),
+        }));
+
+        let app = Router::new()
+            .route("/settle", post(settle_handler))
+            .with_state(state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let settle_url = format!("http://{}/settle", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // Proved offline, e.g. on a GPU box
+        let proofs = BatchProofs {
+            batch_id: "offline-1".to_string(),
+            proofs: vec![dispatcher::ChunkProof {
+                chunk_id: 0,
+                worker_id: 0,
+                proof: "deadbeef".to_string(),
+                public_inputs: vec!["0x1".to_string()],
+                vk_hash: "ab".repeat(32),
+                proving_time_ms: 5_000,
+            }],
+            total_time_ms: 5_000,
+            workers_used: 1,
+        };
+        let client = reqwest::Client::new();
+
+        // Settling spends the settler's funds: admin only
+        for auth in [None, Some("Bearer wrong")] {
+            let mut request = client.post(&settle_url).json(&proofs);
+            if let Some(auth) = auth {
+                request = request.header("authorization", auth);
+            }
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+        assert!(state.read().await.batches.is_empty());
+
+        // Callbacks may only target allowed hosts
+        for url in ["http://169.254.169.254/latest", "file:///etc/passwd"] {
+            let response = client
+                .post(&settle_url)
+                .bearer_auth("secret")
+                .header(CALLBACK_URL_HEADER, url)
+                .json(&proofs)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+        assert!(state.read().await.batches.is_empty());
+
+        let response: ApiResponse<BatchStatus> = client
+            .post(&settle_url)
+            .bearer_auth("secret")
+            .header(CALLBACK_URL_HEADER, &callback_url)
+            .json(&proofs)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let ApiResponse::Success { data: accepted } = response else {
+            panic!("settlement was not accepted");
+        };
+        assert_eq!(accepted.state, BatchState::Settling);
+
+        let status = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let status = state.read().await.batches["offline-1"].clone();
+                if status.state != BatchState::Settling {
+                    return status;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("settlement should finish");
+
+        assert_eq!(status.state, BatchState::Completed);
+        let settlement = status.settlement.unwrap();
+        assert!(settlement.all_verified);
+        assert!(
+            settlement
+                .batched_tx_signature
+                .unwrap()
+                .starts_with("mock_batch_")
+        );
+        assert_eq!(worker_hits.load(Ordering::SeqCst), 0);
+
+        // The submitter hears about the settlement too
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while callbacks.lock().unwrap().is_empty() {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("callback should be delivered");
+        let delivered = callbacks.lock().unwrap()[0].clone();
+        assert_eq!(delivered.batch_id, "offline-1");
+        assert_eq!(delivered.state, BatchState::Completed);
+        assert!(delivered.callback_url.is_none());
+
+        // Nothing to settle
+        let empty = BatchProofs {
+            batch_id: "offline-2".to_string(),
+            proofs: vec![],
+            total_time_ms: 0,
+            workers_used: 0,
+        };
+        let response = client
+            .post(&settle_url)
+            .bearer_auth("secret")
+            .json(&empty)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
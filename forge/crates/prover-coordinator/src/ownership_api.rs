@@ -14,7 +14,13 @@
 //! - nullifier: The unique nullifier for this note
 //! - blinded_proxy: For delegated Merkle path fetching
 
-use axum::{Json, Router, extract::State, http::StatusCode, routing::post};
+use axum::{
+    Json, Router,
+    extract::{DefaultBodyLimit, State},
+    http::StatusCode,
+    routing::post,
+};
+use prover_network::limits::{CONTROL_BODY_LIMIT, PROVE_BODY_LIMIT};
 use serde::{Deserialize, Serialize};
 use std::{path::PathBuf, process::Stdio, sync::Arc, time::Instant};
 use tokio::{process::Command, sync::RwLock};
@@ -139,8 +145,12 @@ impl OwnershipProverState {
 /// Create the ownership API router
 pub fn ownership_api_router(state: SharedOwnershipState) -> Router {
     Router::new()
-        .route("/v2/ownership/prove", post(prove_handler))
+        .route(
+            "/v2/ownership/prove",
+            post(prove_handler).layer(DefaultBodyLimit::max(PROVE_BODY_LIMIT)),
+        )
         .route("/v2/ownership/health", axum::routing::get(health_handler))
+        .layer(DefaultBodyLimit::max(CONTROL_BODY_LIMIT))
         .with_state(state)
 }
 
@@ -7,13 +7,16 @@
 //! 2. Real mode - Uses the SolanaVerifierClient to submit proofs on-chain
 
 use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{error, info, warn};
+use zelana_config::Domain;
 
 use crate::dispatcher::{BatchProofs, ChunkProof};
 use crate::solana_client::{
-    ProofData, SolanaClientError, SolanaVerifierClient, SolanaVerifierConfig,
+    ProofData, SolanaClientError, SolanaVerifierClient, SolanaVerifierConfig, VerificationCache,
 };
 
 // Types
@@ -31,6 +34,19 @@ pub struct SettlerConfig {
     pub circuit_target_path: Option<PathBuf>,
     /// Compute units to request
     pub compute_units: u32,
+    /// Zelana verifier program owning the `BatchVerifyingKey` accounts
+    pub vk_program_id: String,
+    /// Domain whose VK, deployed at `[b"batch_vk", domain]` under
+    /// `vk_program_id`, proofs must match
+    pub domain: Option<Domain>,
+    /// [`prover_worker::vk_hash`] of the verifying key deployed for the
+    /// domain, skipping the fetch. Without it or a domain, falls back to
+    /// hashing `zelana_batch.vk` in `circuit_target_path`.
+    pub expected_vk_hash: Option<String>,
+    /// Verifications shared across settlements, so resubmitted proofs skip the chain
+    pub verification_cache: VerificationCache,
+    /// Simulate each verification before sending it
+    pub preflight: bool,
 }
 
 impl Default for SettlerConfig {
@@ -41,10 +57,55 @@ impl Default for SettlerConfig {
             keypair_path: None,
             circuit_target_path: None,
             compute_units: 500_000,
+            vk_program_id: DEFAULT_VK_PROGRAM_ID.to_string(),
+            domain: None,
+            expected_vk_hash: None,
+            verification_cache: VerificationCache::default(),
+            preflight: true,
         }
     }
 }
 
+/// Default Zelana verifier program, where batch VKs are deployed
+pub const DEFAULT_VK_PROGRAM_ID: &str = "7rsVijhQ1ipfc6uxzcs4R2gBtD9L5ZLubSc6vPKXgawo";
+
+/// Check that every chunk proof was produced against the same verifying key,
+/// and that it is the one deployed on-chain (when known).
+///
+/// A batch mixing circuits cannot settle: the verifier only accepts proofs
+/// for its own VK, so a partial settlement would leave the batch half-applied.
+pub fn check_vk_hashes(proofs: &[ChunkProof], expected: Option<&str>) -> Result<(), String> {
+    let normalize = |h: &str| h.trim_start_matches("0x").to_ascii_lowercase();
+
+    let Some(first) = proofs.first() else {
+        return Ok(());
+    };
+    let batch_vk = normalize(&first.vk_hash);
+
+    for proof in proofs {
+        if proof.vk_hash.is_empty() {
+            return Err(format!("Chunk {} proof has no vk hash", proof.chunk_id));
+        }
+        if normalize(&proof.vk_hash) != batch_vk {
+            return Err(format!(
+                "Chunk {} vk hash {} does not match chunk {} vk hash {}",
+                proof.chunk_id, proof.vk_hash, first.chunk_id, first.vk_hash
+            ));
+        }
+    }
+
+    if let Some(expected) = expected
+        && normalize(expected) != batch_vk
+    {
+        return Err(format!(
+            "Batch vk hash {} does not match on-chain vk hash {}",
+            first.vk_hash, expected
+        ));
+    }
+
+    Ok(())
+}
+
 /// Settlement result for a single proof
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProofSettlement {
@@ -105,6 +166,8 @@ impl Settler {
                 keypair_path: self.config.keypair_path.clone(),
                 compute_units: self.config.compute_units,
                 priority_fee_micro_lamports: 1000,
+                verification_cache: self.config.verification_cache.clone(),
+                preflight: self.config.preflight,
             };
 
             match SolanaVerifierClient::new(solana_config) {
@@ -120,6 +183,38 @@ impl Settler {
         Ok(self.client.as_ref().unwrap())
     }
 
+    /// Resolve the VK hash proofs must match before settlement
+    ///
+    /// An explicit `expected_vk_hash` wins; otherwise the VK deployed for the
+    /// domain is fetched and hashed. A domain whose VK can't be read refuses
+    /// settlement rather than falling back to the local key.
+    async fn resolve_expected_vk_hash(&mut self) -> Result<Option<String>, String> {
+        if let Some(hash) = &self.config.expected_vk_hash {
+            return Ok(Some(hash.clone()));
+        }
+
+        if let Some(domain) = self.config.domain {
+            let vk_program = Pubkey::from_str(&self.config.vk_program_id)
+                .map_err(|e| format!("Invalid VK program id: {}", e))?;
+            let client = self.get_or_init_client()?;
+            return client
+                .fetch_batch_vk_hash(&vk_program, &domain)
+                .await
+                .map(Some)
+                .map_err(|e| format!("Failed to read deployed VK for '{}': {}", domain, e));
+        }
+
+        let Some(target) = &self.config.circuit_target_path else {
+            return Ok(None);
+        };
+        let Ok(vk) = std::fs::read(target.join("zelana_batch.vk")) else {
+            return Ok(None);
+        };
+        prover_worker::vk_file_hash(&vk)
+            .map(Some)
+            .map_err(|e| format!("Local verifying key: {}", e))
+    }
+
     /// Submit a single proof to Solana
     async fn submit_proof(&mut self, proof: &ChunkProof) -> Result<ProofSettlement, String> {
         info!(
@@ -293,6 +388,9 @@ impl Settler {
             self.mode
         );
 
+        let expected_vk_hash = self.resolve_expected_vk_hash().await?;
+        check_vk_hashes(&batch_proofs.proofs, expected_vk_hash.as_deref())?;
+
         let mut result = match self.mode {
             SettlementMode::Batched => self.submit_batched(&batch_proofs.proofs).await?,
             SettlementMode::Sequential => self.submit_sequential(&batch_proofs.proofs).await?,
@@ -343,6 +441,8 @@ impl FileBasedSettler {
                 keypair_path: self.config.keypair_path.clone(),
                 compute_units: self.config.compute_units,
                 priority_fee_micro_lamports: 1000,
+                verification_cache: self.config.verification_cache.clone(),
+                preflight: self.config.preflight,
             };
 
             match SolanaVerifierClient::new(solana_config) {
@@ -412,6 +512,8 @@ pub struct MockSettler {
     delay_ms: u64,
     /// Optional: path to real proof files (for hybrid testing)
     proof_files_path: Option<PathBuf>,
+    /// Optional: VK hash the simulated verifier accepts
+    expected_vk_hash: Option<String>,
 }
 
 impl MockSettler {
@@ -419,6 +521,7 @@ impl MockSettler {
         Self {
             delay_ms,
             proof_files_path: None,
+            expected_vk_hash: None,
         }
     }
 
@@ -427,9 +530,16 @@ impl MockSettler {
         Self {
             delay_ms,
             proof_files_path: Some(path),
+            expected_vk_hash: None,
         }
     }
 
+    /// Only accept proofs made against the given VK hash
+    pub fn with_expected_vk_hash(mut self, vk_hash: impl Into<String>) -> Self {
+        self.expected_vk_hash = Some(vk_hash.into());
+        self
+    }
+
     pub async fn settle_batch(
         &self,
         batch_proofs: &BatchProofs,
@@ -442,6 +552,8 @@ impl MockSettler {
             batch_proofs.proofs.len()
         );
 
+        check_vk_hashes(&batch_proofs.proofs, self.expected_vk_hash.as_deref())?;
+
         // Simulate settlement delay
         tokio::time::sleep(tokio::time::Duration::from_millis(self.delay_ms)).await;
 
@@ -485,6 +597,7 @@ mod tests {
                     worker_id: 1,
                     proof: "deadbeef".to_string(),
                     public_inputs: vec!["0x1".to_string(), "0x2".to_string()],
+                    vk_hash: "ab".repeat(32),
                     proving_time_ms: 100,
                 },
                 ChunkProof {
@@ -492,6 +605,7 @@ mod tests {
                     worker_id: 2,
                     proof: "cafebabe".to_string(),
                     public_inputs: vec!["0x2".to_string(), "0x3".to_string()],
+                    vk_hash: "ab".repeat(32),
                     proving_time_ms: 150,
                 },
             ],
@@ -507,6 +621,58 @@ mod tests {
         assert!(result.batched_tx_signature.is_some());
     }
 
+    fn chunk_proof(chunk_id: u32, vk_hash: &str) -> ChunkProof {
+        ChunkProof {
+            chunk_id,
+            worker_id: chunk_id + 1,
+            proof: "deadbeef".to_string(),
+            public_inputs: vec![],
+            vk_hash: vk_hash.to_string(),
+            proving_time_ms: 100,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_vk_hashes_refuse_settlement() {
+        let vk_a = "aa".repeat(32);
+        let vk_b = "bb".repeat(32);
+
+        // Mixed-circuit batch
+        let mixed = BatchProofs {
+            batch_id: "mixed".to_string(),
+            proofs: vec![chunk_proof(0, &vk_a), chunk_proof(1, &vk_b)],
+            total_time_ms: 200,
+            workers_used: 2,
+        };
+        let err = MockSettler::new(0).settle_batch(&mixed).await.unwrap_err();
+        assert!(err.contains("Chunk 1"), "{}", err);
+
+        // Consistent batch, but not the VK deployed on-chain
+        let stale = BatchProofs {
+            batch_id: "stale".to_string(),
+            proofs: vec![chunk_proof(0, &vk_a), chunk_proof(1, &vk_a)],
+            total_time_ms: 200,
+            workers_used: 2,
+        };
+        let err = MockSettler::new(0)
+            .with_expected_vk_hash(vk_b.clone())
+            .settle_batch(&stale)
+            .await
+            .unwrap_err();
+        assert!(err.contains("on-chain"), "{}", err);
+
+        // Proof from a worker that did not report a VK hash
+        assert!(check_vk_hashes(&[chunk_proof(0, &vk_a), chunk_proof(1, "")], None).is_err());
+
+        // Matching batch settles
+        let result = MockSettler::new(0)
+            .with_expected_vk_hash(format!("0x{}", vk_a.to_uppercase()))
+            .settle_batch(&stale)
+            .await
+            .unwrap();
+        assert!(result.all_verified);
+    }
+
     #[test]
     fn test_default_config() {
         let config = SettlerConfig::default();
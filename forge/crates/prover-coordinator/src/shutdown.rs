@@ -0,0 +1,156 @@
+//! Graceful Shutdown
+//!
+//! Tracks in-flight batch tasks so the coordinator can stop taking new
+//! submissions on SIGINT/SIGTERM and wait for running batches to reach a
+//! terminal state instead of abandoning them half-settled.
+
+use std::future::Future;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+/// Returned by [`BatchDrain::spawn`] once shutdown has begun
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("coordinator is shutting down")]
+pub struct ShuttingDown;
+
+/// Set of in-flight batch tasks that can be drained on shutdown
+#[derive(Debug, Default)]
+pub struct BatchDrain {
+    draining: AtomicBool,
+    tasks: Mutex<JoinSet<()>>,
+}
+
+impl BatchDrain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether new batches are still accepted
+    pub fn is_accepting(&self) -> bool {
+        !self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Spawn a batch task, or refuse it if shutdown has begun
+    pub fn spawn<F>(&self, task: F) -> Result<(), ShuttingDown>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        // Checked under the lock so a task can't slip in after drain() took the set
+        let mut tasks = self.tasks.lock().unwrap();
+        if self.draining.load(Ordering::SeqCst) {
+            return Err(ShuttingDown);
+        }
+
+        // Reap finished batches so the set only holds in-flight ones
+        while let Some(result) = tasks.try_join_next() {
+            if let Err(e) = result {
+                warn!("Batch task failed: {}", e);
+            }
+        }
+        tasks.spawn(task);
+        Ok(())
+    }
+
+    /// Stop accepting batches and wait up to `timeout` for in-flight ones.
+    ///
+    /// Returns how many batches were still running when the timeout expired;
+    /// those tasks are aborted.
+    pub async fn drain(&self, timeout: Duration) -> usize {
+        let mut tasks = {
+            let mut tasks = self.tasks.lock().unwrap();
+            self.draining.store(true, Ordering::SeqCst);
+            std::mem::take(&mut *tasks)
+        };
+
+        if tasks.is_empty() {
+            info!("No in-flight batches to drain");
+            return 0;
+        }
+
+        info!(
+            "Draining {} in-flight batch(es), timeout {:?}",
+            tasks.len(),
+            timeout
+        );
+        let deadline = tokio::time::Instant::now() + timeout;
+        while !tasks.is_empty() {
+            match tokio::time::timeout_at(deadline, tasks.join_next()).await {
+                Ok(Some(Err(e))) if e.is_panic() => {
+                    warn!("Batch task panicked during drain: {}", e);
+                }
+                Ok(_) => info!("{} batch(es) still in flight", tasks.len()),
+                Err(_) => {
+                    let remaining = tasks.len();
+                    warn!(
+                        "Drain timed out with {} batch(es) still in flight, aborting them",
+                        remaining
+                    );
+                    tasks.abort_all();
+                    return remaining;
+                }
+            }
+        }
+
+        info!("All in-flight batches drained");
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_drain_rejects_new_batches_and_waits_for_in_flight() {
+        let drain = Arc::new(BatchDrain::new());
+        let completed = Arc::new(AtomicBool::new(false));
+
+        // In-flight mock batch
+        let done = completed.clone();
+        drain
+            .spawn(async move {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                done.store(true, Ordering::SeqCst);
+            })
+            .unwrap();
+
+        let draining = tokio::spawn({
+            let drain = drain.clone();
+            async move { drain.drain(Duration::from_secs(30)).await }
+        });
+        tokio::task::yield_now().await;
+
+        // Submissions during shutdown are refused
+        assert!(!drain.is_accepting());
+        assert_eq!(drain.spawn(async {}), Err(ShuttingDown));
+
+        // The in-flight batch finishes before drain returns
+        assert_eq!(draining.await.unwrap(), 0);
+        assert!(completed.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_reaps_finished_batches() {
+        let drain = BatchDrain::new();
+        for _ in 0..10 {
+            drain.spawn(async {}).unwrap();
+            tokio::task::yield_now().await;
+        }
+
+        // Earlier batches completed and were reaped by later spawns
+        assert!(drain.tasks.lock().unwrap().len() < 10);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_drain_times_out_on_stuck_batch() {
+        let drain = BatchDrain::new();
+        drain.spawn(std::future::pending::<()>()).unwrap();
+
+        assert_eq!(drain.drain(Duration::from_secs(5)).await, 1);
+    }
+}
@@ -9,6 +9,7 @@
 //! - proof_bytes: Groth16 proof from sunspot (gnark format)
 //! - public_witness_bytes: 4-byte count + 8-byte padding + (32 bytes × N inputs)
 
+use sha2::{Digest, Sha256};
 use solana_client::rpc_client::RpcClient;
 use solana_client::rpc_config::RpcTransactionConfig;
 use solana_sdk::{
@@ -20,10 +21,14 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use solana_transaction_status::UiTransactionEncoding;
+use std::collections::HashMap;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{debug, error, info};
+use zelana_config::Domain;
 
 // Errors
 
@@ -46,6 +51,16 @@ pub enum SolanaClientError {
 
     #[error("Insufficient balance for transaction")]
     InsufficientBalance,
+
+    #[error("Invalid batch VK account: {0}")]
+    InvalidVkAccount(String),
+
+    #[error("Simulation failed: {err}")]
+    SimulationFailed {
+        err: String,
+        /// Program logs from the simulated transaction
+        logs: Vec<String>,
+    },
 }
 
 // Configuration
@@ -63,6 +78,11 @@ pub struct SolanaVerifierConfig {
     pub compute_units: u32,
     /// Priority fee in micro-lamports per compute unit
     pub priority_fee_micro_lamports: u64,
+    /// Recently verified proofs, consulted before submitting
+    pub verification_cache: VerificationCache,
+    /// Simulate verification transactions first and skip sending ones that
+    /// would fail (saves fees and surfaces the verifier's logs)
+    pub preflight: bool,
 }
 
 impl Default for SolanaVerifierConfig {
@@ -73,6 +93,8 @@ impl Default for SolanaVerifierConfig {
             keypair_path: None,
             compute_units: 500_000,
             priority_fee_micro_lamports: 1000,
+            verification_cache: VerificationCache::default(),
+            preflight: true,
         }
     }
 }
@@ -92,6 +114,97 @@ pub struct VerificationResult {
     pub compute_units_consumed: Option<u64>,
 }
 
+/// Outcome of simulating a transaction with `simulateTransaction`
+#[derive(Debug, Clone, Default)]
+pub struct SimulationResult {
+    /// Transaction error, if the transaction would fail
+    pub err: Option<String>,
+    /// Program logs
+    pub logs: Vec<String>,
+    /// Compute units the transaction consumed
+    pub units_consumed: Option<u64>,
+}
+
+impl SimulationResult {
+    pub fn succeeded(&self) -> bool {
+        self.err.is_none()
+    }
+}
+
+// Verification Cache
+
+/// How long a verification is remembered by default
+pub const DEFAULT_VERIFICATION_CACHE_TTL: Duration = Duration::from_secs(600);
+
+/// Recent successful verifications, keyed by proof + public witness hash.
+///
+/// A client-side optimization, not a consensus change: resubmitting a proof
+/// that was verified within the TTL (settlement retries, idempotent
+/// resubmits) returns the earlier result instead of paying for another
+/// on-chain verification. The verifier program is untouched. Clones share
+/// their entries.
+#[derive(Debug, Clone)]
+pub struct VerificationCache {
+    ttl: Duration,
+    entries: Arc<Mutex<HashMap<[u8; 32], CachedVerification>>>,
+}
+
+#[derive(Debug)]
+struct CachedVerification {
+    verified_at: Instant,
+    result: VerificationResult,
+}
+
+impl Default for VerificationCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_VERIFICATION_CACHE_TTL)
+    }
+}
+
+impl VerificationCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Arc::default(),
+        }
+    }
+
+    /// Cache key for a proof and its public witness
+    pub fn key(proof_bytes: &[u8], public_witness_bytes: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update((proof_bytes.len() as u64).to_le_bytes());
+        hasher.update(proof_bytes);
+        hasher.update(public_witness_bytes);
+        hasher.finalize().into()
+    }
+
+    /// Cached result for `key`, if verified within the TTL
+    pub fn get(&self, key: &[u8; 32]) -> Option<VerificationResult> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some(cached) if cached.verified_at.elapsed() < self.ttl => Some(cached.result.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Remember a successful verification, dropping expired entries
+    pub fn insert(&self, key: [u8; 32], result: VerificationResult) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, cached| cached.verified_at.elapsed() < self.ttl);
+        entries.insert(
+            key,
+            CachedVerification {
+                verified_at: Instant::now(),
+                result,
+            },
+        );
+    }
+}
+
 // Solana Verifier Client
 
 /// Client for submitting proofs to Solana verifier program
@@ -180,6 +293,16 @@ impl SolanaVerifierClient {
             )));
         }
 
+        // Identical proofs verified recently don't need another transaction
+        let cache_key = VerificationCache::key(proof_bytes, public_witness_bytes);
+        if let Some(result) = self.config.verification_cache.get(&cache_key) {
+            info!(
+                "Proof already verified in {}, skipping resubmission",
+                result.signature
+            );
+            return Ok(result);
+        }
+
         // Build instruction data: proof + public_witness
         let mut instruction_data =
             Vec::with_capacity(proof_bytes.len() + public_witness_bytes.len());
@@ -216,6 +339,24 @@ impl SolanaVerifierClient {
             recent_blockhash,
         );
 
+        if self.config.preflight {
+            let simulation = self.simulate(&tx).await?;
+            if let Some(err) = simulation.err {
+                error!("Verification simulation failed: {}", err);
+                for log in &simulation.logs {
+                    debug!("  {}", log);
+                }
+                return Err(SolanaClientError::SimulationFailed {
+                    err,
+                    logs: simulation.logs,
+                });
+            }
+            debug!(
+                "Verification simulation passed (CU: {:?})",
+                simulation.units_consumed
+            );
+        }
+
         info!("Submitting verification transaction...");
 
         // Send and confirm transaction
@@ -245,11 +386,28 @@ impl SolanaVerifierClient {
             Err(_) => (0, None),
         };
 
-        Ok(VerificationResult {
+        let result = VerificationResult {
             signature,
             verified: true,
             slot,
             compute_units_consumed,
+        };
+        self.config
+            .verification_cache
+            .insert(cache_key, result.clone());
+        Ok(result)
+    }
+
+    /// Simulate a signed transaction without sending it.
+    ///
+    /// Costs no fees and doesn't consume the blockhash, so failures such as a
+    /// bad public input or a pairing check failure show up before paying.
+    pub async fn simulate(&self, tx: &Transaction) -> Result<SimulationResult, SolanaClientError> {
+        let simulation = self.rpc.simulate_transaction(tx)?.value;
+        Ok(SimulationResult {
+            err: simulation.err.map(|e| e.to_string()),
+            logs: simulation.logs.unwrap_or_default(),
+            units_consumed: simulation.units_consumed,
         })
     }
 
@@ -285,6 +443,61 @@ impl SolanaVerifierClient {
             }
         }
     }
+
+    /// Fetch the VK deployed at `[b"batch_vk", domain]` and hash it
+    ///
+    /// The account belongs to the Zelana verifier program (`vk_program`),
+    /// not the Sunspot verifier proofs are submitted to.
+    pub async fn fetch_batch_vk_hash(
+        &self,
+        vk_program: &Pubkey,
+        domain: &Domain,
+    ) -> Result<String, SolanaClientError> {
+        let (vk_pda, _) =
+            Pubkey::find_program_address(&[b"batch_vk", &domain.as_bytes()], vk_program);
+        let account = self.rpc.get_account(&vk_pda)?;
+        if account.owner != *vk_program {
+            return Err(SolanaClientError::InvalidVkAccount(format!(
+                "{} is owned by {}, not {}",
+                vk_pda, account.owner, vk_program
+            )));
+        }
+        batch_vk_hash(&account.data)
+            .map_err(|e| SolanaClientError::InvalidVkAccount(format!("{}: {}", vk_pda, e)))
+    }
+}
+
+// Deployed Verifying Key
+
+/// Size of `BatchVerifyingKey` points: alpha (64) + beta, gamma, delta (3 × 128)
+const VK_BASE_POINTS_LEN: usize = 64 + 3 * 128;
+
+/// IC slots in a `BatchVerifyingKey` account
+const VK_MAX_IC_POINTS: usize = 8;
+
+/// Hash a `BatchVerifyingKey` account's key with [`prover_worker::vk_hash`].
+///
+/// The preimage is alpha ‖ beta ‖ gamma ‖ delta ‖ the used IC points, the
+/// canonical encoding workers hash their `zelana_batch.vk` into. Keys that
+/// are still being uploaded (not finalized) are rejected.
+pub fn batch_vk_hash(data: &[u8]) -> Result<String, String> {
+    // discriminator (8) + authority (32) + domain (32)
+    let points_start = 8 + 32 + 32;
+    let ic_start = points_start + VK_BASE_POINTS_LEN;
+    let ic_len_at = ic_start + VK_MAX_IC_POINTS * 64;
+    if data.len() < ic_len_at + 2 {
+        return Err(format!("account data is {} bytes", data.len()));
+    }
+
+    let ic_len = data[ic_len_at] as usize;
+    if ic_len > VK_MAX_IC_POINTS {
+        return Err(format!("ic_len {} exceeds {}", ic_len, VK_MAX_IC_POINTS));
+    }
+    if data[ic_len_at + 1] == 0 {
+        return Err("verifying key is not finalized".to_string());
+    }
+
+    Ok(prover_worker::vk_hash(&data[points_start..ic_start + ic_len * 64]))
 }
 
 // Helper Functions
@@ -395,4 +608,132 @@ mod tests {
         );
         assert_eq!(config.compute_units, 500_000);
     }
+
+    #[test]
+    fn test_batch_vk_hash_covers_used_points() {
+        let points_start = 8 + 32 + 32;
+        let ic_start = points_start + VK_BASE_POINTS_LEN;
+        let ic_len_at = ic_start + VK_MAX_IC_POINTS * 64;
+        let mut data = vec![0u8; ic_len_at + 2 + 8 + 1];
+        data[points_start..ic_start].fill(1);
+        data[ic_start..ic_start + 2 * 64].fill(2);
+        data[ic_len_at] = 2;
+        data[ic_len_at + 1] = 1;
+
+        let mut preimage = vec![1u8; VK_BASE_POINTS_LEN];
+        preimage.extend_from_slice(&[2u8; 2 * 64]);
+        let expected = hex::encode(Sha256::digest(&preimage));
+        assert_eq!(batch_vk_hash(&data), Ok(expected.clone()));
+
+        // A worker hashing the same key from sunspot's gnark file agrees
+        let mut gnark_vk = vec![1u8; 64];
+        gnark_vk.extend_from_slice(&[0u8; 64]); // beta_g1
+        gnark_vk.extend_from_slice(&[1u8; 2 * 128]); // beta_g2, gamma_g2
+        gnark_vk.extend_from_slice(&[0u8; 64]); // delta_g1
+        gnark_vk.extend_from_slice(&[1u8; 128]); // delta_g2
+        gnark_vk.extend_from_slice(&2u32.to_be_bytes());
+        gnark_vk.extend_from_slice(&[2u8; 2 * 64]);
+        assert_eq!(prover_worker::vk_file_hash(&gnark_vk).unwrap(), expected);
+
+        // Unused IC slots and the authority don't change the hash
+        data[ic_start + 2 * 64] = 9;
+        data[8] = 9;
+        assert_eq!(batch_vk_hash(&data), Ok(expected));
+
+        data[ic_len_at + 1] = 0;
+        assert!(batch_vk_hash(&data).is_err());
+        assert!(batch_vk_hash(&data[..100]).is_err());
+    }
+
+    fn mock_client(cache: VerificationCache) -> SolanaVerifierClient {
+        SolanaVerifierClient {
+            rpc: RpcClient::new_mock("succeeds".to_string()),
+            payer: Keypair::new(),
+            program_id: Pubkey::new_unique(),
+            config: SolanaVerifierConfig {
+                verification_cache: cache,
+                ..SolanaVerifierConfig::default()
+            },
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_failed_simulation_skips_send() {
+        use solana_client::rpc_client::Mocks;
+        use solana_client::rpc_request::RpcRequest;
+
+        let failing_simulation = || {
+            Mocks::from([(
+                RpcRequest::SimulateTransaction,
+                serde_json::json!({
+                    "context": { "slot": 1 },
+                    "value": {
+                        "err": { "InstructionError": [2, { "Custom": 1 }] },
+                        "logs": ["Program log: pairing check failed"],
+                        "accounts": null,
+                        "unitsConsumed": 180000,
+                        "returnData": null,
+                    },
+                }),
+            )])
+        };
+        let (proof, pw) = (vec![1u8; 388], vec![2u8; 236]);
+
+        let mut client = mock_client(VerificationCache::default());
+        client.rpc = RpcClient::new_mock_with_mocks("succeeds".to_string(), failing_simulation());
+        match client.verify_proof(&proof, &pw).await {
+            Err(SolanaClientError::SimulationFailed { err, logs }) => {
+                assert!(err.contains("custom program error"), "{}", err);
+                assert_eq!(logs, ["Program log: pairing check failed"]);
+            }
+            other => panic!("expected a simulation failure, got {:?}", other),
+        }
+
+        // Without preflight the same RPC goes straight to sending
+        client.config.preflight = false;
+        client.rpc = RpcClient::new_mock_with_mocks("succeeds".to_string(), failing_simulation());
+        assert!(client.verify_proof(&proof, &pw).await.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_repeated_verification_hits_cache() {
+        let cache = VerificationCache::default();
+        let mut client = mock_client(cache.clone());
+        let (proof, pw) = (vec![1u8; 388], vec![2u8; 236]);
+
+        let first = client.verify_proof(&proof, &pw).await.unwrap();
+
+        // Every RPC call fails from here on, so only a cache hit can succeed
+        client.rpc = RpcClient::new_mock("fails".to_string());
+        let second = client.verify_proof(&proof, &pw).await.unwrap();
+        assert_eq!(second.signature, first.signature);
+        assert!(client.verify_proof(&proof, &[3u8; 236]).await.is_err());
+
+        // A fresh client sharing the cache (as each settlement does) hits it too
+        let mut other = mock_client(cache);
+        other.rpc = RpcClient::new_mock("fails".to_string());
+        assert!(other.verify_proof(&proof, &pw).await.is_ok());
+    }
+
+    #[test]
+    fn test_verification_cache_expires() {
+        let cache = VerificationCache::new(Duration::ZERO);
+        let key = VerificationCache::key(&[1], &[2]);
+        cache.insert(
+            key,
+            VerificationResult {
+                signature: Signature::default(),
+                verified: true,
+                slot: 1,
+                compute_units_consumed: None,
+            },
+        );
+        assert!(cache.get(&key).is_none());
+
+        // Moving bytes between proof and witness changes the key
+        assert_ne!(
+            VerificationCache::key(&[1, 2], &[3]),
+            VerificationCache::key(&[1], &[2, 3])
+        );
+    }
 }
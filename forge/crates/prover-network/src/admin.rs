@@ -0,0 +1,97 @@
+//! Admin Authentication
+//!
+//! Bearer-token check shared by the admin endpoints (coordinator
+//! `DELETE /cache` and `POST /settle`, worker `POST /reload`). The endpoints
+//! are disabled when no token is configured.
+
+use axum::http::{HeaderMap, StatusCode, header::AUTHORIZATION};
+use sha2::{Digest, Sha256};
+
+/// Why an admin request was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminAuthError {
+    /// No admin token is configured, so admin endpoints are off
+    Disabled,
+    /// The `Authorization` header is missing or carries the wrong token
+    InvalidToken,
+}
+
+impl AdminAuthError {
+    pub fn status(self) -> StatusCode {
+        match self {
+            AdminAuthError::Disabled => StatusCode::FORBIDDEN,
+            AdminAuthError::InvalidToken => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    /// Machine-readable code for `ApiResponse::error_with_code`
+    pub fn code(self) -> &'static str {
+        match self {
+            AdminAuthError::Disabled => "FORBIDDEN",
+            AdminAuthError::InvalidToken => "UNAUTHORIZED",
+        }
+    }
+
+    pub fn message(self) -> &'static str {
+        match self {
+            AdminAuthError::Disabled => "No admin token configured",
+            AdminAuthError::InvalidToken => "Missing or invalid admin token",
+        }
+    }
+}
+
+/// Check `Authorization: Bearer <token>` against the configured admin token
+///
+/// Both tokens are hashed and the digests compared without early exit, so
+/// timing reveals neither the token's bytes nor its length.
+pub fn check_admin_token(
+    configured: Option<&str>,
+    headers: &HeaderMap,
+) -> Result<(), AdminAuthError> {
+    let token = configured.ok_or(AdminAuthError::Disabled)?;
+    let presented = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or(AdminAuthError::InvalidToken)?;
+
+    let expected = Sha256::digest(token.as_bytes());
+    let actual = Sha256::digest(presented.as_bytes());
+    let diff = expected
+        .iter()
+        .zip(actual.iter())
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+    if diff == 0 {
+        Ok(())
+    } else {
+        Err(AdminAuthError::InvalidToken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, format!("Bearer {}", token).parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_check_admin_token() {
+        assert_eq!(
+            check_admin_token(None, &bearer("secret")),
+            Err(AdminAuthError::Disabled)
+        );
+        assert_eq!(
+            check_admin_token(Some("secret"), &HeaderMap::new()),
+            Err(AdminAuthError::InvalidToken)
+        );
+        assert_eq!(
+            check_admin_token(Some("secret"), &bearer("secre")),
+            Err(AdminAuthError::InvalidToken)
+        );
+        assert_eq!(check_admin_token(Some("secret"), &bearer("secret")), Ok(()));
+    }
+}
@@ -6,7 +6,13 @@
 //! - Message types for all protocol phases
 //! - Base64 serialization for arkworks types
 //! - Standardized API responses
+//! - Admin token checks for admin endpoints
+//! - Shared HTTP body size limits
+//! - Shared log output setup (`--log-format`)
 
+pub mod admin;
+pub mod limits;
+pub mod logging;
 pub mod messages;
 pub mod serde_utils;
 
@@ -0,0 +1,18 @@
+//! HTTP request body limits shared by the coordinator, worker and node.
+//!
+//! Services apply [`CONTROL_BODY_LIMIT`] router-wide and raise it only on the
+//! routes that carry proving payloads. Bodies over the limit are rejected with
+//! `413 Payload Too Large` as soon as the limit is crossed, without buffering
+//! the rest of the body.
+
+/// Health, status, cancel and other small control requests
+pub const CONTROL_BODY_LIMIT: usize = 64 * 1024;
+
+/// A single proving request (worker `/prove`, core/ownership prove)
+pub const PROVE_BODY_LIMIT: usize = 8 * 1024 * 1024;
+
+/// A full batch submitted to the coordinator (`/batch/submit`)
+pub const BATCH_BODY_LIMIT: usize = 32 * 1024 * 1024;
+
+/// Witness shares and proof fragments exchanged with nodes
+pub const SHARE_BODY_LIMIT: usize = 64 * 1024 * 1024;
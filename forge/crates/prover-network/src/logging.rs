@@ -0,0 +1,121 @@
+//! Log output setup shared by the coordinator and worker.
+//!
+//! Services log human-readable lines by default. `--log-format json` switches
+//! to one JSON object per line for log aggregators, carrying the fields of the
+//! enclosing spans (`batch_id`, `chunk_id`, ...) alongside each event.
+
+use std::str::FromStr;
+use tracing::Subscriber;
+use tracing_subscriber::{EnvFilter, fmt::MakeWriter, util::SubscriberInitExt};
+
+/// `--log-format` / `LOG_FORMAT` values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable output for local development
+    #[default]
+    Pretty,
+    /// JSON lines with span fields
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown log format '{}' (expected pretty or json)",
+                other
+            )),
+        }
+    }
+}
+
+/// Install the global subscriber; `default_filter` applies when `RUST_LOG` is unset
+pub fn init_tracing(format: LogFormat, default_filter: &str) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| default_filter.into());
+    match format {
+        LogFormat::Pretty => tracing_subscriber::fmt().with_env_filter(filter).init(),
+        LogFormat::Json => json_subscriber(filter, std::io::stdout).init(),
+    }
+}
+
+/// JSON-lines subscriber writing to `writer`
+pub fn json_subscriber<W>(filter: EnvFilter, writer: W) -> impl Subscriber + Send + Sync + 'static
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt()
+        .json()
+        .with_current_span(true)
+        .with_span_list(true)
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!("pretty".parse(), Ok(LogFormat::Pretty));
+        assert_eq!("JSON".parse(), Ok(LogFormat::Json));
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_json_lines_carry_span_fields() {
+        let buffer = Buffer::default();
+        let subscriber = json_subscriber(EnvFilter::new("info"), buffer.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let batch = tracing::info_span!("batch", batch_id = "batch-1");
+            let _batch = batch.enter();
+            let chunk = tracing::info_span!("chunk", chunk_id = 3);
+            let _chunk = chunk.enter();
+            tracing::info!(txs = 25, "chunk proved");
+            tracing::debug!("filtered out");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line is JSON"))
+            .collect();
+        assert_eq!(lines.len(), 1);
+
+        let event = &lines[0];
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(event["fields"]["message"], "chunk proved");
+        assert_eq!(event["fields"]["txs"], 25);
+        assert_eq!(event["span"]["chunk_id"], 3);
+        assert_eq!(event["spans"][0]["batch_id"], "batch-1");
+    }
+}
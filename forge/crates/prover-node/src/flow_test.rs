@@ -0,0 +1,174 @@
+//! End-to-end test of the distributed Schnorr flow over HTTP.
+//!
+//! Spins up in-process prover nodes on loopback and drives them with the
+//! real `prover-network` message types, playing the coordinator's part with
+//! `prover-core`. Catches contract drift between the crates that the
+//! per-crate unit tests can't see.
+
+#[cfg(test)]
+mod tests {
+    use crate::{new_state, node_router};
+    use ark_ec::CurveGroup;
+    use ark_std::{test_rng, UniformRand, Zero};
+    use prover_core::{
+        commit_witness, generate_challenge_from_commitment,
+        schnorr::{DistributedProof, ProofFragment, PublicParams},
+        shamir::{lagrange_coefficient, share_secret},
+        verify_commitment, Fr, G1Projective, SALT_SIZE,
+    };
+    use prover_network::{
+        ApiResponse, BlindShareAssignment, CircuitType, CommitmentRequest, CommitmentResponse,
+        FragmentRequest, FragmentResponse, HealthResponse, WitnessCommitment,
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+
+    const NUM_NODES: usize = 3;
+    const SESSION_ID: &str = "flow-test-session";
+
+    /// Serve a fresh node on an ephemeral loopback port, returning its base URL
+    async fn spawn_node(node_id: u32) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, node_router(new_state(node_id)))
+                .await
+                .unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    async fn call<T: DeserializeOwned>(request: reqwest::RequestBuilder) -> ApiResponse<T> {
+        let response = request.send().await.unwrap();
+        assert!(response.status().is_success(), "HTTP {}", response.status());
+        response.json().await.unwrap()
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(
+        client: &reqwest::Client,
+        url: String,
+        body: &B,
+    ) -> T {
+        match call(client.post(&url).json(body)).await {
+            ApiResponse::Success { data } => data,
+            ApiResponse::Error { message } => panic!("{} failed: {}", url, message),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_distributed_schnorr_over_http() {
+        let mut rng = test_rng();
+        let client = reqwest::Client::new();
+
+        // Coordinator setup: secret, public params and witness commitment
+        let secret = Fr::rand(&mut rng);
+        let generator = G1Projective::rand(&mut rng).into_affine();
+        let params = PublicParams::from_generator(generator, secret);
+        let public_witness = b"flow test witness";
+        let salt = [7u8; SALT_SIZE];
+        let witness_commitment = commit_witness(public_witness, &salt);
+        let share_set = share_secret(secret, NUM_NODES, NUM_NODES, &mut rng);
+
+        let mut nodes = Vec::new();
+        for share in &share_set.shares {
+            nodes.push((share.index as u32, spawn_node(share.index as u32).await));
+        }
+
+        // Nodes report not ready until they hold a share
+        for (node_id, url) in &nodes {
+            let health: ApiResponse<HealthResponse> =
+                call(client.get(format!("{}/health", url))).await;
+            let ApiResponse::Success { data } = health else {
+                panic!("health check failed");
+            };
+            assert_eq!(data.node_id, Some(*node_id));
+            assert!(!data.ready);
+        }
+
+        // Assign blind shares
+        for (share, (node_id, url)) in share_set.shares.iter().zip(&nodes) {
+            let assignment = BlindShareAssignment {
+                session_id: SESSION_ID.to_string(),
+                node_id: *node_id,
+                share_index: share.index as u32,
+                share_value: share.y,
+                generator,
+                witness_commitment: WitnessCommitment {
+                    hash: witness_commitment.hash,
+                },
+                circuit_type: CircuitType::Schnorr,
+            };
+            let _: String = post(&client, format!("{}/share", url), &assignment).await;
+        }
+
+        // Phase 1: collect commitments
+        let mut commitments = Vec::new();
+        for (node_id, url) in &nodes {
+            let request = CommitmentRequest {
+                session_id: SESSION_ID.to_string(),
+            };
+            let response: CommitmentResponse =
+                post(&client, format!("{}/commitment", url), &request).await;
+            assert_eq!(response.node_id, *node_id);
+            assert_eq!(response.session_id, SESSION_ID);
+            commitments.push(response);
+        }
+
+        // Phase 2: aggregate commitments and derive the challenge
+        let x_coords: Vec<Fr> = commitments
+            .iter()
+            .map(|c| Fr::from(c.node_id as u64))
+            .collect();
+        let mut agg_commitment = G1Projective::zero();
+        for (i, c) in commitments.iter().enumerate() {
+            agg_commitment += G1Projective::from(c.commitment) * lagrange_coefficient(&x_coords, i);
+        }
+        let agg_commitment = agg_commitment.into_affine();
+        let challenge = generate_challenge_from_commitment(
+            &generator,
+            &witness_commitment,
+            &agg_commitment,
+            SESSION_ID,
+        )
+        .unwrap();
+
+        // Phase 3: gather fragments
+        let mut fragments = Vec::new();
+        for (c, (node_id, url)) in commitments.iter().zip(&nodes) {
+            let request = FragmentRequest {
+                session_id: SESSION_ID.to_string(),
+                challenge,
+            };
+            let response: FragmentResponse =
+                post(&client, format!("{}/fragment", url), &request).await;
+            assert_eq!(response.node_id, *node_id);
+            fragments.push(ProofFragment {
+                node_id: response.node_id as usize,
+                commitment: c.commitment,
+                response: response.response,
+            });
+        }
+
+        // Phase 4: aggregate and verify
+        let proof = DistributedProof::aggregate(&fragments, challenge).unwrap();
+        assert_eq!(proof.commitment, agg_commitment);
+        assert!(proof.verify(&params), "aggregated proof should verify");
+        assert!(verify_commitment(
+            public_witness,
+            &salt,
+            &witness_commitment
+        ));
+
+        // Session nonces are single-use
+        let (_, url) = &nodes[0];
+        let replay: ApiResponse<FragmentResponse> = call(
+            client
+                .post(format!("{}/fragment", url))
+                .json(&FragmentRequest {
+                    session_id: SESSION_ID.to_string(),
+                    challenge,
+                }),
+        )
+        .await;
+        assert!(replay.is_error());
+    }
+}
@@ -21,7 +21,7 @@ fn secure_rng() -> StdRng {
 }
 
 use axum::{
-    extract::State,
+    extract::{DefaultBodyLimit, State},
     http::StatusCode,
     routing::{get, post},
     Json, Router,
@@ -29,6 +29,7 @@ use axum::{
 use clap::Parser;
 use prover_core::{schnorr::Commitment, Fr, G1Affine, SecretShare};
 use prover_network::{
+    limits::{CONTROL_BODY_LIMIT, SHARE_BODY_LIMIT},
     ApiResponse, BlindShareAssignment, CircuitType, CommitmentRequest, CommitmentResponse,
     FragmentRequest, FragmentResponse, HealthResponse, WitnessCommitment,
 };
@@ -37,6 +38,9 @@ use tokio::sync::RwLock;
 use tower_http::trace::TraceLayer;
 use tracing::{info, warn};
 
+#[cfg(test)]
+mod flow_test;
+
 /// Command-line arguments
 #[derive(Parser, Debug)]
 #[command(name = "prover-node")]
@@ -93,23 +97,7 @@ async fn main() -> anyhow::Result<()> {
         args.node_id, args.host, args.port
     );
 
-    // Initialize state
-    let state = Arc::new(RwLock::new(NodeState {
-        node_id: args.node_id,
-        share: None,
-        generator: None,
-        session_commitments: HashMap::new(),
-        blind_sessions: HashMap::new(),
-    }));
-
-    // Build router (only blind proving endpoints)
-    let app = Router::new()
-        .route("/health", get(health_handler))
-        .route("/share", post(blind_share_handler))
-        .route("/commitment", post(commitment_handler))
-        .route("/fragment", post(fragment_handler))
-        .layer(TraceLayer::new_for_http())
-        .with_state(state);
+    let app = node_router(new_state(args.node_id));
 
     // Start server
     let addr = format!("{}:{}", args.host, args.port);
@@ -122,6 +110,35 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Fresh state for a node that has not been assigned a share yet
+fn new_state(node_id: u32) -> SharedState {
+    Arc::new(RwLock::new(NodeState {
+        node_id,
+        share: None,
+        generator: None,
+        session_commitments: HashMap::new(),
+        blind_sessions: HashMap::new(),
+    }))
+}
+
+/// Build the node router (only blind proving endpoints)
+fn node_router(state: SharedState) -> Router {
+    Router::new()
+        .route("/health", get(health_handler))
+        .route(
+            "/share",
+            post(blind_share_handler).layer(DefaultBodyLimit::max(SHARE_BODY_LIMIT)),
+        )
+        .route("/commitment", post(commitment_handler))
+        .route(
+            "/fragment",
+            post(fragment_handler).layer(DefaultBodyLimit::max(SHARE_BODY_LIMIT)),
+        )
+        .layer(DefaultBodyLimit::max(CONTROL_BODY_LIMIT))
+        .layer(TraceLayer::new_for_http())
+        .with_state(state)
+}
+
 /// Health check handler
 async fn health_handler(State(state): State<SharedState>) -> Json<ApiResponse<HealthResponse>> {
     let node_state = state.read().await;
@@ -0,0 +1,59 @@
+//! Scalar vs batched MiMC throughput.
+//!
+//! Run with:
+//!   cargo bench -p prover-worker --bench mimc_batch
+//!   cargo bench -p prover-worker --bench mimc_batch --features parallel
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use ark_bn254::Fr;
+use prover_worker::MiMC;
+
+const HASHES: usize = 4096;
+const ITERATIONS: u32 = 5;
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    // Warm up once, then take the best of several runs
+    f();
+    (0..ITERATIONS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn main() {
+    let mimc = MiMC::new();
+    let pairs: Vec<[Fr; 2]> = (0..HASHES as u64)
+        .map(|i| [Fr::from(i), Fr::from(i.wrapping_mul(0x9e37_79b9_7f4a_7c15))])
+        .collect();
+
+    let scalar = time(|| {
+        let out: Vec<Fr> = pairs.iter().map(|[a, b]| mimc.hash_2(*a, *b)).collect();
+        black_box(out);
+    });
+    let batched = time(|| {
+        black_box(mimc.hash_2_batch(&pairs));
+    });
+
+    let per_hash = |d: Duration| d.as_nanos() as f64 / HASHES as f64 / 1000.0;
+    println!("MiMC hash_2 x {}", HASHES);
+    println!(
+        "  scalar : {:>10.2?} ({:.2} us/hash)",
+        scalar,
+        per_hash(scalar)
+    );
+    println!(
+        "  batched: {:>10.2?} ({:.2} us/hash)",
+        batched,
+        per_hash(batched)
+    );
+    println!(
+        "  speedup: {:.2}x",
+        scalar.as_secs_f64() / batched.as_secs_f64().max(f64::EPSILON)
+    );
+}
@@ -14,11 +14,12 @@ pub mod prover;
 pub use ark_bn254::Fr;
 
 pub use mimc::{
-    MiMC, ShieldedData, TransferData, WithdrawalData, compute_batch_hash, compute_withdrawal_root,
-    field_to_hex, hex_to_field,
+    InputError, MiMC, ShieldedData, TransferData, WithdrawalData, compute_batch_hash, field_to_hex,
+    hex_to_field, parse_field_checked,
 };
 pub use prover::{
-    BatchInputs, ChunkInputs, MAX_SHIELDED, MAX_TRANSFERS, MAX_WITHDRAWALS, MERKLE_DEPTH,
-    MockProver, NoirProver, ProofResult, ProverError, ShieldedWitness, TransferWitness,
-    WithdrawalWitness,
+    BatchInputs, CIRCUIT_ARTIFACT, ChunkInputs, MAX_SHIELDED, MAX_TRANSFERS, MAX_WITHDRAWALS,
+    MERKLE_DEPTH, MockProver, NoirProver, ProofResult, ProverError, ShieldedWitness,
+    TransferWitness, WithdrawalWitness, circuit_version, mock_vk_hash, u128_halves, vk_file_hash,
+    vk_hash,
 };
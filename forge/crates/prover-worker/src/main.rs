@@ -8,6 +8,7 @@
 //! - `GET /health` - Health check and worker status
 //! - `POST /prove` - Execute Noir circuit and return proof
 //! - `GET /status/:job_id` - Check job status (for async proving)
+//! - `POST /reload` - Re-read the circuit after a deploy (requires `Authorization: Bearer <admin token>`)
 //!
 //! ## Architecture
 //!
@@ -22,11 +23,14 @@
 
 use axum::{
     Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{DefaultBodyLimit, Path, State},
+    http::{HeaderMap, StatusCode},
     routing::{get, post},
 };
 use clap::Parser;
+use prover_network::admin::check_admin_token;
+use prover_network::limits::{CONTROL_BODY_LIMIT, PROVE_BODY_LIMIT};
+use prover_network::logging::{LogFormat, init_tracing};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Instant};
 use tokio::sync::RwLock;
@@ -73,6 +77,14 @@ pub struct Args {
     /// Mock prover delay in milliseconds (simulates proving time)
     #[arg(long, default_value = "500", env = "MOCK_DELAY_MS")]
     pub mock_delay_ms: u64,
+
+    /// Log output: "pretty" (human-readable) or "json" (one object per line)
+    #[arg(long, default_value = "pretty", env = "LOG_FORMAT")]
+    pub log_format: LogFormat,
+
+    /// Bearer token for admin endpoints (`POST /reload`); disabled when unset
+    #[arg(long, env = "ADMIN_TOKEN")]
+    pub admin_token: Option<String>,
 }
 
 /// Worker state
@@ -92,6 +104,10 @@ pub struct WorkerState {
 
     /// Average proving time (ms)
     pub avg_proving_time_ms: u64,
+
+    /// Circuit loaded at startup or by the last `/reload` (None if no valid
+    /// artifact was found); every proof uses this copy
+    pub circuit: Option<Arc<NoirProver>>,
 }
 
 /// Job status
@@ -164,6 +180,9 @@ pub struct ProveResponse {
     /// Public inputs used
     pub public_inputs: Vec<String>,
 
+    /// Hash of the verifying key the proof was generated against
+    pub vk_hash: String,
+
     /// Proving time in milliseconds
     pub proving_time_ms: u64,
 }
@@ -178,6 +197,16 @@ pub struct HealthResponse {
     pub max_concurrent_jobs: usize,
     pub total_proofs: u64,
     pub avg_proving_time_ms: u64,
+    /// Hex SHA-256 of the compiled circuit artifact
+    #[serde(default)]
+    pub circuit_version: Option<String>,
+}
+
+/// Reload response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReloadResponse {
+    pub circuit_version: String,
+    pub previous_version: Option<String>,
 }
 
 /// API response wrapper
@@ -204,16 +233,11 @@ impl<T> ApiResponse<T> {
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "prover_worker=debug,tower_http=debug".into()),
-        )
-        .init();
-
     let args = Args::parse();
 
+    // Initialize tracing
+    init_tracing(args.log_format, "prover_worker=debug,tower_http=debug");
+
     info!(
         "Starting prover worker {} on {}:{} (circuit: {:?})",
         args.worker_id, args.host, args.port, args.circuit_path
@@ -227,6 +251,25 @@ async fn main() -> anyhow::Result<()> {
         );
     }
 
+    // Refuse to start if our MiMC parameters disagree with the circuit's
+    let hash_lib = args.circuit_path.join("../zelana_lib/src/poseidon.nr");
+    let hash_lib_source = std::fs::read_to_string(&hash_lib).ok();
+    mimc::check_circuit_parameters(hash_lib_source.as_deref()).map_err(anyhow::Error::msg)?;
+
+    let circuit = match NoirProver::load(args.circuit_path.clone()) {
+        Ok(circuit) => {
+            info!(
+                "Loaded circuit version {}",
+                circuit.version().unwrap_or_default()
+            );
+            Some(Arc::new(circuit))
+        }
+        Err(e) => {
+            warn!("No valid circuit artifact loaded: {}", e);
+            None
+        }
+    };
+
     // Initialize state
     let state = Arc::new(RwLock::new(WorkerState {
         config: args.clone(),
@@ -234,13 +277,19 @@ async fn main() -> anyhow::Result<()> {
         active_job_count: 0,
         total_proofs: 0,
         avg_proving_time_ms: 0,
+        circuit,
     }));
 
     // Build router
     let app = Router::new()
         .route("/health", get(health_handler))
-        .route("/prove", post(prove_handler))
+        .route(
+            "/prove",
+            post(prove_handler).layer(DefaultBodyLimit::max(PROVE_BODY_LIMIT)),
+        )
         .route("/status/:job_id", get(status_handler))
+        .route("/reload", post(reload_handler))
+        .layer(DefaultBodyLimit::max(CONTROL_BODY_LIMIT))
         .layer(TraceLayer::new_for_http())
         .with_state(state);
 
@@ -271,16 +320,36 @@ async fn health_handler(State(state): State<SharedState>) -> Json<ApiResponse<He
         max_concurrent_jobs: worker_state.config.max_concurrent_jobs,
         total_proofs: worker_state.total_proofs,
         avg_proving_time_ms: worker_state.avg_proving_time_ms,
+        circuit_version: worker_state
+            .circuit
+            .as_ref()
+            .and_then(|c| c.version())
+            .map(str::to_string),
     }))
 }
 
 /// Prove handler - executes Noir circuit and returns proof
+#[tracing::instrument(skip_all, fields(chunk_id = request.chunk_id))]
 async fn prove_handler(
     State(state): State<SharedState>,
     Json(request): Json<ProveRequest>,
-) -> Result<Json<ApiResponse<ProveResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<ProveResponse>>, (StatusCode, Json<ApiResponse<ProveResponse>>)> {
     let start = Instant::now();
 
+    // Reject malformed inputs before they reach nargo, checking the exact
+    // witness they convert to
+    let inputs = chunk_inputs(&request);
+    if let Err(e) = inputs.validate() {
+        warn!(
+            "Rejecting prove request for chunk {}: {}",
+            request.chunk_id, e
+        );
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(format!("Invalid prove request: {}", e))),
+        ));
+    }
+
     // Check capacity
     {
         let worker_state = state.read().await;
@@ -298,15 +367,20 @@ async fn prove_handler(
     let job_id = uuid::Uuid::new_v4().to_string();
 
     // Get worker config
-    let (worker_id, circuit_path, use_mock, mock_delay) = {
+    let (worker_id, circuit, use_mock, mock_delay) = {
         let worker_state = state.read().await;
         (
             worker_state.config.worker_id,
-            worker_state.config.circuit_path.clone(),
+            worker_state.circuit.clone(),
             worker_state.config.mock_prover,
             worker_state.config.mock_delay_ms,
         )
     };
+    if circuit.is_none() && !use_mock {
+        return Ok(Json(ApiResponse::error(
+            "No circuit loaded; deploy one and POST /reload",
+        )));
+    }
 
     info!(
         "Worker {} received prove request for chunk {} (job: {})",
@@ -334,41 +408,14 @@ async fn prove_handler(
         );
     }
 
-    // Convert request to circuit inputs
-    let inputs = ChunkInputs {
-        old_root: request.pre_root.clone(),
-        new_root: request.post_root.clone(),
-        sender_pubkeys: request
-            .transactions
-            .iter()
-            .map(|tx| tx.sender_pubkey.clone())
-            .collect(),
-        receiver_pubkeys: request
-            .transactions
-            .iter()
-            .map(|tx| tx.receiver_pubkey.clone())
-            .collect(),
-        amounts: request.transactions.iter().map(|tx| tx.amount).collect(),
-        signatures: request
-            .transactions
-            .iter()
-            .map(|tx| tx.signature.clone())
-            .collect(),
-        merkle_paths: request
-            .transactions
-            .iter()
-            .map(|tx| tx.merkle_path.clone())
-            .collect(),
-    };
-
     // Execute proof generation (mock or real)
-    let result = if use_mock {
-        info!("Using mock prover (delay: {}ms)", mock_delay);
-        let prover = MockProver::new(mock_delay);
-        prover.generate_proof(inputs).await
-    } else {
-        let prover = NoirProver::new(circuit_path);
-        prover.generate_proof(inputs).await
+    let result = match circuit.filter(|_| !use_mock) {
+        Some(prover) => prover.generate_proof(inputs).await,
+        None => {
+            info!("Using mock prover (delay: {}ms)", mock_delay);
+            let prover = MockProver::new(mock_delay);
+            prover.generate_proof(inputs).await
+        }
     };
 
     let proving_time_ms = start.elapsed().as_millis() as u64;
@@ -408,6 +455,7 @@ async fn prove_handler(
                 worker_id,
                 proof: proof_result.proof,
                 public_inputs: proof_result.public_inputs,
+                vk_hash: proof_result.vk_hash,
                 proving_time_ms,
             })))
         }
@@ -440,6 +488,35 @@ async fn prove_handler(
     }
 }
 
+/// Convert a prove request to circuit inputs
+fn chunk_inputs(request: &ProveRequest) -> ChunkInputs {
+    ChunkInputs {
+        old_root: request.pre_root.clone(),
+        new_root: request.post_root.clone(),
+        sender_pubkeys: request
+            .transactions
+            .iter()
+            .map(|tx| tx.sender_pubkey.clone())
+            .collect(),
+        receiver_pubkeys: request
+            .transactions
+            .iter()
+            .map(|tx| tx.receiver_pubkey.clone())
+            .collect(),
+        amounts: request.transactions.iter().map(|tx| tx.amount).collect(),
+        signatures: request
+            .transactions
+            .iter()
+            .map(|tx| tx.signature.clone())
+            .collect(),
+        merkle_paths: request
+            .transactions
+            .iter()
+            .map(|tx| tx.merkle_path.clone())
+            .collect(),
+    }
+}
+
 /// Status handler - check job status
 async fn status_handler(
     State(state): State<SharedState>,
@@ -452,3 +529,202 @@ async fn status_handler(
         None => Json(ApiResponse::error(format!("Job {} not found", job_id))),
     }
 }
+
+/// Reload handler - re-reads the circuit from `circuit_path` after a deploy
+///
+/// The new artifact set is validated and snapshotted before it replaces the
+/// current one; if it is invalid the previous circuit stays in place. Proofs
+/// already running finish on the snapshot they started with.
+async fn reload_handler(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<ReloadResponse>>, (StatusCode, Json<ApiResponse<ReloadResponse>>)> {
+    let (admin_token, circuit_path) = {
+        let worker_state = state.read().await;
+        (
+            worker_state.config.admin_token.clone(),
+            worker_state.config.circuit_path.clone(),
+        )
+    };
+
+    if let Err(e) = check_admin_token(admin_token.as_deref(), &headers) {
+        return Err((e.status(), Json(ApiResponse::error(e.message()))));
+    }
+
+    // Snapshotting copies the proving key, so keep it off the runtime threads
+    let loaded = {
+        let circuit_path = circuit_path.clone();
+        tokio::task::spawn_blocking(move || load_circuit(&circuit_path))
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()))
+    };
+    let circuit = match loaded {
+        Ok(circuit) => circuit,
+        Err(e) => {
+            warn!("Rejecting circuit reload from {:?}: {}", circuit_path, e);
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ApiResponse::error(format!("Invalid circuit: {}", e))),
+            ));
+        }
+    };
+    let circuit_version = circuit.version().unwrap_or_default().to_string();
+
+    let previous = state.write().await.circuit.replace(Arc::new(circuit));
+    let previous_version = previous.and_then(|c| c.version().map(str::to_string));
+    info!(
+        "Reloaded circuit {:?}: {:?} -> {}",
+        circuit_path, previous_version, circuit_version
+    );
+
+    Ok(Json(ApiResponse::success(ReloadResponse {
+        circuit_version,
+        previous_version,
+    })))
+}
+
+/// Check the circuit's MiMC parameters and snapshot its artifact set
+fn load_circuit(circuit_path: &std::path::Path) -> Result<NoirProver, String> {
+    let hash_lib = circuit_path.join("../zelana_lib/src/poseidon.nr");
+    let hash_lib_source = std::fs::read_to_string(&hash_lib).ok();
+    mimc::check_circuit_parameters(hash_lib_source.as_deref())?;
+    NoirProver::load(circuit_path.to_path_buf()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::header::AUTHORIZATION;
+
+    fn write_artifact(circuit_path: &std::path::Path, contents: &str) {
+        let artifact = circuit_path.join(prover::CIRCUIT_ARTIFACT);
+        std::fs::create_dir_all(artifact.parent().unwrap()).unwrap();
+        std::fs::write(artifact, contents).unwrap();
+    }
+
+    /// A deployable circuit: Noir package, ACIR and sunspot artifacts
+    fn write_circuit(circuit_path: &std::path::Path, bytecode: &str) {
+        std::fs::create_dir_all(circuit_path.join("src")).unwrap();
+        std::fs::write(
+            circuit_path.join("Nargo.toml"),
+            "[package]\nname = \"batch_processor\"\n",
+        )
+        .unwrap();
+        std::fs::write(circuit_path.join("src/main.nr"), "fn main() {}").unwrap();
+        write_artifact(circuit_path, bytecode);
+        for artifact in prover::SUNSPOT_ARTIFACTS {
+            std::fs::write(circuit_path.join(artifact), artifact).unwrap();
+        }
+    }
+
+    async fn reported_version(state: &SharedState) -> Option<String> {
+        match health_handler(State(state.clone())).await.0 {
+            ApiResponse::Success { data } => data.circuit_version,
+            ApiResponse::Error { message } => panic!("health failed: {}", message),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reload_updates_circuit_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let circuit_path = dir.path().join("batch_processor");
+        write_circuit(&circuit_path, r#"{"bytecode":"v1"}"#);
+
+        let config = Args::try_parse_from([
+            "prover-worker",
+            "--circuit-path",
+            circuit_path.to_str().unwrap(),
+            "--admin-token",
+            "secret",
+        ])
+        .unwrap();
+        let circuit = NoirProver::load(circuit_path.clone()).unwrap();
+        let v1 = circuit.version().unwrap().to_string();
+        let state: SharedState = Arc::new(RwLock::new(WorkerState {
+            config,
+            jobs: HashMap::new(),
+            active_job_count: 0,
+            total_proofs: 0,
+            avg_proving_time_ms: 0,
+            circuit: Some(Arc::new(circuit)),
+        }));
+
+        let reload = |auth: Option<&str>| {
+            let mut headers = HeaderMap::new();
+            if let Some(auth) = auth {
+                headers.insert(AUTHORIZATION, auth.parse().unwrap());
+            }
+            reload_handler(State(state.clone()), headers)
+        };
+
+        // Deploy a new artifact: the version only changes once reloaded
+        write_artifact(&circuit_path, r#"{"bytecode":"v2"}"#);
+        assert_eq!(reported_version(&state).await, Some(v1.clone()));
+        assert_eq!(
+            reload(Some("Bearer wrong")).await.unwrap_err().0,
+            StatusCode::UNAUTHORIZED
+        );
+
+        let ApiResponse::Success { data } = reload(Some("Bearer secret")).await.unwrap().0 else {
+            panic!("reload failed");
+        };
+        assert_ne!(data.circuit_version, v1);
+        assert_eq!(data.previous_version, Some(v1));
+        let v2 = data.circuit_version;
+        assert_eq!(reported_version(&state).await, Some(v2.clone()));
+        assert_eq!(v2, prover::circuit_version(&circuit_path).unwrap());
+
+        // A broken artifact is rejected and the old version kept
+        write_artifact(&circuit_path, "not json");
+        assert_eq!(
+            reload(Some("Bearer secret")).await.unwrap_err().0,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(reported_version(&state).await, Some(v2));
+    }
+
+    #[tokio::test]
+    async fn test_prove_rejects_invalid_request() {
+        let config = Args::try_parse_from([
+            "prover-worker",
+            "--mock-prover",
+            "true",
+            "--mock-delay-ms",
+            "0",
+        ])
+        .unwrap();
+        let state: SharedState = Arc::new(RwLock::new(WorkerState {
+            config,
+            jobs: HashMap::new(),
+            active_job_count: 0,
+            total_proofs: 0,
+            avg_proving_time_ms: 0,
+            circuit: None,
+        }));
+        let request = |signature: &str| ProveRequest {
+            chunk_id: 0,
+            pre_root: "0x1".to_string(),
+            post_root: "0x2".to_string(),
+            transactions: vec![ChunkTransaction {
+                sender_pubkey: "0xa".to_string(),
+                receiver_pubkey: "0xb".to_string(),
+                amount: 5,
+                signature: signature.to_string(),
+                merkle_path: vec!["0x0".to_string(); prover::MERKLE_DEPTH],
+            }],
+        };
+
+        // A signature that isn't a field element never reaches the prover
+        let (status, _) = prove_handler(State(state.clone()), Json(request("0xsig")))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(state.read().await.jobs.is_empty());
+
+        assert!(
+            prove_handler(State(state.clone()), Json(request("0x1")))
+                .await
+                .is_ok()
+        );
+    }
+}
@@ -3,14 +3,37 @@
 //! Rust implementation of MiMC hash matching the Noir circuit in zelana_lib/poseidon.nr.
 //! Uses the same MiMC-like construction with x^7 S-box and 91 rounds.
 //!
-//! This is used to compute public inputs (batch_hash, withdrawal_root) that
-//! will match what the circuit computes internally.
+//! This is used to compute the `batch_hash` public input so it matches what
+//! the circuit computes internally. The `withdrawal_root` is a Poseidon tree
+//! shared with the bridge (`zelana_bridge_events::merkle`).
 
 use ark_bn254::Fr;
-use ark_ff::{BigInteger, Field, PrimeField};
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use num_bigint::BigUint;
 
-/// Number of MiMC rounds (matches circuit)
-const MIMC_ROUNDS: usize = 91;
+/// Number of MiMC rounds.
+///
+/// Must equal `MIMC_ROUNDS` in `circuits/zelana_lib/src/poseidon.nr`; any
+/// difference yields hashes the circuit rejects, so proofs fail verification.
+pub const MIMC_ROUNDS: usize = 91;
+
+/// Round constants matching Noir: RC[i] = (i+1)^3 + (i+1)
+pub const ROUND_CONSTANTS: [u64; MIMC_ROUNDS] = round_constants();
+
+const fn round_constants() -> [u64; MIMC_ROUNDS] {
+    let mut rc = [0u64; MIMC_ROUNDS];
+    let mut i = 0;
+    while i < MIMC_ROUNDS {
+        let idx = (i + 1) as u64;
+        rc[i] = idx * idx * idx + idx;
+        i += 1;
+    }
+    rc
+}
+
+/// Number of independent hashes interleaved per batched permutation.
+/// Also the work unit handed to each rayon task with the `parallel` feature.
+const BATCH_LANES: usize = 64;
 
 /// Domain separators matching Noir circuit
 pub mod domain {
@@ -52,13 +75,26 @@ impl Default for MiMC {
 impl MiMC {
     /// Create a new MiMC hasher with precomputed round constants
     pub fn new() -> Self {
-        let round_constants: Vec<Fr> = (0..MIMC_ROUNDS)
+        Self::with_rounds(MIMC_ROUNDS)
+    }
+
+    /// Create a hasher with a non-standard round count.
+    ///
+    /// Only useful for tests and circuit experiments: anything other than
+    /// `MIMC_ROUNDS` will not match the deployed circuit.
+    pub fn with_rounds(rounds: usize) -> Self {
+        let round_constants: Vec<Fr> = (0..rounds)
             .map(|i| Self::compute_round_constant(i))
             .collect();
 
         Self { round_constants }
     }
 
+    /// Number of rounds this hasher applies
+    pub fn rounds(&self) -> usize {
+        self.round_constants.len()
+    }
+
     /// Compute round constant matching Noir: RC[i] = (i+1)^3 + (i+1)
     fn compute_round_constant(i: usize) -> Fr {
         let idx = Fr::from((i + 1) as u64);
@@ -127,6 +163,77 @@ impl MiMC {
         self.sponge_absorb(&[domain, a, b, c, d, e, f], Fr::from(0u64))
     }
 
+    /// Hash many independent N-element inputs (matches `hash_N` per input)
+    ///
+    /// Inputs are processed in lanes of `BATCH_LANES`: every round is applied
+    /// across all lanes before moving on, so round constants stay hot and the
+    /// field multiplications of independent lanes can overlap. The shared
+    /// domain-absorb step is computed once per batch. With the `parallel`
+    /// feature, lane groups are spread across threads via rayon.
+    ///
+    /// Output is bit-identical to calling `hash_N` on each input.
+    pub fn hash_batch<const N: usize>(&self, inputs: &[[Fr; N]]) -> Vec<Fr> {
+        // Every input starts by absorbing the same domain separator (N) into
+        // a zero state, so that first permutation is shared by all lanes
+        let domain_state = self.permute(Fr::from(N as u64), Fr::from(0u64));
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            inputs
+                .par_chunks(BATCH_LANES)
+                .flat_map_iter(|chunk| self.absorb_lanes(domain_state, chunk))
+                .collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            inputs
+                .chunks(BATCH_LANES)
+                .flat_map(|chunk| self.absorb_lanes(domain_state, chunk))
+                .collect()
+        }
+    }
+
+    /// Batched `hash_2` (e.g. one Merkle tree level)
+    pub fn hash_2_batch(&self, pairs: &[[Fr; 2]]) -> Vec<Fr> {
+        self.hash_batch(pairs)
+    }
+
+    /// Batched `hash_3`
+    pub fn hash_3_batch(&self, inputs: &[[Fr; 3]]) -> Vec<Fr> {
+        self.hash_batch(inputs)
+    }
+
+    /// Batched `hash_4` (e.g. account leaves, transaction hashes)
+    pub fn hash_4_batch(&self, inputs: &[[Fr; 4]]) -> Vec<Fr> {
+        self.hash_batch(inputs)
+    }
+
+    /// Absorb one lane group, starting every lane from `domain_state`
+    fn absorb_lanes<const N: usize>(&self, domain_state: Fr, lanes: &[[Fr; N]]) -> Vec<Fr> {
+        let mut states = vec![domain_state; lanes.len()];
+        for j in 0..N {
+            for (state, input) in states.iter_mut().zip(lanes) {
+                *state += input[j];
+            }
+            self.permute_lanes(&mut states, Fr::from(0u64));
+        }
+        states
+    }
+
+    /// MiMC permutation applied to every lane, round by round
+    fn permute_lanes(&self, states: &mut [Fr], k: Fr) {
+        for c in &self.round_constants {
+            for state in states.iter_mut() {
+                *state = self.round(*state, k, *c);
+            }
+        }
+        for state in states.iter_mut() {
+            *state += k; // Final key addition
+        }
+    }
+
     /// Compute account leaf: hash_4(domain_account, pubkey, balance, nonce)
     /// Matches circuit's compute_account_leaf: hash_4(domain_account(), pubkey, balance, nonce)
     pub fn compute_account_leaf(&self, pubkey: Fr, balance: Fr, nonce: Fr) -> Fr {
@@ -141,6 +248,47 @@ impl MiMC {
     }
 }
 
+// Circuit Parameter Guard
+
+/// Extract `MIMC_ROUNDS` from the Noir hash library source (`poseidon.nr`)
+pub fn circuit_mimc_rounds(noir_source: &str) -> Option<usize> {
+    noir_source.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("global MIMC_ROUNDS")?;
+        let value = rest.split('=').nth(1)?.split(';').next()?;
+        value.trim().parse().ok()
+    })
+}
+
+/// Log the active MiMC parameters and compare them against the circuit.
+///
+/// `noir_source` is the contents of `zelana_lib/src/poseidon.nr` if it could
+/// be read. Returns an error when the circuit declares a different round count.
+pub fn check_circuit_parameters(noir_source: Option<&str>) -> Result<(), String> {
+    tracing::info!(
+        rounds = MIMC_ROUNDS,
+        rc_first = ROUND_CONSTANTS[0],
+        rc_last = ROUND_CONSTANTS[MIMC_ROUNDS - 1],
+        "MiMC parameters"
+    );
+
+    let Some(source) = noir_source else {
+        tracing::warn!("Noir hash library not found, skipping MiMC round count check");
+        return Ok(());
+    };
+
+    match circuit_mimc_rounds(source) {
+        Some(rounds) if rounds == MIMC_ROUNDS => Ok(()),
+        Some(rounds) => Err(format!(
+            "MiMC round count mismatch: worker uses {}, circuit declares {}",
+            MIMC_ROUNDS, rounds
+        )),
+        None => {
+            tracing::warn!("MIMC_ROUNDS not found in Noir hash library, skipping check");
+            Ok(())
+        }
+    }
+}
+
 // Batch Hash Computation (matches circuit logic)
 
 /// Transfer data for batch hash computation
@@ -152,6 +300,16 @@ pub struct TransferData {
     pub sender_nonce: Fr,
 }
 
+impl TransferData {
+    /// Check the transfer is a well-formed circuit input
+    pub fn validate(&self) -> Result<(), InputError> {
+        require_non_zero("sender_pubkey", self.sender_pubkey)?;
+        require_non_zero("receiver_pubkey", self.receiver_pubkey)?;
+        require_u64("amount", self.amount)?;
+        require_u64("sender_nonce", self.sender_nonce)
+    }
+}
+
 /// Withdrawal data for batch hash computation
 #[derive(Debug, Clone)]
 pub struct WithdrawalData {
@@ -160,6 +318,15 @@ pub struct WithdrawalData {
     pub amount: Fr,
 }
 
+impl WithdrawalData {
+    /// Check the withdrawal is a well-formed circuit input
+    pub fn validate(&self) -> Result<(), InputError> {
+        require_non_zero("sender_pubkey", self.sender_pubkey)?;
+        require_non_zero("l1_recipient", self.l1_recipient)?;
+        require_u64("amount", self.amount)
+    }
+}
+
 /// Shielded transaction data for batch hash computation
 #[derive(Debug, Clone)]
 pub struct ShieldedData {
@@ -167,6 +334,97 @@ pub struct ShieldedData {
     pub output_commitment: Fr,
 }
 
+impl ShieldedData {
+    /// Check the shielded transaction is a well-formed circuit input
+    pub fn validate(&self) -> Result<(), InputError> {
+        require_non_zero("nullifier", self.nullifier)?;
+        require_non_zero("output_commitment", self.output_commitment)
+    }
+}
+
+// Input Validation
+
+/// A circuit input that would produce an unprovable witness
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InputError {
+    #[error("{field}: malformed field element ({reason})")]
+    Malformed {
+        field: &'static str,
+        reason: &'static str,
+    },
+    #[error("{field}: value is not below the BN254 field modulus")]
+    OutOfRange { field: &'static str },
+    #[error("{field}: value does not fit in u64")]
+    AmountOverflow { field: &'static str },
+    #[error("{field}: must be non-zero")]
+    Zero { field: &'static str },
+}
+
+fn require_non_zero(field: &'static str, value: Fr) -> Result<(), InputError> {
+    if value.is_zero() {
+        return Err(InputError::Zero { field });
+    }
+    Ok(())
+}
+
+fn require_u64(field: &'static str, value: Fr) -> Result<(), InputError> {
+    if value.into_bigint().as_ref()[1..]
+        .iter()
+        .any(|&limb| limb != 0)
+    {
+        return Err(InputError::AmountOverflow { field });
+    }
+    Ok(())
+}
+
+/// Parse an untrusted field element string, naming `field` on failure.
+///
+/// Hex values (`0x`-prefixed or exactly 64 digits) may be up to 32 bytes and
+/// are reduced mod p, matching how 32-byte keys and hashes enter the circuit.
+/// Decimal values must already be canonical (below the modulus).
+pub fn parse_field_checked(field: &'static str, s: &str) -> Result<Fr, InputError> {
+    let (hex_str, is_hex) = match s.strip_prefix("0x") {
+        Some(rest) => (rest, true),
+        None => (s, s.len() == 64),
+    };
+
+    if hex_str.is_empty() {
+        return Err(InputError::Malformed {
+            field,
+            reason: "empty",
+        });
+    }
+
+    if is_hex {
+        let bytes = hex::decode(hex_str).map_err(|_| InputError::Malformed {
+            field,
+            reason: "invalid hex",
+        })?;
+        if bytes.len() > 32 {
+            return Err(InputError::Malformed {
+                field,
+                reason: "longer than 32 bytes",
+            });
+        }
+        return Ok(Fr::from_be_bytes_mod_order(&bytes));
+    }
+
+    if !hex_str.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(InputError::Malformed {
+            field,
+            reason: "not a decimal or hex number",
+        });
+    }
+    let value: BigUint = hex_str.parse().map_err(|_| InputError::Malformed {
+        field,
+        reason: "invalid decimal",
+    })?;
+    if value >= BigUint::from(Fr::MODULUS) {
+        return Err(InputError::OutOfRange { field });
+    }
+    Ok(Fr::from_be_bytes_mod_order(&value.to_bytes_be()))
+}
+
 /// Compute batch hash matching the Noir circuit
 ///
 /// Matches main.nr:
@@ -219,28 +477,6 @@ pub fn compute_batch_hash(
     mimc.hash_4(batch_acc, num_transfers, num_withdrawals, num_shielded)
 }
 
-/// Compute withdrawal root matching the Noir circuit
-///
-/// Matches main.nr:
-/// ```noir
-/// let mut withdrawal_accumulator = hash_2(domain_withdrawal(), batch_id);
-/// // For each withdrawal: withdrawal_accumulator = hash_2(withdrawal_accumulator, wd_hash)
-/// let final_withdrawal_root = hash_2(withdrawal_accumulator, num_withdrawals);
-/// ```
-pub fn compute_withdrawal_root(mimc: &MiMC, batch_id: Fr, withdrawals: &[WithdrawalData]) -> Fr {
-    let mut wd_acc = mimc.hash_2(domain::withdrawal(), batch_id);
-
-    for wd in withdrawals {
-        // wd_hash = hash_3(l1_recipient, amount, sender_pubkey)
-        let wd_hash = mimc.hash_3(wd.l1_recipient, wd.amount, wd.sender_pubkey);
-        wd_acc = mimc.hash_2(wd_acc, wd_hash);
-    }
-
-    // Finalize with count
-    let num_withdrawals = Fr::from(withdrawals.len() as u64);
-    mimc.hash_2(wd_acc, num_withdrawals)
-}
-
 // Utility Functions
 
 /// Convert 32-byte array to field element
@@ -330,6 +566,43 @@ mod tests {
         assert_eq!(rc2, Fr::from(30u64));
     }
 
+    #[test]
+    fn test_committed_parameters() {
+        assert_eq!(MIMC_ROUNDS, 91);
+        assert_eq!(ROUND_CONSTANTS[0], 2);
+        assert_eq!(ROUND_CONSTANTS[1], 10);
+        assert_eq!(ROUND_CONSTANTS[MIMC_ROUNDS - 1], 753_662);
+        // sum_{i=1..91} (i^3 + i) = 4186^2 + 4186
+        assert_eq!(ROUND_CONSTANTS.iter().sum::<u64>(), 17_526_782);
+
+        let mimc = MiMC::new();
+        assert_eq!(mimc.rounds(), MIMC_ROUNDS);
+        for (i, rc) in ROUND_CONSTANTS.iter().enumerate() {
+            assert_eq!(mimc.round_constants[i], Fr::from(*rc));
+        }
+    }
+
+    #[test]
+    fn test_rounds_match_committed_circuit() {
+        let source = include_str!("../../../circuits/zelana_lib/src/poseidon.nr");
+        assert_eq!(circuit_mimc_rounds(source), Some(MIMC_ROUNDS));
+        assert!(check_circuit_parameters(Some(source)).is_ok());
+
+        let tampered = source.replace("MIMC_ROUNDS: u32 = 91", "MIMC_ROUNDS: u32 = 64");
+        assert_eq!(circuit_mimc_rounds(&tampered), Some(64));
+        assert!(check_circuit_parameters(Some(&tampered)).is_err());
+    }
+
+    #[test]
+    fn test_wrong_round_count_changes_output() {
+        let a = Fr::from(123u64);
+        let b = Fr::from(456u64);
+
+        let expected = MiMC::new().hash_2(a, b);
+        assert_ne!(MiMC::with_rounds(MIMC_ROUNDS - 1).hash_2(a, b), expected);
+        assert_ne!(MiMC::with_rounds(MIMC_ROUNDS + 1).hash_2(a, b), expected);
+    }
+
     #[test]
     fn test_empty_batch_hash() {
         let mimc = MiMC::new();
@@ -347,22 +620,231 @@ mod tests {
     }
 
     #[test]
-    fn test_withdrawal_root() {
+    fn test_batch_matches_scalar() {
+        use rand::Rng;
+
         let mimc = MiMC::new();
-        let batch_id = Fr::from(1u64);
+        let mut rng = rand::rng();
+        let mut random_field = || {
+            let bytes: [u8; 32] = rng.random();
+            Fr::from_le_bytes_mod_order(&bytes)
+        };
 
-        // Empty withdrawals
-        let root1 = compute_withdrawal_root(&mimc, batch_id, &[]);
+        // Cover a partial lane group as well as several full ones
+        for count in [0, 1, BATCH_LANES - 1, BATCH_LANES * 3 + 7] {
+            let pairs: Vec<[Fr; 2]> = (0..count)
+                .map(|_| [random_field(), random_field()])
+                .collect();
+            let triples: Vec<[Fr; 3]> = (0..count)
+                .map(|_| [random_field(), random_field(), random_field()])
+                .collect();
+            let quads: Vec<[Fr; 4]> = (0..count)
+                .map(|_| {
+                    [
+                        random_field(),
+                        random_field(),
+                        random_field(),
+                        random_field(),
+                    ]
+                })
+                .collect();
+            let sixes: Vec<[Fr; 6]> = (0..count)
+                .map(|_| std::array::from_fn(|_| random_field()))
+                .collect();
+
+            let batched = mimc.hash_2_batch(&pairs);
+            let scalar: Vec<Fr> = pairs.iter().map(|[a, b]| mimc.hash_2(*a, *b)).collect();
+            assert_eq!(batched, scalar);
+
+            let batched = mimc.hash_3_batch(&triples);
+            let scalar: Vec<Fr> = triples
+                .iter()
+                .map(|[a, b, c]| mimc.hash_3(*a, *b, *c))
+                .collect();
+            assert_eq!(batched, scalar);
+
+            let batched = mimc.hash_4_batch(&quads);
+            let scalar: Vec<Fr> = quads
+                .iter()
+                .map(|[a, b, c, d]| mimc.hash_4(*a, *b, *c, *d))
+                .collect();
+            assert_eq!(batched, scalar);
+
+            let batched = mimc.hash_batch(&sixes);
+            let scalar: Vec<Fr> = sixes
+                .iter()
+                .map(|[a, b, c, d, e, f]| mimc.hash_6(*a, *b, *c, *d, *e, *f))
+                .collect();
+            assert_eq!(batched, scalar);
+        }
+    }
+
+    #[test]
+    fn test_valid_inputs_pass() {
+        let transfer = TransferData {
+            sender_pubkey: Fr::from(1u64),
+            receiver_pubkey: Fr::from(2u64),
+            amount: Fr::from(u64::MAX),
+            sender_nonce: Fr::from(0u64),
+        };
+        assert_eq!(transfer.validate(), Ok(()));
 
-        // One withdrawal
-        let wd = WithdrawalData {
-            sender_pubkey: Fr::from(100u64),
-            l1_recipient: Fr::from(200u64),
+        let withdrawal = WithdrawalData {
+            sender_pubkey: Fr::from(1u64),
+            l1_recipient: Fr::from(2u64),
             amount: Fr::from(1000u64),
         };
-        let root2 = compute_withdrawal_root(&mimc, batch_id, &[wd]);
+        assert_eq!(withdrawal.validate(), Ok(()));
+
+        let shielded = ShieldedData {
+            nullifier: Fr::from(1u64),
+            output_commitment: Fr::from(2u64),
+        };
+        assert_eq!(shielded.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_transfer_validation_failures() {
+        let valid = TransferData {
+            sender_pubkey: Fr::from(1u64),
+            receiver_pubkey: Fr::from(2u64),
+            amount: Fr::from(10u64),
+            sender_nonce: Fr::from(0u64),
+        };
+        let too_big = Fr::from(u64::MAX) + Fr::from(1u64);
+
+        let mut tx = valid.clone();
+        tx.sender_pubkey = Fr::from(0u64);
+        assert_eq!(
+            tx.validate(),
+            Err(InputError::Zero {
+                field: "sender_pubkey"
+            })
+        );
+
+        let mut tx = valid.clone();
+        tx.receiver_pubkey = Fr::from(0u64);
+        assert_eq!(
+            tx.validate(),
+            Err(InputError::Zero {
+                field: "receiver_pubkey"
+            })
+        );
+
+        let mut tx = valid.clone();
+        tx.amount = too_big;
+        assert_eq!(
+            tx.validate(),
+            Err(InputError::AmountOverflow { field: "amount" })
+        );
+
+        let mut tx = valid;
+        tx.sender_nonce = too_big;
+        assert_eq!(
+            tx.validate(),
+            Err(InputError::AmountOverflow {
+                field: "sender_nonce"
+            })
+        );
+    }
+
+    #[test]
+    fn test_withdrawal_validation_failures() {
+        let valid = WithdrawalData {
+            sender_pubkey: Fr::from(1u64),
+            l1_recipient: Fr::from(2u64),
+            amount: Fr::from(10u64),
+        };
+
+        let mut wd = valid.clone();
+        wd.sender_pubkey = Fr::from(0u64);
+        assert_eq!(
+            wd.validate(),
+            Err(InputError::Zero {
+                field: "sender_pubkey"
+            })
+        );
+
+        let mut wd = valid.clone();
+        wd.l1_recipient = Fr::from(0u64);
+        assert_eq!(
+            wd.validate(),
+            Err(InputError::Zero {
+                field: "l1_recipient"
+            })
+        );
+
+        let mut wd = valid;
+        wd.amount = -Fr::from(1u64);
+        assert_eq!(
+            wd.validate(),
+            Err(InputError::AmountOverflow { field: "amount" })
+        );
+    }
+
+    #[test]
+    fn test_shielded_validation_failures() {
+        let sh = ShieldedData {
+            nullifier: Fr::from(0u64),
+            output_commitment: Fr::from(2u64),
+        };
+        assert_eq!(sh.validate(), Err(InputError::Zero { field: "nullifier" }));
+
+        let sh = ShieldedData {
+            nullifier: Fr::from(1u64),
+            output_commitment: Fr::from(0u64),
+        };
+        assert_eq!(
+            sh.validate(),
+            Err(InputError::Zero {
+                field: "output_commitment"
+            })
+        );
+    }
 
-        assert_ne!(root1, root2);
+    #[test]
+    fn test_parse_field_checked() {
+        assert_eq!(parse_field_checked("x", "12345"), Ok(Fr::from(12345u64)));
+        assert_eq!(parse_field_checked("x", "0x3039"), Ok(Fr::from(12345u64)));
+        // 32-byte hex values are reduced like the rest of the pipeline
+        assert!(parse_field_checked("x", &"ff".repeat(32)).is_ok());
+
+        assert_eq!(
+            parse_field_checked("pre_root", ""),
+            Err(InputError::Malformed {
+                field: "pre_root",
+                reason: "empty"
+            })
+        );
+        assert_eq!(
+            parse_field_checked("pubkey", "0xzz"),
+            Err(InputError::Malformed {
+                field: "pubkey",
+                reason: "invalid hex"
+            })
+        );
+        assert_eq!(
+            parse_field_checked("pubkey", &format!("0x{}", "00".repeat(33))),
+            Err(InputError::Malformed {
+                field: "pubkey",
+                reason: "longer than 32 bytes"
+            })
+        );
+        assert_eq!(
+            parse_field_checked("pubkey", "12ab"),
+            Err(InputError::Malformed {
+                field: "pubkey",
+                reason: "not a decimal or hex number"
+            })
+        );
+        // The modulus itself is out of range for a decimal field element
+        assert_eq!(
+            parse_field_checked(
+                "amount",
+                "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+            ),
+            Err(InputError::OutOfRange { field: "amount" })
+        );
     }
 
     #[test]
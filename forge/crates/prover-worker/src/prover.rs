@@ -6,7 +6,8 @@
 
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use thiserror::Error;
 use tokio::process::Command;
@@ -101,6 +102,15 @@ pub enum ProverError {
 
     #[error("Proof file not found: {0}")]
     ProofFileNotFound(PathBuf),
+
+    #[error("Circuit artifact not found: {0}")]
+    MissingArtifact(PathBuf),
+
+    #[error("Invalid circuit artifact {0}: {1}")]
+    InvalidArtifact(PathBuf, String),
+
+    #[error("Invalid verifying key: {0}")]
+    InvalidVerifyingKey(String),
 }
 
 // Circuit Constants
@@ -163,6 +173,12 @@ pub struct WithdrawalWitness {
     pub sender_path: [String; MERKLE_DEPTH],
     pub sender_path_indices: [String; MERKLE_DEPTH],
     pub l1_recipient: String,
+    /// `l1_recipient` and the L2 tx hash as decimal 128-bit halves, the
+    /// inputs of the withdrawal's leaf (see [`u128_halves`])
+    pub l1_recipient_hi: String,
+    pub l1_recipient_lo: String,
+    pub tx_hash_hi: String,
+    pub tx_hash_lo: String,
     pub amount: String,
     pub signature: String,
     pub is_valid: bool,
@@ -177,6 +193,10 @@ impl Default for WithdrawalWitness {
             sender_path: std::array::from_fn(|_| "0".to_string()),
             sender_path_indices: std::array::from_fn(|_| "0".to_string()),
             l1_recipient: "0".to_string(),
+            l1_recipient_hi: "0".to_string(),
+            l1_recipient_lo: "0".to_string(),
+            tx_hash_hi: "0".to_string(),
+            tx_hash_lo: "0".to_string(),
             amount: "0".to_string(),
             signature: "0".to_string(),
             is_valid: false,
@@ -184,6 +204,16 @@ impl Default for WithdrawalWitness {
     }
 }
 
+/// Decimal high and low 128-bit halves of a 32-byte big-endian value, as the
+/// circuit's `*_hi`/`*_lo` withdrawal inputs
+pub fn u128_halves(bytes: &[u8; 32]) -> (String, String) {
+    let (hi, lo) = bytes.split_at(16);
+    (
+        u128::from_be_bytes(hi.try_into().expect("16 bytes")).to_string(),
+        u128::from_be_bytes(lo.try_into().expect("16 bytes")).to_string(),
+    )
+}
+
 /// Shielded transaction witness
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShieldedWitness {
@@ -294,6 +324,137 @@ impl BatchInputs {
         }
     }
 
+    /// Check the witness is provable, using the exact values that will be
+    /// written to `Prover.toml`.
+    ///
+    /// Every field element must parse and be in range, and each active
+    /// transfer, withdrawal and shielded transaction must be well-formed.
+    pub fn validate(&self) -> Result<(), String> {
+        use crate::mimc::{ShieldedData, TransferData, WithdrawalData, parse_field_checked};
+        use ark_bn254::Fr;
+
+        let field = |name: &'static str, value: &str| {
+            parse_field_checked(name, value).map_err(|e| e.to_string())
+        };
+        let path = |name: &'static str, path: &[String]| {
+            path.iter().try_for_each(|p| field(name, p).map(drop))
+        };
+
+        field("pre_state_root", &self.pre_state_root)?;
+        field("post_state_root", &self.post_state_root)?;
+        field("pre_shielded_root", &self.pre_shielded_root)?;
+        field("post_shielded_root", &self.post_shielded_root)?;
+        field("withdrawal_root", &self.withdrawal_root)?;
+        field("batch_hash", &self.batch_hash)?;
+        field("batch_id", &self.batch_id)?;
+
+        for (kind, len, max) in [
+            ("transfers", self.transfers.len(), MAX_TRANSFERS),
+            ("withdrawals", self.withdrawals.len(), MAX_WITHDRAWALS),
+            ("shielded", self.shielded.len(), MAX_SHIELDED),
+        ] {
+            if len > max {
+                return Err(format!(
+                    "{}: {} exceeds circuit capacity {}",
+                    kind, len, max
+                ));
+            }
+        }
+
+        for (i, t) in self.transfers.iter().enumerate() {
+            if !t.is_valid {
+                continue;
+            }
+            let check = || -> Result<(), String> {
+                TransferData {
+                    sender_pubkey: field("sender_pubkey", &t.sender_pubkey)?,
+                    receiver_pubkey: field("receiver_pubkey", &t.receiver_pubkey)?,
+                    amount: field("amount", &t.amount)?,
+                    sender_nonce: field("sender_nonce", &t.sender_nonce)?,
+                }
+                .validate()
+                .map_err(|e| e.to_string())?;
+                field("sender_balance", &t.sender_balance)?;
+                field("receiver_balance", &t.receiver_balance)?;
+                field("receiver_nonce", &t.receiver_nonce)?;
+                field("signature", &t.signature)?;
+                path("sender_path", &t.sender_path)?;
+                path("sender_path_indices", &t.sender_path_indices)?;
+                path("receiver_path", &t.receiver_path)?;
+                path("receiver_path_indices", &t.receiver_path_indices)
+            };
+            check().map_err(|e| format!("transfer {}: {}", i, e))?;
+        }
+
+        for (i, w) in self.withdrawals.iter().enumerate() {
+            if !w.is_valid {
+                continue;
+            }
+            let check = || -> Result<(), String> {
+                WithdrawalData {
+                    sender_pubkey: field("sender_pubkey", &w.sender_pubkey)?,
+                    l1_recipient: field("l1_recipient", &w.l1_recipient)?,
+                    amount: field("amount", &w.amount)?,
+                }
+                .validate()
+                .map_err(|e| e.to_string())?;
+                let half = |name: &str, value: &str| {
+                    value
+                        .parse::<u128>()
+                        .map(Fr::from)
+                        .map_err(|_| format!("{}: not a 128-bit decimal", name))
+                };
+                let recipient_hi = half("l1_recipient_hi", &w.l1_recipient_hi)?;
+                let recipient_lo = half("l1_recipient_lo", &w.l1_recipient_lo)?;
+                half("tx_hash_hi", &w.tx_hash_hi)?;
+                half("tx_hash_lo", &w.tx_hash_lo)?;
+                let two_pow_128 = Fr::from(u128::MAX) + Fr::from(1u64);
+                if recipient_hi * two_pow_128 + recipient_lo
+                    != field("l1_recipient", &w.l1_recipient)?
+                {
+                    return Err("l1_recipient_hi/lo: do not recombine to l1_recipient".to_string());
+                }
+                field("sender_balance", &w.sender_balance)?;
+                field("sender_nonce", &w.sender_nonce)?;
+                field("signature", &w.signature)?;
+                path("sender_path", &w.sender_path)?;
+                path("sender_path_indices", &w.sender_path_indices)
+            };
+            check().map_err(|e| format!("withdrawal {}: {}", i, e))?;
+        }
+
+        for (i, sh) in self.shielded.iter().enumerate() {
+            if !sh.is_valid {
+                continue;
+            }
+            let check = || -> Result<(), String> {
+                ShieldedData {
+                    nullifier: field("nullifier", &sh.nullifier)?,
+                    output_commitment: field("output_commitment", &sh.output_commitment)?,
+                }
+                .validate()
+                .map_err(|e| e.to_string())?;
+                for (name, value) in [
+                    ("input_owner", &sh.input_owner),
+                    ("input_value", &sh.input_value),
+                    ("input_blinding", &sh.input_blinding),
+                    ("input_position", &sh.input_position),
+                    ("spending_key", &sh.spending_key),
+                    ("output_owner", &sh.output_owner),
+                    ("output_value", &sh.output_value),
+                    ("output_blinding", &sh.output_blinding),
+                ] {
+                    field(name, value)?;
+                }
+                path("input_path", &sh.input_path)?;
+                path("input_path_indices", &sh.input_path_indices)
+            };
+            check().map_err(|e| format!("shielded {}: {}", i, e))?;
+        }
+
+        Ok(())
+    }
+
     /// Normalize all field values for Noir circuit consumption.
     /// Converts hex strings (32-byte/64-char) to decimal strings.
     /// Noir/nargo expects Field values as decimal integers, not hex.
@@ -354,7 +515,33 @@ pub struct ChunkInputs {
     pub merkle_paths: Vec<Vec<String>>,
 }
 
+/// Batch id legacy chunk proofs are generated under
+const CHUNK_BATCH_ID: u64 = 1;
+
 impl ChunkInputs {
+    /// Check the chunk fits the circuit and that the witness it converts to
+    /// is provable
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sender_pubkeys.len() > MAX_TRANSFERS {
+            return Err(format!(
+                "transfers: {} exceeds circuit capacity {}",
+                self.sender_pubkeys.len(),
+                MAX_TRANSFERS
+            ));
+        }
+        if let Some(i) = self
+            .merkle_paths
+            .iter()
+            .position(|path| path.len() > MERKLE_DEPTH)
+        {
+            return Err(format!(
+                "transfer {}: merkle path exceeds depth {}",
+                i, MERKLE_DEPTH
+            ));
+        }
+        self.to_batch_inputs(CHUNK_BATCH_ID).validate()
+    }
+
     /// Convert to BatchInputs format for the zelana_batch circuit
     pub fn to_batch_inputs(&self, batch_id: u64) -> BatchInputs {
         let mut batch = BatchInputs::empty_batch(
@@ -423,6 +610,8 @@ pub struct ProofResult {
     pub proof: String,
     /// Public inputs as hex strings
     pub public_inputs: Vec<String>,
+    /// [`vk_hash`] of the verifying key the proof was generated against
+    pub vk_hash: String,
 }
 
 impl ProofResult {
@@ -435,29 +624,333 @@ impl ProofResult {
     }
 }
 
+/// Hash a verifying key in its canonical encoding (hex-encoded SHA-256).
+///
+/// The preimage is alpha_g1 ‖ beta_g2 ‖ gamma_g2 ‖ delta_g2 ‖ IC points, in
+/// the alt_bn128 big-endian layout the verifier program's `BatchVerifyingKey`
+/// stores. The coordinator compares this across chunk proofs and against the
+/// VK deployed for the domain before settling.
+pub fn vk_hash(vk_points: &[u8]) -> String {
+    use sha2::Digest;
+    hex::encode(sha2::Sha256::digest(vk_points))
+}
+
+/// [`vk_hash`] of a verifying key file written by `sunspot setup`
+pub fn vk_file_hash(vk_file: &[u8]) -> Result<String, ProverError> {
+    gnark_vk_points(vk_file).map(|points| vk_hash(&points))
+}
+
+/// Uncompressed BN254 G1 / G2 point sizes in gnark's raw encoding
+const G1_LEN: usize = 64;
+const G2_LEN: usize = 128;
+
+/// Extract the canonical VK points from a gnark Groth16 verifying key.
+///
+/// gnark's raw layout is alpha_g1, beta_g1, beta_g2, gamma_g2, delta_g1,
+/// delta_g2, then the IC points (`K`) behind a big-endian u32 count. Its
+/// uncompressed points are already in the alt_bn128 byte order, so the
+/// points are copied as-is. Compressed keys are rejected.
+fn gnark_vk_points(vk: &[u8]) -> Result<Vec<u8>, ProverError> {
+    let alpha = 0..G1_LEN;
+    let beta = 2 * G1_LEN..2 * G1_LEN + G2_LEN;
+    let gamma = beta.end..beta.end + G2_LEN;
+    let delta = gamma.end + G1_LEN..gamma.end + G1_LEN + G2_LEN;
+    let ic_count_at = delta.end;
+
+    let Some(count) = vk.get(ic_count_at..ic_count_at + 4) else {
+        return Err(ProverError::InvalidVerifyingKey(format!(
+            "key is {} bytes",
+            vk.len()
+        )));
+    };
+    let ic_count = u32::from_be_bytes(count.try_into().expect("4 bytes")) as usize;
+    let ic_start = ic_count_at + 4;
+    let Some(ic) = ic_count
+        .checked_mul(G1_LEN)
+        .and_then(|len| vk.get(ic_start..ic_start + len))
+    else {
+        return Err(ProverError::InvalidVerifyingKey(format!(
+            "key is {} bytes, too short for {} IC points",
+            vk.len(),
+            ic_count
+        )));
+    };
+    if ic_count == 0 {
+        return Err(ProverError::InvalidVerifyingKey(
+            "key has no IC points".to_string(),
+        ));
+    }
+
+    // gnark keeps its point flags in the top two bits of the first byte
+    let points = [alpha, beta, gamma, delta]
+        .into_iter()
+        .map(|range| &vk[range])
+        .chain(ic.chunks(G1_LEN));
+    let mut canonical = Vec::with_capacity(G1_LEN + 3 * G2_LEN + ic.len());
+    for point in points {
+        if point[0] >> 6 != 0 {
+            return Err(ProverError::InvalidVerifyingKey(
+                "key uses compressed points".to_string(),
+            ));
+        }
+        canonical.extend_from_slice(point);
+    }
+    Ok(canonical)
+}
+
+/// VK hash reported by the mock prover when no real VK file is available
+pub fn mock_vk_hash() -> String {
+    vk_hash(b"zelana-mock-vk")
+}
+
+/// Compiled circuit (ACIR) produced by `nargo compile`, relative to the circuit directory
+pub const CIRCUIT_ARTIFACT: &str = "target/zelana_batch.json";
+
+/// Constraint system, proving key and verifying key produced by `sunspot
+/// compile`/`sunspot setup`, relative to the circuit directory
+pub const SUNSPOT_ARTIFACTS: [&str; 3] = [
+    "target/zelana_batch.ccs",
+    "target/zelana_batch.pk",
+    "target/zelana_batch.vk",
+];
+
+/// Where [`NoirProver::load`] keeps its snapshots, relative to the circuit
+/// directory: one directory per artifact-set version
+pub const SNAPSHOT_DIR: &str = "target/snapshots";
+
+/// Version of the circuit in `circuit_path`: hex SHA-256 over every file a
+/// proof reads (the Noir package and its path dependencies, the ACIR, and
+/// the sunspot constraint system and keys).
+///
+/// Fails if any of them is missing or the ACIR isn't a compiled Noir program.
+pub fn circuit_version(circuit_path: &Path) -> Result<String, ProverError> {
+    check_artifact(circuit_path)?;
+    artifact_set_version(&artifact_files(circuit_path)?)
+}
+
+/// Check that the compiled circuit is a Noir program
+fn check_artifact(circuit_path: &Path) -> Result<(), ProverError> {
+    let artifact_path = circuit_path.join(CIRCUIT_ARTIFACT);
+    if !artifact_path.exists() {
+        return Err(ProverError::CircuitNotFound(artifact_path));
+    }
+    let artifact = std::fs::read(&artifact_path)?;
+
+    let program: serde_json::Value = serde_json::from_slice(&artifact)
+        .map_err(|e| ProverError::InvalidArtifact(artifact_path.clone(), e.to_string()))?;
+    if !program.get("bytecode").is_some_and(|b| b.is_string()) {
+        return Err(ProverError::InvalidArtifact(
+            artifact_path,
+            "missing bytecode".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Name of the circuit package directory
+fn package_name(circuit_path: &Path) -> Result<&std::ffi::OsStr, ProverError> {
+    circuit_path
+        .file_name()
+        .ok_or_else(|| ProverError::CircuitNotFound(circuit_path.to_path_buf()))
+}
+
+/// Every file a proof reads, keyed by its path relative to the directory
+/// holding the circuit package, so path dependencies keep their place
+fn artifact_files(circuit_path: &Path) -> Result<BTreeMap<PathBuf, PathBuf>, ProverError> {
+    let root = circuit_path
+        .parent()
+        .ok_or_else(|| ProverError::CircuitNotFound(circuit_path.to_path_buf()))?;
+    let name = Path::new(package_name(circuit_path)?);
+
+    let mut files = BTreeMap::new();
+    let mut packages = vec![circuit_path.to_path_buf()];
+    let mut seen = HashSet::new();
+    while let Some(package) = packages.pop() {
+        if !seen.insert(package.clone()) {
+            continue;
+        }
+        let package_rel = Path::new(package_name(&package)?);
+
+        // `nargo execute` compiles the package and its path dependencies
+        let manifest = package.join("Nargo.toml");
+        if !manifest.is_file() {
+            return Err(ProverError::MissingArtifact(manifest));
+        }
+        let nargo_toml: toml::Table = toml::from_str(&std::fs::read_to_string(&manifest)?)
+            .map_err(|e| ProverError::InvalidArtifact(manifest.clone(), e.to_string()))?;
+        let dependencies = nargo_toml
+            .get("dependencies")
+            .and_then(|d| d.as_table())
+            .into_iter()
+            .flat_map(|d| d.values());
+        for path in dependencies.filter_map(|d| d.get("path")?.as_str()) {
+            let dependency = package
+                .join(path)
+                .canonicalize()
+                .map_err(|_| ProverError::MissingArtifact(package.join(path)))?;
+            // Snapshots lay packages out side by side, as the circuits are
+            if dependency.parent() != Some(root) {
+                return Err(ProverError::InvalidArtifact(
+                    manifest,
+                    format!("dependency {} is not beside the circuit", path),
+                ));
+            }
+            packages.push(dependency);
+        }
+        files.insert(package_rel.join("Nargo.toml"), manifest);
+
+        let src = package.join("src");
+        if !src.is_dir() {
+            return Err(ProverError::MissingArtifact(src));
+        }
+        collect_files(&src, &package_rel.join("src"), &mut files)?;
+    }
+
+    for artifact in std::iter::once(CIRCUIT_ARTIFACT).chain(SUNSPOT_ARTIFACTS) {
+        let path = circuit_path.join(artifact);
+        if !path.is_file() {
+            return Err(ProverError::MissingArtifact(path));
+        }
+        files.insert(name.join(artifact), path);
+    }
+    Ok(files)
+}
+
+/// Add the files under `dir` to `files`, keyed under `rel`
+fn collect_files(
+    dir: &Path,
+    rel: &Path,
+    files: &mut BTreeMap<PathBuf, PathBuf>,
+) -> Result<(), ProverError> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let rel = rel.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), &rel, files)?;
+        } else {
+            files.insert(rel, entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// Hex SHA-256 over an artifact set's relative paths and contents
+fn artifact_set_version(files: &BTreeMap<PathBuf, PathBuf>) -> Result<String, ProverError> {
+    use sha2::Digest;
+
+    let mut hasher = sha2::Sha256::new();
+    for (rel, path) in files {
+        let mut file = std::fs::File::open(path)?;
+        let rel = rel.to_string_lossy();
+        hasher.update((rel.len() as u64).to_le_bytes());
+        hasher.update(rel.as_bytes());
+        hasher.update(file.metadata()?.len().to_le_bytes());
+        std::io::copy(&mut file, &mut hasher)?;
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Copy the circuit's artifact set into `SNAPSHOT_DIR/<version>`, returning
+/// the snapshot's circuit directory and the version
+///
+/// The version is taken over the copied files, so it names exactly what the
+/// snapshot holds even if the circuit is redeployed while copying. Loading
+/// the same artifacts again reuses the existing snapshot.
+fn snapshot(circuit_path: &Path) -> Result<(PathBuf, String), ProverError> {
+    let files = artifact_files(circuit_path)?;
+    let snapshots = circuit_path.join(SNAPSHOT_DIR);
+    let staging = snapshots.join(format!(".staging-{}", uuid::Uuid::new_v4().simple()));
+
+    let result = (|| {
+        let mut copied = BTreeMap::new();
+        for (rel, path) in &files {
+            let dest = staging.join(rel);
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(path, &dest)?;
+            copied.insert(rel.clone(), dest);
+        }
+        let name = package_name(circuit_path)?;
+        check_artifact(&staging.join(name))?;
+        let version = artifact_set_version(&copied)?;
+
+        let dest = snapshots.join(&version);
+        match std::fs::rename(&staging, &dest) {
+            Ok(()) => {}
+            // Already taken by an earlier load of the same artifacts
+            Err(_) if dest.is_dir() => std::fs::remove_dir_all(&staging)?,
+            Err(e) => return Err(e.into()),
+        }
+        Ok((dest.join(name), version))
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(&staging);
+    }
+    result
+}
+
 // Noir Prover (Real)
 
 /// Noir prover wrapper that uses nargo + sunspot
 pub struct NoirProver {
     circuit_path: PathBuf,
+    /// Snapshot's circuit directory and its version, when taken by `load`
+    snapshot: Option<(PathBuf, String)>,
 }
 
 impl NoirProver {
     /// Create a new prover for the given circuit path
+    ///
+    /// Proofs read the circuit's files as they are on disk at the time.
     pub fn new(circuit_path: PathBuf) -> Self {
         // Canonicalize to absolute path to avoid working directory issues
         let circuit_path = circuit_path.canonicalize().unwrap_or(circuit_path);
-        Self { circuit_path }
+        Self {
+            circuit_path,
+            snapshot: None,
+        }
+    }
+
+    /// Create a prover that snapshots the circuit's current artifact set
+    ///
+    /// Proofs run against the snapshot, so replacing any of the circuit's
+    /// files on disk later (source, ACIR, constraint system or keys) doesn't
+    /// change what this prover proves with.
+    pub fn load(circuit_path: PathBuf) -> Result<Self, ProverError> {
+        let circuit_path = circuit_path.canonicalize().unwrap_or(circuit_path);
+        let snapshot = snapshot(&circuit_path)?;
+        Ok(Self {
+            circuit_path,
+            snapshot: Some(snapshot),
+        })
+    }
+
+    /// Version of the snapshot taken by `load` (None for `new`)
+    pub fn version(&self) -> Option<&str> {
+        self.snapshot.as_ref().map(|(_, version)| version.as_str())
+    }
+
+    /// Directory proofs run in: the snapshot, or the live circuit for `new`
+    fn circuit_dir(&self) -> &Path {
+        self.snapshot
+            .as_ref()
+            .map_or(&self.circuit_path, |(dir, _)| dir)
     }
 
     /// Generate a proof for batch inputs
+    ///
+    /// Dropping the returned future kills any running nargo/sunspot child,
+    /// so callers can cancel proving with `tokio::select!`.
     pub async fn generate_batch_proof(
         &self,
         inputs: BatchInputs,
     ) -> Result<ProofResult, ProverError> {
         // Verify circuit path exists
-        if !self.circuit_path.exists() {
-            return Err(ProverError::CircuitNotFound(self.circuit_path.clone()));
+        let circuit_dir = self.circuit_dir();
+        if !circuit_dir.exists() {
+            return Err(ProverError::CircuitNotFound(circuit_dir.to_path_buf()));
         }
 
         info!(
@@ -471,7 +964,7 @@ impl NoirProver {
         debug!("Normalized inputs for Noir (hex -> decimal conversion applied)");
 
         // Step 1: Write Prover.toml
-        let prover_toml_path = self.circuit_path.join("Prover.toml");
+        let prover_toml_path = circuit_dir.join("Prover.toml");
         let toml_content = toml::to_string_pretty(&normalized_inputs)?;
 
         debug!("Writing Prover.toml ({} bytes)", toml_content.len());
@@ -483,9 +976,10 @@ impl NoirProver {
 
         let nargo_output = Command::new("nargo")
             .args(["execute", &witness_name])
-            .current_dir(&self.circuit_path)
+            .current_dir(circuit_dir)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .output()
             .await?;
 
@@ -502,8 +996,8 @@ impl NoirProver {
 
         // Step 3: Generate proof using sunspot
         // sunspot prove <acir> <witness> <ccs> <pk>
-        let target_dir = self.circuit_path.join("target");
-        let acir_path = target_dir.join("zelana_batch.json");
+        let target_dir = circuit_dir.join("target");
+        let acir_path = circuit_dir.join(CIRCUIT_ARTIFACT);
         let witness_path = target_dir.join(format!("{}.gz", witness_name));
         let ccs_path = target_dir.join("zelana_batch.ccs");
         let pk_path = target_dir.join("zelana_batch.pk");
@@ -517,9 +1011,10 @@ impl NoirProver {
                 ccs_path.to_str().unwrap(),
                 pk_path.to_str().unwrap(),
             ])
-            .current_dir(&self.circuit_path)
+            .current_dir(circuit_dir)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .output()
             .await?;
 
@@ -543,6 +1038,13 @@ impl NoirProver {
         let proof_bytes = tokio::fs::read(&proof_path).await?;
         let public_witness_bytes = tokio::fs::read(&pw_path).await?;
 
+        // The VK is produced by `sunspot setup` alongside the proving key
+        let vk_path = target_dir.join("zelana_batch.vk");
+        if !vk_path.exists() {
+            return Err(ProverError::ProofFileNotFound(vk_path));
+        }
+        let vk_hash = vk_file_hash(&tokio::fs::read(&vk_path).await?)?;
+
         info!(
             "Proof generated: {} bytes proof, {} bytes public witness",
             proof_bytes.len(),
@@ -561,12 +1063,13 @@ impl NoirProver {
             proof_bytes,
             public_witness_bytes,
             public_inputs,
+            vk_hash,
         })
     }
 
     /// Generate a proof using legacy ChunkInputs format
     pub async fn generate_proof(&self, inputs: ChunkInputs) -> Result<ProofResult, ProverError> {
-        let batch_inputs = inputs.to_batch_inputs(1);
+        let batch_inputs = inputs.to_batch_inputs(CHUNK_BATCH_ID);
         self.generate_batch_proof(batch_inputs).await
     }
 }
@@ -640,12 +1143,19 @@ impl MockProver {
                 let proof_bytes = tokio::fs::read(&proof_path).await?;
                 let public_witness_bytes = tokio::fs::read(&pw_path).await?;
                 let public_inputs = parse_public_witness(&public_witness_bytes);
+                let vk_path = path.join("zelana_batch.vk");
+                let vk_hash = if vk_path.exists() {
+                    vk_file_hash(&tokio::fs::read(&vk_path).await?)?
+                } else {
+                    mock_vk_hash()
+                };
 
                 return Ok(ProofResult {
                     proof: hex::encode(&proof_bytes),
                     proof_bytes,
                     public_witness_bytes,
                     public_inputs,
+                    vk_hash,
                 });
             }
         }
@@ -683,6 +1193,7 @@ impl MockProver {
             proof_bytes,
             public_witness_bytes,
             public_inputs: vec![inputs.old_root, inputs.new_root],
+            vk_hash: mock_vk_hash(),
         })
     }
 
@@ -754,6 +1265,63 @@ mod tests {
         assert!(!batch.transfers[1].is_valid);
     }
 
+    #[test]
+    fn test_batch_inputs_validate_every_kind() {
+        let mut batch = BatchInputs::empty_batch("0x1", "0x2", 1, "0x0", "0x0");
+        assert!(batch.validate().is_ok());
+
+        batch.transfers[0] = TransferWitness {
+            sender_pubkey: "0xa".to_string(),
+            receiver_pubkey: "0xb".to_string(),
+            amount: "5".to_string(),
+            sender_nonce: "3".to_string(),
+            signature: "0x1".to_string(),
+            is_valid: true,
+            ..TransferWitness::default()
+        };
+        assert!(batch.validate().is_ok());
+
+        // The witness's own nonce is checked, not a stand-in
+        batch.transfers[0].sender_nonce = (u64::MAX as u128 + 1).to_string();
+        let err = batch.validate().unwrap_err();
+        assert!(err.starts_with("transfer 0: sender_nonce"), "{}", err);
+        batch.transfers[0].sender_nonce = "3".to_string();
+
+        batch.withdrawals[1] = WithdrawalWitness {
+            sender_pubkey: "0xa".to_string(),
+            l1_recipient: "0".to_string(),
+            amount: "5".to_string(),
+            is_valid: true,
+            ..WithdrawalWitness::default()
+        };
+        let err = batch.validate().unwrap_err();
+        assert!(err.starts_with("withdrawal 1: l1_recipient"), "{}", err);
+        batch.withdrawals[1].l1_recipient = "0xc".to_string();
+        let err = batch.validate().unwrap_err();
+        assert!(
+            err.starts_with("withdrawal 1: l1_recipient_hi/lo"),
+            "{}",
+            err
+        );
+        let mut recipient = [0u8; 32];
+        recipient[31] = 0xc;
+        (
+            batch.withdrawals[1].l1_recipient_hi,
+            batch.withdrawals[1].l1_recipient_lo,
+        ) = u128_halves(&recipient);
+        assert!(batch.validate().is_ok());
+
+        batch.shielded[0] = ShieldedWitness {
+            nullifier: "0x1".to_string(),
+            output_commitment: "0x2".to_string(),
+            is_valid: true,
+            ..ShieldedWitness::default()
+        };
+        batch.shielded[0].input_path[5] = "not a field".to_string();
+        let err = batch.validate().unwrap_err();
+        assert!(err.starts_with("shielded 0: input_path"), "{}", err);
+    }
+
     #[test]
     fn test_empty_batch() {
         let batch = BatchInputs::empty_batch("0xroot", "0xshielded", 1, "0xhash", "0xwd");
@@ -788,6 +1356,112 @@ mod tests {
         assert!(inputs[1].starts_with("0x01"));
     }
 
+    #[test]
+    fn test_vk_file_hash_uses_canonical_points() {
+        // gnark raw layout: alpha, beta_g1, beta_g2, gamma, delta_g1, delta_g2, K
+        let mut vk = Vec::new();
+        vk.extend_from_slice(&[1u8; G1_LEN]);
+        vk.extend_from_slice(&[9u8; G1_LEN]);
+        vk.extend_from_slice(&[2u8; G2_LEN]);
+        vk.extend_from_slice(&[3u8; G2_LEN]);
+        vk.extend_from_slice(&[9u8; G1_LEN]);
+        vk.extend_from_slice(&[4u8; G2_LEN]);
+        vk.extend_from_slice(&2u32.to_be_bytes());
+        vk.extend_from_slice(&[5u8; 2 * G1_LEN]);
+        // Trailing commitment data is not part of the key's points
+        vk.extend_from_slice(&[0u8; 8]);
+
+        let mut canonical = vec![1u8; G1_LEN];
+        canonical.extend_from_slice(&[2u8; G2_LEN]);
+        canonical.extend_from_slice(&[3u8; G2_LEN]);
+        canonical.extend_from_slice(&[4u8; G2_LEN]);
+        canonical.extend_from_slice(&[5u8; 2 * G1_LEN]);
+        assert_eq!(vk_file_hash(&vk).unwrap(), vk_hash(&canonical));
+
+        // Truncated and compressed keys are rejected
+        assert!(vk_file_hash(&vk[..vk.len() - 8 - G1_LEN]).is_err());
+        let mut compressed = vk.clone();
+        compressed[0] |= 0b10 << 6;
+        assert!(vk_file_hash(&compressed).is_err());
+    }
+
+    /// Lay out `circuits/zelana_batch` with a `zelana_lib` path dependency
+    fn write_circuit(root: &Path) -> PathBuf {
+        let lib = root.join("zelana_lib");
+        std::fs::create_dir_all(lib.join("src")).unwrap();
+        std::fs::write(lib.join("Nargo.toml"), "[package]\nname = \"zelana_lib\"\n").unwrap();
+        std::fs::write(lib.join("src/lib.nr"), "fn hash() {}").unwrap();
+
+        let circuit = root.join("zelana_batch");
+        std::fs::create_dir_all(circuit.join("src")).unwrap();
+        std::fs::create_dir_all(circuit.join("target")).unwrap();
+        std::fs::write(
+            circuit.join("Nargo.toml"),
+            "[package]\nname = \"zelana_batch\"\n\n[dependencies]\n\
+             zelana_lib = { path = \"../zelana_lib\" }\n",
+        )
+        .unwrap();
+        std::fs::write(circuit.join("src/main.nr"), "fn main() {}").unwrap();
+        std::fs::write(circuit.join(CIRCUIT_ARTIFACT), r#"{"bytecode":"v1"}"#).unwrap();
+        for artifact in SUNSPOT_ARTIFACTS {
+            std::fs::write(circuit.join(artifact), artifact).unwrap();
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_load_snapshots_every_artifact() {
+        let dir = tempfile::tempdir().unwrap();
+        let circuit = write_circuit(dir.path());
+
+        let prover = NoirProver::load(circuit.clone()).unwrap();
+        let version = prover.version().unwrap().to_string();
+        assert_eq!(version, circuit_version(&circuit).unwrap());
+        let snapshot = prover.circuit_dir().to_path_buf();
+        assert_eq!(
+            snapshot,
+            circuit
+                .canonicalize()
+                .unwrap()
+                .join(SNAPSHOT_DIR)
+                .join(&version)
+                .join("zelana_batch")
+        );
+        assert!(snapshot.join("../zelana_lib/src/lib.nr").is_file());
+
+        // Redeploying any input changes the version but not the snapshot
+        let pk = circuit.join(SUNSPOT_ARTIFACTS[1]);
+        let redeploys: [(PathBuf, &str); 4] = [
+            (pk.clone(), "new pk"),
+            (circuit.join(CIRCUIT_ARTIFACT), r#"{"bytecode":"v2"}"#),
+            (circuit.join("src/main.nr"), "fn main() { }"),
+            (dir.path().join("zelana_lib/src/lib.nr"), "fn hash() { }"),
+        ];
+        let mut versions = HashSet::from([version.clone()]);
+        for (path, contents) in redeploys {
+            std::fs::write(path, contents).unwrap();
+            assert!(versions.insert(circuit_version(&circuit).unwrap()));
+        }
+        assert_eq!(prover.version(), Some(version.as_str()));
+        assert_eq!(
+            std::fs::read(snapshot.join(SUNSPOT_ARTIFACTS[1])).unwrap(),
+            SUNSPOT_ARTIFACTS[1].as_bytes()
+        );
+
+        // Loading unchanged artifacts again reuses their snapshot
+        let reloaded = NoirProver::load(circuit.clone()).unwrap();
+        let again = NoirProver::load(circuit.clone()).unwrap();
+        assert_eq!(reloaded.version(), again.version());
+        assert_eq!(reloaded.circuit_dir(), again.circuit_dir());
+
+        // A circuit missing its proving key can't be loaded
+        std::fs::remove_file(&pk).unwrap();
+        assert!(matches!(
+            NoirProver::load(circuit.clone()),
+            Err(ProverError::MissingArtifact(path)) if path.ends_with(SUNSPOT_ARTIFACTS[1])
+        ));
+    }
+
     #[test]
     fn test_proof_result_to_solana_data() {
         let result = ProofResult {
@@ -795,6 +1469,7 @@ mod tests {
             public_witness_bytes: vec![5, 6, 7, 8],
             proof: "01020304".to_string(),
             public_inputs: vec![],
+            vk_hash: mock_vk_hash(),
         };
 
         let data = result.to_solana_instruction_data();
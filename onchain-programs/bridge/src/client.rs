@@ -0,0 +1,36 @@
+//! Off-chain accessors for bridge state
+//!
+//! `helpers::derive_*_pda` go through the `find_program_address` syscall and
+//! only work on-chain. These derive the same addresses off-chain, for the
+//! bridge deployed at [`crate::ID`], so an indexer or dashboard can locate
+//! and decode the bridge accounts with [`Config::try_from_account_data`] and
+//! [`Vault::try_from_account_data`].
+//!
+//! [`Config::try_from_account_data`]: crate::state::Config::try_from_account_data
+//! [`Vault::try_from_account_data`]: crate::state::Vault::try_from_account_data
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::ID;
+
+/// Config PDA (and bump) for `domain`. Seeds: `["config", domain]`
+pub fn get_config_pda(domain: &[u8; 32]) -> (Pubkey, u8) {
+    find_program_address(&[b"config", domain.as_ref()])
+}
+
+/// Vault PDA (and bump) for `domain`. Seeds: `["vault", domain]`
+pub fn get_vault_pda(domain: &[u8; 32]) -> (Pubkey, u8) {
+    find_program_address(&[b"vault", domain.as_ref()])
+}
+
+/// Withdrawal root PDA (and bump) of a settled batch.
+/// Seeds: `["withdrawal_root", domain, batch_id (LE)]`
+pub fn get_withdrawal_root_pda(domain: &[u8; 32], batch_id: u64) -> (Pubkey, u8) {
+    find_program_address(&[b"withdrawal_root", domain.as_ref(), &batch_id.to_le_bytes()])
+}
+
+fn find_program_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    let program_id = solana_pubkey::Pubkey::new_from_array(ID);
+    let (address, bump) = solana_pubkey::Pubkey::find_program_address(seeds, &program_id);
+    (address.to_bytes(), bump)
+}
@@ -43,5 +43,19 @@ fn process_instruction(
             instruction::submit_batch::process_submit_batch(program_id, accounts, data)?;
             Ok(())
         }
+        BridgeIx::FinalizeWithdrawal => {
+            instruction::finalize_withdrawal::process_finalize_withdrawal(
+                program_id, accounts, data,
+            )?;
+            Ok(())
+        }
+        BridgeIx::SetPaused => {
+            instruction::set_paused::process_set_paused(accounts, data)?;
+            Ok(())
+        }
+        BridgeIx::TransferAuthority => {
+            instruction::transfer_authority::process_transfer_authority(accounts, data)?;
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,31 @@
+use pinocchio::program_error::ProgramError;
+
+/// Bridge-specific failures, surfaced as `ProgramError::Custom(code)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum BridgeError {
+    /// `sequencer_authority` is the default (all-zero) pubkey
+    InvalidSequencerAuthority = 0,
+    /// `domain` is all zeroes (it seeds the config and vault PDAs)
+    InvalidDomain = 1,
+    /// `min_deposit` is zero or above `max_deposit`
+    InvalidDepositBounds = 2,
+    /// Deposit amount below the configured `min_deposit`
+    DepositBelowMinimum = 3,
+    /// Deposit amount above the configured `max_deposit`
+    DepositAboveMaximum = 4,
+    /// Merkle proof does not lead from the withdrawal to the settled root
+    InvalidWithdrawalProof = 5,
+    /// The withdrawal's nullifier PDA already exists
+    WithdrawalAlreadyClaimed = 6,
+    /// The bridge is paused (`SetPaused`)
+    Paused = 7,
+    /// A withdrawal would take `total_withdrawn` above `total_deposited`
+    WithdrawalExceedsDeposits = 8,
+}
+
+impl From<BridgeError> for ProgramError {
+    fn from(e: BridgeError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -140,3 +140,15 @@ pub fn derive_nullifier_pda(
 ) -> (Pubkey, u8) {
     find_program_address(&[b"nullifier", domain.as_ref(), nullifier], program_id)
 }
+
+#[inline(always)]
+pub fn derive_withdrawal_root_pda(
+    program_id: &Pubkey,
+    domain: &[u8; 32],
+    batch_id: u64,
+) -> (Pubkey, u8) {
+    find_program_address(
+        &[b"withdrawal_root", domain.as_ref(), &batch_id.to_le_bytes()],
+        program_id,
+    )
+}
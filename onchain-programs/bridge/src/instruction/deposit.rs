@@ -2,18 +2,19 @@ use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
     instruction::{Seed, Signer},
+    log::sol_log_data,
     program_error::ProgramError,
     sysvars::{Sysvar, clock::Clock, rent::Rent},
 };
-use pinocchio_log::log;
 use pinocchio_system::instructions::{CreateAccount, Transfer};
+use zelana_bridge_events::DepositEvent;
 
 use crate::helpers::utils::Initialized;
 use crate::{
     ID,
     helpers::{
         StateDefinition, check_signer, derive_deposit_receipt_pda, derive_vault_pda,
-        load_acc_mut_unchecked, load_ix_data,
+        load_acc_mut_unchecked,
     },
     instruction::DepositParams,
     state::{Config, DepositReceipt},
@@ -31,7 +32,7 @@ pub fn process_deposit(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResul
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
-    let params = unsafe { load_ix_data::<DepositParams>(ix_data)? };
+    let params = DepositParams::from_bytes(ix_data).ok_or(ProgramError::InvalidInstructionData)?;
 
     check_signer(depositor)?;
 
@@ -49,6 +50,9 @@ pub fn process_deposit(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResul
         return Err(ProgramError::UninitializedAccount);
     }
 
+    config.check_not_paused()?;
+    config.check_deposit_amount(params.amount)?;
+
     let domain = config.domain;
 
     //verify vault pda
@@ -77,6 +81,7 @@ pub fn process_deposit(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResul
         lamports: params.amount,
     }
     .invoke()?;
+    config.record_deposit(params.amount)?;
     //creation of deposit receipt PDA
     let rent = Rent::get()?;
 
@@ -115,12 +120,13 @@ pub fn process_deposit(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResul
         receipt_bump,
     )?;
 
-    log!(
-        "ZE_DEPOSIT:{}:{}:{}",
-        depositor.key(),
-        params.amount,
-        params.nonce
-    );
+    let event = DepositEvent {
+        depositor: *depositor.key(),
+        amount: params.amount,
+        nonce: params.nonce,
+        l1_seq: config.next_l1_seq()?,
+    };
+    sol_log_data(&[&event.to_bytes()]);
 
     Ok(())
 }
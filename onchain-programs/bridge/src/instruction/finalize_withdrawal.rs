@@ -0,0 +1,170 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    log::sol_log_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
+};
+use pinocchio_system::instructions::CreateAccount;
+use zelana_bridge_events::{
+    WithdrawEvent,
+    merkle::{self, WITHDRAWAL_TREE_DEPTH},
+};
+use zelana_transaction::bridge::DataLen;
+
+use crate::helpers::utils::Initialized;
+use crate::{
+    ID,
+    error::BridgeError,
+    helpers::{
+        StateDefinition, check_signer, derive_nullifier_pda, derive_vault_pda,
+        derive_withdrawal_root_pda, load_acc, load_acc_mut, load_acc_mut_unchecked,
+    },
+    instruction::FinalizeWithdrawalParams,
+    state::{Config, UsedNullifier, WithdrawalRoot},
+};
+
+/// Release a withdrawal included in a settled batch. Anyone may submit the
+/// claim; the funds only ever go to the recipient committed in the leaf.
+pub fn process_finalize_withdrawal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ix_data: &[u8],
+) -> ProgramResult {
+    let [
+        claimant,
+        config_account,
+        vault_account,
+        recipient,
+        withdrawal_root_account,
+        nullifier_account,
+        _system_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(claimant)?;
+
+    let mut config_data = config_account.try_borrow_mut_data()?;
+    let config = unsafe { load_acc_mut::<Config>(&mut config_data)? };
+
+    if !config.is_initialized() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    config.check_not_paused()?;
+
+    let domain = config.domain;
+
+    // Fixed params, then the full sibling path (32-byte hashes, leaf level
+    // first) of the batch's fixed-depth withdrawal tree
+    if ix_data.len() != FinalizeWithdrawalParams::LEN + 32 * WITHDRAWAL_TREE_DEPTH {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (params_bytes, proof_bytes) = ix_data.split_at(FinalizeWithdrawalParams::LEN);
+    let params = FinalizeWithdrawalParams::from_bytes(params_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    let mut proof = [[0u8; 32]; WITHDRAWAL_TREE_DEPTH];
+    for (sibling, bytes) in proof.iter_mut().zip(proof_bytes.chunks_exact(32)) {
+        sibling.copy_from_slice(bytes);
+    }
+
+    if params.amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if recipient.key() != &params.recipient {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (expected_vault_pda, _) = derive_vault_pda(&ID, &domain);
+
+    if vault_account.key() != &expected_vault_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (withdrawal_root_pda, _) = derive_withdrawal_root_pda(&ID, &domain, params.batch_id);
+
+    if withdrawal_root_account.key() != &withdrawal_root_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if !withdrawal_root_account.is_owned_by(program_id) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let root_data = withdrawal_root_account.try_borrow_data()?;
+    let withdrawal_root = unsafe { load_acc::<WithdrawalRoot>(&root_data)? };
+
+    let leaf = merkle::withdrawal_leaf(&params.tx_hash, &params.recipient, params.amount);
+
+    if !withdrawal_root.is_initialized() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if !merkle::verify_withdrawal_proof(leaf, params.leaf_position, &proof, withdrawal_root.root) {
+        return Err(BridgeError::InvalidWithdrawalProof.into());
+    }
+
+    // Same nullifier PDA as `WithdrawAttested`, so a withdrawal can only be
+    // paid out once whichever path releases it
+    let (nullifier_pda, nullifier_bump) = derive_nullifier_pda(&ID, &domain, &params.tx_hash);
+
+    if nullifier_account.key() != &nullifier_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if !nullifier_account.data_is_empty() {
+        return Err(BridgeError::WithdrawalAlreadyClaimed.into());
+    }
+
+    let rent = Rent::get()?;
+    let bump_bytes = [nullifier_bump];
+
+    let nullifier_seeds = [
+        Seed::from(b"nullifier"),
+        Seed::from(domain.as_ref()),
+        Seed::from(&params.tx_hash),
+        Seed::from(&bump_bytes),
+    ];
+
+    CreateAccount {
+        from: claimant,
+        to: nullifier_account,
+        space: UsedNullifier::LEN as u64,
+        owner: program_id,
+        lamports: rent.minimum_balance(UsedNullifier::LEN),
+    }
+    .invoke_signed(&[Signer::from(&nullifier_seeds)])?;
+
+    let mut nullifier_data = nullifier_account.try_borrow_mut_data()?;
+    let nullifier_state = unsafe { load_acc_mut_unchecked::<UsedNullifier>(&mut nullifier_data)? };
+    nullifier_state.new(
+        domain,
+        params.tx_hash,
+        *recipient.key(),
+        params.amount,
+        nullifier_bump,
+    )?;
+
+    config.record_withdrawal(params.amount)?;
+
+    *vault_account.try_borrow_mut_lamports()? -= params.amount;
+    *recipient.try_borrow_mut_lamports()? += params.amount;
+
+    let clock = Clock::get()?;
+
+    let event = WithdrawEvent {
+        recipient: *recipient.key(),
+        amount: params.amount,
+        nullifier: params.tx_hash,
+        timestamp: clock.unix_timestamp,
+    };
+    sol_log_data(&[&event.to_bytes()]);
+
+    Ok(())
+}
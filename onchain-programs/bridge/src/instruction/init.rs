@@ -11,7 +11,7 @@ use pinocchio_system::instructions::CreateAccount;
 use crate::{
     ID,
     helpers::{
-        StateDefinition, check_signer, load_acc_mut_unchecked, load_ix_data,
+        StateDefinition, check_signer, load_acc_mut_unchecked,
         utils::{derive_config_pda, derive_vault_pda},
     },
     instruction::InitParams,
@@ -30,11 +30,15 @@ pub fn process_initialize(
     check_signer(payer)?;
 
     // decode ix data
-    let params = unsafe { load_ix_data::<InitParams>(ix_data)? };
+    let params = InitParams::from_bytes(ix_data).ok_or(ProgramError::InvalidInstructionData)?;
 
-    if params.domain == [0u8; 32] {
-        return Err(ProgramError::InvalidInstructionData);
-    }
+    // Validate before creating anything: a bad config would brick the bridge
+    Config::validate(
+        &params.sequencer_authority,
+        &params.domain,
+        params.min_deposit,
+        params.max_deposit,
+    )?;
 
     let (expected_config_pda, config_bump) = derive_config_pda(&ID, &params.domain);
 
@@ -100,7 +104,13 @@ pub fn process_initialize(
     let config_data = &mut config_account.try_borrow_mut_data()?;
     let config_state = unsafe { load_acc_mut_unchecked::<Config>(config_data)? };
 
-    config_state.new(params.sequencer_authority, params.domain, config_bump)?;
+    config_state.new(
+        params.sequencer_authority,
+        params.domain,
+        params.min_deposit,
+        params.max_deposit,
+        config_bump,
+    )?;
 
     let vault_account = &mut vault_account.try_borrow_mut_data()?;
     let vault_state = unsafe { load_acc_mut_unchecked::<Vault>(vault_account)? };
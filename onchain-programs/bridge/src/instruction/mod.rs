@@ -3,9 +3,17 @@ use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 
 use crate::helpers::DataLen;
 
+/// Instruction data layouts shared with the off-chain clients
+pub use zelana_transaction::bridge::{
+    DepositParams, FinalizeWithdrawalParams, InitParams, SetPausedParams, WithdrawAttestedParams,
+};
+
 pub mod deposit;
+pub mod finalize_withdrawal;
 pub mod init;
+pub mod set_paused;
 pub mod submit_batch;
+pub mod transfer_authority;
 pub mod withdraw;
 
 #[repr(u8)]
@@ -14,6 +22,9 @@ pub enum BridgeIx {
     DEPOSIT = 1,
     WITHDRAWATTESTED = 2,
     SubmitBatch = 3,
+    FinalizeWithdrawal = 4,
+    SetPaused = 5,
+    TransferAuthority = 6,
 }
 
 impl TryFrom<&u8> for BridgeIx {
@@ -24,49 +35,20 @@ impl TryFrom<&u8> for BridgeIx {
             1 => Ok(BridgeIx::DEPOSIT),
             2 => Ok(BridgeIx::WITHDRAWATTESTED),
             3 => Ok(BridgeIx::SubmitBatch),
+            4 => Ok(BridgeIx::FinalizeWithdrawal),
+            5 => Ok(BridgeIx::SetPaused),
+            6 => Ok(BridgeIx::TransferAuthority),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
 }
-#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq, shank::ShankType)]
-#[repr(C)]
-pub struct InitParams {
-    pub sequencer_authority: Pubkey,
-    pub domain: [u8; 32],
-}
-
-impl DataLen for InitParams {
-    const LEN: usize = core::mem::size_of::<InitParams>();
-}
-
-#[derive(Pod, Zeroable, Clone, Copy, shank::ShankType)]
-#[repr(C)]
-pub struct DepositParams {
-    pub amount: u64,
-    pub nonce: u64,
-}
-
-impl DataLen for DepositParams {
-    const LEN: usize = core::mem::size_of::<DepositParams>();
-}
-
-#[derive(Pod, Zeroable, Clone, Copy, shank::ShankType)]
-#[repr(C)]
-pub struct WithdrawAttestedParams {
-    pub recipient: Pubkey,
-    pub amount: u64,
-    pub nullifier: [u8; 32],
-}
-
-impl DataLen for WithdrawAttestedParams {
-    const LEN: usize = core::mem::size_of::<WithdrawAttestedParams>();
-}
-
 #[derive(Pod, Zeroable, Clone, Copy, shank::ShankType)]
 #[repr(C)]
 pub struct WithdrawalRequest {
     pub recipient: Pubkey,
     pub amount: u64,
+    /// L2 withdrawal tx hash (its leaf in the batch's withdrawal tree)
+    pub tx_hash: [u8; 32],
 }
 
 #[repr(C)]
@@ -116,7 +98,10 @@ impl DataLen for BatchPublicInputs {
 }
 
 mod idl_gen {
-    use super::{DepositParams, InitParams, SubmitBatchHeader, WithdrawAttestedParams};
+    use super::{
+        DepositParams, FinalizeWithdrawalParams, InitParams, SetPausedParams, SubmitBatchHeader,
+        WithdrawAttestedParams,
+    };
     #[derive(shank::ShankInstruction)]
     #[rustfmt::skip]
     enum _BridgeInstruction{
@@ -127,25 +112,44 @@ mod idl_gen {
         Initialize(InitParams),
 
         #[account(0, writable, signer, name="depositor", desc="The user depositing SOL")]
-        #[account(1, name="config", desc="The bridge's config account")]
+        #[account(1, writable, name="config", desc="The bridge's config account (deposit sequence and totals)")]
         #[account(2, writable, name="vault", desc="The bridge's vault account")]
         #[account(3, writable, name="deposit_receipt", desc="The unique PDA receipt for this deposit")]
         #[account(4, name="system_program", desc="System Program")]
         Deposit(DepositParams),
 
         #[account(0, signer, name="sequencer", desc="The authorized sequencer signing the withdrawal")]
-        #[account(1, name="config", desc="The bridge's config account")]
+        #[account(1, writable, name="config", desc="The bridge's config account (withdrawal totals)")]
         #[account(2, writable, name="vault", desc="The bridge's vault account")]
         #[account(3, writable, name="recipient", desc="The account receiving the withdrawn SOL")]
         #[account(4, writable, name="used_nullifier", desc="The nullifier PDA to prevent replay attacks")]
         #[account(5, name="system_program", desc="System Program")]
         WithdrawAttested(WithdrawAttestedParams),
 
-        #[account(0, signer, name="sequencer", desc="The authorized sequencer submitting the batch")]
+        #[account(0, writable, signer, name="sequencer", desc="The authorized sequencer submitting the batch (pays for the withdrawal root)")]
         #[account(1, writable, name="config", desc="The bridge's config account")]
-        #[account(2,name="vault",desc="Vault PDA for the domain (read-only, consistency check)")]
-        #[account(3, name="verifier_program", desc="The Verifier Program (Optional)")]
-        #[account(4, name="system_program", desc="System Program")]
+        #[account(2, name="verifier_program", desc="The Verifier Program")]
+        #[account(3, name="vk_account", desc="The batch verifying key account")]
+        #[account(4, writable, name="withdrawal_root", desc="The batch's withdrawal root PDA, created when the batch has withdrawals. Seeds: ['withdrawal_root', domain, batch_id]")]
+        #[account(5, name="system_program", desc="System Program")]
         SubmitBatch(SubmitBatchHeader),
+
+        #[account(0, writable, signer, name="claimant", desc="Anyone submitting the claim (pays for the nullifier)")]
+        #[account(1, writable, name="config", desc="The bridge's config account (withdrawal totals)")]
+        #[account(2, writable, name="vault", desc="The bridge's vault account")]
+        #[account(3, writable, name="recipient", desc="The account receiving the withdrawn SOL")]
+        #[account(4, name="withdrawal_root", desc="The settled batch's withdrawal root PDA")]
+        #[account(5, writable, name="used_nullifier", desc="The nullifier PDA to prevent double claims")]
+        #[account(6, name="system_program", desc="System Program")]
+        FinalizeWithdrawal(FinalizeWithdrawalParams),
+
+        #[account(0, signer, name="authority", desc="The sequencer authority")]
+        #[account(1, writable, name="config", desc="The bridge's config account")]
+        SetPaused(SetPausedParams),
+
+        #[account(0, signer, name="authority", desc="The current sequencer authority")]
+        #[account(1, signer, name="new_authority", desc="The key taking over as sequencer authority")]
+        #[account(2, writable, name="config", desc="The bridge's config account")]
+        TransferAuthority,
     }
 }
@@ -0,0 +1,34 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+use pinocchio_log::log;
+
+use crate::{
+    helpers::{check_signer, load_acc_mut},
+    instruction::SetPausedParams,
+    state::Config,
+};
+
+/// Halt or resume deposits and withdrawals. Only the sequencer authority
+/// may flip the switch; batch settlement is unaffected.
+pub fn process_set_paused(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    let [authority, config_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority)?;
+
+    let params =
+        SetPausedParams::from_bytes(ix_data).ok_or(ProgramError::InvalidInstructionData)?;
+
+    let mut config_data = config_account.try_borrow_mut_data()?;
+    let config = unsafe { load_acc_mut::<Config>(&mut config_data)? };
+
+    if authority.key() != &config.sequencer_authority {
+        return Err(ProgramError::IncorrectAuthority);
+    }
+
+    config.paused = params.paused;
+
+    log!("ZE_BRIDGE_PAUSED:{}", params.paused);
+
+    Ok(())
+}
@@ -1,18 +1,26 @@
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
-    instruction::{AccountMeta, Instruction},
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    log::sol_log_data,
     program::invoke,
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvars::{Sysvar, rent::Rent},
 };
 use pinocchio_log::log;
+use pinocchio_system::instructions::CreateAccount;
+use zelana_bridge_events::WithdrawalInitiatedEvent;
 
 use crate::helpers::utils::{DataLen, Initialized};
 use crate::{
-    helpers::{check_signer, load_acc_mut},
+    ID,
+    helpers::{
+        StateDefinition, check_signer, derive_withdrawal_root_pda, load_acc_mut,
+        load_acc_mut_unchecked,
+    },
     instruction::{BatchPublicInputs, Groth16Proof, SubmitBatchHeader, WithdrawalRequest},
-    state::Config,
+    state::{Config, WithdrawalRoot},
 };
 
 /// Parse SubmitBatchHeader from unaligned bytes (avoids bytemuck alignment issues)
@@ -129,8 +137,15 @@ fn parse_withdrawal_unaligned(data: &[u8]) -> Result<WithdrawalRequest, ProgramE
             .try_into()
             .map_err(|_| ProgramError::InvalidInstructionData)?,
     );
+    let tx_hash: [u8; 32] = data[40..72]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
 
-    Ok(WithdrawalRequest { recipient, amount })
+    Ok(WithdrawalRequest {
+        recipient,
+        amount,
+        tx_hash,
+    })
 }
 
 /// Anchor instruction discriminator for verify_batch_proof
@@ -163,12 +178,13 @@ fn build_verify_instruction_data(proof: &Groth16Proof, inputs: &BatchPublicInput
 }
 
 pub fn process_submit_batch(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     ix_data: &[u8],
 ) -> ProgramResult {
-    // Minimum accounts: sequencer, config, verifier_program, vk_account
-    if accounts.len() < 4 {
+    // Minimum accounts: sequencer, config, verifier_program, vk_account,
+    // withdrawal_root, system_program
+    if accounts.len() < 6 {
         return Err(ProgramError::NotEnoughAccountKeys);
     }
 
@@ -176,9 +192,10 @@ pub fn process_submit_batch(
     let config_account = &accounts[1];
     let verifier_program = &accounts[2];
     let vk_account = &accounts[3];
+    let withdrawal_root_account = &accounts[4];
 
     // Remaining accounts are withdrawal recipients
-    let recipients_iter = &accounts[4..];
+    let recipients_iter = &accounts[6..];
 
     check_signer(sequencer)?;
     let mut config_data = unsafe { config_account.borrow_mut_data_unchecked() };
@@ -307,11 +324,52 @@ pub fn process_submit_batch(
             return Err(ProgramError::InvalidAccountData);
         }
 
-        log!(
-            "ZE_WITHDRAW_INTENT:{}:{}",
-            recipient_account.key(),
-            w.amount
-        );
+        let event = WithdrawalInitiatedEvent {
+            batch_id: header.new_batch_index,
+            tx_hash: w.tx_hash,
+            recipient: w.recipient,
+            amount: w.amount,
+        };
+        sol_log_data(&[&event.to_bytes()]);
+    }
+
+    // Keep the withdrawal root so withdrawals can be claimed against it
+    let (withdrawal_root_pda, withdrawal_root_bump) =
+        derive_withdrawal_root_pda(&ID, &domain, header.new_batch_index);
+
+    if withdrawal_root_account.key() != &withdrawal_root_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if public_inputs.withdrawal_root != [0u8; 32] {
+        let rent = Rent::get()?;
+        let batch_id_bytes = header.new_batch_index.to_le_bytes();
+        let bump_bytes = [withdrawal_root_bump];
+
+        let withdrawal_root_seeds = [
+            Seed::from(b"withdrawal_root"),
+            Seed::from(domain.as_ref()),
+            Seed::from(&batch_id_bytes),
+            Seed::from(&bump_bytes),
+        ];
+
+        CreateAccount {
+            from: sequencer,
+            to: withdrawal_root_account,
+            space: WithdrawalRoot::LEN as u64,
+            owner: program_id,
+            lamports: rent.minimum_balance(WithdrawalRoot::LEN),
+        }
+        .invoke_signed(&[Signer::from(&withdrawal_root_seeds)])?;
+
+        let mut root_data = withdrawal_root_account.try_borrow_mut_data()?;
+        let root_state = unsafe { load_acc_mut_unchecked::<WithdrawalRoot>(&mut root_data)? };
+        root_state.new(
+            domain,
+            public_inputs.withdrawal_root,
+            header.new_batch_index,
+            withdrawal_root_bump,
+        )?;
     }
 
     // Update config - only after successful verification
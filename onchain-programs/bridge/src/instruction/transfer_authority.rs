@@ -0,0 +1,36 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+use pinocchio_log::log;
+
+use crate::{
+    helpers::{check_signer, load_acc_mut},
+    state::Config,
+};
+
+/// Hand the sequencer authority to a new key. Both keys sign, so the
+/// authority can't be moved to a key nobody controls; every
+/// authority-gated instruction follows the new key from then on.
+pub fn process_transfer_authority(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    let [authority, new_authority, config_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority)?;
+    check_signer(new_authority)?;
+
+    if !ix_data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut config_data = config_account.try_borrow_mut_data()?;
+    let config = unsafe { load_acc_mut::<Config>(&mut config_data)? };
+
+    if authority.key() != &config.sequencer_authority {
+        return Err(ProgramError::IncorrectAuthority);
+    }
+
+    config.sequencer_authority = *new_authority.key();
+
+    log!("ZE_BRIDGE_AUTHORITY_TRANSFERRED");
+
+    Ok(())
+}
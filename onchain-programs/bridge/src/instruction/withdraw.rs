@@ -2,19 +2,20 @@ use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
     instruction::{Seed, Signer},
+    log::sol_log_data,
     program_error::ProgramError,
     pubkey::Pubkey,
     sysvars::{Sysvar, clock::Clock, rent::Rent},
 };
-use pinocchio_log::log;
 use pinocchio_system::instructions::CreateAccount;
+use zelana_bridge_events::WithdrawEvent;
 
 use crate::helpers::utils::Initialized;
 use crate::{
     ID,
     helpers::{
-        StateDefinition, check_signer, derive_nullifier_pda, derive_vault_pda, load_acc,
-        load_acc_mut_unchecked, load_ix_data,
+        StateDefinition, check_signer, derive_nullifier_pda, derive_vault_pda, load_acc_mut,
+        load_acc_mut_unchecked,
     },
     instruction::WithdrawAttestedParams,
     state::{Config, UsedNullifier},
@@ -39,8 +40,8 @@ pub fn process_withdraw_attested(
 
     check_signer(sequencer)?;
 
-    let config_data = config_account.try_borrow_data()?;
-    let config = unsafe { load_acc::<Config>(&config_data)? };
+    let mut config_data = config_account.try_borrow_mut_data()?;
+    let config = unsafe { load_acc_mut::<Config>(&mut config_data)? };
 
     if !config.is_initialized() {
         return Err(ProgramError::UninitializedAccount);
@@ -50,9 +51,12 @@ pub fn process_withdraw_attested(
         return Err(ProgramError::IncorrectAuthority);
     }
 
+    config.check_not_paused()?;
+
     let domain = config.domain;
 
-    let params = unsafe { load_ix_data::<WithdrawAttestedParams>(ix_data)? };
+    let params =
+        WithdrawAttestedParams::from_bytes(ix_data).ok_or(ProgramError::InvalidInstructionData)?;
 
     if params.amount == 0 {
         return Err(ProgramError::InvalidInstructionData);
@@ -103,13 +107,20 @@ pub fn process_withdraw_attested(
         nullifier_bump,
     )?;
 
+    config.record_withdrawal(params.amount)?;
+
     *vault_account.try_borrow_mut_lamports()? -= params.amount;
     *recipient.try_borrow_mut_lamports()? += params.amount;
 
     let clock = Clock::get()?;
 
-    log!("withdraw:{}", params.amount);
-    log!("ts:{}", clock.unix_timestamp);
+    let event = WithdrawEvent {
+        recipient: *recipient.key(),
+        amount: params.amount,
+        nullifier: params.nullifier,
+        timestamp: clock.unix_timestamp,
+    };
+    sol_log_data(&[&event.to_bytes()]);
 
     Ok(())
 }
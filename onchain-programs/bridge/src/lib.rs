@@ -6,6 +6,9 @@ mod entrypoint;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "client")]
+pub mod client;
+pub mod error;
 pub mod helpers;
 pub mod instruction;
 pub mod state;
@@ -1,6 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 
+use crate::error::BridgeError;
 use crate::helpers::{Initialized, StateDefinition};
 
 #[derive(Pod, Zeroable, Debug, Clone, Copy, PartialEq, shank::ShankAccount)]
@@ -12,9 +13,21 @@ pub struct Config {
     pub state_root: [u8; 32],
     /// The index of the last processed batch
     pub batch_index: u64,
+    /// Smallest deposit accepted, in lamports
+    pub min_deposit: u64,
+    /// Largest deposit accepted, in lamports
+    pub max_deposit: u64,
+    /// Sequence number the next deposit is assigned (deposits so far)
+    pub l1_seq: u64,
+    /// Lamports ever deposited through `Deposit`
+    pub total_deposited: u64,
+    /// Lamports ever released by withdrawals (never above `total_deposited`)
+    pub total_withdrawn: u64,
     pub bump: u8,
     pub is_initialized: u8,
-    pub _padding: [u8; 6],
+    /// 1 while deposits and withdrawals are halted (`SetPaused`)
+    pub paused: u8,
+    pub _padding: [u8; 5],
 }
 
 impl StateDefinition for Config {
@@ -32,23 +45,112 @@ impl Config {
         &mut self,
         sequencer_authority: Pubkey,
         domain: [u8; 32],
+        min_deposit: u64,
+        max_deposit: u64,
         bump: u8,
     ) -> Result<(), ProgramError> {
-        if sequencer_authority == Pubkey::default() {
-            return Err(ProgramError::InvalidArgument);
-        }
-        if domain == [0u8; 32] {
-            return Err(ProgramError::InvalidArgument);
-        }
+        Self::validate(&sequencer_authority, &domain, min_deposit, max_deposit)?;
 
         self.sequencer_authority = sequencer_authority;
         self.domain = domain;
         self.state_root = [0u8; 32];
         self.batch_index = 0;
+        self.min_deposit = min_deposit;
+        self.max_deposit = max_deposit;
+        self.l1_seq = 0;
+        self.total_deposited = 0;
+        self.total_withdrawn = 0;
         self.bump = bump;
         self.is_initialized = 1;
-        self._padding = [0; 6];
+        self.paused = 0;
+        self._padding = [0; 5];
+
+        Ok(())
+    }
+
+    /// Reject parameters that would leave the bridge unusable
+    pub fn validate(
+        sequencer_authority: &Pubkey,
+        domain: &[u8; 32],
+        min_deposit: u64,
+        max_deposit: u64,
+    ) -> Result<(), BridgeError> {
+        if *sequencer_authority == Pubkey::default() {
+            return Err(BridgeError::InvalidSequencerAuthority);
+        }
+        if *domain == [0u8; 32] {
+            return Err(BridgeError::InvalidDomain);
+        }
+        if min_deposit == 0 || min_deposit > max_deposit {
+            return Err(BridgeError::InvalidDepositBounds);
+        }
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused == 1
+    }
+
+    /// Guard for instructions that move funds in or out of the vault
+    pub fn check_not_paused(&self) -> Result<(), BridgeError> {
+        if self.is_paused() {
+            return Err(BridgeError::Paused);
+        }
+        Ok(())
+    }
+
+    /// Check a deposit amount against the configured bounds
+    pub fn check_deposit_amount(&self, amount: u64) -> Result<(), BridgeError> {
+        if amount < self.min_deposit {
+            return Err(BridgeError::DepositBelowMinimum);
+        }
+        if amount > self.max_deposit {
+            return Err(BridgeError::DepositAboveMaximum);
+        }
+        Ok(())
+    }
+
+    /// Assign the next deposit sequence number
+    pub fn next_l1_seq(&mut self) -> Result<u64, ProgramError> {
+        let l1_seq = self.l1_seq;
+        self.l1_seq = l1_seq
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(l1_seq)
+    }
 
+    /// Add a deposit to the running total
+    pub fn record_deposit(&mut self, amount: u64) -> Result<(), ProgramError> {
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         Ok(())
     }
+
+    /// Add a withdrawal to the running total, refusing any that would release
+    /// more than was ever deposited
+    pub fn record_withdrawal(&mut self, amount: u64) -> Result<(), ProgramError> {
+        let total_withdrawn = self
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if total_withdrawn > self.total_deposited {
+            return Err(BridgeError::WithdrawalExceedsDeposits.into());
+        }
+        self.total_withdrawn = total_withdrawn;
+        Ok(())
+    }
+
+    /// Decode an initialized config from raw account data (e.g. fetched over RPC)
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let config: Self = bytemuck::pod_read_unaligned(data);
+        if !config.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(config)
+    }
 }
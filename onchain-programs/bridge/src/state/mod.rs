@@ -2,8 +2,79 @@ pub mod config;
 pub mod depositreceipt;
 pub mod usernullifier;
 pub mod vault;
+pub mod withdrawalroot;
 
 pub use config::*;
 pub use depositreceipt::*;
 pub use usernullifier::*;
 pub use vault::*;
+pub use withdrawalroot::*;
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::*;
+    use crate::helpers::StateDefinition;
+
+    /// `LEN` is the account space the program allocates; the state is
+    /// read back by casting the account data, so it must equal the
+    /// serialized size and the field-by-field layout
+    fn assert_layout<T: StateDefinition + Zeroable + bytemuck::Pod>(fields: usize) {
+        assert_eq!(T::LEN, fields);
+        assert_eq!(bytemuck::bytes_of(&T::zeroed()).len(), T::LEN);
+        // u64 fields stay aligned when the account data is cast in place
+        assert_eq!(T::LEN % 8, 0);
+    }
+
+    #[test]
+    fn test_state_len_matches_layout() {
+        assert_layout::<Config>(
+            32 + // sequencer_authority
+            32 + // domain
+            32 + // state_root
+            8 + // batch_index
+            8 + // min_deposit
+            8 + // max_deposit
+            8 + // l1_seq
+            8 + // total_deposited
+            8 + // total_withdrawn
+            1 + // bump
+            1 + // is_initialized
+            1 + // paused
+            5, // _padding
+        );
+        assert_layout::<Vault>(
+            32 + // domain
+            1 + // bump
+            7, // _padding
+        );
+        assert_layout::<DepositReceipt>(
+            32 + // depositor
+            32 + // domain
+            8 + // amount
+            8 + // nonce
+            8 + // ts
+            1 + // bump
+            1 + // is_initialized
+            6, // _padding
+        );
+        assert_layout::<UsedNullifier>(
+            32 + // domain
+            32 + // nullifier
+            32 + // recipient
+            8 + // amount
+            1 + // used
+            1 + // bump
+            6, // _padding
+        );
+        assert_layout::<WithdrawalRoot>(
+            32 + // domain
+            32 + // root
+            8 + // batch_id
+            1 + // bump
+            1 + // is_initialized
+            6, // _padding
+        );
+    }
+}
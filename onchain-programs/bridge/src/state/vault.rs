@@ -1,5 +1,6 @@
 use crate::helpers::{Initialized, StateDefinition};
 use bytemuck::{Pod, Zeroable};
+use pinocchio::program_error::ProgramError;
 
 #[derive(Pod, Zeroable, Debug, Clone, Copy, PartialEq, shank::ShankAccount)]
 #[repr(C)]
@@ -27,4 +28,16 @@ impl Vault {
         self.bump = bump;
         self._padding = [0; 7];
     }
+
+    /// Decode an initialized vault from raw account data (e.g. fetched over RPC)
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let vault: Self = bytemuck::pod_read_unaligned(data);
+        if !vault.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(vault)
+    }
 }
@@ -0,0 +1,51 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::program_error::ProgramError;
+
+use crate::helpers::{Initialized, StateDefinition};
+
+/// Withdrawal Merkle root of a settled batch, written by `SubmitBatch` and
+/// checked by `FinalizeWithdrawal`
+#[derive(Pod, Zeroable, Debug, Clone, Copy, PartialEq, shank::ShankAccount)]
+#[repr(C)]
+pub struct WithdrawalRoot {
+    pub domain: [u8; 32],
+    pub root: [u8; 32],
+    pub batch_id: u64,
+    pub bump: u8,
+    pub is_initialized: u8,
+    pub _padding: [u8; 6],
+}
+
+impl StateDefinition for WithdrawalRoot {
+    const LEN: usize = core::mem::size_of::<WithdrawalRoot>();
+}
+
+impl Initialized for WithdrawalRoot {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized == 1
+    }
+}
+
+impl WithdrawalRoot {
+    pub fn new(
+        &mut self,
+        domain: [u8; 32],
+        root: [u8; 32],
+        batch_id: u64,
+        bump: u8,
+    ) -> Result<(), ProgramError> {
+        // An all-zero root is the empty tree; nothing can be claimed against it
+        if root == [0u8; 32] {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.domain = domain;
+        self.root = root;
+        self.batch_id = batch_id;
+        self.bump = bump;
+        self.is_initialized = 1;
+        self._padding = [0; 6];
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+mod common;
+use bridge_z::{
+    client::{get_config_pda, get_vault_pda, get_withdrawal_root_pda},
+    helpers::StateDefinition,
+    state::{Config, Vault},
+};
+use common::TestFixture;
+use pinocchio::program_error::ProgramError;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+#[test]
+fn test_read_bridge_state_with_client_helpers() {
+    let mut fixture = TestFixture::new();
+    fixture.initialize_bridge().expect("Bridge initialize fail");
+
+    let (config_pda, _) = get_config_pda(&fixture.domain);
+    let (vault_pda, vault_bump) = get_vault_pda(&fixture.domain);
+    assert_eq!(Pubkey::from(config_pda), fixture.config_pda);
+    assert_eq!(Pubkey::from(vault_pda), fixture.vault_pda);
+    assert_eq!(
+        Pubkey::from(get_withdrawal_root_pda(&fixture.domain, 7).0),
+        common::derive_withdrawal_root_pda(&fixture.program_id, &fixture.domain, 7).0
+    );
+
+    let config_account = fixture
+        .svm
+        .get_account(&Pubkey::from(config_pda))
+        .expect("Config account not found");
+    let config = Config::try_from_account_data(&config_account.data).unwrap();
+    assert_eq!(
+        config.sequencer_authority,
+        fixture.sequencer.pubkey().to_bytes()
+    );
+    assert_eq!(config.domain, fixture.domain);
+    assert_eq!(config.batch_index, 0);
+
+    let vault_account = fixture
+        .svm
+        .get_account(&Pubkey::from(vault_pda))
+        .expect("Vault account not found");
+    let vault = Vault::try_from_account_data(&vault_account.data).unwrap();
+    assert_eq!(vault.domain, fixture.domain);
+    assert_eq!(vault.bump, vault_bump);
+
+    // Wrong-sized or uninitialized data is rejected rather than misread
+    assert_eq!(
+        Config::try_from_account_data(&config_account.data[1..]),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(
+        Config::try_from_account_data(&[0u8; Config::LEN]),
+        Err(ProgramError::UninitializedAccount)
+    );
+}
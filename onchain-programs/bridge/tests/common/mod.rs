@@ -1,7 +1,7 @@
 use bridge_z::{
     ID,
     helpers::StateDefinition,
-    instruction::{BridgeIx, InitParams},
+    instruction::{BridgeIx, DepositParams, InitParams},
     state::{Config, Vault},
 };
 use litesvm::{
@@ -19,6 +19,8 @@ use solana_sdk::{
 };
 
 pub const TEST_DOMAIN: [u8; 32] = [1u8; 32];
+pub const TEST_MIN_DEPOSIT: u64 = 1_000;
+pub const TEST_MAX_DEPOSIT: u64 = 5_000_000_000;
 
 pub fn derive_config_pda(program_id: &Pubkey, domain: &[u8; 32]) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[b"config", domain.as_ref()], program_id)
@@ -28,6 +30,17 @@ pub fn derive_vault_pda(program_id: &Pubkey, domain: &[u8; 32]) -> (Pubkey, u8)
     Pubkey::find_program_address(&[b"vault", domain.as_ref()], program_id)
 }
 
+pub fn derive_withdrawal_root_pda(
+    program_id: &Pubkey,
+    domain: &[u8; 32],
+    batch_id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"withdrawal_root", domain.as_ref(), &batch_id.to_le_bytes()],
+        program_id,
+    )
+}
+
 pub fn setup_svm_and_program() -> (LiteSVM, Keypair, Keypair, Pubkey) {
     let mut svm = LiteSVM::new();
     let fee_payer = Keypair::new();
@@ -104,10 +117,19 @@ impl TestFixture {
 
     pub fn initialize_bridge(&mut self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
         let sequencer_pubkey = self.sequencer.pubkey();
-        let ix_data = InitParams {
+        self.initialize_bridge_with(InitParams {
             sequencer_authority: *sequencer_pubkey.as_array(),
             domain: self.domain,
-        };
+            min_deposit: TEST_MIN_DEPOSIT,
+            max_deposit: TEST_MAX_DEPOSIT,
+        })
+    }
+
+    /// Send `Initialize` with arbitrary params (PDAs still derived from `self.domain`)
+    pub fn initialize_bridge_with(
+        &mut self,
+        ix_data: InitParams,
+    ) -> Result<TransactionMetadata, FailedTransactionMetadata> {
         let mut instruction_data = vec![BridgeIx::INIT as u8];
         instruction_data.extend_from_slice(bytemuck::bytes_of(&ix_data));
 
@@ -127,9 +149,47 @@ impl TestFixture {
         self.build_and_send_transaction(&[], vec![init_ix])
     }
 
+    /// Deposit `amount` from the payer, so withdrawals stay within `total_deposited`
     pub fn fund_vault(
         &mut self,
         amount: u64,
+    ) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let config_account = self.svm.get_account(&self.config_pda).unwrap();
+        let nonce = Config::try_from_account_data(&config_account.data)
+            .unwrap()
+            .l1_seq;
+        let (receipt_pda, _) = Pubkey::find_program_address(
+            &[
+                b"receipt",
+                self.domain.as_ref(),
+                self.payer.pubkey().as_ref(),
+                &nonce.to_le_bytes(),
+            ],
+            &self.program_id,
+        );
+
+        let mut data = vec![BridgeIx::DEPOSIT as u8];
+        data.extend_from_slice(&DepositParams { amount, nonce }.to_bytes());
+
+        let deposit_ix = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.payer.pubkey(), true),
+                AccountMeta::new(self.config_pda, false),
+                AccountMeta::new(self.vault_pda, false),
+                AccountMeta::new(receipt_pda, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data,
+        };
+
+        self.build_and_send_transaction(&[], vec![deposit_ix])
+    }
+
+    /// Move lamports into the vault without a `Deposit` (not counted in `total_deposited`)
+    pub fn transfer_to_vault(
+        &mut self,
+        amount: u64,
     ) -> Result<TransactionMetadata, FailedTransactionMetadata> {
         let transfer_ix =
             system_instruction::transfer(&self.payer.pubkey(), &self.vault_pda, amount);
@@ -1,5 +1,6 @@
 mod common;
-use bridge_z::{ID, helpers::{Initialized, StateDefinition, derive_deposit_receipt_pda}, instruction::{BridgeIx, DepositParams}, state::DepositReceipt};
+use base64::prelude::*;
+use bridge_z::{ID, helpers::{Initialized, StateDefinition, derive_deposit_receipt_pda}, instruction::{BridgeIx, DepositParams}, state::{Config, DepositReceipt}};
 use common::TestFixture;
 use pinocchio::pubkey::find_program_address;
 use solana_sdk::{instruction::{AccountMeta, Instruction}, pubkey::Pubkey, signature::Keypair, signer::Signer, system_program};
@@ -39,7 +40,7 @@ fn text_deposit_success(){
 
     let accounts = vec![
     AccountMeta::new(depositor_pubkey, true),
-    AccountMeta::new_readonly(fixture.config_pda, false),
+    AccountMeta::new(fixture.config_pda, false),
     AccountMeta::new(fixture.vault_pda, false),
     AccountMeta::new(receipt_pda.into(), false),
     AccountMeta::new_readonly(system_program::ID, false),
@@ -100,7 +101,7 @@ fn test_deposit_replay_fails() {
 
     let accounts = vec![
         AccountMeta::new(depositor.pubkey(), true),
-        AccountMeta::new_readonly(fixture.config_pda, false),
+        AccountMeta::new(fixture.config_pda, false),
         AccountMeta::new(fixture.vault_pda, false),
         AccountMeta::new(receipt_pda.into(), false),
         AccountMeta::new_readonly(system_program::ID, false),
@@ -124,3 +125,81 @@ fn test_deposit_replay_fails() {
     assert!(receipt_state.is_initialized());
 }
 
+/// Deposit `amount` from `depositor` and return the emitted `DepositEvent`
+fn deposit_and_read_event(
+    fixture: &mut TestFixture,
+    depositor: &Keypair,
+    amount: u64,
+    nonce: u64,
+) -> zelana_bridge_events::DepositEvent {
+    let (receipt_pda, _) = Pubkey::find_program_address(
+        &[
+            b"receipt",
+            fixture.domain.as_ref(),
+            depositor.pubkey().as_ref(),
+            &nonce.to_le_bytes(),
+        ],
+        &fixture.program_id,
+    );
+
+    let ix_data = DepositParams { amount, nonce };
+    let mut instruction_data = vec![BridgeIx::DEPOSIT as u8];
+    instruction_data.extend_from_slice(bytemuck::bytes_of(&ix_data));
+
+    let accounts = vec![
+        AccountMeta::new(depositor.pubkey(), true),
+        AccountMeta::new(fixture.config_pda, false),
+        AccountMeta::new(fixture.vault_pda, false),
+        AccountMeta::new(receipt_pda, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    let deposit_ix = Instruction {
+        program_id: fixture.program_id,
+        accounts,
+        data: instruction_data,
+    };
+
+    // The fee payer always signs, so only add other depositors
+    let signers = if depositor.pubkey() == fixture.payer.pubkey() {
+        vec![]
+    } else {
+        vec![depositor]
+    };
+    let meta = fixture
+        .build_and_send_transaction(&signers, vec![deposit_ix])
+        .expect("deposit failed");
+    meta.logs
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .find_map(|data| {
+            let bytes = BASE64_STANDARD.decode(data).ok()?;
+            zelana_bridge_events::DepositEvent::from_bytes(&bytes)
+        })
+        .expect("deposit emitted no DepositEvent")
+}
+
+#[test]
+fn test_deposits_get_consecutive_l1_seq() {
+    let mut fixture = TestFixture::new();
+    fixture
+        .initialize_bridge()
+        .expect("Bridge initialization failed");
+
+    let payer = fixture.payer.insecure_clone();
+    let other = fixture.sequencer.insecure_clone();
+
+    // Nonces are per depositor, so the same nonce from two depositors must
+    // still get distinct sequence numbers
+    let first = deposit_and_read_event(&mut fixture, &payer, 100_000_000, 1);
+    let second = deposit_and_read_event(&mut fixture, &other, 200_000_000, 1);
+    let third = deposit_and_read_event(&mut fixture, &payer, 300_000_000, 2);
+
+    assert_eq!([first.l1_seq, second.l1_seq, third.l1_seq], [0, 1, 2]);
+    assert_eq!(first.depositor, payer.pubkey().to_bytes());
+    assert_eq!(second.depositor, other.pubkey().to_bytes());
+    assert_eq!(third.amount, 300_000_000);
+
+    let config_account = fixture.svm.get_account(&fixture.config_pda).unwrap();
+    let config = Config::try_from_account_data(&config_account.data).unwrap();
+    assert_eq!(config.l1_seq, 3);
+}
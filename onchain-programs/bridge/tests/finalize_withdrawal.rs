@@ -0,0 +1,240 @@
+mod common;
+
+use bridge_z::{
+    error::BridgeError,
+    helpers::StateDefinition,
+    instruction::{BridgeIx, FinalizeWithdrawalParams},
+    state::{UsedNullifier, WithdrawalRoot},
+};
+use common::TestFixture;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::TransactionError,
+};
+use zelana_bridge_events::merkle::{WithdrawalTree, withdrawal_leaf};
+
+const BATCH_ID: u64 = 1;
+
+/// A settled batch of two withdrawals whose root is stored on-chain
+struct SettledBatch {
+    recipients: [Keypair; 2],
+    tx_hashes: [[u8; 32]; 2],
+    amounts: [u64; 2],
+    tree: WithdrawalTree,
+}
+
+impl SettledBatch {
+    fn new() -> Self {
+        let recipients = [Keypair::new(), Keypair::new()];
+        let tx_hashes = [[11u8; 32], [12u8; 32]];
+        let amounts = [300_000_000, 700_000_000];
+        let leaves = [0, 1].map(|i| {
+            withdrawal_leaf(
+                &tx_hashes[i],
+                &recipients[i].pubkey().to_bytes(),
+                amounts[i],
+            )
+        });
+        Self {
+            recipients,
+            tx_hashes,
+            amounts,
+            tree: WithdrawalTree::new(&leaves).unwrap(),
+        }
+    }
+
+    /// Sibling path of withdrawal `i`, as the sequencer's index serves it
+    fn proof(&self, i: usize) -> [[u8; 32]; 2] {
+        self.tree.proof(i).unwrap()
+    }
+
+    /// Store the root as `SubmitBatch` would after a verified batch
+    fn settle(&self, fixture: &mut TestFixture) {
+        let (pda, bump) =
+            common::derive_withdrawal_root_pda(&fixture.program_id, &fixture.domain, BATCH_ID);
+        let state = WithdrawalRoot {
+            domain: fixture.domain,
+            root: self.tree.root(),
+            batch_id: BATCH_ID,
+            bump,
+            is_initialized: 1,
+            _padding: [0; 6],
+        };
+        let lamports = fixture
+            .svm
+            .minimum_balance_for_rent_exemption(WithdrawalRoot::LEN);
+        fixture
+            .svm
+            .set_account(
+                pda,
+                Account {
+                    lamports,
+                    data: bytemuck::bytes_of(&state).to_vec(),
+                    owner: fixture.program_id,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+    }
+
+    /// Claim withdrawal `i` with the given proof
+    fn claim_ix(
+        &self,
+        fixture: &TestFixture,
+        claimant: &Pubkey,
+        i: usize,
+        proof: &[[u8; 32]],
+    ) -> Instruction {
+        let params = FinalizeWithdrawalParams {
+            batch_id: BATCH_ID,
+            tx_hash: self.tx_hashes[i],
+            recipient: self.recipients[i].pubkey().to_bytes(),
+            amount: self.amounts[i],
+            leaf_position: i as u64,
+        };
+        let mut data = vec![BridgeIx::FinalizeWithdrawal as u8];
+        data.extend_from_slice(&params.to_bytes());
+        for sibling in proof {
+            data.extend_from_slice(sibling);
+        }
+
+        let (withdrawal_root_pda, _) =
+            common::derive_withdrawal_root_pda(&fixture.program_id, &fixture.domain, BATCH_ID);
+        let (nullifier_pda, _) = Pubkey::find_program_address(
+            &[b"nullifier", fixture.domain.as_ref(), &self.tx_hashes[i]],
+            &fixture.program_id,
+        );
+
+        Instruction {
+            program_id: fixture.program_id,
+            accounts: vec![
+                AccountMeta::new(*claimant, true),
+                AccountMeta::new(fixture.config_pda, false),
+                AccountMeta::new(fixture.vault_pda, false),
+                AccountMeta::new(self.recipients[i].pubkey(), false),
+                AccountMeta::new_readonly(withdrawal_root_pda, false),
+                AccountMeta::new(nullifier_pda, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data,
+        }
+    }
+}
+
+fn setup() -> (TestFixture, SettledBatch) {
+    let mut fixture = TestFixture::new();
+    fixture.initialize_bridge().expect("bridge init failed");
+    fixture
+        .fund_vault(2_000_000_000)
+        .expect("funding vault failed");
+
+    let batch = SettledBatch::new();
+    batch.settle(&mut fixture);
+    (fixture, batch)
+}
+
+#[test]
+fn test_finalize_withdrawal_success() {
+    let (mut fixture, batch) = setup();
+    let payer = fixture.payer.pubkey();
+
+    let vault_before = fixture.svm.get_balance(&fixture.vault_pda).unwrap();
+
+    let ix = batch.claim_ix(&fixture, &payer, 1, &batch.proof(1));
+    let result = fixture.build_and_send_transaction(&[], vec![ix]);
+    assert!(result.is_ok(), "claim failed {:?}", result.unwrap_err());
+
+    let vault_after = fixture.svm.get_balance(&fixture.vault_pda).unwrap();
+    assert_eq!(vault_after, vault_before - batch.amounts[1]);
+    let recipient_balance = fixture
+        .svm
+        .get_balance(&batch.recipients[1].pubkey())
+        .unwrap();
+    assert_eq!(recipient_balance, batch.amounts[1]);
+
+    // The claim consumed the withdrawal's nullifier
+    let (nullifier_pda, _) = Pubkey::find_program_address(
+        &[b"nullifier", fixture.domain.as_ref(), &batch.tx_hashes[1]],
+        &fixture.program_id,
+    );
+    let nullifier_account = fixture.svm.get_account(&nullifier_pda).unwrap();
+    let nullifier: &UsedNullifier = bytemuck::from_bytes(&nullifier_account.data);
+    assert_eq!(nullifier.amount, batch.amounts[1]);
+    assert_eq!(nullifier.used, 1);
+}
+
+#[test]
+fn test_finalize_withdrawal_double_claim_fails() {
+    let (mut fixture, batch) = setup();
+    let payer = fixture.payer.pubkey();
+
+    let ix = batch.claim_ix(&fixture, &payer, 0, &batch.proof(0));
+    fixture
+        .build_and_send_transaction(&[], vec![ix])
+        .expect("first claim failed");
+
+    // A different claimant makes the retry a distinct transaction
+    let claimant = Keypair::new();
+    fixture
+        .svm
+        .airdrop(&claimant.pubkey(), 1_000_000_000)
+        .unwrap();
+    let ix = batch.claim_ix(&fixture, &claimant.pubkey(), 0, &batch.proof(0));
+    let err = fixture
+        .build_and_send_transaction(&[&claimant], vec![ix])
+        .expect_err("double claim must fail")
+        .err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(BridgeError::WithdrawalAlreadyClaimed as u32)
+        )
+    );
+}
+
+#[test]
+fn test_finalize_withdrawal_bad_proof_fails() {
+    let (mut fixture, batch) = setup();
+    let payer = fixture.payer.pubkey();
+    let vault_before = fixture.svm.get_balance(&fixture.vault_pda).unwrap();
+
+    // Wrong sibling hash, or another withdrawal's path
+    let mut wrong_sibling = batch.proof(0);
+    wrong_sibling[0] = [7u8; 32];
+    for proof in [wrong_sibling, batch.proof(1)] {
+        let ix = batch.claim_ix(&fixture, &payer, 0, &proof);
+        let err = fixture
+            .build_and_send_transaction(&[], vec![ix])
+            .expect_err("bad proof must fail")
+            .err;
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(BridgeError::InvalidWithdrawalProof as u32)
+            )
+        );
+    }
+
+    // A truncated path is malformed instruction data
+    let ix = batch.claim_ix(&fixture, &payer, 0, &batch.proof(0)[..1]);
+    let err = fixture
+        .build_and_send_transaction(&[], vec![ix])
+        .expect_err("short proof must fail")
+        .err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+    assert_eq!(
+        fixture.svm.get_balance(&fixture.vault_pda).unwrap(),
+        vault_before
+    );
+}
@@ -1,7 +1,7 @@
 mod common;
-use bridge_z::{ID, helpers::{Initialized, StateDefinition}, state::Config};
-use common::TestFixture;
-use solana_sdk::{signer::Signer};
+use bridge_z::{ID, error::BridgeError, helpers::{Initialized, StateDefinition}, instruction::InitParams, state::Config};
+use common::{TEST_MAX_DEPOSIT, TEST_MIN_DEPOSIT, TestFixture};
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, signer::Signer, transaction::TransactionError};
 #[test]
 fn test_initialize_bridge_success(){
     let mut fixture = TestFixture::new();
@@ -17,6 +17,8 @@ fn test_initialize_bridge_success(){
     let pubkey = fixture.sequencer.pubkey();
     assert_eq!(config_state.sequencer_authority, *pubkey.as_array());
     assert_eq!(config_state.domain, fixture.domain);
+    assert_eq!(config_state.min_deposit, TEST_MIN_DEPOSIT);
+    assert_eq!(config_state.max_deposit, TEST_MAX_DEPOSIT);
     assert!(
         config_state.is_initialized(),
         "config should be initialized"
@@ -50,3 +52,62 @@ fn test_initialize_bridge_twice_fails(){
         "config state must not change on re-init attempt"
     );
 }
+
+#[test]
+fn test_initialize_rejects_invalid_params() {
+    let valid = InitParams {
+        sequencer_authority: Pubkey::new_unique().to_bytes(),
+        domain: common::TEST_DOMAIN,
+        min_deposit: TEST_MIN_DEPOSIT,
+        max_deposit: TEST_MAX_DEPOSIT,
+    };
+
+    let cases = [
+        (
+            InitParams {
+                sequencer_authority: [0u8; 32],
+                ..valid
+            },
+            BridgeError::InvalidSequencerAuthority,
+        ),
+        (
+            InitParams {
+                domain: [0u8; 32],
+                ..valid
+            },
+            BridgeError::InvalidDomain,
+        ),
+        (
+            InitParams {
+                min_deposit: 0,
+                ..valid
+            },
+            BridgeError::InvalidDepositBounds,
+        ),
+        (
+            InitParams {
+                min_deposit: TEST_MAX_DEPOSIT + 1,
+                ..valid
+            },
+            BridgeError::InvalidDepositBounds,
+        ),
+    ];
+
+    for (params, expected) in cases {
+        let mut fixture = TestFixture::new();
+        let result = fixture.initialize_bridge_with(params);
+
+        let err = result.expect_err("invalid init params must be rejected").err;
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(0, InstructionError::Custom(expected as u32)),
+            "expected {:?}",
+            expected
+        );
+        // Nothing was created, so a valid init still goes through
+        assert!(fixture.svm.get_account(&fixture.config_pda).is_none());
+        fixture
+            .initialize_bridge_with(valid)
+            .expect("valid init after a rejected one");
+    }
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use bridge_z::{
+    error::BridgeError,
+    instruction::{BridgeIx, DepositParams, SetPausedParams, WithdrawAttestedParams},
+    state::Config,
+};
+use common::TestFixture;
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::TransactionError,
+};
+
+fn set_paused(
+    fixture: &mut TestFixture,
+    authority: &Keypair,
+    paused: bool,
+) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+    let mut data = vec![BridgeIx::SetPaused as u8];
+    data.extend_from_slice(
+        &SetPausedParams {
+            paused: paused as u8,
+        }
+        .to_bytes(),
+    );
+
+    let ix = Instruction {
+        program_id: fixture.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new(fixture.config_pda, false),
+        ],
+        data,
+    };
+    fixture.build_and_send_transaction(&[authority], vec![ix])
+}
+
+fn deposit(
+    fixture: &mut TestFixture,
+    nonce: u64,
+) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+    let depositor = fixture.payer.pubkey();
+    let (receipt_pda, _) = Pubkey::find_program_address(
+        &[
+            b"receipt",
+            fixture.domain.as_ref(),
+            depositor.as_ref(),
+            &nonce.to_le_bytes(),
+        ],
+        &fixture.program_id,
+    );
+
+    let mut data = vec![BridgeIx::DEPOSIT as u8];
+    data.extend_from_slice(
+        &DepositParams {
+            amount: 1_000_000,
+            nonce,
+        }
+        .to_bytes(),
+    );
+
+    let ix = Instruction {
+        program_id: fixture.program_id,
+        accounts: vec![
+            AccountMeta::new(depositor, true),
+            AccountMeta::new(fixture.config_pda, false),
+            AccountMeta::new(fixture.vault_pda, false),
+            AccountMeta::new(receipt_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    };
+    fixture.build_and_send_transaction(&[], vec![ix])
+}
+
+fn withdraw(
+    fixture: &mut TestFixture,
+    nullifier: [u8; 32],
+) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+    let recipient = Pubkey::new_unique();
+    let (nullifier_pda, _) = Pubkey::find_program_address(
+        &[b"nullifier", fixture.domain.as_ref(), &nullifier],
+        &fixture.program_id,
+    );
+
+    let mut data = vec![BridgeIx::WITHDRAWATTESTED as u8];
+    data.extend_from_slice(
+        &WithdrawAttestedParams {
+            recipient: recipient.to_bytes(),
+            amount: 100_000_000,
+            nullifier,
+        }
+        .to_bytes(),
+    );
+
+    let ix = Instruction {
+        program_id: fixture.program_id,
+        accounts: vec![
+            AccountMeta::new(fixture.sequencer.pubkey(), true),
+            AccountMeta::new(fixture.config_pda, false),
+            AccountMeta::new(fixture.vault_pda, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new(nullifier_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    };
+    let sequencer = fixture.sequencer.insecure_clone();
+    fixture.build_and_send_transaction(&[&sequencer], vec![ix])
+}
+
+fn is_paused(fixture: &TestFixture) -> bool {
+    let account = fixture.svm.get_account(&fixture.config_pda).unwrap();
+    Config::try_from_account_data(&account.data)
+        .unwrap()
+        .is_paused()
+}
+
+fn instruction_error(
+    result: Result<TransactionMetadata, FailedTransactionMetadata>,
+) -> TransactionError {
+    result.expect_err("transaction must fail").err
+}
+
+#[test]
+fn test_pause_blocks_deposits_and_withdrawals() {
+    let mut fixture = TestFixture::new();
+    fixture.initialize_bridge().expect("bridge init failed");
+    fixture
+        .fund_vault(2_000_000_000)
+        .expect("funding vault failed");
+
+    let sequencer = fixture.sequencer.insecure_clone();
+    set_paused(&mut fixture, &sequencer, true).expect("pause failed");
+    assert!(is_paused(&fixture));
+
+    let paused =
+        TransactionError::InstructionError(0, InstructionError::Custom(BridgeError::Paused as u32));
+    assert_eq!(instruction_error(deposit(&mut fixture, 1)), paused);
+    assert_eq!(instruction_error(withdraw(&mut fixture, [1u8; 32])), paused);
+
+    set_paused(&mut fixture, &sequencer, false).expect("unpause failed");
+    assert!(!is_paused(&fixture));
+
+    deposit(&mut fixture, 2).expect("deposit after unpause failed");
+    withdraw(&mut fixture, [2u8; 32]).expect("withdraw after unpause failed");
+}
+
+#[test]
+fn test_only_authority_can_pause() {
+    let mut fixture = TestFixture::new();
+    fixture.initialize_bridge().expect("bridge init failed");
+
+    let intruder = Keypair::new();
+    fixture
+        .svm
+        .airdrop(&intruder.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    let result = set_paused(&mut fixture, &intruder, true);
+    assert!(result.is_err(), "non-authority pause must fail");
+    assert!(!is_paused(&fixture));
+
+    let sequencer = fixture.sequencer.insecure_clone();
+    set_paused(&mut fixture, &sequencer, true).expect("pause failed");
+
+    let result = set_paused(&mut fixture, &intruder, false);
+    assert!(result.is_err(), "non-authority unpause must fail");
+    assert!(is_paused(&fixture));
+}
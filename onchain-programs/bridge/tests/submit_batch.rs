@@ -1,10 +1,12 @@
 mod common;
 
+use base64::{Engine as _, prelude::BASE64_STANDARD};
 use bridge_z::{
-    instruction::{BatchPublicInputs, BridgeIx, Groth16Proof, SubmitBatchHeader},
-    state::Config,
+    instruction::{
+        BatchPublicInputs, BridgeIx, Groth16Proof, SubmitBatchHeader, WithdrawalRequest,
+    },
+    state::{Config, WithdrawalRoot},
 };
-use common::TestFixture;
 use litesvm::LiteSVM;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -12,11 +14,40 @@ use solana_sdk::{
     signature::Keypair,
     signer::Signer,
 };
+use zelana_bridge_events::BridgeEvent;
 
 /// Verifier program ID (must match the deployed verifier)
 const VERIFIER_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("8TveT3mvH59qLzZNwrTT6hBqDHEobW2XnCPb7xZLBYHd");
 
+/// Anchor discriminator for store_batch_vk instruction
+/// = sha256("global:store_batch_vk")[0..8]
+const STORE_BATCH_VK_DISCRIMINATOR: [u8; 8] = [0x5f, 0x81, 0xed, 0x49, 0x8b, 0x44, 0x24, 0xf5];
+
+/// BN254 G1 generator (1, 2), big-endian
+const G1: &str = concat!(
+    "0000000000000000000000000000000000000000000000000000000000000001",
+    "0000000000000000000000000000000000000000000000000000000000000002",
+);
+
+/// Negated G1 generator (1, p - 2), big-endian
+const NEG_G1: &str = concat!(
+    "0000000000000000000000000000000000000000000000000000000000000001",
+    "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45",
+);
+
+/// BN254 G2 generator in EIP-197 order (x_c1, x_c0, y_c1, y_c0)
+const G2: &str = concat!(
+    "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+    "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+    "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+    "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+);
+
+fn decode<const N: usize>(hex_str: &str) -> [u8; N] {
+    hex::decode(hex_str).unwrap().try_into().unwrap()
+}
+
 /// Derive the batch VK PDA
 fn derive_batch_vk_pda(domain: &[u8; 32]) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[b"batch_vk", domain.as_ref()], &VERIFIER_PROGRAM_ID)
@@ -103,6 +134,8 @@ impl SubmitBatchFixture {
         let ix_data = bridge_z::instruction::InitParams {
             sequencer_authority: *sequencer_pubkey.as_array(),
             domain: self.domain,
+            min_deposit: common::TEST_MIN_DEPOSIT,
+            max_deposit: common::TEST_MAX_DEPOSIT,
         };
 
         let mut instruction_data = vec![BridgeIx::INIT as u8];
@@ -165,13 +198,117 @@ impl SubmitBatchFixture {
         data.extend_from_slice(bytemuck::bytes_of(&proof));
         data.extend_from_slice(bytemuck::bytes_of(&public_inputs));
 
-        // Accounts: [sequencer, config, verifier_program, vk_account]
+        let (withdrawal_root_pda, _) =
+            common::derive_withdrawal_root_pda(&self.program_id, &self.domain, new_batch_index);
+
+        // Accounts: [sequencer, config, verifier_program, vk_account, withdrawal_root, system_program]
         let accounts = vec![
             AccountMeta::new(*signer, true),
             AccountMeta::new(self.config_pda, false),
             AccountMeta::new_readonly(VERIFIER_PROGRAM_ID, false),
             AccountMeta::new_readonly(self.vk_pda, false),
+            AccountMeta::new(withdrawal_root_pda, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }
+    }
+
+    /// Store a verifying key every input set satisfies: with all-zero IC
+    /// points `vk_x` is the identity, so the pairing check reduces to
+    /// `e(-g1, g2) * e(g1, g2) == 1` for the proof from [`Self::trivial_proof`]
+    fn store_trivial_batch_vk(
+        &mut self,
+    ) -> Result<litesvm::types::TransactionMetadata, litesvm::types::FailedTransactionMetadata>
+    {
+        let g2: [u8; 128] = decode(G2);
+
+        let mut data = STORE_BATCH_VK_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&self.domain);
+        data.extend_from_slice(&decode::<64>(G1)); // alpha_g1
+        data.extend_from_slice(&g2); // beta_g2
+        data.extend_from_slice(&g2); // gamma_g2
+        data.extend_from_slice(&g2); // delta_g2
+        // 8 IC points (7 public inputs + 1)
+        data.extend_from_slice(&8u32.to_le_bytes());
+        data.extend_from_slice(&[0u8; 8 * 64]);
+
+        let ix = Instruction {
+            program_id: VERIFIER_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.payer.pubkey(), true),
+                AccountMeta::new(self.vk_pda, false),
+                AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+            ],
+            data,
+        };
+
+        self.build_and_send_transaction(&[], vec![ix])
+    }
+
+    /// Proof accepted by the key from [`Self::store_trivial_batch_vk`]
+    fn trivial_proof() -> Groth16Proof {
+        Groth16Proof {
+            pi_a: decode(NEG_G1),
+            pi_b: decode(G2),
+            pi_c: [0u8; 64],
+        }
+    }
+
+    /// Build a submit_batch instruction settling batch 1 with the given
+    /// withdrawal root and withdrawals, laid out the way the sequencer sends it
+    fn build_settling_batch_ix(
+        &self,
+        new_state_root: [u8; 32],
+        withdrawal_root: [u8; 32],
+        withdrawals: &[WithdrawalRequest],
+    ) -> Instruction {
+        let header = SubmitBatchHeader {
+            prev_batch_index: 0,
+            new_batch_index: 1,
+            new_state_root,
+            proof_len: 256,
+            withdrawal_count: withdrawals.len() as u32,
+        };
+        let public_inputs = BatchPublicInputs {
+            pre_state_root: [0u8; 32],
+            post_state_root: new_state_root,
+            pre_shielded_root: [0u8; 32],
+            post_shielded_root: [0u8; 32],
+            withdrawal_root,
+            batch_hash: [0u8; 32],
+            batch_id: 1,
+        };
+
+        // The handler skips one byte after the discriminator
+        let mut data = vec![BridgeIx::SubmitBatch as u8, 0];
+        data.extend_from_slice(bytemuck::bytes_of(&header));
+        data.extend_from_slice(bytemuck::bytes_of(&Self::trivial_proof()));
+        data.extend_from_slice(bytemuck::bytes_of(&public_inputs));
+        for w in withdrawals {
+            data.extend_from_slice(bytemuck::bytes_of(w));
+        }
+
+        let (withdrawal_root_pda, _) =
+            common::derive_withdrawal_root_pda(&self.program_id, &self.domain, 1);
+
+        let mut accounts = vec![
+            AccountMeta::new(self.sequencer.pubkey(), true),
+            AccountMeta::new(self.config_pda, false),
+            AccountMeta::new_readonly(VERIFIER_PROGRAM_ID, false),
+            AccountMeta::new_readonly(self.vk_pda, false),
+            AccountMeta::new(withdrawal_root_pda, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
         ];
+        accounts.extend(
+            withdrawals
+                .iter()
+                .map(|w| AccountMeta::new_readonly(Pubkey::from(w.recipient), false)),
+        );
 
         Instruction {
             program_id: self.program_id,
@@ -245,3 +382,79 @@ fn test_submit_batch_invalid_batch_sequence_fails() {
 
     assert!(result.is_err(), "invalid batch sequence must fail");
 }
+
+#[test]
+fn test_submit_batch_records_withdrawal_root() {
+    let mut fixture = SubmitBatchFixture::new();
+    fixture.initialize_bridge().expect("bridge init failed");
+    fixture.store_trivial_batch_vk().expect("store vk failed");
+
+    let recipient = Keypair::new().pubkey();
+    let withdrawal = WithdrawalRequest {
+        recipient: recipient.to_bytes(),
+        amount: 250_000_000,
+        tx_hash: [0x11; 32],
+    };
+    // Any in-field value; the program stores what the proof committed to
+    let withdrawal_root = [0x07; 32];
+    let ix = fixture.build_settling_batch_ix([9u8; 32], withdrawal_root, &[withdrawal]);
+
+    let sequencer = fixture.sequencer.insecure_clone();
+    let meta = fixture
+        .build_and_send_transaction(&[&sequencer], vec![ix])
+        .expect("submit_batch failed");
+
+    let (withdrawal_root_pda, bump) =
+        common::derive_withdrawal_root_pda(&fixture.program_id, &fixture.domain, 1);
+    let account = fixture
+        .svm
+        .get_account(&withdrawal_root_pda)
+        .expect("withdrawal root not created");
+    assert_eq!(account.owner, fixture.program_id);
+    let root: &WithdrawalRoot = bytemuck::from_bytes(&account.data);
+    assert_eq!(root.domain, fixture.domain);
+    assert_eq!(root.root, withdrawal_root);
+    assert_eq!(root.batch_id, 1);
+    assert_eq!(root.bump, bump);
+    assert_eq!(root.is_initialized, 1);
+
+    let config_account = fixture.svm.get_account(&fixture.config_pda).unwrap();
+    let config: &Config = bytemuck::from_bytes(&config_account.data);
+    assert_eq!(config.batch_index, 1);
+    assert_eq!(config.state_root, [9u8; 32]);
+
+    // Each withdrawal is announced as a `Program data:` log
+    let events: Vec<BridgeEvent> = meta
+        .logs
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| {
+            let bytes = BASE64_STANDARD.decode(data).expect("event is not base64");
+            BridgeEvent::from_bytes(&bytes).expect("event does not parse")
+        })
+        .collect();
+    let [BridgeEvent::WithdrawalInitiated(event)] = events.as_slice() else {
+        panic!("expected one WithdrawalInitiatedEvent, got {events:?}");
+    };
+    assert_eq!(event.batch_id, 1);
+    assert_eq!(event.tx_hash, withdrawal.tx_hash);
+    assert_eq!(event.recipient, recipient.to_bytes());
+    assert_eq!(event.amount, withdrawal.amount);
+}
+
+#[test]
+fn test_submit_batch_without_withdrawals_skips_root() {
+    let mut fixture = SubmitBatchFixture::new();
+    fixture.initialize_bridge().expect("bridge init failed");
+    fixture.store_trivial_batch_vk().expect("store vk failed");
+
+    let ix = fixture.build_settling_batch_ix([9u8; 32], [0u8; 32], &[]);
+    let sequencer = fixture.sequencer.insecure_clone();
+    fixture
+        .build_and_send_transaction(&[&sequencer], vec![ix])
+        .expect("submit_batch failed");
+
+    let (withdrawal_root_pda, _) =
+        common::derive_withdrawal_root_pda(&fixture.program_id, &fixture.domain, 1);
+    assert!(fixture.svm.get_account(&withdrawal_root_pda).is_none());
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use bridge_z::{
+    instruction::{BridgeIx, SetPausedParams},
+    state::Config,
+};
+use common::TestFixture;
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::TransactionError,
+};
+
+fn transfer_authority_ix(
+    fixture: &TestFixture,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+    new_authority_signs: bool,
+) -> Instruction {
+    Instruction {
+        program_id: fixture.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*new_authority, new_authority_signs),
+            AccountMeta::new(fixture.config_pda, false),
+        ],
+        data: vec![BridgeIx::TransferAuthority as u8],
+    }
+}
+
+fn transfer_authority(
+    fixture: &mut TestFixture,
+    authority: &Keypair,
+    new_authority: &Keypair,
+) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+    let ix = transfer_authority_ix(fixture, &authority.pubkey(), &new_authority.pubkey(), true);
+    fixture.build_and_send_transaction(&[authority, new_authority], vec![ix])
+}
+
+fn set_paused(
+    fixture: &mut TestFixture,
+    authority: &Keypair,
+) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+    let mut data = vec![BridgeIx::SetPaused as u8];
+    data.extend_from_slice(&SetPausedParams { paused: 1 }.to_bytes());
+
+    let ix = Instruction {
+        program_id: fixture.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new(fixture.config_pda, false),
+        ],
+        data,
+    };
+    fixture.build_and_send_transaction(&[authority], vec![ix])
+}
+
+fn config(fixture: &TestFixture) -> Config {
+    let account = fixture.svm.get_account(&fixture.config_pda).unwrap();
+    Config::try_from_account_data(&account.data).unwrap()
+}
+
+#[test]
+fn test_transfer_authority_success() {
+    let mut fixture = TestFixture::new();
+    fixture.initialize_bridge().expect("bridge init failed");
+
+    let old_authority = fixture.sequencer.insecure_clone();
+    let new_authority = Keypair::new();
+    transfer_authority(&mut fixture, &old_authority, &new_authority)
+        .expect("authority transfer failed");
+
+    assert_eq!(
+        config(&fixture).sequencer_authority,
+        new_authority.pubkey().to_bytes()
+    );
+
+    // Authority-gated instructions follow the new key
+    let result = set_paused(&mut fixture, &old_authority);
+    assert!(result.is_err(), "old authority must lose control");
+    assert!(!config(&fixture).is_paused());
+
+    set_paused(&mut fixture, &new_authority).expect("new authority pause failed");
+    assert!(config(&fixture).is_paused());
+}
+
+#[test]
+fn test_transfer_authority_unauthorized() {
+    let mut fixture = TestFixture::new();
+    fixture.initialize_bridge().expect("bridge init failed");
+
+    let intruder = Keypair::new();
+    fixture
+        .svm
+        .airdrop(&intruder.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    let result = transfer_authority(&mut fixture, &intruder, &Keypair::new());
+    assert!(result.is_err(), "non-authority transfer must fail");
+    assert_eq!(
+        config(&fixture).sequencer_authority,
+        fixture.sequencer.pubkey().to_bytes()
+    );
+}
+
+#[test]
+fn test_transfer_authority_requires_new_authority_signature() {
+    let mut fixture = TestFixture::new();
+    fixture.initialize_bridge().expect("bridge init failed");
+
+    let sequencer = fixture.sequencer.insecure_clone();
+    let ix = transfer_authority_ix(&fixture, &sequencer.pubkey(), &Pubkey::new_unique(), false);
+    let err = fixture
+        .build_and_send_transaction(&[&sequencer], vec![ix])
+        .expect_err("unsigned new authority must fail")
+        .err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+    );
+    assert_eq!(
+        config(&fixture).sequencer_authority,
+        sequencer.pubkey().to_bytes()
+    );
+}
@@ -1,15 +1,18 @@
 mod common;
+use base64::prelude::*;
 use common::TestFixture;
 use bridge_z::{
-     ID, helpers::{Initialized, derive_nullifier_pda}, instruction::{BridgeIx, WithdrawAttestedParams}, state::UsedNullifier
+     ID, error::BridgeError, helpers::{Initialized, derive_nullifier_pda}, instruction::{BridgeIx, WithdrawAttestedParams}, state::{Config, UsedNullifier}
 };
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    instruction::{AccountMeta, Instruction, InstructionError},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program,
+    transaction::TransactionError,
 };
+use zelana_bridge_events::BridgeEvent;
 
 #[test]
 fn test_withdraw_attested_success() {
@@ -45,7 +48,7 @@ fn test_withdraw_attested_success() {
 
     let accounts = vec![
         AccountMeta::new(fixture.sequencer.pubkey(), true),
-        AccountMeta::new_readonly(fixture.config_pda, false),
+        AccountMeta::new(fixture.config_pda, false),
         AccountMeta::new(fixture.vault_pda, false),
         AccountMeta::new(recipient.pubkey(), false),
         AccountMeta::new(nullifier_pda.into(), false),
@@ -109,7 +112,7 @@ fn test_withdraw_unauthorized_sequencer_fails() {
 
     let accounts = vec![
         AccountMeta::new(unauthorized.pubkey(), true),
-        AccountMeta::new_readonly(fixture.config_pda, false),
+        AccountMeta::new(fixture.config_pda, false),
         AccountMeta::new(fixture.vault_pda, false),
         AccountMeta::new(recipient.pubkey(), false),
         AccountMeta::new(nullifier_pda.into(), false),
@@ -151,7 +154,7 @@ fn test_withdraw_replay_fails() {
 
     let accounts = vec![
         AccountMeta::new(fixture.sequencer.pubkey(), true),
-        AccountMeta::new_readonly(fixture.config_pda, false),
+        AccountMeta::new(fixture.config_pda, false),
         AccountMeta::new(fixture.vault_pda, false),
         AccountMeta::new(recipient.pubkey(), false),
         AccountMeta::new(nullifier_pda.into(), false),
@@ -177,4 +180,127 @@ fn test_withdraw_replay_fails() {
         fixture.build_and_send_transaction(&[&sequencer], vec![ix]);
 
     assert!(replay.is_err(), "replay withdraw must fail");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_withdraw_cannot_exceed_total_deposited() {
+    let mut fixture = TestFixture::new();
+    fixture
+        .initialize_bridge()
+        .expect("Bridge initialization failed");
+    fixture.fund_vault(1_000_000_000).expect("Deposit failed");
+    // Lamports sent straight to the vault are not withdrawable deposits
+    fixture
+        .transfer_to_vault(1_000_000_000)
+        .expect("Transfer to vault failed");
+
+    let recipient = Keypair::new();
+    let sequencer = fixture.sequencer.insecure_clone();
+    let withdraw = |fixture: &mut TestFixture, amount: u64, nullifier: [u8; 32]| {
+        let (nullifier_pda, _) = Pubkey::find_program_address(
+            &[b"nullifier", fixture.domain.as_ref(), &nullifier],
+            &fixture.program_id,
+        );
+        let params = WithdrawAttestedParams {
+            recipient: recipient.pubkey().to_bytes(),
+            amount,
+            nullifier,
+        };
+        let mut data = vec![BridgeIx::WITHDRAWATTESTED as u8];
+        data.extend_from_slice(&params.to_bytes());
+
+        let ix = Instruction {
+            program_id: fixture.program_id,
+            accounts: vec![
+                AccountMeta::new(sequencer.pubkey(), true),
+                AccountMeta::new(fixture.config_pda, false),
+                AccountMeta::new(fixture.vault_pda, false),
+                AccountMeta::new(recipient.pubkey(), false),
+                AccountMeta::new(nullifier_pda, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data,
+        };
+        fixture.build_and_send_transaction(&[&sequencer], vec![ix])
+    };
+
+    withdraw(&mut fixture, 600_000_000, [5u8; 32]).expect("Withdraw within deposits failed");
+
+    let config_account = fixture.svm.get_account(&fixture.config_pda).unwrap();
+    let config = Config::try_from_account_data(&config_account.data).unwrap();
+    assert_eq!(config.total_deposited, 1_000_000_000);
+    assert_eq!(config.total_withdrawn, 600_000_000);
+
+    // The vault still holds enough, but only 400_000_000 was deposited and not withdrawn
+    let err = withdraw(&mut fixture, 500_000_000, [6u8; 32])
+        .expect_err("Over-withdrawal must fail")
+        .err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(BridgeError::WithdrawalExceedsDeposits as u32)
+        )
+    );
+
+    withdraw(&mut fixture, 400_000_000, [7u8; 32]).expect("Withdrawing the remainder failed");
+}
+
+#[test]
+fn test_withdraw_emits_withdraw_event() {
+    let mut fixture = TestFixture::new();
+    fixture
+        .initialize_bridge()
+        .expect("Bridge initialization failed");
+    fixture
+        .fund_vault(1_000_000_000)
+        .expect("Funding vault failed");
+
+    let recipient = Keypair::new();
+    let tx_hash = [9u8; 32];
+    let (nullifier_pda, _) = Pubkey::find_program_address(
+        &[b"nullifier", fixture.domain.as_ref(), &tx_hash],
+        &fixture.program_id,
+    );
+    let params = WithdrawAttestedParams {
+        recipient: recipient.pubkey().to_bytes(),
+        amount: 250_000_000,
+        nullifier: tx_hash,
+    };
+    let mut data = vec![BridgeIx::WITHDRAWATTESTED as u8];
+    data.extend_from_slice(&params.to_bytes());
+
+    let ix = Instruction {
+        program_id: fixture.program_id,
+        accounts: vec![
+            AccountMeta::new(fixture.sequencer.pubkey(), true),
+            AccountMeta::new(fixture.config_pda, false),
+            AccountMeta::new(fixture.vault_pda, false),
+            AccountMeta::new(recipient.pubkey(), false),
+            AccountMeta::new(nullifier_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    };
+    let sequencer = fixture.sequencer.insecure_clone();
+    let meta = fixture
+        .build_and_send_transaction(&[&sequencer], vec![ix])
+        .expect("Withdraw failed");
+
+    // The indexer sees the withdrawal as a `Program data:` log it can decode
+    let events: Vec<BridgeEvent> = meta
+        .logs
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| {
+            let bytes = BASE64_STANDARD.decode(data).expect("event is not base64");
+            BridgeEvent::from_bytes(&bytes).expect("event does not parse")
+        })
+        .collect();
+    let [BridgeEvent::Withdraw(event)] = events.as_slice() else {
+        panic!("expected one WithdrawEvent, got {events:?}");
+    };
+    assert_eq!(event.recipient, recipient.pubkey().to_bytes());
+    assert_eq!(event.amount, 250_000_000);
+    assert_eq!(event.nullifier, tx_hash);
+}
@@ -118,6 +118,8 @@ impl ZkTestFixture {
         let ix_data = bridge_z::instruction::InitParams {
             sequencer_authority: *sequencer_pubkey.as_array(),
             domain: self.domain,
+            min_deposit: common::TEST_MIN_DEPOSIT,
+            max_deposit: common::TEST_MAX_DEPOSIT,
         };
 
         let mut instruction_data = vec![BridgeIx::INIT as u8];
@@ -308,12 +310,16 @@ fn test_zk_flow_submit_batch_accounts_structure() {
     );
 
     // Build accounts list
-    // [sequencer, config, verifier_program, vk_account, ...recipients]
+    // [sequencer, config, verifier_program, vk_account, withdrawal_root, system_program, ...recipients]
+    let (withdrawal_root_pda, _) =
+        common::derive_withdrawal_root_pda(&fixture.bridge_program_id, &fixture.domain, 1);
     let accounts = vec![
         AccountMeta::new(fixture.sequencer.pubkey(), true),
         AccountMeta::new(fixture.config_pda, false),
         AccountMeta::new_readonly(fixture.verifier_program_id, false),
         AccountMeta::new_readonly(fixture.vk_pda, false),
+        AccountMeta::new(withdrawal_root_pda, false),
+        AccountMeta::new_readonly(system_program::ID, false),
     ];
 
     let submit_ix = Instruction {
@@ -15,12 +15,12 @@ use std::path::Path;
 
 use anyhow::{Context, Result};
 use ark_bn254::Bn254;
-use ark_ec::AffineRepr;
-use ark_ff::{BigInteger, PrimeField};
 use ark_groth16::VerifyingKey;
 use ark_serialize::CanonicalDeserialize;
 use serde::Serialize;
 
+use prover::vk_deploy::{g1_to_bytes, g2_to_bytes};
+
 /// JSON output format for VK
 #[derive(Serialize)]
 struct VkJson {
@@ -103,23 +103,27 @@ fn main() -> Result<()> {
     println!("Converting to Solana format...");
 
     // Alpha G1 (64 bytes)
-    let alpha_g1 = g1_to_bytes(&vk.alpha_g1);
+    let alpha_g1 = g1_to_bytes(&vk.alpha_g1).to_vec();
     println!("  alpha_g1: {} bytes", alpha_g1.len());
 
     // Beta G2 (128 bytes)
-    let beta_g2 = g2_to_bytes(&vk.beta_g2);
+    let beta_g2 = g2_to_bytes(&vk.beta_g2).to_vec();
     println!("  beta_g2: {} bytes", beta_g2.len());
 
     // Gamma G2 (128 bytes)
-    let gamma_g2 = g2_to_bytes(&vk.gamma_g2);
+    let gamma_g2 = g2_to_bytes(&vk.gamma_g2).to_vec();
     println!("  gamma_g2: {} bytes", gamma_g2.len());
 
     // Delta G2 (128 bytes)
-    let delta_g2 = g2_to_bytes(&vk.delta_g2);
+    let delta_g2 = g2_to_bytes(&vk.delta_g2).to_vec();
     println!("  delta_g2: {} bytes", delta_g2.len());
 
     // IC points (each 64 bytes)
-    let ic: Vec<Vec<u8>> = vk.gamma_abc_g1.iter().map(|p| g1_to_bytes(p)).collect();
+    let ic: Vec<Vec<u8>> = vk
+        .gamma_abc_g1
+        .iter()
+        .map(|p| g1_to_bytes(p).to_vec())
+        .collect();
     println!("  ic: {} points x 64 bytes", ic.len());
 
     // Compute VK hash
@@ -160,36 +164,6 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-/// Convert G1 affine point to 64 bytes (little-endian)
-fn g1_to_bytes(p: &ark_bn254::G1Affine) -> Vec<u8> {
-    let mut bytes = vec![0u8; 64];
-    if !p.is_zero() {
-        let x_bytes = p.x.into_bigint().to_bytes_le();
-        let y_bytes = p.y.into_bigint().to_bytes_le();
-        bytes[..32].copy_from_slice(&x_bytes[..32.min(x_bytes.len())]);
-        bytes[32..64].copy_from_slice(&y_bytes[..32.min(y_bytes.len())]);
-    }
-    bytes
-}
-
-/// Convert G2 affine point to 128 bytes (little-endian)
-fn g2_to_bytes(p: &ark_bn254::G2Affine) -> Vec<u8> {
-    let mut bytes = vec![0u8; 128];
-    if !p.is_zero() {
-        // G2 point has Fq2 coordinates: (x.c0, x.c1, y.c0, y.c1)
-        let x_c0_bytes = p.x.c0.into_bigint().to_bytes_le();
-        let x_c1_bytes = p.x.c1.into_bigint().to_bytes_le();
-        let y_c0_bytes = p.y.c0.into_bigint().to_bytes_le();
-        let y_c1_bytes = p.y.c1.into_bigint().to_bytes_le();
-
-        bytes[0..32].copy_from_slice(&x_c0_bytes[..32.min(x_c0_bytes.len())]);
-        bytes[32..64].copy_from_slice(&x_c1_bytes[..32.min(x_c1_bytes.len())]);
-        bytes[64..96].copy_from_slice(&y_c0_bytes[..32.min(y_c0_bytes.len())]);
-        bytes[96..128].copy_from_slice(&y_c1_bytes[..32.min(y_c1_bytes.len())]);
-    }
-    bytes
-}
-
 fn print_help() {
     println!("Zelana VK Conversion Tool");
     println!();
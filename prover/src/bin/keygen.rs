@@ -6,6 +6,12 @@
 //! Usage:
 //!   cargo run --package prover --bin keygen -- --pk-out ./proving.key --vk-out ./verifying.key
 //!
+//! With `--deploy-out`, it also writes the signed verifier transactions that
+//! store the VK on-chain (`store_batch_vk`, or the chunked upload when the key
+//! doesn't fit in one transaction):
+//!   cargo run --package prover --bin keygen -- --deploy-out ./vk_deploy.json \
+//!     --program-id <VERIFIER_ID> --keypair ~/.config/solana/id.json
+//!
 //! Note: Key generation is a one-time operation. Keys must be regenerated if the circuit changes.
 
 use std::fs;
@@ -14,11 +20,29 @@ use std::path::Path;
 use anyhow::{Context, Result};
 use ark_bn254::Bn254;
 use ark_groth16::Groth16;
-use ark_serialize::CanonicalSerialize;
+use ark_groth16::VerifyingKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_snark::SNARK;
 use ark_std::rand::{SeedableRng, rngs::StdRng};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::signer::keypair::read_keypair_file;
 
 use prover::L2BlockCircuit;
+use prover::vk_deploy::{SolanaVk, VkDeployment, deploy_transactions};
+use zelana_config::Domain;
+
+/// Where and how to emit the on-chain VK deployment
+struct DeployArgs {
+    out_path: String,
+    program_id: Option<String>,
+    domain: String,
+    keypair_path: String,
+    blockhash: Option<String>,
+    rpc_url: String,
+}
 
 fn main() -> Result<()> {
     let args: Vec<String> = std::env::args().collect();
@@ -27,6 +51,14 @@ fn main() -> Result<()> {
     let mut pk_path = String::from("./proving.key");
     let mut vk_path = String::from("./verifying.key");
     let mut force = false;
+    let mut deploy_out: Option<String> = None;
+    let mut program_id: Option<String> = None;
+    let mut domain = String::from("zelana");
+    let mut keypair_path = std::env::var("HOME")
+        .map(|home| format!("{}/.config/solana/id.json", home))
+        .unwrap_or_else(|_| String::from("id.json"));
+    let mut blockhash: Option<String> = None;
+    let mut rpc_url = String::from("http://127.0.0.1:8899");
 
     let mut i = 1;
     while i < args.len() {
@@ -46,6 +78,36 @@ fn main() -> Result<()> {
             "--force" | "-f" => {
                 force = true;
             }
+            "--deploy-out" => {
+                i += 1;
+                deploy_out = args.get(i).cloned();
+            }
+            "--program-id" => {
+                i += 1;
+                program_id = args.get(i).cloned();
+            }
+            "--domain" => {
+                i += 1;
+                if i < args.len() {
+                    domain = args[i].clone();
+                }
+            }
+            "--keypair" => {
+                i += 1;
+                if i < args.len() {
+                    keypair_path = args[i].clone();
+                }
+            }
+            "--blockhash" => {
+                i += 1;
+                blockhash = args.get(i).cloned();
+            }
+            "--rpc-url" => {
+                i += 1;
+                if i < args.len() {
+                    rpc_url = args[i].clone();
+                }
+            }
             "--help" | "-h" => {
                 print_help();
                 return Ok(());
@@ -59,12 +121,29 @@ fn main() -> Result<()> {
         i += 1;
     }
 
+    let deploy = deploy_out.map(|out_path| DeployArgs {
+        out_path,
+        program_id,
+        domain,
+        keypair_path,
+        blockhash,
+        rpc_url,
+    });
+
     // Check if keys already exist
     if !force && Path::new(&pk_path).exists() && Path::new(&vk_path).exists() {
         println!("Keys already exist at:");
         println!("  Proving key:   {}", pk_path);
         println!("  Verifying key: {}", vk_path);
         println!("\nUse --force to regenerate keys.");
+
+        if let Some(deploy) = &deploy {
+            let vk_bytes = fs::read(&vk_path).context("Failed to read verifying key")?;
+            let vk = VerifyingKey::<Bn254>::deserialize_compressed(&*vk_bytes)
+                .context("Failed to deserialize verifying key")?;
+            println!();
+            write_deployment(deploy, &vk)?;
+        }
         return Ok(());
     }
 
@@ -129,6 +208,11 @@ fn main() -> Result<()> {
     println!("Verification key hash (blake3):");
     println!("  {}", hex::encode(vk_hash.as_bytes()));
 
+    if let Some(deploy) = &deploy {
+        println!();
+        write_deployment(deploy, &vk)?;
+    }
+
     println!();
     println!("Key generation complete!");
     println!();
@@ -140,6 +224,57 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Sign the verifier transactions storing `vk` and write them to the deploy file
+fn write_deployment(deploy: &DeployArgs, vk: &VerifyingKey<Bn254>) -> Result<()> {
+    let program_id: Pubkey = deploy
+        .program_id
+        .as_deref()
+        .context("--deploy-out requires --program-id")?
+        .parse()
+        .context("Invalid --program-id")?;
+    let domain = deploy
+        .domain
+        .parse::<Domain>()
+        .context("Invalid --domain")?
+        .as_bytes();
+    let authority = read_keypair_file(&deploy.keypair_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read keypair {}: {}", deploy.keypair_path, e))?;
+
+    let recent_blockhash = match &deploy.blockhash {
+        Some(hash) => hash.parse::<Hash>().context("Invalid --blockhash")?,
+        None => RpcClient::new(deploy.rpc_url.clone())
+            .get_latest_blockhash()
+            .with_context(|| format!("Failed to fetch blockhash from {}", deploy.rpc_url))?,
+    };
+
+    println!("Building VK deployment for program {}...", program_id);
+    let solana_vk = SolanaVk::from_arkworks(vk);
+    let transactions = deploy_transactions(
+        &program_id,
+        &authority,
+        &domain,
+        &solana_vk,
+        recent_blockhash,
+    )?;
+    let deployment = VkDeployment::new(&program_id, &domain, &authority.pubkey(), &transactions)?;
+
+    if let Some(parent) = Path::new(&deploy.out_path).parent() {
+        fs::create_dir_all(parent).context("Failed to create deployment directory")?;
+    }
+    fs::write(&deploy.out_path, serde_json::to_string_pretty(&deployment)?)
+        .context("Failed to write deployment")?;
+
+    println!("  Domain:       {}", deploy.domain);
+    println!("  VK PDA:       {}", deployment.vk_pda);
+    println!("  Authority:    {}", deployment.authority);
+    println!(
+        "  Transactions: {} (send in order, before the blockhash expires)",
+        transactions.len()
+    );
+    println!("  Written to:   {}", deploy.out_path);
+    Ok(())
+}
+
 fn print_help() {
     println!("Zelana L2 ZK Key Generation Tool");
     println!();
@@ -147,12 +282,21 @@ fn print_help() {
     println!("    keygen [OPTIONS]");
     println!();
     println!("OPTIONS:");
-    println!("    --pk-out <PATH>    Path for proving key output (default: ./proving.key)");
-    println!("    --vk-out <PATH>    Path for verifying key output (default: ./verifying.key)");
-    println!("    --force, -f        Overwrite existing keys");
-    println!("    --help, -h         Show this help message");
+    println!("    --pk-out <PATH>       Path for proving key output (default: ./proving.key)");
+    println!("    --vk-out <PATH>       Path for verifying key output (default: ./verifying.key)");
+    println!("    --force, -f           Overwrite existing keys");
+    println!("    --deploy-out <PATH>   Also write signed transactions storing the VK on-chain");
+    println!("    --program-id <ID>     Verifier program id (required with --deploy-out)");
+    println!("    --domain <NAME>       Domain the VK is stored under (default: zelana)");
+    println!(
+        "    --keypair <PATH>      VK authority and fee payer (default: ~/.config/solana/id.json)"
+    );
+    println!("    --blockhash <HASH>    Blockhash to sign with (default: fetched from --rpc-url)");
+    println!("    --rpc-url <URL>       RPC for the blockhash (default: http://127.0.0.1:8899)");
+    println!("    --help, -h            Show this help message");
     println!();
     println!("EXAMPLES:");
     println!("    keygen --pk-out ./keys/proving.key --vk-out ./keys/verifying.key");
     println!("    keygen -f  # Force regeneration of keys");
+    println!("    keygen --deploy-out ./vk_deploy.json --program-id <VERIFIER_ID>");
 }
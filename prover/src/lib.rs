@@ -5,6 +5,7 @@ pub mod witness_builder;
 
 pub mod circuit;
 pub mod l2_circuit;
+pub mod vk_deploy;
 
 // Re-export key types for external usage
 pub use l2_circuit::{
@@ -24,4 +24,7 @@ fn main() {
     println!("  cargo run --package prover --bin keygen -- \\");
     println!("    --pk-out ./keys/proving.key \\");
     println!("    --vk-out ./keys/verifying.key");
+    println!();
+    println!("To also emit the signed transactions storing the VK on-chain, add:");
+    println!("    --deploy-out ./keys/vk_deploy.json --program-id <VERIFIER_ID>");
 }
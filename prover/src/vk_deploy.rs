@@ -0,0 +1,389 @@
+//! Batch VK Deployment
+//!
+//! Turns an arkworks verifying key into the transactions that store it in the
+//! Zelana verifier program, so `keygen` output can go straight on-chain.
+//!
+//! A VK that fits in one transaction is stored with `store_batch_vk`. Larger
+//! ones fall back to the chunked upload: `init_batch_vk`, one or more
+//! `append_ic_points`, then `finalize_batch_vk`.
+
+use anyhow::{Context, Result, ensure};
+use ark_bn254::{Bn254, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::VerifyingKey;
+use serde::Serialize;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::Transaction;
+
+/// Anchor discriminator for store_batch_vk
+/// = sha256("global:store_batch_vk")[0..8]
+pub const STORE_BATCH_VK_DISCRIMINATOR: [u8; 8] = [0xc8, 0x55, 0x33, 0x19, 0xe6, 0xbb, 0x61, 0x05];
+
+/// Anchor discriminator for init_batch_vk
+/// = sha256("global:init_batch_vk")[0..8]
+pub const INIT_BATCH_VK_DISCRIMINATOR: [u8; 8] = [0x71, 0x53, 0x1b, 0x0e, 0x53, 0xf1, 0x7d, 0x72];
+
+/// Anchor discriminator for append_ic_points
+/// = sha256("global:append_ic_points")[0..8]
+pub const APPEND_IC_POINTS_DISCRIMINATOR: [u8; 8] =
+    [0xdd, 0xf3, 0xb9, 0x87, 0x2f, 0x31, 0x5a, 0x57];
+
+/// Anchor discriminator for finalize_batch_vk
+/// = sha256("global:finalize_batch_vk")[0..8]
+pub const FINALIZE_BATCH_VK_DISCRIMINATOR: [u8; 8] =
+    [0xd6, 0x5c, 0xdf, 0x94, 0x35, 0x36, 0x08, 0xde];
+
+/// IC points per `append_ic_points` transaction in the chunked upload
+pub const IC_POINTS_PER_TX: usize = 4;
+
+/// Largest serialized transaction the cluster accepts (`PACKET_DATA_SIZE`)
+const MAX_TX_SIZE: usize = 1232;
+
+/// Verifying key in the verifier program's byte layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaVk {
+    pub alpha_g1: [u8; 64],
+    pub beta_g2: [u8; 128],
+    pub gamma_g2: [u8; 128],
+    pub delta_g2: [u8; 128],
+    pub ic: Vec<[u8; 64]>,
+}
+
+impl SolanaVk {
+    pub fn from_arkworks(vk: &VerifyingKey<Bn254>) -> Self {
+        Self {
+            alpha_g1: g1_to_bytes(&vk.alpha_g1),
+            beta_g2: g2_to_bytes(&vk.beta_g2),
+            gamma_g2: g2_to_bytes(&vk.gamma_g2),
+            delta_g2: g2_to_bytes(&vk.delta_g2),
+            ic: vk.gamma_abc_g1.iter().map(g1_to_bytes).collect(),
+        }
+    }
+}
+
+/// Convert G1 affine point to 64 bytes (little-endian)
+pub fn g1_to_bytes(p: &G1Affine) -> [u8; 64] {
+    let mut bytes = [0u8; 64];
+    if !p.is_zero() {
+        let x_bytes = p.x.into_bigint().to_bytes_le();
+        let y_bytes = p.y.into_bigint().to_bytes_le();
+        bytes[..32].copy_from_slice(&x_bytes[..32.min(x_bytes.len())]);
+        bytes[32..64].copy_from_slice(&y_bytes[..32.min(y_bytes.len())]);
+    }
+    bytes
+}
+
+/// Convert G2 affine point to 128 bytes (little-endian)
+pub fn g2_to_bytes(p: &G2Affine) -> [u8; 128] {
+    let mut bytes = [0u8; 128];
+    if !p.is_zero() {
+        // G2 point has Fq2 coordinates: (x.c0, x.c1, y.c0, y.c1)
+        let x_c0_bytes = p.x.c0.into_bigint().to_bytes_le();
+        let x_c1_bytes = p.x.c1.into_bigint().to_bytes_le();
+        let y_c0_bytes = p.y.c0.into_bigint().to_bytes_le();
+        let y_c1_bytes = p.y.c1.into_bigint().to_bytes_le();
+
+        bytes[0..32].copy_from_slice(&x_c0_bytes[..32.min(x_c0_bytes.len())]);
+        bytes[32..64].copy_from_slice(&x_c1_bytes[..32.min(x_c1_bytes.len())]);
+        bytes[64..96].copy_from_slice(&y_c0_bytes[..32.min(y_c0_bytes.len())]);
+        bytes[96..128].copy_from_slice(&y_c1_bytes[..32.min(y_c1_bytes.len())]);
+    }
+    bytes
+}
+
+/// PDA holding the batch VK for `domain`
+pub fn batch_vk_pda(program_id: &Pubkey, domain: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"batch_vk", domain], program_id).0
+}
+
+/// `store_batch_vk`: the whole VK in one instruction
+pub fn store_batch_vk_instruction(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    domain: &[u8; 32],
+    vk: &SolanaVk,
+) -> Instruction {
+    let mut data = init_data(STORE_BATCH_VK_DISCRIMINATOR, domain, vk);
+    push_ic_vec(&mut data, &vk.ic);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(batch_vk_pda(program_id, domain), false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// `init_batch_vk`, `append_ic_points`... and `finalize_batch_vk`
+pub fn chunked_upload_instructions(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    domain: &[u8; 32],
+    vk: &SolanaVk,
+) -> Vec<Instruction> {
+    let vk_pda = batch_vk_pda(program_id, domain);
+    let vk_accounts = vec![
+        AccountMeta::new(*authority, true),
+        AccountMeta::new(vk_pda, false),
+    ];
+
+    let mut instructions = vec![Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(vk_pda, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data: init_data(INIT_BATCH_VK_DISCRIMINATOR, domain, vk),
+    }];
+
+    for chunk in vk.ic.chunks(IC_POINTS_PER_TX) {
+        let mut data = APPEND_IC_POINTS_DISCRIMINATOR.to_vec();
+        push_ic_vec(&mut data, chunk);
+        instructions.push(Instruction {
+            program_id: *program_id,
+            accounts: vk_accounts.clone(),
+            data,
+        });
+    }
+
+    instructions.push(Instruction {
+        program_id: *program_id,
+        accounts: vk_accounts,
+        data: FINALIZE_BATCH_VK_DISCRIMINATOR.to_vec(),
+    });
+    instructions
+}
+
+/// Signed transactions deploying `vk`, to be sent in order
+///
+/// Uses a single `store_batch_vk` when it fits in a packet, the chunked
+/// upload otherwise.
+pub fn deploy_transactions(
+    program_id: &Pubkey,
+    authority: &impl Signer,
+    domain: &[u8; 32],
+    vk: &SolanaVk,
+    recent_blockhash: Hash,
+) -> Result<Vec<Transaction>> {
+    ensure!(!vk.ic.is_empty(), "verifying key has no IC points");
+
+    let authority_pk = authority.pubkey();
+    let sign = |ix: Instruction| {
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority_pk),
+            &[authority],
+            recent_blockhash,
+        )
+    };
+
+    let single = sign(store_batch_vk_instruction(
+        program_id,
+        &authority_pk,
+        domain,
+        vk,
+    ));
+    if serialized_len(&single)? <= MAX_TX_SIZE {
+        return Ok(vec![single]);
+    }
+
+    let transactions: Vec<_> = chunked_upload_instructions(program_id, &authority_pk, domain, vk)
+        .into_iter()
+        .map(sign)
+        .collect();
+    for tx in &transactions {
+        ensure!(
+            serialized_len(tx)? <= MAX_TX_SIZE,
+            "VK upload transaction exceeds {} bytes",
+            MAX_TX_SIZE
+        );
+    }
+    Ok(transactions)
+}
+
+/// `keygen --deploy-out` file: transactions ready for `sendTransaction`
+#[derive(Debug, Serialize)]
+pub struct VkDeployment {
+    pub program_id: String,
+    /// Domain seed (hex)
+    pub domain: String,
+    pub vk_pda: String,
+    pub authority: String,
+    pub recent_blockhash: String,
+    /// Signed transactions in send order (base64 wire format)
+    pub transactions: Vec<String>,
+}
+
+impl VkDeployment {
+    pub fn new(
+        program_id: &Pubkey,
+        domain: &[u8; 32],
+        authority: &Pubkey,
+        transactions: &[Transaction],
+    ) -> Result<Self> {
+        use base64::Engine;
+
+        let recent_blockhash = transactions
+            .first()
+            .map(|tx| tx.message.recent_blockhash.to_string())
+            .unwrap_or_default();
+        let encoded = transactions
+            .iter()
+            .map(|tx| {
+                bincode::serialize(tx)
+                    .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes))
+                    .context("Failed to serialize transaction")
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            program_id: program_id.to_string(),
+            domain: hex::encode(domain),
+            vk_pda: batch_vk_pda(program_id, domain).to_string(),
+            authority: authority.to_string(),
+            recent_blockhash,
+            transactions: encoded,
+        })
+    }
+}
+
+/// Init payload shared by `store_batch_vk` and `init_batch_vk`
+fn init_data(discriminator: [u8; 8], domain: &[u8; 32], vk: &SolanaVk) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(domain);
+    data.extend_from_slice(&vk.alpha_g1);
+    data.extend_from_slice(&vk.beta_g2);
+    data.extend_from_slice(&vk.gamma_g2);
+    data.extend_from_slice(&vk.delta_g2);
+    data
+}
+
+/// Borsh `Vec<[u8; 64]>`: u32 length, then the points
+fn push_ic_vec(data: &mut Vec<u8>, points: &[[u8; 64]]) {
+    data.extend_from_slice(&(points.len() as u32).to_le_bytes());
+    for point in points {
+        data.extend_from_slice(point);
+    }
+}
+
+fn serialized_len(tx: &Transaction) -> Result<usize> {
+    Ok(bincode::serialized_size(tx).context("Failed to size transaction")? as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+    use zelana_config::Domain;
+
+    /// VK with distinct bytes in every field, `ic_len` IC points
+    fn test_vk(ic_len: usize) -> SolanaVk {
+        SolanaVk {
+            alpha_g1: [1u8; 64],
+            beta_g2: [2u8; 128],
+            gamma_g2: [3u8; 128],
+            delta_g2: [4u8; 128],
+            ic: (0..ic_len).map(|i| [10 + i as u8; 64]).collect(),
+        }
+    }
+
+    /// Split the init payload back into its domain and curve points
+    fn decode_init(data: &[u8]) -> ([u8; 32], SolanaVk) {
+        let domain = data[..32].try_into().unwrap();
+        let vk = SolanaVk {
+            alpha_g1: data[32..96].try_into().unwrap(),
+            beta_g2: data[96..224].try_into().unwrap(),
+            gamma_g2: data[224..352].try_into().unwrap(),
+            delta_g2: data[352..480].try_into().unwrap(),
+            ic: decode_ic_vec(&data[480..]),
+        };
+        (domain, vk)
+    }
+
+    fn decode_ic_vec(data: &[u8]) -> Vec<[u8; 64]> {
+        if data.is_empty() {
+            return vec![];
+        }
+        let len = u32::from_le_bytes(data[..4].try_into().unwrap()) as usize;
+        assert_eq!(data.len(), 4 + len * 64);
+        data[4..]
+            .chunks(64)
+            .map(|p| p.try_into().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_store_batch_vk_decodes_to_vk() {
+        let program_id = Pubkey::new_unique();
+        let authority = Keypair::new();
+        let domain = "zelana".parse::<Domain>().unwrap().as_bytes();
+        let vk = test_vk(3);
+
+        let txs =
+            deploy_transactions(&program_id, &authority, &domain, &vk, Hash::default()).unwrap();
+        assert_eq!(txs.len(), 1, "a small VK fits in one transaction");
+        txs[0].verify().unwrap();
+
+        let message = &txs[0].message;
+        let ix = &message.instructions[0];
+        assert_eq!(
+            message.account_keys[ix.program_id_index as usize],
+            program_id
+        );
+        assert_eq!(message.account_keys[0], authority.pubkey());
+        assert!(
+            message
+                .account_keys
+                .contains(&batch_vk_pda(&program_id, &domain))
+        );
+
+        assert_eq!(ix.data[..8], STORE_BATCH_VK_DISCRIMINATOR);
+        let (decoded_domain, decoded_vk) = decode_init(&ix.data[8..]);
+        assert_eq!(decoded_domain, domain);
+        assert_eq!(decoded_vk, vk);
+    }
+
+    #[test]
+    fn test_large_vk_uses_chunked_upload() {
+        let program_id = Pubkey::new_unique();
+        let authority = Keypair::new();
+        let domain = "zelana".parse::<Domain>().unwrap().as_bytes();
+        // 7 public inputs, as in the batch circuit
+        let vk = test_vk(8);
+
+        let txs =
+            deploy_transactions(&program_id, &authority, &domain, &vk, Hash::default()).unwrap();
+        assert_eq!(txs.len(), 1 + vk.ic.len().div_ceil(IC_POINTS_PER_TX) + 1);
+
+        let data: Vec<&[u8]> = txs
+            .iter()
+            .map(|tx| tx.message.instructions[0].data.as_slice())
+            .collect();
+        assert_eq!(data[0][..8], INIT_BATCH_VK_DISCRIMINATOR);
+        let (decoded_domain, mut decoded_vk) = decode_init(&data[0][8..]);
+        assert_eq!(decoded_domain, domain);
+
+        for append in &data[1..data.len() - 1] {
+            assert_eq!(append[..8], APPEND_IC_POINTS_DISCRIMINATOR);
+            decoded_vk.ic.extend(decode_ic_vec(&append[8..]));
+        }
+        assert_eq!(decoded_vk, vk);
+        assert_eq!(data[data.len() - 1], FINALIZE_BATCH_VK_DISCRIMINATOR);
+
+        let deployment =
+            VkDeployment::new(&program_id, &domain, &authority.pubkey(), &txs).unwrap();
+        assert_eq!(deployment.transactions.len(), txs.len());
+        assert_eq!(
+            deployment.vk_pda,
+            batch_vk_pda(&program_id, &domain).to_string()
+        );
+    }
+}
@@ -139,7 +139,7 @@ async fn main() -> anyhow::Result<()> {
 
     let accounts = vec![
         AccountMeta::new(payer.pubkey(), true), // depositor (signer, payer)
-        AccountMeta::new_readonly(config_pda, false), // config
+        AccountMeta::new(config_pda, false),    // config (writable: deposit sequence)
         AccountMeta::new(vault_pda, false),     // vault (receives SOL)
         AccountMeta::new(receipt_pda, false),   // receipt (created)
         AccountMeta::new_readonly(system_program::ID, false), // system_program
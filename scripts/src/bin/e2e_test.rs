@@ -191,7 +191,7 @@ async fn main() -> anyhow::Result<()> {
 
         let accounts = vec![
             AccountMeta::new(payer.pubkey(), true),
-            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new(config_pda, false),
             AccountMeta::new(vault_pda, false),
             AccountMeta::new(receipt_pda, false),
             AccountMeta::new_readonly(system_program::ID, false),
@@ -7,8 +7,10 @@
 //!   cargo run --bin init_bridge
 //!
 //! Environment variables:
-//!   SEQUENCER_KEYPAIR - Path to sequencer keypair (default: ~/.config/solana/id.json)
-//!   PAYER_KEYPAIR     - Path to payer keypair (default: ~/.config/solana/id.json)
+//!   SEQUENCER_KEYPAIR     - Path to sequencer keypair (default: ~/.config/solana/id.json)
+//!   PAYER_KEYPAIR         - Path to payer keypair (default: ~/.config/solana/id.json)
+//!   MIN_DEPOSIT_LAMPORTS  - Smallest accepted deposit (default: 0.001 SOL)
+//!   MAX_DEPOSIT_LAMPORTS  - Largest accepted deposit (default: 1000 SOL)
 
 use zelana_scripts::config::*;
 use solana_client::rpc_client::RpcClient;
@@ -20,17 +22,14 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use zelana_config::SOLANA;
+use zelana_transaction::InitParams;
 
 /// Bridge instruction discriminator for Init
 const BRIDGE_IX_INIT: u8 = 0;
 
-/// InitParams structure matching the bridge program
-#[repr(C, packed)]
-#[derive(Clone, Copy)]
-struct InitParams {
-    sequencer_authority: [u8; 32],
-    domain: [u8; 32],
-}
+/// Deposit bounds used unless MIN_DEPOSIT_LAMPORTS / MAX_DEPOSIT_LAMPORTS are set
+const DEFAULT_MIN_DEPOSIT: u64 = 1_000_000; // 0.001 SOL
+const DEFAULT_MAX_DEPOSIT: u64 = 1_000_000_000_000; // 1000 SOL
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -85,19 +84,17 @@ async fn main() -> anyhow::Result<()> {
 
     // Build init instruction
     let domain = domain_bytes();
+    let min_deposit = lamports_from_env("MIN_DEPOSIT_LAMPORTS", DEFAULT_MIN_DEPOSIT)?;
+    let max_deposit = lamports_from_env("MAX_DEPOSIT_LAMPORTS", DEFAULT_MAX_DEPOSIT)?;
     let params = InitParams {
         sequencer_authority: sequencer.pubkey().to_bytes(),
         domain,
+        min_deposit,
+        max_deposit,
     };
 
     let mut instruction_data = vec![BRIDGE_IX_INIT];
-    // Safety: InitParams is repr(C, packed) and contains only byte arrays
-    instruction_data.extend_from_slice(unsafe {
-        std::slice::from_raw_parts(
-            &params as *const InitParams as *const u8,
-            std::mem::size_of::<InitParams>(),
-        )
-    });
+    instruction_data.extend_from_slice(&params.to_bytes());
 
     let accounts = vec![
         AccountMeta::new(payer.pubkey(), true),
@@ -132,6 +129,16 @@ async fn main() -> anyhow::Result<()> {
     println!("  Vault PDA: {}", vault_pda);
     println!("  Sequencer: {}", sequencer.pubkey());
     println!("  Domain: {}", hex::encode(&domain[..6]));
+    println!("  Deposits: {} - {} lamports", min_deposit, max_deposit);
 
     Ok(())
 }
+
+fn lamports_from_env(var: &str, default: u64) -> anyhow::Result<u64> {
+    match std::env::var(var) {
+        Ok(value) => value
+            .parse()
+            .map_err(|_| anyhow::anyhow!("{} must be a lamport amount, got {:?}", var, value)),
+        Err(_) => Ok(default),
+    }
+}
@@ -56,6 +56,7 @@ fn main() {
 
     println!("\n   PIPELINE:");
     println!("     prover_mode      = {:?}", PIPELINE.prover_mode);
+    println!("     proving_backend  = {:?}", PIPELINE.proving_backend);
     println!("     settlement_enabled = {}", PIPELINE.settlement_enabled);
     println!("     max_retries      = {}", PIPELINE.max_settlement_retries);
 
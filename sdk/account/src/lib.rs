@@ -11,8 +11,52 @@ pub struct AccountState {
     pub nonce: u64,
 }
 
+/// Why an account update was rejected
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ExecError {
+    #[error("insufficient balance: has {balance}, needs {needed}")]
+    InsufficientBalance { balance: u64, needed: u64 },
+    #[error("amount plus fee overflows")]
+    AmountOverflow,
+    #[error("balance overflow")]
+    BalanceOverflow,
+    #[error("nonce overflow")]
+    NonceOverflow,
+}
+
+impl AccountState {
+    /// Debit `amount + fee` from the sender and bump its nonce.
+    ///
+    /// The single definition of a sender-side update, shared by the sequencer
+    /// and replay tools. On error the state is left unchanged.
+    pub fn apply_transfer(&mut self, amount: u64, fee: u64) -> Result<(), ExecError> {
+        let needed = amount.checked_add(fee).ok_or(ExecError::AmountOverflow)?;
+        let balance = self
+            .balance
+            .checked_sub(needed)
+            .ok_or(ExecError::InsufficientBalance {
+                balance: self.balance,
+                needed,
+            })?;
+        let nonce = self.nonce.checked_add(1).ok_or(ExecError::NonceOverflow)?;
+
+        self.balance = balance;
+        self.nonce = nonce;
+        Ok(())
+    }
+
+    /// Credit a recipient; receiving funds doesn't touch the nonce.
+    pub fn apply_credit(&mut self, amount: u64) -> Result<(), ExecError> {
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or(ExecError::BalanceOverflow)?;
+        Ok(())
+    }
+}
+
 /// The canonical identifier for a user on L2 (32 bytes).
-/// Derived from H(SignerPK || PrivacyPK)
+/// The owner's Ed25519 signer public key (see `PublicKeys::derive_id`)
 #[derive(
     Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default, SchemaWrite, SchemaRead,
 )]
@@ -42,3 +86,76 @@ impl fmt::Display for AccountId {
         write!(f, "{}", hex::encode(&self.0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_transfer_debits_and_bumps_nonce() {
+        let mut state = AccountState {
+            balance: 100,
+            nonce: 4,
+        };
+        state.apply_transfer(60, 5).unwrap();
+        assert_eq!(
+            state,
+            AccountState {
+                balance: 35,
+                nonce: 5
+            }
+        );
+
+        // Spending the exact balance is allowed
+        state.apply_transfer(35, 0).unwrap();
+        assert_eq!(state.balance, 0);
+        assert_eq!(state.nonce, 6);
+    }
+
+    #[test]
+    fn test_apply_transfer_rejects_insufficient_balance() {
+        let mut state = AccountState {
+            balance: 100,
+            nonce: 0,
+        };
+        assert_eq!(
+            state.apply_transfer(100, 1),
+            Err(ExecError::InsufficientBalance {
+                balance: 100,
+                needed: 101
+            })
+        );
+        assert_eq!(
+            state,
+            AccountState {
+                balance: 100,
+                nonce: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_apply_transfer_rejects_overflow() {
+        let mut state = AccountState {
+            balance: u64::MAX,
+            nonce: 0,
+        };
+        assert_eq!(
+            state.apply_transfer(u64::MAX, 1),
+            Err(ExecError::AmountOverflow)
+        );
+
+        let mut exhausted = AccountState {
+            balance: 10,
+            nonce: u64::MAX,
+        };
+        assert_eq!(
+            exhausted.apply_transfer(1, 0),
+            Err(ExecError::NonceOverflow)
+        );
+        assert_eq!(exhausted.balance, 10);
+
+        assert_eq!(state.apply_credit(1), Err(ExecError::BalanceOverflow));
+        assert_eq!(state.balance, u64::MAX);
+    }
+}
@@ -0,0 +1,237 @@
+//! Zelana Bridge Events
+//!
+//! Canonical byte layout of the events the bridge program emits with
+//! `sol_log_data` (surfacing as `Program data: <base64>` log lines) and the
+//! sequencer's indexer parses. Both sides encode and decode through this
+//! crate, so the layout can only change in one place. The withdrawal tree
+//! the two sides share lives in [`merkle`].
+//!
+//! Every event is a one-byte tag followed by its fields in declaration
+//! order, integers little-endian:
+//!
+//! ```text
+//! DepositEvent             : tag(1) | depositor(32) | amount(8) | nonce(8) | l1_seq(8)
+//! WithdrawEvent            : tag(2) | recipient(32) | amount(8) | nullifier(32) | timestamp(8)
+//! WithdrawalInitiatedEvent : tag(3) | batch_id(8) | tx_hash(32) | recipient(32) | amount(8)
+//! ```
+//!
+//! A withdrawal made on L2 shows up twice: `SubmitBatch` emits a
+//! [`WithdrawalInitiatedEvent`] once its batch settles, and the payout
+//! (`WithdrawAttested` or `FinalizeWithdrawal`) emits a [`WithdrawEvent`]
+//! whose nullifier is the same L2 tx hash. New tags are only ever appended.
+
+#![cfg_attr(not(test), no_std)]
+
+pub mod merkle;
+
+/// Tag of a [`DepositEvent`]
+pub const DEPOSIT_EVENT_TAG: u8 = 1;
+/// Tag of a [`WithdrawEvent`]
+pub const WITHDRAW_EVENT_TAG: u8 = 2;
+/// Tag of a [`WithdrawalInitiatedEvent`]
+pub const WITHDRAWAL_INITIATED_EVENT_TAG: u8 = 3;
+
+/// SOL locked in the vault by `Deposit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositEvent {
+    /// L1 account that deposited (credited 1:1 on L2)
+    pub depositor: [u8; 32],
+    pub amount: u64,
+    /// Per-depositor nonce (seeds the deposit receipt)
+    pub nonce: u64,
+    /// Bridge-wide deposit sequence number, gap-free from 0
+    pub l1_seq: u64,
+}
+
+impl DepositEvent {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8;
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[0] = DEPOSIT_EVENT_TAG;
+        bytes[1..33].copy_from_slice(&self.depositor);
+        bytes[33..41].copy_from_slice(&self.amount.to_le_bytes());
+        bytes[41..49].copy_from_slice(&self.nonce.to_le_bytes());
+        bytes[49..57].copy_from_slice(&self.l1_seq.to_le_bytes());
+        bytes
+    }
+
+    /// Decode an event, `None` on a different tag or length
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN || bytes[0] != DEPOSIT_EVENT_TAG {
+            return None;
+        }
+        Some(Self {
+            depositor: array(&bytes[1..33]),
+            amount: u64::from_le_bytes(array(&bytes[33..41])),
+            nonce: u64::from_le_bytes(array(&bytes[41..49])),
+            l1_seq: u64::from_le_bytes(array(&bytes[49..57])),
+        })
+    }
+}
+
+/// SOL released from the vault by `WithdrawAttested` or `FinalizeWithdrawal`
+/// (the withdrawal is finalized)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WithdrawEvent {
+    pub recipient: [u8; 32],
+    pub amount: u64,
+    /// Nullifier consumed by the withdrawal (the L2 withdrawal tx hash)
+    pub nullifier: [u8; 32],
+    /// L1 clock at execution (unix seconds)
+    pub timestamp: i64,
+}
+
+impl WithdrawEvent {
+    pub const LEN: usize = 1 + 32 + 8 + 32 + 8;
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[0] = WITHDRAW_EVENT_TAG;
+        bytes[1..33].copy_from_slice(&self.recipient);
+        bytes[33..41].copy_from_slice(&self.amount.to_le_bytes());
+        bytes[41..73].copy_from_slice(&self.nullifier);
+        bytes[73..81].copy_from_slice(&self.timestamp.to_le_bytes());
+        bytes
+    }
+
+    /// Decode an event, `None` on a different tag or length
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN || bytes[0] != WITHDRAW_EVENT_TAG {
+            return None;
+        }
+        Some(Self {
+            recipient: array(&bytes[1..33]),
+            amount: u64::from_le_bytes(array(&bytes[33..41])),
+            nullifier: array(&bytes[41..73]),
+            timestamp: i64::from_le_bytes(array(&bytes[73..81])),
+        })
+    }
+}
+
+/// L2 withdrawal settled by `SubmitBatch`, claimable from then on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WithdrawalInitiatedEvent {
+    /// Batch that settled the withdrawal
+    pub batch_id: u64,
+    /// L2 withdrawal tx hash (the nullifier its payout consumes)
+    pub tx_hash: [u8; 32],
+    pub recipient: [u8; 32],
+    pub amount: u64,
+}
+
+impl WithdrawalInitiatedEvent {
+    pub const LEN: usize = 1 + 8 + 32 + 32 + 8;
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[0] = WITHDRAWAL_INITIATED_EVENT_TAG;
+        bytes[1..9].copy_from_slice(&self.batch_id.to_le_bytes());
+        bytes[9..41].copy_from_slice(&self.tx_hash);
+        bytes[41..73].copy_from_slice(&self.recipient);
+        bytes[73..81].copy_from_slice(&self.amount.to_le_bytes());
+        bytes
+    }
+
+    /// Decode an event, `None` on a different tag or length
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN || bytes[0] != WITHDRAWAL_INITIATED_EVENT_TAG {
+            return None;
+        }
+        Some(Self {
+            batch_id: u64::from_le_bytes(array(&bytes[1..9])),
+            tx_hash: array(&bytes[9..41]),
+            recipient: array(&bytes[41..73]),
+            amount: u64::from_le_bytes(array(&bytes[73..81])),
+        })
+    }
+}
+
+/// Any event the bridge program emits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeEvent {
+    Deposit(DepositEvent),
+    Withdraw(WithdrawEvent),
+    WithdrawalInitiated(WithdrawalInitiatedEvent),
+}
+
+impl BridgeEvent {
+    /// Decode by tag, `None` for unknown tags or malformed data
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match *bytes.first()? {
+            DEPOSIT_EVENT_TAG => DepositEvent::from_bytes(bytes).map(Self::Deposit),
+            WITHDRAW_EVENT_TAG => WithdrawEvent::from_bytes(bytes).map(Self::Withdraw),
+            WITHDRAWAL_INITIATED_EVENT_TAG => {
+                WithdrawalInitiatedEvent::from_bytes(bytes).map(Self::WithdrawalInitiated)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Copy a slice whose length the caller already checked
+fn array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(bytes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_round_trip() {
+        let deposit = DepositEvent {
+            depositor: [7u8; 32],
+            amount: 1_000_000_000,
+            nonce: 42,
+            l1_seq: 7,
+        };
+        let withdraw = WithdrawEvent {
+            recipient: [8u8; 32],
+            amount: 250,
+            nullifier: [9u8; 32],
+            timestamp: 1_760_000_000,
+        };
+        let initiated = WithdrawalInitiatedEvent {
+            batch_id: 12,
+            tx_hash: [9u8; 32],
+            recipient: [8u8; 32],
+            amount: 250,
+        };
+
+        let bytes = deposit.to_bytes();
+        assert_eq!(bytes[0], DEPOSIT_EVENT_TAG);
+        assert_eq!(&bytes[33..41], &1_000_000_000u64.to_le_bytes());
+        assert_eq!(&bytes[49..], &7u64.to_le_bytes());
+        assert_eq!(
+            BridgeEvent::from_bytes(&bytes),
+            Some(BridgeEvent::Deposit(deposit))
+        );
+
+        let bytes = withdraw.to_bytes();
+        assert_eq!(bytes[0], WITHDRAW_EVENT_TAG);
+        assert_eq!(&bytes[73..], &1_760_000_000i64.to_le_bytes());
+        assert_eq!(
+            BridgeEvent::from_bytes(&bytes),
+            Some(BridgeEvent::Withdraw(withdraw))
+        );
+
+        let bytes = initiated.to_bytes();
+        assert_eq!(bytes[0], WITHDRAWAL_INITIATED_EVENT_TAG);
+        assert_eq!(&bytes[1..9], &12u64.to_le_bytes());
+        assert_eq!(&bytes[73..], &250u64.to_le_bytes());
+        assert_eq!(
+            BridgeEvent::from_bytes(&bytes),
+            Some(BridgeEvent::WithdrawalInitiated(initiated))
+        );
+
+        // A tag never decodes as the other event, and truncation is rejected
+        assert_eq!(WithdrawEvent::from_bytes(&deposit.to_bytes()), None);
+        assert_eq!(DepositEvent::from_bytes(&deposit.to_bytes()[..56]), None);
+        assert_eq!(WithdrawEvent::from_bytes(&initiated.to_bytes()), None);
+        assert_eq!(BridgeEvent::from_bytes(&[4u8; DepositEvent::LEN]), None);
+        assert_eq!(BridgeEvent::from_bytes(&[]), None);
+    }
+}
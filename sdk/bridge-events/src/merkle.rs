@@ -0,0 +1,242 @@
+//! Withdrawal Merkle Tree
+//!
+//! Each settled batch commits to its withdrawals with a fixed-depth binary
+//! Poseidon tree (BN254, circom parameters) whose root is the batch's
+//! `withdrawal_root` public input. The batch circuit builds the same tree
+//! over its withdrawal slots, the sequencer stores each leaf's sibling path,
+//! and the bridge program checks the path (through the `sol_poseidon`
+//! syscall) when a withdrawal is claimed.
+//!
+//! Field elements are 32-byte big-endian. A leaf commits to the withdrawal's
+//! L2 tx hash, L1 recipient and amount; the 32-byte values enter as two
+//! 128-bit halves so no encoding is reduced mod p:
+//!
+//! ```text
+//! leaf = Poseidon(tx_hash_hi, tx_hash_lo, recipient_hi, recipient_lo, amount)
+//! node = Poseidon(left, right)
+//! ```
+//!
+//! Unused slots are the zero leaf, and a batch without withdrawals commits
+//! to the all-zero root (nothing is claimable against it).
+
+use solana_poseidon::{Endianness, Parameters, hashv};
+
+/// Depth of every batch's withdrawal tree (the circuit's withdrawal slots)
+pub const WITHDRAWAL_TREE_DEPTH: usize = 2;
+
+/// Withdrawals one batch can commit to
+pub const MAX_BATCH_WITHDRAWALS: usize = 1 << WITHDRAWAL_TREE_DEPTH;
+
+/// Poseidon over big-endian field elements, `None` if one is not below the
+/// BN254 modulus
+fn poseidon(inputs: &[&[u8]]) -> Option<[u8; 32]> {
+    hashv(Parameters::Bn254X5, Endianness::BigEndian, inputs)
+        .ok()
+        .map(|hash| hash.to_bytes())
+}
+
+/// High and low 128-bit halves of a 32-byte value, each as a field element
+pub fn split_halves(value: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let mut hi = [0u8; 32];
+    let mut lo = [0u8; 32];
+    hi[16..].copy_from_slice(&value[..16]);
+    lo[16..].copy_from_slice(&value[16..]);
+    (hi, lo)
+}
+
+/// Leaf committing to one withdrawal
+pub fn withdrawal_leaf(tx_hash: &[u8; 32], recipient: &[u8; 32], amount: u64) -> [u8; 32] {
+    let (tx_hash_hi, tx_hash_lo) = split_halves(tx_hash);
+    let (recipient_hi, recipient_lo) = split_halves(recipient);
+    let mut amount_field = [0u8; 32];
+    amount_field[24..].copy_from_slice(&amount.to_be_bytes());
+    poseidon(&[
+        &tx_hash_hi,
+        &tx_hash_lo,
+        &recipient_hi,
+        &recipient_lo,
+        &amount_field,
+    ])
+    .expect("128-bit halves and a u64 are below the field modulus")
+}
+
+/// Parent of two sibling nodes, `None` if either is not a field element
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> Option<[u8; 32]> {
+    poseidon(&[left, right])
+}
+
+/// Fold a leaf's sibling path (leaf level first) from `position` up to the
+/// root and compare it with `root`. The path must be exactly
+/// [`WITHDRAWAL_TREE_DEPTH`] long.
+pub fn verify_withdrawal_proof(
+    leaf: [u8; 32],
+    position: u64,
+    proof: &[[u8; 32]],
+    root: [u8; 32],
+) -> bool {
+    // Positions beyond the tree would alias onto another leaf
+    if proof.len() != WITHDRAWAL_TREE_DEPTH || position >= MAX_BATCH_WITHDRAWALS as u64 {
+        return false;
+    }
+    let mut node = leaf;
+    for (depth, sibling) in proof.iter().enumerate() {
+        let parent = if (position >> depth) & 1 == 0 {
+            hash_pair(&node, sibling)
+        } else {
+            hash_pair(sibling, &node)
+        };
+        match parent {
+            Some(parent) => node = parent,
+            None => return false,
+        }
+    }
+    node == root
+}
+
+/// A batch's withdrawal tree, leaves in circuit slot order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawalTree {
+    /// `levels[0]` holds the leaves; level `d` uses its first
+    /// `MAX_BATCH_WITHDRAWALS >> d` nodes
+    levels: [[[u8; 32]; MAX_BATCH_WITHDRAWALS]; WITHDRAWAL_TREE_DEPTH + 1],
+    len: usize,
+}
+
+impl WithdrawalTree {
+    /// Build the tree over `leaves`, `None` above [`MAX_BATCH_WITHDRAWALS`]
+    pub fn new(leaves: &[[u8; 32]]) -> Option<Self> {
+        if leaves.len() > MAX_BATCH_WITHDRAWALS {
+            return None;
+        }
+        let mut levels = [[[0u8; 32]; MAX_BATCH_WITHDRAWALS]; WITHDRAWAL_TREE_DEPTH + 1];
+        levels[0][..leaves.len()].copy_from_slice(leaves);
+        for depth in 1..=WITHDRAWAL_TREE_DEPTH {
+            for i in 0..MAX_BATCH_WITHDRAWALS >> depth {
+                levels[depth][i] =
+                    hash_pair(&levels[depth - 1][2 * i], &levels[depth - 1][2 * i + 1])
+                        .expect("tree nodes are Poseidon outputs or leaves");
+            }
+        }
+        Some(Self {
+            levels,
+            len: leaves.len(),
+        })
+    }
+
+    /// Number of withdrawals in the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Root committed as the batch's `withdrawal_root` (all-zero when empty)
+    pub fn root(&self) -> [u8; 32] {
+        if self.is_empty() {
+            return [0u8; 32];
+        }
+        self.levels[WITHDRAWAL_TREE_DEPTH][0]
+    }
+
+    /// Sibling path of the leaf at `position` (leaf level first), `None`
+    /// past the last withdrawal
+    pub fn proof(&self, position: usize) -> Option<[[u8; 32]; WITHDRAWAL_TREE_DEPTH]> {
+        if position >= self.len {
+            return None;
+        }
+        Some(core::array::from_fn(|depth| {
+            self.levels[depth][(position >> depth) ^ 1]
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poseidon_matches_circom() {
+        // Poseidon([1, 2]) from circomlib, which Noir's
+        // `std::hash::poseidon::bn254::hash_2` also produces
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let mut two = [0u8; 32];
+        two[31] = 2;
+        assert_eq!(
+            hex(&hash_pair(&one, &two).unwrap()),
+            "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+        );
+
+        // The modulus itself is not a field element
+        let modulus = [
+            0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81,
+            0x58, 0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93,
+            0xf0, 0x00, 0x00, 0x01,
+        ];
+        assert_eq!(hash_pair(&one, &modulus), None);
+    }
+
+    fn hex(bytes: &[u8; 32]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_verify_withdrawal_proof() {
+        let leaves: [[u8; 32]; 3] =
+            core::array::from_fn(|i| withdrawal_leaf(&[i as u8; 32], &[9u8; 32], 100 + i as u64));
+        let tree = WithdrawalTree::new(&leaves).unwrap();
+        let root = tree.root();
+
+        let zero = [0u8; 32];
+        let left = hash_pair(&leaves[0], &leaves[1]).unwrap();
+        let right = hash_pair(&leaves[2], &zero).unwrap();
+        assert_eq!(root, hash_pair(&left, &right).unwrap());
+        assert_eq!(tree.proof(0), Some([leaves[1], right]));
+        assert_eq!(tree.proof(2), Some([zero, left]));
+        assert_eq!(tree.proof(3), None);
+
+        for (position, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(position).unwrap();
+            assert!(verify_withdrawal_proof(
+                *leaf,
+                position as u64,
+                &proof,
+                root
+            ));
+        }
+
+        // Wrong position, wrong sibling, out-of-range position or wrong depth
+        assert!(!verify_withdrawal_proof(
+            leaves[0],
+            1,
+            &[leaves[1], right],
+            root
+        ));
+        assert!(!verify_withdrawal_proof(
+            leaves[0],
+            0,
+            &[leaves[2], right],
+            root
+        ));
+        assert!(!verify_withdrawal_proof(
+            leaves[0],
+            4,
+            &[leaves[1], right],
+            root
+        ));
+        assert!(!verify_withdrawal_proof(leaves[0], 0, &[leaves[1]], root));
+
+        // The leaf commits to every field
+        assert_ne!(leaves[0], withdrawal_leaf(&[0u8; 32], &[9u8; 32], 101));
+        assert_ne!(leaves[0], withdrawal_leaf(&[0u8; 32], &[8u8; 32], 100));
+    }
+
+    #[test]
+    fn test_withdrawal_tree_capacity() {
+        assert_eq!(WithdrawalTree::new(&[]).unwrap().root(), [0u8; 32]);
+        assert!(WithdrawalTree::new(&[[1u8; 32]; MAX_BATCH_WITHDRAWALS]).is_some());
+        assert!(WithdrawalTree::new(&[[1u8; 32]; MAX_BATCH_WITHDRAWALS + 1]).is_none());
+    }
+}
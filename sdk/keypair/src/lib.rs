@@ -1,7 +1,8 @@
 use chacha20poly1305::aead::OsRng;
 use chacha20poly1305::aead::rand_core::RngCore;
-use ed25519_dalek::{Signer, SigningKey};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use solana_sdk::signature::Keypair as SolanaKeypair;
+use std::path::Path;
 use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
 use zelana_account::AccountId;
 use zelana_pubkey::PublicKeys;
@@ -26,20 +27,26 @@ fn build_transfer_message(
     amount: u64,
     nonce: u64,
     chain_id: u64,
+    valid_until_batch: Option<u64>,
 ) -> String {
+    // Omitted when unset so messages without an expiry are unchanged
+    let expiry = valid_until_batch
+        .map(|batch| format!("Valid until batch: {}\n", batch))
+        .unwrap_or_default();
     format!(
         "Zelana L2 Transfer\n\n\
          From: {}\n\
          To: {}\n\
          Amount: {} lamports\n\
          Nonce: {}\n\
-         Chain ID: {}\n\n\
+         Chain ID: {}\n{}\n\
          Sign to authorize this L2 transfer.",
         hex::encode(from),
         hex::encode(to),
         amount,
         nonce,
-        chain_id
+        chain_id,
+        expiry
     )
 }
 
@@ -64,6 +71,32 @@ fn build_withdraw_message(
     )
 }
 
+/// Prefix for arbitrary-message signatures.
+///
+/// Starts with 0x19, which no human-readable transaction message can, so a
+/// message signature can never verify as a transfer or withdrawal.
+const MESSAGE_PREFIX: &[u8] = b"\x19Zelana Signed Message:\n";
+
+/// Fixed salt for [`Keypair::from_passphrase`] (dev wallets only).
+const PASSPHRASE_SALT: &[u8] = b"zelana-dev-keypair";
+
+fn prefixed_message(msg: &[u8]) -> Vec<u8> {
+    let mut prefixed = Vec::with_capacity(MESSAGE_PREFIX.len() + msg.len());
+    prefixed.extend_from_slice(MESSAGE_PREFIX);
+    prefixed.extend_from_slice(msg);
+    prefixed
+}
+
+/// Verifies a signature produced by [`Keypair::sign_message`].
+pub fn verify_message(pubkey: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+    let Ok(verifying_key) = VerifyingKey::from_bytes(pubkey) else {
+        return false;
+    };
+    verifying_key
+        .verify(&prefixed_message(msg), &Signature::from_bytes(sig))
+        .is_ok()
+}
+
 impl Keypair {
     /// Generates a fresh random wallet.
     pub fn new_random() -> Self {
@@ -100,11 +133,22 @@ impl Keypair {
         }
     }
 
+    /// Deterministic wallet derived from a passphrase via Argon2.
+    ///
+    /// ️ TEST/DEV ONLY: the salt is fixed, so anyone who guesses the
+    /// passphrase gets the keys. Never hold real funds with this wallet.
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        let mut seed = [0u8; 64];
+        argon2::Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), PASSPHRASE_SALT, &mut seed)
+            .expect("64-byte output and fixed salt are valid Argon2 parameters");
+        Self::from_seed(&seed)
+    }
+
     /// Returns the public Account ID (The "Address").
     /// IMPORTANT: This must match the bridge's map_l1_to_l2 function!
     pub fn account_id(&self) -> AccountId {
-        // Use ONLY the signing key to match L1 compatibility
-        AccountId(self.signing_key.verifying_key().to_bytes())
+        self.public_keys().derive_id()
     }
     /// Returns the public key set (safe to share).
     pub fn public_keys(&self) -> PublicKeys {
@@ -126,6 +170,7 @@ impl Keypair {
             data.amount,
             data.nonce,
             data.chain_id,
+            data.valid_until_batch,
         );
         let msg = msg_text.as_bytes();
 
@@ -165,6 +210,14 @@ impl Keypair {
         }
     }
 
+    /// Signs an arbitrary message (auth challenges, ownership attestations).
+    ///
+    /// The message is domain-separated from transaction payloads; check it
+    /// with [`verify_message`].
+    pub fn sign_message(&self, msg: &[u8]) -> [u8; 64] {
+        self.signing_key.sign(&prefixed_message(msg)).to_bytes()
+    }
+
     /// Exports the keypair as a 64-byte seed (for saving to file).
     /// ️ SENSITIVE: Only use this for encrypted storage!
     pub fn to_seed(&self) -> [u8; 64] {
@@ -174,20 +227,145 @@ impl Keypair {
         seed
     }
 
-    /// Loads a keypair from a JSON file (Solana CLI format).
+    /// Loads a keypair from a JSON file (Solana CLI or Zelana `genkey` format).
+    ///
+    /// Same as [`Self::from_solana_keypair_file`].
     pub fn from_file(path: &str) -> std::io::Result<Self> {
+        Self::from_solana_keypair_file(path)
+    }
+
+    /// Loads a keypair from a Solana `id.json` (or a Zelana `genkey` file).
+    ///
+    /// Both are JSON arrays of 64 bytes whose first half is the Ed25519
+    /// secret seed, so the account id always equals the Solana pubkey. The
+    /// second half tells them apart:
+    /// - Solana: the Ed25519 public key. Solana keys carry no privacy key, so
+    ///   it is the X25519 conversion of the signing key (as libsodium's
+    ///   `crypto_sign_ed25519_sk_to_curve25519`).
+    /// - Zelana: the X25519 privacy seed, loaded as by [`Self::from_seed`].
+    pub fn from_solana_keypair_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
         let contents = std::fs::read_to_string(path)?;
         let bytes: Vec<u8> = serde_json::from_str(&contents)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-
-        if bytes.len() != 64 {
-            return Err(std::io::Error::new(
+        let bytes: [u8; 64] = bytes.try_into().map_err(|_| {
+            std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 "Invalid keypair file: expected 64 bytes",
-            ));
+            )
+        })?;
+
+        let sign_seed: [u8; 32] = bytes[0..32].try_into().unwrap();
+        let signing_key = SigningKey::from_bytes(&sign_seed);
+        if bytes[32..64] != signing_key.verifying_key().to_bytes() {
+            return Ok(Self::from_seed(&bytes));
         }
 
-        let seed: [u8; 64] = bytes.try_into().unwrap();
-        Ok(Self::from_seed(&seed))
+        Ok(Self {
+            privacy_key: StaticSecret::from(signing_key.to_scalar_bytes()),
+            signing_key,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signer::Signer as _;
+
+    #[test]
+    fn test_message_signature_roundtrip() {
+        let keypair = Keypair::new_random();
+        let pubkey = keypair.public_keys().signer_pk;
+        let sig = keypair.sign_message(b"login challenge 42");
+
+        assert!(verify_message(&pubkey, b"login challenge 42", &sig));
+        assert!(!verify_message(&pubkey, b"login challenge 43", &sig));
+
+        let other = Keypair::new_random().public_keys().signer_pk;
+        assert!(!verify_message(&other, b"login challenge 42", &sig));
+
+        let mut tampered = sig;
+        tampered[0] ^= 1;
+        assert!(!verify_message(&pubkey, b"login challenge 42", &tampered));
+    }
+
+    #[test]
+    fn test_transaction_signature_is_not_a_message_signature() {
+        let keypair = Keypair::new_random();
+        let data = TransactionData {
+            from: keypair.account_id(),
+            to: AccountId([2u8; 32]),
+            amount: 100,
+            nonce: 0,
+            chain_id: 1,
+            valid_until_batch: None,
+        };
+        let signed = keypair.sign_transaction(data.clone());
+        let sig: [u8; 64] = signed.signature.as_slice().try_into().unwrap();
+
+        let tx_message = build_transfer_message(
+            &data.from.0,
+            &data.to.0,
+            data.amount,
+            data.nonce,
+            data.chain_id,
+            data.valid_until_batch,
+        );
+        assert!(!verify_message(
+            &signed.signer_pubkey,
+            tx_message.as_bytes(),
+            &sig
+        ));
+    }
+
+    #[test]
+    fn test_from_passphrase_is_deterministic() {
+        let alice = Keypair::from_passphrase("alice");
+        let alice_again = Keypair::from_passphrase("alice");
+        let bob = Keypair::from_passphrase("bob");
+
+        assert_eq!(alice.account_id(), alice_again.account_id());
+        assert_eq!(alice.to_seed(), alice_again.to_seed());
+        assert_ne!(alice.account_id(), bob.account_id());
+    }
+
+    #[test]
+    fn test_account_id_matches_public_keys() {
+        let keypair = Keypair::new_random();
+        let keys = keypair.public_keys();
+
+        assert_eq!(keypair.account_id(), keys.derive_id());
+        assert_eq!(keypair.account_id(), AccountId(keys.signer_pk));
+    }
+
+    #[test]
+    fn test_from_solana_keypair_file() {
+        let dir = std::env::temp_dir().join(format!("zelana-keypair-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write_json = |name: &str, bytes: &[u8]| {
+            let path = dir.join(name);
+            std::fs::write(&path, serde_json::to_string(bytes).unwrap()).unwrap();
+            path
+        };
+
+        // `zelana genkey` writes `to_seed()` as a JSON array
+        let keypair = Keypair::new_random();
+        let genkey = write_json("genkey.json", &keypair.to_seed());
+        let loaded = Keypair::from_solana_keypair_file(&genkey).unwrap();
+        assert_eq!(loaded.to_seed(), keypair.to_seed());
+
+        // A Solana id.json for the same signing key maps to the same account
+        let solana = keypair.solana_keypair();
+        let id_json = write_json("id.json", &solana.to_bytes());
+        let loaded = Keypair::from_solana_keypair_file(&id_json).unwrap();
+        assert_eq!(loaded.account_id().0, solana.pubkey().to_bytes());
+        assert_eq!(loaded.solana_keypair().to_bytes(), solana.to_bytes());
+        let via_from_file = Keypair::from_file(id_json.to_str().unwrap()).unwrap();
+        assert_eq!(via_from_file.to_seed(), loaded.to_seed());
+
+        let short = write_json("short.json", &[7u8; 32]);
+        assert!(Keypair::from_solana_keypair_file(&short).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -0,0 +1,39 @@
+//! Hash cross-check tool for circuit authors.
+//!
+//! Prints the output of each client-side hash function (decimal and hex) for
+//! the given field inputs, in the same format as `print_noir_test_values`.
+//!
+//! Usage:
+//!   cargo run -p zelana-ownership-prover --bin hashcheck -- 12345 0 9999999
+//!   cargo run -p zelana-ownership-prover --bin hashcheck --features poseidon -- 1 2
+
+use zelana_ownership_prover::hashcheck::{parse_field, render};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage: hashcheck <field> <field> [<field> ...]");
+        eprintln!("  Fields are decimal or 0x-prefixed big-endian hex (2 to 6 inputs).");
+        std::process::exit(if args.is_empty() { 1 } else { 0 });
+    }
+
+    let inputs = match args
+        .iter()
+        .map(|a| parse_field(a))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match render(&inputs) {
+        Ok(report) => print!("{}", report),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
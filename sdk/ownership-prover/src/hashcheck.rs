@@ -0,0 +1,142 @@
+//! Hash Cross-Check
+//!
+//! Renders the outputs of every client-side hash function for a fixed set of
+//! field inputs, in the same `name = "value"` layout as the Noir test values
+//! (`print_noir_test_values`). Circuit authors paste the output next to the
+//! values printed by `nargo test` to confirm Rust and Noir agree.
+//!
+//! Hex outputs are the little-endian `field_to_bytes` encoding, exactly as
+//! `print_noir_test_values` prints them; decimal outputs are canonical.
+//!
+//! MiMC is always available. Poseidon (planned for the circuit) is rendered
+//! when the crate is built with the `poseidon` feature.
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use std::str::FromStr;
+
+use crate::field_to_bytes;
+use crate::mimc::{hash_2, hash_3, hash_4, hash_5, hash_6};
+
+/// Parse a field element from decimal or `0x`-prefixed big-endian hex
+pub fn parse_field(input: &str) -> Result<Fr, String> {
+    if let Some(hex_str) = input.strip_prefix("0x") {
+        let bytes = hex::decode(hex_str).map_err(|e| format!("invalid hex '{}': {}", input, e))?;
+        if bytes.len() > 32 {
+            return Err(format!("hex value '{}' is longer than 32 bytes", input));
+        }
+        return Ok(Fr::from_be_bytes_mod_order(&bytes));
+    }
+
+    Fr::from_str(input).map_err(|_| format!("invalid decimal field element '{}'", input))
+}
+
+/// MiMC hash for 2..=6 inputs (dispatches to the matching `hash_N`)
+pub fn mimc_hash(inputs: &[Fr]) -> Option<Fr> {
+    match *inputs {
+        [a, b] => Some(hash_2(a, b)),
+        [a, b, c] => Some(hash_3(a, b, c)),
+        [a, b, c, d] => Some(hash_4(a, b, c, d)),
+        [a, b, c, d, e] => Some(hash_5(a, b, c, d, e)),
+        [a, b, c, d, e, f] => Some(hash_6(a, b, c, d, e, f)),
+        _ => None,
+    }
+}
+
+/// Poseidon sponge hash (rate 2, capacity 1, alpha 5, 8 full / 56 partial rounds)
+///
+/// Parameters match `prover::circuit::poseidon::poseidon_config`.
+#[cfg(feature = "poseidon")]
+pub fn poseidon_hash(inputs: &[Fr]) -> Fr {
+    use ark_crypto_primitives::sponge::CryptographicSponge;
+    use ark_crypto_primitives::sponge::poseidon::{
+        PoseidonConfig, PoseidonSponge, find_poseidon_ark_and_mds,
+    };
+
+    let (ark, mds) = find_poseidon_ark_and_mds::<Fr>(254, 2, 8, 56, 0);
+    let config = PoseidonConfig::new(8, 56, 5, mds, ark, 2, 1);
+
+    let mut sponge = PoseidonSponge::<Fr>::new(&config);
+    sponge.absorb(&inputs.to_vec());
+    sponge.squeeze_field_elements::<Fr>(1)[0]
+}
+
+/// Render the cross-check report for `inputs`
+pub fn render(inputs: &[Fr]) -> Result<String, String> {
+    let mimc = mimc_hash(inputs)
+        .ok_or_else(|| format!("MiMC takes 2 to 6 inputs, got {}", inputs.len()))?;
+
+    #[allow(unused_mut)]
+    let mut outputs = vec![("mimc", mimc)];
+    #[cfg(feature = "poseidon")]
+    outputs.push(("poseidon", poseidon_hash(inputs)));
+
+    let input_list = inputs
+        .iter()
+        .map(|f| format!("\"{}\"", f.into_bigint()))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut out = String::new();
+    out.push_str("=== Noir Hash Cross-Check ===\n");
+    out.push_str(&format!("inputs = [{}]\n", input_list));
+    out.push('\n');
+    for (name, value) in &outputs {
+        out.push_str(&format!(
+            "{} = \"0x{}\"\n",
+            name,
+            hex::encode(field_to_bytes(*value))
+        ));
+    }
+    out.push('\n');
+    out.push_str("# As decimal (for Noir):\n");
+    for (name, value) in &outputs {
+        out.push_str(&format!("{} = \"{}\"\n", name, value.into_bigint()));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_field_decimal_and_hex() {
+        assert_eq!(parse_field("12345").unwrap(), Fr::from(12345u64));
+        assert_eq!(parse_field("0x3039").unwrap(), Fr::from(12345u64));
+        assert!(parse_field("12a").is_err());
+        assert!(parse_field("0xzz").is_err());
+    }
+
+    #[test]
+    fn test_render_matches_library() {
+        let a = Fr::from(12345u64);
+        let b = Fr::from(0u64);
+        let c = Fr::from(9999999u64);
+
+        let report = render(&[a, b, c]).unwrap();
+        let expected = hash_3(a, b, c);
+
+        assert!(report.contains("inputs = [\"12345\", \"0\", \"9999999\"]"));
+        assert!(report.contains(&format!(
+            "mimc = \"0x{}\"",
+            hex::encode(field_to_bytes(expected))
+        )));
+        assert!(report.contains(&format!("mimc = \"{}\"", expected.into_bigint())));
+    }
+
+    #[test]
+    fn test_render_public_key_derivation() {
+        // pk = hash_3(PK_DOMAIN, spending_key, 0)
+        let sk = Fr::from(12345u64);
+        let report = render(&[crate::mimc::pk_domain(), sk, Fr::from(0u64)]).unwrap();
+        let pk = crate::derive_public_key(sk);
+        assert!(report.contains(&format!("mimc = \"{}\"", pk.into_bigint())));
+    }
+
+    #[test]
+    fn test_render_rejects_bad_arity() {
+        assert!(render(&[Fr::from(1u64)]).is_err());
+        assert!(render(&[Fr::from(1u64); 7]).is_err());
+    }
+}
@@ -15,6 +15,7 @@
 //! let blinded_proxy = compute_blinded_proxy(commitment, position);
 //! ```
 
+pub mod hashcheck;
 pub mod mimc;
 
 #[cfg(feature = "wasm")]
@@ -16,6 +16,8 @@ use ark_crypto_primitives::sponge::{
 use ark_ff::{BigInteger, PrimeField};
 use ark_std::rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// A note commitment (32 bytes)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -39,6 +41,16 @@ impl Commitment {
     pub fn as_bytes(&self) -> &[u8; 32] {
         &self.0
     }
+
+    /// Lowercase hex, the canonical form shown by tooling
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    /// Parse hex (optionally `0x`-prefixed)
+    pub fn from_hex(s: &str) -> Result<Self, ParseHexError> {
+        parse_hex32(s).map(Self)
+    }
 }
 
 impl AsRef<[u8]> for Commitment {
@@ -47,6 +59,40 @@ impl AsRef<[u8]> for Commitment {
     }
 }
 
+impl fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl FromStr for Commitment {
+    type Err = ParseHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
+/// Error parsing a 32-byte commitment or nullifier from hex
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseHexError {
+    #[error("invalid hex: {0}")]
+    InvalidHex(#[from] hex::FromHexError),
+    #[error("expected 32 bytes, got {0}")]
+    InvalidLength(usize),
+}
+
+/// Decode user-entered hex into 32 bytes, tolerating whitespace and `0x`
+pub(crate) fn parse_hex32(s: &str) -> Result<[u8; 32], ParseHexError> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    let bytes = hex::decode(s)?;
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| ParseHexError::InvalidLength(len))
+}
+
 /// Commitment scheme using Poseidon hash
 pub struct CommitmentScheme {
     config: PoseidonConfig<Fr>,
@@ -161,6 +207,43 @@ fn poseidon_config() -> PoseidonConfig<Fr> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_commitment_hex_roundtrip() {
+        let c = Commitment([0xab; 32]);
+        let hex = c.to_string();
+        assert_eq!(hex, "ab".repeat(32));
+        assert_eq!(hex, c.to_hex());
+        assert_eq!(hex.parse::<Commitment>().unwrap(), c);
+        assert_eq!(Commitment::from_hex(&hex).unwrap(), c);
+        // Pasted values: prefix, uppercase and surrounding whitespace
+        let pasted = format!(" 0x{} \n", hex.to_uppercase());
+        assert_eq!(pasted.parse::<Commitment>().unwrap(), c);
+    }
+
+    #[test]
+    fn test_commitment_from_hex_rejects_invalid() {
+        assert_eq!(
+            Commitment::from_hex(&"ab".repeat(31)),
+            Err(ParseHexError::InvalidLength(31))
+        );
+        assert_eq!(
+            Commitment::from_hex(&"ab".repeat(33)),
+            Err(ParseHexError::InvalidLength(33))
+        );
+        assert!(matches!(
+            Commitment::from_hex(&"zz".repeat(32)),
+            Err(ParseHexError::InvalidHex(_))
+        ));
+        assert!(matches!(
+            Commitment::from_hex("abc"),
+            Err(ParseHexError::InvalidHex(_))
+        ));
+        assert_eq!(
+            Commitment::from_hex(""),
+            Err(ParseHexError::InvalidLength(0))
+        );
+    }
+
     #[test]
     fn test_commitment_deterministic() {
         let scheme = CommitmentScheme::new();
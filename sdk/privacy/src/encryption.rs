@@ -76,7 +76,7 @@ pub fn encrypt_note(note: &Note, recipient_pk: &[u8; 32], memo: Option<&[u8]>) -
 
     // Create plaintext
     let plaintext = NotePlaintext {
-        value: note.value.0,
+        value: note.value.as_u64(),
         randomness: note.randomness,
         memo: memo
             .map(|m| m[..m.len().min(512)].to_vec())
@@ -139,7 +139,9 @@ pub fn decrypt_note(
     let plaintext = deserialize_plaintext(&plaintext_bytes)?;
 
     // Reconstruct note
-    let note = Note::with_randomness(plaintext.value, expected_owner_pk, plaintext.randomness);
+    // A value outside the note range can't belong to a valid commitment
+    let note =
+        Note::with_randomness(plaintext.value, expected_owner_pk, plaintext.randomness).ok()?;
 
     Some((note, plaintext.memo))
 }
@@ -239,14 +241,14 @@ mod tests {
     fn test_encrypt_decrypt_note() {
         let (recipient_sk, recipient_pk) = generate_keypair();
 
-        let note = Note::with_randomness(1000, recipient_pk, [42u8; 32]);
+        let note = Note::with_randomness(1000, recipient_pk, [42u8; 32]).unwrap();
         let memo = b"test memo";
 
         let encrypted = encrypt_note(&note, &recipient_pk, Some(memo));
         let (decrypted, decrypted_memo) = decrypt_note(&encrypted, &recipient_sk, recipient_pk)
             .expect("decryption should succeed");
 
-        assert_eq!(decrypted.value.0, note.value.0);
+        assert_eq!(decrypted.value, note.value);
         assert_eq!(decrypted.randomness, note.randomness);
         assert_eq!(decrypted_memo, memo);
     }
@@ -256,7 +258,7 @@ mod tests {
         let (_, recipient_pk) = generate_keypair();
         let (wrong_sk, _) = generate_keypair();
 
-        let note = Note::with_randomness(1000, recipient_pk, [42u8; 32]);
+        let note = Note::with_randomness(1000, recipient_pk, [42u8; 32]).unwrap();
         let encrypted = encrypt_note(&note, &recipient_pk, None);
 
         let result = decrypt_note(&encrypted, &wrong_sk, recipient_pk);
@@ -267,7 +269,7 @@ mod tests {
     fn test_commitment_verification() {
         let (recipient_sk, recipient_pk) = generate_keypair();
 
-        let note = Note::with_randomness(1000, recipient_pk, [42u8; 32]);
+        let note = Note::with_randomness(1000, recipient_pk, [42u8; 32]).unwrap();
         let commitment = note.commitment();
 
         let encrypted = encrypt_note(&note, &recipient_pk, None);
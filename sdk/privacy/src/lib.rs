@@ -28,8 +28,11 @@ pub mod merkle;
 pub mod note;
 pub mod nullifier;
 
-pub use commitment::{Commitment, CommitmentScheme};
+pub use commitment::{Commitment, CommitmentScheme, ParseHexError};
 pub use encryption::{EncryptedNote, decrypt_note, encrypt_note, try_decrypt_note};
 pub use merkle::{MerkleHasher, MerklePath, MerkleTree, RootHistory, TREE_DEPTH};
-pub use note::{Note, NoteValue, ShieldedKeyBundle, SpendingKey, ViewingKey};
+pub use note::{
+    MAX_NOTE_VALUE, Note, NoteValue, NoteValueOutOfRange, ShieldedKeyBundle, SpendingKey,
+    ViewingKey,
+};
 pub use nullifier::{Nullifier, NullifierKey};
@@ -10,7 +10,18 @@
 //!     position: u64,        // Position in commitment tree (set on insertion)
 //! }
 //! ```
+//!
+//! Note values are capped at [`MAX_NOTE_VALUE`] when a [`NoteValue`] is
+//! built (including on deserialization), so summing two notes can't wrap a
+//! u64. The circuits don't range-check values themselves; this type is
+//! where the bound is enforced.
+//!
+//! Blinding factors are derived from the owner's viewing key and the note's
+//! tree position ([`Note::derive_blinding`]) rather than drawn from an RNG, so
+//! a wallet restored from its seed can recompute every commitment it needs to
+//! spend.
 
+use ark_bls12_381::Fr;
 use ark_std::rand::Rng;
 use serde::{Deserialize, Serialize};
 
@@ -30,25 +41,40 @@ pub struct Note {
     pub position: Option<u64>,
 }
 
+/// Largest value a single note may hold (2^63 - 1).
+///
+/// Keeping values below 2^63 means the sum of any two notes still fits in a
+/// u64, so balance checks (Σ inputs = Σ outputs) can't wrap.
+pub const MAX_NOTE_VALUE: u64 = (1 << 63) - 1;
+
 /// Note value with overflow protection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub struct NoteValue(pub u64);
+#[serde(try_from = "u64", into = "u64")]
+pub struct NoteValue(u64);
+
+/// A note value above [`MAX_NOTE_VALUE`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("note value {0} exceeds maximum {MAX_NOTE_VALUE}")]
+pub struct NoteValueOutOfRange(pub u64);
 
 impl NoteValue {
     pub const ZERO: Self = Self(0);
-    pub const MAX: Self = Self(u64::MAX);
+    pub const MAX: Self = Self(MAX_NOTE_VALUE);
 
-    pub fn new(value: u64) -> Self {
-        Self(value)
+    pub fn new(value: u64) -> Result<Self, NoteValueOutOfRange> {
+        if value > MAX_NOTE_VALUE {
+            return Err(NoteValueOutOfRange(value));
+        }
+        Ok(Self(value))
     }
 
     pub fn as_u64(&self) -> u64 {
         self.0
     }
 
-    /// Checked addition
+    /// Checked addition (fails past [`MAX_NOTE_VALUE`])
     pub fn checked_add(self, rhs: Self) -> Option<Self> {
-        self.0.checked_add(rhs.0).map(Self)
+        Self::new(self.0.checked_add(rhs.0)?).ok()
     }
 
     /// Checked subtraction
@@ -57,28 +83,78 @@ impl NoteValue {
     }
 }
 
-impl Note {
-    /// Create a new note with random blinding
-    pub fn new<R: Rng>(value: u64, owner_pk: [u8; 32], rng: &mut R) -> Self {
-        let mut randomness = [0u8; 32];
-        rng.fill_bytes(&mut randomness);
+impl TryFrom<u64> for NoteValue {
+    type Error = NoteValueOutOfRange;
 
-        Self {
-            value: NoteValue(value),
-            randomness,
-            owner_pk,
-            position: None,
-        }
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<NoteValue> for u64 {
+    fn from(value: NoteValue) -> Self {
+        value.0
     }
+}
 
+impl Note {
     /// Create a note with explicit randomness (for testing/recovery)
-    pub fn with_randomness(value: u64, owner_pk: [u8; 32], randomness: [u8; 32]) -> Self {
-        Self {
-            value: NoteValue(value),
+    pub fn with_randomness(
+        value: u64,
+        owner_pk: [u8; 32],
+        randomness: [u8; 32],
+    ) -> Result<Self, NoteValueOutOfRange> {
+        Ok(Self {
+            value: NoteValue::new(value)?,
             randomness,
             owner_pk,
             position: None,
-        }
+        })
+    }
+
+    /// Create a new note at `position` with blinding derived from the viewing key
+    ///
+    /// The note can be fully reconstructed later from just the viewing key,
+    /// the value and its tree position.
+    pub fn new(
+        value: u64,
+        owner_pk: [u8; 32],
+        viewing_key: &ViewingKey,
+        position: u64,
+    ) -> Result<Self, NoteValueOutOfRange> {
+        use ark_ff::{BigInteger, PrimeField};
+
+        let bytes = Self::derive_blinding(viewing_key, position)
+            .into_bigint()
+            .to_bytes_le();
+        let mut randomness = [0u8; 32];
+        randomness[..bytes.len()].copy_from_slice(&bytes);
+
+        Ok(Self::with_randomness(value, owner_pk, randomness)?.with_position(position))
+    }
+
+    /// Derive a note's blinding factor: r = Poseidon("ZelanaBlind", ivk, position)
+    pub fn derive_blinding(viewing_key: &ViewingKey, position: u64) -> Fr {
+        use ark_crypto_primitives::sponge::{
+            CryptographicSponge,
+            poseidon::{PoseidonConfig, PoseidonSponge, find_poseidon_ark_and_mds},
+        };
+        use ark_ff::PrimeField;
+
+        let (ark, mds) = find_poseidon_ark_and_mds::<Fr>(255, 2, 8, 57, 0);
+        let config = PoseidonConfig::new(8, 57, 5, mds, ark, 2, 1);
+
+        let mut sponge = PoseidonSponge::new(&config);
+
+        let domain =
+            Fr::from_le_bytes_mod_order(b"ZelanaBlind\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
+        sponge.absorb(&domain);
+
+        let key_f = Fr::from_le_bytes_mod_order(viewing_key.as_bytes());
+        sponge.absorb(&key_f);
+        sponge.absorb(&Fr::from(position));
+
+        sponge.squeeze_field_elements(1)[0]
     }
 
     /// Compute the commitment for this note
@@ -143,7 +219,6 @@ impl SpendingKey {
     /// Derive the viewing key (for read-only access)
     pub fn viewing_key(&self) -> ViewingKey {
         // ivk = Poseidon("ZelanaIVK", ask)
-        use ark_bls12_381::Fr;
         use ark_crypto_primitives::sponge::{
             CryptographicSponge,
             poseidon::{PoseidonConfig, PoseidonSponge, find_poseidon_ark_and_mds},
@@ -172,7 +247,6 @@ impl SpendingKey {
 
     /// Derive the public key (address)
     pub fn public_key(&self) -> [u8; 32] {
-        use ark_bls12_381::Fr;
         use ark_crypto_primitives::sponge::{
             CryptographicSponge,
             poseidon::{PoseidonConfig, PoseidonSponge, find_poseidon_ark_and_mds},
@@ -272,9 +346,8 @@ mod tests {
 
     #[test]
     fn test_note_commitment() {
-        let mut rng = OsRng;
-        let owner_pk = [1u8; 32];
-        let note = Note::new(1000, owner_pk, &mut rng);
+        let keys = ShieldedKeyBundle::random(&mut OsRng);
+        let note = Note::new(1000, [1u8; 32], &keys.viewing_key, 0).unwrap();
 
         let c1 = note.commitment();
         let c2 = note.commitment();
@@ -286,7 +359,7 @@ mod tests {
     fn test_note_nullifier_requires_position() {
         let mut rng = OsRng;
         let spending_key = SpendingKey::random(&mut rng);
-        let note = Note::new(1000, spending_key.public_key(), &mut rng);
+        let note = Note::with_randomness(1000, spending_key.public_key(), [7u8; 32]).unwrap();
 
         // Without position, nullifier should be None
         assert!(note.nullifier(&spending_key).is_none());
@@ -315,12 +388,53 @@ mod tests {
 
     #[test]
     fn test_note_value_checked_ops() {
-        let v1 = NoteValue::new(100);
-        let v2 = NoteValue::new(50);
+        let v1 = NoteValue::new(100).unwrap();
+        let v2 = NoteValue::new(50).unwrap();
 
-        assert_eq!(v1.checked_add(v2), Some(NoteValue::new(150)));
-        assert_eq!(v1.checked_sub(v2), Some(NoteValue::new(50)));
+        assert_eq!(v1.checked_add(v2), NoteValue::new(150).ok());
+        assert_eq!(v1.checked_sub(v2), NoteValue::new(50).ok());
         assert_eq!(v2.checked_sub(v1), None); // Underflow
-        assert_eq!(NoteValue::MAX.checked_add(NoteValue::new(1)), None); // Overflow
+        assert_eq!(NoteValue::MAX.checked_add(NoteValue::new(1).unwrap()), None); // Overflow
+    }
+
+    #[test]
+    fn test_derived_blinding_is_recoverable() {
+        let keys = ShieldedKeyBundle::random(&mut OsRng);
+
+        assert_eq!(
+            Note::derive_blinding(&keys.viewing_key, 7),
+            Note::derive_blinding(&keys.viewing_key, 7)
+        );
+        assert_ne!(
+            Note::derive_blinding(&keys.viewing_key, 7),
+            Note::derive_blinding(&keys.viewing_key, 8)
+        );
+
+        // Rebuilding from key + position reproduces the note and its commitment
+        let note = Note::new(1000, keys.public_key, &keys.viewing_key, 7).unwrap();
+        let recovered = Note::new(1000, keys.public_key, &keys.viewing_key, 7).unwrap();
+        assert_eq!(note.randomness, recovered.randomness);
+        assert_eq!(note.commitment(), recovered.commitment());
+        assert_eq!(note.position, Some(7));
+
+        let other = ShieldedKeyBundle::random(&mut OsRng);
+        let foreign = Note::new(1000, keys.public_key, &other.viewing_key, 7);
+        assert_ne!(note.commitment(), foreign.unwrap().commitment());
+    }
+
+    #[test]
+    fn test_note_value_range() {
+        assert_eq!(NoteValue::new(MAX_NOTE_VALUE), Ok(NoteValue::MAX));
+        assert_eq!(
+            NoteValue::new(MAX_NOTE_VALUE + 1),
+            Err(NoteValueOutOfRange(1 << 63))
+        );
+        assert_eq!(NoteValue::new(u64::MAX), Err(NoteValueOutOfRange(u64::MAX)));
+
+        // Constructors and deserialization (via TryFrom) enforce the same bound
+        assert!(Note::with_randomness(MAX_NOTE_VALUE, [1u8; 32], [2u8; 32]).is_ok());
+        assert!(Note::with_randomness(MAX_NOTE_VALUE + 1, [1u8; 32], [2u8; 32]).is_err());
+        assert!(NoteValue::try_from(MAX_NOTE_VALUE + 1).is_err());
+        assert_eq!(u64::from(NoteValue::MAX), MAX_NOTE_VALUE);
     }
 }
@@ -15,8 +15,10 @@ use ark_crypto_primitives::sponge::{
 };
 use ark_ff::{BigInteger, PrimeField};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
-use crate::commitment::Commitment;
+use crate::commitment::{Commitment, ParseHexError, parse_hex32};
 
 /// A nullifier (32 bytes) - unique tag for a spent note
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -45,6 +47,16 @@ impl Nullifier {
     pub fn from_bytes(bytes: [u8; 32]) -> Self {
         Self(bytes)
     }
+
+    /// Lowercase hex, the canonical form shown by tooling
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    /// Parse hex (optionally `0x`-prefixed)
+    pub fn from_hex(s: &str) -> Result<Self, ParseHexError> {
+        parse_hex32(s).map(Self)
+    }
 }
 
 impl AsRef<[u8]> for Nullifier {
@@ -53,6 +65,20 @@ impl AsRef<[u8]> for Nullifier {
     }
 }
 
+impl fmt::Display for Nullifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl FromStr for Nullifier {
+    type Err = ParseHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
 /// Nullifier derivation key (spending key)
 ///
 /// This is the secret key that allows spending notes.
@@ -162,6 +188,31 @@ fn poseidon_config() -> PoseidonConfig<Fr> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_nullifier_hex_roundtrip() {
+        let key = NullifierKey::from_bytes([1u8; 32]);
+        let nf = key.derive_nullifier(&Commitment([2u8; 32]), 7);
+
+        let hex = nf.to_string();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(hex, nf.to_hex());
+        assert_eq!(hex.parse::<Nullifier>().unwrap(), nf);
+        assert_eq!(Nullifier::from_hex(&format!("0x{}", hex)).unwrap(), nf);
+    }
+
+    #[test]
+    fn test_nullifier_from_hex_rejects_invalid() {
+        assert_eq!(
+            "00".repeat(16).parse::<Nullifier>(),
+            Err(ParseHexError::InvalidLength(16))
+        );
+        assert!(matches!(
+            format!("{}g", "0".repeat(63)).parse::<Nullifier>(),
+            Err(ParseHexError::InvalidHex(_))
+        ));
+        assert!("0x".parse::<Nullifier>().is_err());
+    }
+
     #[test]
     fn test_nullifier_deterministic() {
         let key = NullifierKey::from_bytes([1u8; 32]);
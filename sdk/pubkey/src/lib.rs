@@ -1,5 +1,4 @@
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use wincode::{SchemaRead, SchemaWrite};
 use zelana_account::AccountId;
 
@@ -16,13 +15,15 @@ pub struct PublicKeys {
 }
 
 impl PublicKeys {
-    /// Deterministically derives the L2 Account ID.
-    /// Formula: SHA256( signer_pk_bytes || privacy_pk_bytes )
+    /// Derives the L2 Account ID: the Ed25519 signer key itself.
+    ///
+    /// This is the single source of truth for account ids; it matches the
+    /// bridge's 1:1 L1 pubkey mapping, so the privacy key doesn't take part.
+    ///
+    /// FROZEN: every account id on the network comes from this. Changing it
+    /// forks all existing accounts; `test_derive_id_vector` pins the output.
     pub fn derive_id(&self) -> AccountId {
-        let mut hasher = Sha256::new();
-        hasher.update(&self.signer_pk);
-        hasher.update(&self.privacy_pk);
-        AccountId(hasher.finalize().into())
+        AccountId(self.signer_pk)
     }
 
     /// Returns the Ed25519 public key as Base58
@@ -43,3 +44,42 @@ impl PublicKeys {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_id_vector() {
+        // RFC 8032 test 1 Ed25519 public key, RFC 7748 Alice X25519 public key
+        let keys = PublicKeys {
+            signer_pk: [
+                0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64,
+                0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68,
+                0xf7, 0x07, 0x51, 0x1a,
+            ],
+            privacy_pk: [
+                0x85, 0x20, 0xf0, 0x09, 0x89, 0x30, 0xa7, 0x54, 0x74, 0x8b, 0x7d, 0xdc, 0xb4, 0x3e,
+                0xf7, 0x5a, 0x0d, 0xbf, 0x3a, 0x0d, 0x26, 0x38, 0x1a, 0xf4, 0xeb, 0xa4, 0xa9, 0x8e,
+                0xaa, 0x9b, 0x4e, 0x6a,
+            ],
+        };
+
+        // Frozen: if this fails, the change forks every account id
+        assert_eq!(
+            keys.derive_id(),
+            AccountId([
+                0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64,
+                0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68,
+                0xf7, 0x07, 0x51, 0x1a,
+            ])
+        );
+
+        // The privacy key never feeds into the id
+        let rotated = PublicKeys {
+            privacy_pk: [0u8; 32],
+            ..keys.clone()
+        };
+        assert_eq!(rotated.derive_id(), keys.derive_id());
+    }
+}
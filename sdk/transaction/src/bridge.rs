@@ -1,5 +1,17 @@
+//! L1 Bridge Types
+//!
+//! `InitParams`, `DepositParams`, `WithdrawAttestedParams`,
+//! `FinalizeWithdrawalParams` and `SetPausedParams` are the bridge program's
+//! instruction data (after the one-byte instruction tag). They are
+//! `#[repr(C)]` with no padding, so their little-endian field encoding is
+//! also their in-memory layout; `to_bytes`/`from_bytes` need neither `std`
+//! nor an allocator and match `wincode` byte for byte.
+
+#[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
 use wincode::{SchemaRead, SchemaWrite};
+#[cfg(feature = "std")]
 use zelana_account::AccountId;
 
 pub trait DataLen {
@@ -7,6 +19,7 @@ pub trait DataLen {
 }
 
 /// Event coming from the L1 Listener.
+#[cfg(feature = "std")]
 #[derive(Debug, Serialize, Deserialize, Clone, SchemaRead, SchemaWrite)]
 pub struct DepositEvent {
     pub to: AccountId,
@@ -14,13 +27,51 @@ pub struct DepositEvent {
     pub l1_seq: u64,
 }
 
-#[derive(SchemaWrite)]
+/// `Initialize` instruction data
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(SchemaRead, SchemaWrite))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
 pub struct InitParams {
     pub sequencer_authority: [u8; 32],
     pub domain: [u8; 32],
+    /// Smallest deposit the bridge accepts, in lamports (non-zero)
+    pub min_deposit: u64,
+    /// Largest deposit the bridge accepts, in lamports (at least `min_deposit`)
+    pub max_deposit: u64,
+}
+
+impl DataLen for InitParams {
+    const LEN: usize = core::mem::size_of::<InitParams>();
+}
+
+impl InitParams {
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[..32].copy_from_slice(&self.sequencer_authority);
+        bytes[32..64].copy_from_slice(&self.domain);
+        bytes[64..72].copy_from_slice(&self.min_deposit.to_le_bytes());
+        bytes[72..].copy_from_slice(&self.max_deposit.to_le_bytes());
+        bytes
+    }
+
+    /// Decode instruction data, `None` unless it is exactly `LEN` bytes
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().ok()?;
+        Some(Self {
+            sequencer_authority: array(&bytes[..32]),
+            domain: array(&bytes[32..64]),
+            min_deposit: u64::from_le_bytes(array(&bytes[64..72])),
+            max_deposit: u64::from_le_bytes(array(&bytes[72..])),
+        })
+    }
 }
 
-#[derive(SchemaWrite)]
+/// `Deposit` instruction data
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(SchemaRead, SchemaWrite))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
 pub struct DepositParams {
     pub amount: u64,
     pub nonce: u64,
@@ -30,6 +81,141 @@ impl DataLen for DepositParams {
     const LEN: usize = core::mem::size_of::<DepositParams>();
 }
 
+impl DepositParams {
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[..8].copy_from_slice(&self.amount.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.nonce.to_le_bytes());
+        bytes
+    }
+
+    /// Decode instruction data, `None` unless it is exactly `LEN` bytes
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().ok()?;
+        Some(Self {
+            amount: u64::from_le_bytes(array(&bytes[..8])),
+            nonce: u64::from_le_bytes(array(&bytes[8..])),
+        })
+    }
+}
+
+/// `WithdrawAttested` instruction data
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(SchemaRead, SchemaWrite))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct WithdrawAttestedParams {
+    pub recipient: [u8; 32],
+    pub amount: u64,
+    /// Replay guard (the sequencer uses the L2 withdrawal tx hash)
+    pub nullifier: [u8; 32],
+}
+
+impl DataLen for WithdrawAttestedParams {
+    const LEN: usize = core::mem::size_of::<WithdrawAttestedParams>();
+}
+
+impl WithdrawAttestedParams {
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[..32].copy_from_slice(&self.recipient);
+        bytes[32..40].copy_from_slice(&self.amount.to_le_bytes());
+        bytes[40..].copy_from_slice(&self.nullifier);
+        bytes
+    }
+
+    /// Decode instruction data, `None` unless it is exactly `LEN` bytes
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().ok()?;
+        Some(Self {
+            recipient: array(&bytes[..32]),
+            amount: u64::from_le_bytes(array(&bytes[32..40])),
+            nullifier: array(&bytes[40..]),
+        })
+    }
+}
+
+/// `FinalizeWithdrawal` instruction data, followed on the wire by the
+/// Merkle proof (32-byte sibling hashes, leaf level first)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(SchemaRead, SchemaWrite))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct FinalizeWithdrawalParams {
+    /// Settled batch whose withdrawal root includes the withdrawal
+    pub batch_id: u64,
+    /// L2 withdrawal tx hash (also the claim's nullifier)
+    pub tx_hash: [u8; 32],
+    pub recipient: [u8; 32],
+    pub amount: u64,
+    /// Index of the withdrawal's leaf in the batch tree
+    pub leaf_position: u64,
+}
+
+impl DataLen for FinalizeWithdrawalParams {
+    const LEN: usize = core::mem::size_of::<FinalizeWithdrawalParams>();
+}
+
+impl FinalizeWithdrawalParams {
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[..8].copy_from_slice(&self.batch_id.to_le_bytes());
+        bytes[8..40].copy_from_slice(&self.tx_hash);
+        bytes[40..72].copy_from_slice(&self.recipient);
+        bytes[72..80].copy_from_slice(&self.amount.to_le_bytes());
+        bytes[80..].copy_from_slice(&self.leaf_position.to_le_bytes());
+        bytes
+    }
+
+    /// Decode instruction data, `None` unless it is exactly `LEN` bytes
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().ok()?;
+        Some(Self {
+            batch_id: u64::from_le_bytes(array(&bytes[..8])),
+            tx_hash: array(&bytes[8..40]),
+            recipient: array(&bytes[40..72]),
+            amount: u64::from_le_bytes(array(&bytes[72..80])),
+            leaf_position: u64::from_le_bytes(array(&bytes[80..])),
+        })
+    }
+}
+
+/// `SetPaused` instruction data
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(SchemaRead, SchemaWrite))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct SetPausedParams {
+    /// 1 halts deposits and withdrawals, 0 resumes them
+    pub paused: u8,
+}
+
+impl DataLen for SetPausedParams {
+    const LEN: usize = core::mem::size_of::<SetPausedParams>();
+}
+
+impl SetPausedParams {
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        [self.paused]
+    }
+
+    /// Decode instruction data, `None` unless it is a single 0 or 1 byte
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match *bytes {
+            [paused @ (0 | 1)] => Some(Self { paused }),
+            _ => None,
+        }
+    }
+}
+
+/// Copy a slice whose length the caller already checked
+fn array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(bytes);
+    out
+}
+
+#[cfg(feature = "std")]
 #[derive(Debug, Serialize, Deserialize, Clone, SchemaRead, SchemaWrite)]
 pub struct WithdrawRequest {
     pub from: AccountId,
@@ -39,3 +225,80 @@ pub struct WithdrawRequest {
     pub signature: Vec<u8>,
     pub signer_pubkey: [u8; 32],
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_std_encoding_matches_wincode() {
+        let init = InitParams {
+            sequencer_authority: [1u8; 32],
+            domain: *b"zelana\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+            min_deposit: 1_000,
+            max_deposit: 100_000_000_000,
+        };
+        let deposit = DepositParams {
+            amount: 1_000_000_000,
+            nonce: 619,
+        };
+        let withdraw = WithdrawAttestedParams {
+            recipient: [2u8; 32],
+            amount: 250,
+            nullifier: [3u8; 32],
+        };
+        let finalize = FinalizeWithdrawalParams {
+            batch_id: 12,
+            tx_hash: [4u8; 32],
+            recipient: [5u8; 32],
+            amount: 250,
+            leaf_position: 3,
+        };
+        let pause = SetPausedParams { paused: 1 };
+
+        // Bytes from the no_std encoder decode through wincode, and back
+        let decoded: InitParams = wincode::deserialize(&init.to_bytes()).unwrap();
+        assert_eq!(decoded, init);
+        assert_eq!(
+            InitParams::from_bytes(&wincode::serialize(&init).unwrap()),
+            Some(init)
+        );
+
+        let decoded: DepositParams = wincode::deserialize(&deposit.to_bytes()).unwrap();
+        assert_eq!(decoded, deposit);
+        assert_eq!(
+            DepositParams::from_bytes(&wincode::serialize(&deposit).unwrap()),
+            Some(deposit)
+        );
+
+        let decoded: WithdrawAttestedParams = wincode::deserialize(&withdraw.to_bytes()).unwrap();
+        assert_eq!(decoded, withdraw);
+        assert_eq!(
+            WithdrawAttestedParams::from_bytes(&wincode::serialize(&withdraw).unwrap()),
+            Some(withdraw)
+        );
+
+        let decoded: FinalizeWithdrawalParams = wincode::deserialize(&finalize.to_bytes()).unwrap();
+        assert_eq!(decoded, finalize);
+        assert_eq!(
+            FinalizeWithdrawalParams::from_bytes(&wincode::serialize(&finalize).unwrap()),
+            Some(finalize)
+        );
+
+        let decoded: SetPausedParams = wincode::deserialize(&pause.to_bytes()).unwrap();
+        assert_eq!(decoded, pause);
+        assert_eq!(
+            SetPausedParams::from_bytes(&wincode::serialize(&pause).unwrap()),
+            Some(pause)
+        );
+
+        // The encoding is the in-memory layout the program used to cast to
+        assert_eq!(InitParams::LEN, 80);
+        assert_eq!(DepositParams::LEN, 16);
+        assert_eq!(WithdrawAttestedParams::LEN, 72);
+        assert_eq!(FinalizeWithdrawalParams::LEN, 88);
+        assert_eq!(SetPausedParams::LEN, 1);
+        assert_eq!(SetPausedParams::from_bytes(&[2]), None);
+        assert_eq!(DepositParams::from_bytes(&[0u8; 15]), None);
+    }
+}
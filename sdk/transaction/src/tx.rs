@@ -0,0 +1,386 @@
+use rocksdb::{ColumnFamily, WriteBatch};
+use serde::{Deserialize, Serialize};
+use wincode::{SchemaRead, SchemaWrite};
+use zelana_account::AccountId;
+use zelana_pubkey::Pubkey;
+use zelana_signature::Signature;
+
+use crate::bridge::{DepositEvent, WithdrawRequest};
+
+/// The enum for all inputs to the L2 State Machine.
+#[derive(Debug, Clone, SchemaRead, SchemaWrite, Serialize, Deserialize)]
+pub enum TransactionType {
+    /// PRIVACY: An opaque shielded transaction (The Blob).
+    /// Sender/Receiver are hidden. Validity is proven via ZK.
+    Shielded(PrivateTransaction),
+    /// A standard transfer or interaction submitted by a user via UDP.
+    Transfer(SignedTransaction),
+
+    /// A deposit event detected on L1 (Solana) and bridged to L2.
+    Deposit(DepositEvent),
+
+    /// A withdrawal request to move funds back to L1.
+    Withdraw(WithdrawRequest),
+}
+
+/// The Opaque Blob for Privacy
+#[derive(Debug, Clone, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+pub struct PrivateTransaction {
+    /// The ZK Proof (Groth16 bytes) attesting validity.
+    pub proof: Vec<u8>,
+    /// The unique tag preventing double-spends.
+    pub nullifier: [u8; 32],
+    /// The new note created (Encrypted Hash).
+    pub commitment: [u8; 32],
+    /// The encrypted data for the recipient to decrypt.
+    pub ciphertext: Vec<u8>,
+    /// Optional: Ephemeral public key for ECDH shared secret derivation.
+    pub ephemeral_key: [u8; 32],
+    /// Optional: Nonce for ChaCha20-Poly1305 encryption (12 bytes).
+    #[serde(default)]
+    pub nonce: Option<[u8; 12]>,
+    /// For SHIELD operations: L2 account to debit (transparent -> shielded)
+    #[serde(default)]
+    pub shield_from: Option<[u8; 32]>,
+    /// For SHIELD operations: Amount to shield (in lamports)
+    #[serde(default)]
+    pub shield_amount: Option<u64>,
+    /// For UNSHIELD operations: L2 account to credit (shielded -> transparent)
+    #[serde(default)]
+    pub unshield_to: Option<[u8; 32]>,
+    /// For UNSHIELD operations: Amount to unshield (in lamports)
+    #[serde(default)]
+    pub unshield_amount: Option<u64>,
+}
+/// The Wrapper Structure
+#[derive(Clone, Debug, SchemaWrite, SchemaRead, Serialize, Deserialize)]
+pub struct Transaction {
+    /// For Shielded txs, this might be all zeros or a "Relayer" key.
+    pub sender: Pubkey,
+    pub tx_type: TransactionType,
+    /// Signature is used for Transparent txs.
+    /// For Shielded, the authentication is inside the ZK Proof.
+    pub signature: Signature,
+}
+
+/// The payload a user signs.
+///
+/// `valid_until_batch` extends the binary encoding, so decode wire bytes with
+/// [`TransactionType::decode`] / [`SignedTransaction::decode`], which also
+/// accept the layout from before it existed. JSON payloads without it still
+/// deserialize and never expire.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SchemaRead, SchemaWrite, Default)]
+pub struct TransactionData {
+    pub from: AccountId,
+    pub to: AccountId,
+    pub amount: u64,
+    pub nonce: u64,
+    /// Replay protection ID (e.g. 1 for Mainnet, 2 for Devnet)
+    pub chain_id: u64,
+    /// Last batch this transaction may execute in (`None` never expires)
+    #[serde(default)]
+    pub valid_until_batch: Option<u64>,
+}
+
+/// A transaction executed in a batch after its `valid_until_batch`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("transaction expired: valid until batch {valid_until}, current batch is {batch_id}")]
+pub struct TxExpired {
+    pub valid_until: u64,
+    pub batch_id: u64,
+}
+
+impl TransactionData {
+    /// Bytes covered by a binary (non human-readable) transfer signature.
+    ///
+    /// Without an expiry this is the original layout, so signatures made
+    /// before `valid_until_batch` existed keep verifying.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        match self.valid_until_batch {
+            None => wincode::serialize(&LegacyTransactionData::from(self)),
+            Some(_) => wincode::serialize(self),
+        }
+        .expect("transaction data always encodes")
+    }
+
+    /// Reject execution in any batch later than `valid_until_batch`
+    pub fn check_expiry(&self, batch_id: u64) -> Result<(), TxExpired> {
+        match self.valid_until_batch {
+            Some(valid_until) if batch_id > valid_until => Err(TxExpired {
+                valid_until,
+                batch_id,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The authenticated wrapper around TransactionData.
+#[derive(Debug, Clone, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+pub struct SignedTransaction {
+    pub data: TransactionData,
+    /// The Ed25519 signature of the serialized `data`.
+    pub signature: Vec<u8>,
+    /// The raw public key of the signer.
+    pub signer_pubkey: [u8; 32],
+}
+
+/// Bytes that decode as neither the current nor the legacy layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("malformed transaction encoding")]
+pub struct TxDecodeError;
+
+impl SignedTransaction {
+    /// Decode a signed transfer in either the current or the legacy layout
+    pub fn decode(bytes: &[u8]) -> Result<Self, TxDecodeError> {
+        wincode::deserialize(bytes)
+            .or_else(|_| wincode::deserialize::<LegacySignedTransaction>(bytes).map(Into::into))
+            .map_err(|_| TxDecodeError)
+    }
+}
+
+impl TransactionType {
+    /// Decode a transaction in either the current or the legacy layout.
+    ///
+    /// The layouts only differ inside `Transfer`, where the legacy one has a
+    /// signature length prefix (64) where the current one has the expiry's
+    /// option tag (0 or 1), so at most one of them accepts a given input.
+    pub fn decode(bytes: &[u8]) -> Result<Self, TxDecodeError> {
+        wincode::deserialize(bytes)
+            .or_else(|_| wincode::deserialize::<LegacyTransactionType>(bytes).map(Into::into))
+            .map_err(|_| TxDecodeError)
+    }
+
+    pub fn apply_storage_effects(&self, batch: &mut WriteBatch, cf_nullifiers: &ColumnFamily) {
+        match self {
+            TransactionType::Shielded(blob) => {
+                batch.put_cf(cf_nullifiers, &blob.nullifier, b"1");
+            }
+            _ => {}
+        }
+    }
+}
+
+// Legacy Layout
+
+/// [`TransactionData`] as encoded before `valid_until_batch`
+#[derive(SchemaRead, SchemaWrite)]
+struct LegacyTransactionData {
+    from: AccountId,
+    to: AccountId,
+    amount: u64,
+    nonce: u64,
+    chain_id: u64,
+}
+
+impl From<&TransactionData> for LegacyTransactionData {
+    fn from(data: &TransactionData) -> Self {
+        Self {
+            from: data.from,
+            to: data.to,
+            amount: data.amount,
+            nonce: data.nonce,
+            chain_id: data.chain_id,
+        }
+    }
+}
+
+#[derive(SchemaRead, SchemaWrite)]
+struct LegacySignedTransaction {
+    data: LegacyTransactionData,
+    signature: Vec<u8>,
+    signer_pubkey: [u8; 32],
+}
+
+impl From<LegacySignedTransaction> for SignedTransaction {
+    fn from(tx: LegacySignedTransaction) -> Self {
+        Self {
+            data: TransactionData {
+                from: tx.data.from,
+                to: tx.data.to,
+                amount: tx.data.amount,
+                nonce: tx.data.nonce,
+                chain_id: tx.data.chain_id,
+                valid_until_batch: None,
+            },
+            signature: tx.signature,
+            signer_pubkey: tx.signer_pubkey,
+        }
+    }
+}
+
+/// [`TransactionType`] as encoded before `valid_until_batch`; variant order
+/// must match
+#[derive(SchemaRead, SchemaWrite)]
+enum LegacyTransactionType {
+    Shielded(PrivateTransaction),
+    Transfer(LegacySignedTransaction),
+    Deposit(DepositEvent),
+    Withdraw(WithdrawRequest),
+}
+
+impl From<LegacyTransactionType> for TransactionType {
+    fn from(tx: LegacyTransactionType) -> Self {
+        match tx {
+            LegacyTransactionType::Shielded(blob) => TransactionType::Shielded(blob),
+            LegacyTransactionType::Transfer(signed) => TransactionType::Transfer(signed.into()),
+            LegacyTransactionType::Deposit(deposit) => TransactionType::Deposit(deposit),
+            LegacyTransactionType::Withdraw(withdraw) => TransactionType::Withdraw(withdraw),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use proptest::sample::Index;
+
+    fn shielded() -> PrivateTransaction {
+        PrivateTransaction {
+            proof: vec![7u8; 64],
+            nullifier: [1u8; 32],
+            commitment: [2u8; 32],
+            ciphertext: vec![3u8; 48],
+            ephemeral_key: [4u8; 32],
+            nonce: Some([5u8; 12]),
+            shield_from: None,
+            shield_amount: None,
+            unshield_to: Some([6u8; 32]),
+            unshield_amount: Some(1_000),
+        }
+    }
+
+    /// One valid transaction per variant, used as fuzzing seeds
+    fn seeds() -> Vec<TransactionType> {
+        vec![
+            TransactionType::Shielded(shielded()),
+            TransactionType::Transfer(SignedTransaction {
+                data: TransactionData {
+                    from: AccountId([1u8; 32]),
+                    to: AccountId([2u8; 32]),
+                    amount: 500,
+                    nonce: 3,
+                    chain_id: 1,
+                    valid_until_batch: None,
+                },
+                signature: vec![9u8; 64],
+                signer_pubkey: [1u8; 32],
+            }),
+            TransactionType::Deposit(DepositEvent {
+                to: AccountId([2u8; 32]),
+                amount: 1_000_000,
+                l1_seq: 42,
+            }),
+            TransactionType::Withdraw(WithdrawRequest {
+                from: AccountId([1u8; 32]),
+                to_l1_address: [8u8; 32],
+                amount: 250,
+                nonce: 4,
+                signature: vec![9u8; 64],
+                signer_pubkey: [1u8; 32],
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_deserialize_arbitrary_bytes_never_panics(
+            bytes in prop::collection::vec(any::<u8>(), 0..1024),
+        ) {
+            let _ = wincode::deserialize::<TransactionType>(&bytes);
+        }
+
+        #[test]
+        fn test_deserialize_mutated_encoding_never_panics(
+            seed in 0usize..4,
+            flips in prop::collection::vec((any::<Index>(), any::<u8>()), 0..8),
+            cut in any::<Index>(),
+        ) {
+            let mut bytes = wincode::serialize(&seeds()[seed]).unwrap();
+            for (at, byte) in flips {
+                let at = at.index(bytes.len());
+                bytes[at] = byte;
+            }
+            bytes.truncate(cut.index(bytes.len() + 1));
+
+            let _ = wincode::deserialize::<TransactionType>(&bytes);
+        }
+    }
+
+    #[test]
+    fn test_check_expiry() {
+        let mut data = TransactionData::default();
+        assert_eq!(data.check_expiry(u64::MAX), Ok(()));
+
+        data.valid_until_batch = Some(5);
+        assert_eq!(data.check_expiry(5), Ok(()));
+        assert_eq!(
+            data.check_expiry(6),
+            Err(TxExpired {
+                valid_until: 5,
+                batch_id: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_accepts_legacy_transfer() {
+        let TransactionType::Transfer(signed) = seeds().swap_remove(1) else {
+            unreachable!()
+        };
+        let legacy = LegacySignedTransaction {
+            data: LegacyTransactionData::from(&signed.data),
+            signature: signed.signature.clone(),
+            signer_pubkey: signed.signer_pubkey,
+        };
+
+        // Legacy signatures cover the original layout
+        assert_eq!(
+            signed.data.signing_bytes(),
+            wincode::serialize(&legacy.data).unwrap()
+        );
+
+        let decoded = SignedTransaction::decode(&wincode::serialize(&legacy).unwrap()).unwrap();
+        assert_eq!(decoded.data, signed.data);
+        let bytes = wincode::serialize(&LegacyTransactionType::Transfer(legacy)).unwrap();
+        let TransactionType::Transfer(decoded) = TransactionType::decode(&bytes).unwrap() else {
+            panic!("expected a transfer");
+        };
+        assert_eq!(decoded.data, signed.data);
+    }
+
+    #[test]
+    fn test_decode_accepts_current_layout() {
+        for mut tx in seeds() {
+            if let TransactionType::Transfer(signed) = &mut tx {
+                signed.data.valid_until_batch = Some(7);
+            }
+            let bytes = wincode::serialize(&tx).unwrap();
+            let decoded = TransactionType::decode(&bytes).unwrap();
+            assert_eq!(wincode::serialize(&decoded).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_roundtrips_seeds() {
+        for tx in seeds() {
+            let bytes = wincode::serialize(&tx).unwrap();
+            let decoded = wincode::deserialize::<TransactionType>(&bytes).unwrap();
+            assert_eq!(wincode::serialize(&decoded).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_rejects_oversized_length_prefix() {
+        let tx = shielded();
+        let bytes = wincode::serialize(&TransactionType::Shielded(tx.clone())).unwrap();
+        // The proof's u64 length prefix follows the variant tag
+        let tag_len = bytes.len() - wincode::serialize(&tx).unwrap().len();
+
+        let mut bytes = bytes;
+        bytes[tag_len..tag_len + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        assert!(wincode::deserialize::<TransactionType>(&bytes).is_err());
+    }
+}
@@ -103,5 +103,5 @@ pub fn decrypt_signed_tx(
         )
         .map_err(|_| TxBlobError::DecryptionFailed)?;
 
-    wincode::deserialize(&plaintext).map_err(|_| TxBlobError::DeserializationFailed)
+    SignedTransaction::decode(&plaintext).map_err(|_| TxBlobError::DeserializationFailed)
 }
@@ -11,6 +11,7 @@ fn dummy_signed_tx() -> SignedTransaction {
             amount: 10,
             nonce: 0,
             chain_id: 1,
+            valid_until_batch: None,
         },
         signature: vec![7u8; 64],
         signer_pubkey: [1u8; 32],
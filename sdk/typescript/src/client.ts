@@ -251,6 +251,8 @@ export class ApiClient {
       amount: Number(request.amount),
       nonce: Number(request.nonce),
       chain_id: Number(request.chainId),
+      valid_until_batch:
+        request.validUntilBatch !== undefined ? Number(request.validUntilBatch) : undefined,
       signature: Array.from(request.signature),
       signer_pubkey: Array.from(request.signerPubkey),
     });
@@ -35,8 +35,12 @@ function buildTransferMessage(
   to: Uint8Array,
   amount: bigint,
   nonce: bigint,
-  chainId: bigint
+  chainId: bigint,
+  validUntilBatch?: bigint
 ): string {
+  // Omitted when unset so messages without an expiry are unchanged
+  const expiry =
+    validUntilBatch !== undefined ? `Valid until batch: ${validUntilBatch.toString()}\n` : '';
   return `Zelana L2 Transfer
 
 From: ${bytesToHex(from)}
@@ -44,7 +48,7 @@ To: ${bytesToHex(to)}
 Amount: ${amount.toString()} lamports
 Nonce: ${nonce.toString()}
 Chain ID: ${chainId.toString()}
-
+${expiry}
 Sign to authorize this L2 transfer.`;
 }
 
@@ -86,7 +90,13 @@ export interface Signer {
   /** Sign a message and return the 64-byte signature */
   sign(message: Uint8Array): Promise<Uint8Array>;
   /** Sign a transfer and return the signed request */
-  signTransfer(to: Bytes32, amount: bigint, nonce: bigint, chainId?: bigint): Promise<TransferRequest>;
+  signTransfer(
+    to: Bytes32,
+    amount: bigint,
+    nonce: bigint,
+    chainId?: bigint,
+    validUntilBatch?: bigint
+  ): Promise<TransferRequest>;
   /** Sign a withdrawal and return the signed request */
   signWithdrawal(toL1Address: Bytes32, amount: bigint, nonce: bigint): Promise<WithdrawRequest>;
 }
@@ -211,12 +221,16 @@ export class Keypair implements Signer {
    * The message is a human-readable text string, which:
    * 1. Works with Phantom/Privy (not blocked as Solana tx)
    * 2. Matches the format verified by the Rust sequencer
+   *
+   * If `validUntilBatch` is set, the sequencer rejects the transfer once
+   * it would land in a later batch.
    */
   async signTransfer(
     to: Bytes32,
     amount: bigint,
     nonce: bigint,
-    chainId: bigint = BigInt(1)
+    chainId: bigint = BigInt(1),
+    validUntilBatch?: bigint
   ): Promise<TransferRequest> {
     // Build human-readable message
     const messageText = buildTransferMessage(
@@ -224,7 +238,8 @@ export class Keypair implements Signer {
       to,
       amount,
       nonce,
-      chainId
+      chainId,
+      validUntilBatch
     );
 
     // Convert to UTF-8 bytes for signing
@@ -237,6 +252,7 @@ export class Keypair implements Signer {
       amount,
       nonce,
       chainId,
+      validUntilBatch,
       signature,
       signerPubkey: this.publicKey
     };
@@ -67,6 +67,8 @@ export interface TransferRequest {
   amount: bigint;
   nonce: bigint;
   chainId: bigint;
+  /** Last batch the transfer may execute in (omit for no expiry) */
+  validUntilBatch?: bigint;
   signature: Uint8Array;
   signerPubkey: Bytes32;
 }
@@ -1,6 +1,6 @@
 use {
     crate::keys::{EphemeralKeyPair, SessionKeys},
-    crate::packet::{KIND_APP_DATA, KIND_CLIENT_HELLO, Packet},
+    crate::packet::{KIND_CLIENT_HELLO, Packet, encode_app_data},
     anyhow::{Context, Result, anyhow},
     tokio::{
         net::UdpSocket,
@@ -14,12 +14,17 @@ pub struct ZelanaClient {
     #[allow(dead_code)] // Reserved for reconnection logic
     server_addr: String,
     session: SessionKeys,
+    /// Tags the fragments of each AppData message
+    next_msg_id: u32,
 }
 
 impl ZelanaClient {
     /// Establishes a secure, encrypted session with the Sequencer.
     /// This performs the Diffie-Hellman Handshake.
-    pub async fn connect(server_addr: &str) -> Result<Self> {
+    ///
+    /// `chain_id` must match the sequencer's, otherwise the derived session
+    /// keys differ and every AppData frame is rejected.
+    pub async fn connect(server_addr: &str, chain_id: u64) -> Result<Self> {
         // 1. Bind to a random local port
         let socket = UdpSocket::bind("0.0.0.0:0")
             .await
@@ -59,11 +64,12 @@ impl ZelanaClient {
         let shared = my_keys.sk.diffie_hellman(&server_public);
         let shared_secret: [u8; 32] = shared.to_bytes();
 
-        let session = SessionKeys::derive(shared_secret, &my_pub_bytes, server_pk_bytes);
+        let session = SessionKeys::derive(shared_secret, &my_pub_bytes, server_pk_bytes, chain_id);
         Ok(Self {
             socket,
             server_addr: server_addr.to_string(),
             session,
+            next_msg_id: 0,
         })
     }
 
@@ -79,13 +85,15 @@ impl ZelanaClient {
         // 3. Encrypt (adds Nonce automatically)
         let payload = self.session.encrypt(&plaintext)?;
 
-        // 4. Prepend AppData Header
-        let mut frame = Vec::with_capacity(1 + payload.len());
-        frame.push(KIND_APP_DATA);
-        frame.extend_from_slice(&payload);
+        // 4. Frame it, fragmenting if it won't fit one datagram
+        let msg_id = self.next_msg_id;
+        self.next_msg_id = self.next_msg_id.wrapping_add(1);
+        let frames = encode_app_data(msg_id, &payload)?;
 
         // 5. Blast it
-        self.socket.send(&frame).await?;
+        for frame in frames {
+            self.socket.send(&frame).await?;
+        }
 
         Ok(())
     }
@@ -6,6 +6,24 @@ use hkdf::Hkdf;
 use sha2::{Digest, Sha256};
 use x25519_dalek::{EphemeralSecret, PublicKey};
 
+use crate::packet::TAG_SIZE;
+
+/// Largest plaintext a session will decrypt (64 KiB).
+///
+/// Checked before decryption allocates its output buffer, so a transport that
+/// allows larger frames than a UDP datagram can't be used for a memory DoS.
+pub const MAX_PLAINTEXT_SIZE: usize = 64 * 1024;
+
+/// HKDF info prefix; the deployment's chain id is appended
+const SESSION_INFO: &[u8] = b"zelana-v2-session";
+
+/// Typed session failures callers may want to tell apart
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SessionError {
+    #[error("ciphertext of {len} bytes exceeds the {max} byte plaintext limit")]
+    CiphertextTooLarge { len: usize, max: usize },
+}
+
 /// A temporary keypair generated for every new connection session.
 pub struct EphemeralKeyPair {
     pub sk: EphemeralSecret,
@@ -23,6 +41,9 @@ impl EphemeralKeyPair {
 /// The established session state after a successful handshake.
 pub struct SessionKeys {
     aead: ChaCha20Poly1305,
+    /// Raw AEAD key, only kept so the session can be exported
+    #[cfg(feature = "session-export")]
+    key: [u8; 32],
     base_iv: [u8; 12],
     /// We track the sequence number to prevent replay attacks
     tx_counter: u64,
@@ -33,7 +54,13 @@ pub struct SessionKeys {
 impl SessionKeys {
     /// Derives session keys from a Diffie-Hellman shared secret.
     /// salt = H(client_pk || server_pk)
-    pub fn derive(shared_secret: [u8; 32], client_pk: &[u8; 32], server_pk: &[u8; 32]) -> Self {
+    /// info = "zelana-v2-session" || chain_id (BE), so each network gets its own keys
+    pub fn derive(
+        shared_secret: [u8; 32],
+        client_pk: &[u8; 32],
+        server_pk: &[u8; 32],
+        chain_id: u64,
+    ) -> Self {
         // 1. Compute Salt
         let mut hasher = Sha256::new();
         hasher.update(client_pk);
@@ -43,14 +70,18 @@ impl SessionKeys {
         // 2. HKDF Expand
         let hk = Hkdf::<Sha256>::new(Some(&salt), &shared_secret);
         let mut okm = [0u8; 44]; // 32 bytes Key + 12 bytes IV
-        hk.expand(b"zelana-v2-session", &mut okm)
-            .expect("HKDF expansion failed");
+        let mut info = [0u8; SESSION_INFO.len() + 8];
+        info[..SESSION_INFO.len()].copy_from_slice(SESSION_INFO);
+        info[SESSION_INFO.len()..].copy_from_slice(&chain_id.to_be_bytes());
+        hk.expand(&info, &mut okm).expect("HKDF expansion failed");
 
         let key = Key::from_slice(&okm[0..32]);
         let iv: [u8; 12] = okm[32..44].try_into().unwrap();
 
         Self {
             aead: ChaCha20Poly1305::new(key),
+            #[cfg(feature = "session-export")]
+            key: okm[0..32].try_into().unwrap(),
             base_iv: iv,
             tx_counter: 0,
             rx_counter: 0,
@@ -82,6 +113,13 @@ impl SessionKeys {
         if nonce_bytes.len() != 12 {
             return Err(anyhow::anyhow!("Invalid nonce length"));
         }
+        if ciphertext.len() > MAX_PLAINTEXT_SIZE + TAG_SIZE {
+            return Err(SessionError::CiphertextTooLarge {
+                len: ciphertext.len(),
+                max: MAX_PLAINTEXT_SIZE,
+            }
+            .into());
+        }
         let nonce = Nonce::from_slice(nonce_bytes);
 
         let plaintext = self
@@ -93,6 +131,60 @@ impl SessionKeys {
     }
 }
 
+/// Raw secrets and counters of an established session.
+///
+/// SENSITIVE: whoever holds this can decrypt and forge traffic for the
+/// session. Only move it over an authenticated, encrypted channel (e.g. to a
+/// warm-standby sequencer) and never log or persist it in plaintext. The
+/// counters are part of the state so an imported session never reuses a nonce.
+#[cfg(feature = "session-export")]
+#[derive(Clone, PartialEq, Eq)]
+pub struct SessionState {
+    pub key: [u8; 32],
+    pub base_iv: [u8; 12],
+    pub tx_counter: u64,
+    pub rx_counter: u64,
+}
+
+#[cfg(feature = "session-export")]
+impl std::fmt::Debug for SessionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionState")
+            .field("key", &"<redacted>")
+            .field("base_iv", &"<redacted>")
+            .field("tx_counter", &self.tx_counter)
+            .field("rx_counter", &self.rx_counter)
+            .finish()
+    }
+}
+
+#[cfg(feature = "session-export")]
+impl SessionKeys {
+    /// Snapshot the session, including nonce counters. See [`SessionState`].
+    pub fn export(&self) -> SessionState {
+        SessionState {
+            key: self.key,
+            base_iv: self.base_iv,
+            tx_counter: self.tx_counter,
+            rx_counter: self.rx_counter,
+        }
+    }
+
+    /// Resume a session exported with [`SessionKeys::export`].
+    ///
+    /// The original must not keep encrypting afterwards, or both copies would
+    /// hand out the same nonces.
+    pub fn import(state: SessionState) -> Self {
+        Self {
+            aead: ChaCha20Poly1305::new(Key::from_slice(&state.key)),
+            key: state.key,
+            base_iv: state.base_iv,
+            tx_counter: state.tx_counter,
+            rx_counter: state.rx_counter,
+        }
+    }
+}
+
 /// XOR-based counter nonce generation (WireGuard style).
 fn compute_nonce(base_iv: &[u8; 12], counter: u64) -> Nonce {
     let mut n = *base_iv;
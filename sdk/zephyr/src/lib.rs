@@ -1,11 +1,13 @@
 pub mod client;
 pub mod keys;
 pub mod packet;
-pub use keys::{EphemeralKeyPair, SessionKeys};
+pub mod reassembly;
+pub use keys::{EphemeralKeyPair, MAX_PLAINTEXT_SIZE, SessionError, SessionKeys};
 
 #[cfg(test)]
 mod tests {
-    use crate::keys::{EphemeralKeyPair, SessionKeys};
+    use crate::keys::{EphemeralKeyPair, MAX_PLAINTEXT_SIZE, SessionError, SessionKeys};
+    use crate::packet::TAG_SIZE;
 
     #[test]
     fn test_handshake_derivation() {
@@ -27,8 +29,8 @@ mod tests {
         );
 
         // 3. Derive Session Keys
-        let mut client_session = SessionKeys::derive(client_shared, &client_pub, &server_pub);
-        let mut server_session = SessionKeys::derive(server_shared, &client_pub, &server_pub);
+        let mut client_session = SessionKeys::derive(client_shared, &client_pub, &server_pub, 1);
+        let mut server_session = SessionKeys::derive(server_shared, &client_pub, &server_pub, 1);
 
         // 4. Test Encryption Loop
         let msg = b"Hello Zelana";
@@ -42,13 +44,100 @@ mod tests {
         assert_eq!(msg, decrypted.as_slice());
     }
 
+    #[test]
+    fn test_decrypt_rejects_oversized_ciphertext() {
+        let keys = EphemeralKeyPair::generate();
+        let pk = *keys.pk.as_bytes();
+        let mut sender = SessionKeys::derive([7u8; 32], &pk, &pk, 1);
+        let mut receiver = SessionKeys::derive([7u8; 32], &pk, &pk, 1);
+
+        // Largest allowed plaintext still round-trips
+        let max = vec![0u8; MAX_PLAINTEXT_SIZE];
+        let encrypted = sender.encrypt(&max).unwrap();
+        let decrypted = receiver
+            .decrypt(&encrypted[..12], &encrypted[12..])
+            .unwrap();
+        assert_eq!(decrypted.len(), MAX_PLAINTEXT_SIZE);
+
+        // One byte over is refused before decryption
+        let encrypted = sender.encrypt(&[0u8; MAX_PLAINTEXT_SIZE + 1]).unwrap();
+        let err = receiver
+            .decrypt(&encrypted[..12], &encrypted[12..])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SessionError>(),
+            Some(&SessionError::CiphertextTooLarge {
+                len: MAX_PLAINTEXT_SIZE + 1 + TAG_SIZE,
+                max: MAX_PLAINTEXT_SIZE,
+            })
+        );
+    }
+
+    #[cfg(feature = "session-export")]
+    #[test]
+    fn test_session_export_import_continues_counters() {
+        let keys = EphemeralKeyPair::generate();
+        let pk = *keys.pk.as_bytes();
+        let mut sender = SessionKeys::derive([3u8; 32], &pk, &pk, 1);
+        let mut receiver = SessionKeys::derive([3u8; 32], &pk, &pk, 1);
+
+        let first = sender.encrypt(b"one").unwrap();
+        let second = sender.encrypt(b"two").unwrap();
+
+        let state = sender.export();
+        assert_eq!(state.tx_counter, 2);
+        assert!(!format!("{:?}", state).contains(&format!("{:?}", state.key)));
+
+        // The imported session picks up at counter 3 under the same keys
+        let mut resumed = SessionKeys::import(state.clone());
+        let third = resumed.encrypt(b"three").unwrap();
+        assert_ne!(&third[..12], &first[..12]);
+        assert_ne!(&third[..12], &second[..12]);
+        assert_eq!(resumed.export().tx_counter, 3);
+
+        let decrypted = receiver.decrypt(&third[..12], &third[12..]).unwrap();
+        assert_eq!(decrypted, b"three");
+
+        // Matches what the original session would have produced next
+        assert_eq!(sender.encrypt(b"three").unwrap(), third);
+    }
+
+    #[test]
+    fn test_chain_id_separates_sessions() {
+        let keys = EphemeralKeyPair::generate();
+        let pk = *keys.pk.as_bytes();
+        let shared = [5u8; 32];
+
+        let mut mainnet = SessionKeys::derive(shared, &pk, &pk, 1);
+        let mut devnet = SessionKeys::derive(shared, &pk, &pk, 2);
+
+        // Same DH output, same counter: different keystream and nonce
+        let on_mainnet = mainnet.encrypt(b"transfer").unwrap();
+        let on_devnet = devnet.encrypt(b"transfer").unwrap();
+        assert_ne!(on_mainnet, on_devnet);
+        assert_ne!(&on_mainnet[..12], &on_devnet[..12]);
+
+        // A frame captured on one network doesn't decrypt on the other
+        assert!(
+            devnet
+                .decrypt(&on_mainnet[..12], &on_mainnet[12..])
+                .is_err()
+        );
+        let mut mainnet_peer = SessionKeys::derive(shared, &pk, &pk, 1);
+        assert!(
+            mainnet_peer
+                .decrypt(&on_mainnet[..12], &on_mainnet[12..])
+                .is_ok()
+        );
+    }
+
     #[test]
     fn test_nonce_increment() {
         // Test that encryption changes every time even for same message
         let keys = EphemeralKeyPair::generate();
         let shared = [0u8; 32]; // Dummy shared
         let pk = *keys.pk.as_bytes();
-        let mut session = SessionKeys::derive(shared, &pk, &pk);
+        let mut session = SessionKeys::derive(shared, &pk, &pk, 1);
 
         let msg = b"replay attack test";
         let c1 = session.encrypt(msg).unwrap();
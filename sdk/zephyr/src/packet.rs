@@ -3,8 +3,23 @@ use anyhow::{Result, bail};
 pub const KIND_CLIENT_HELLO: u8 = 1;
 pub const KIND_SERVER_HELLO: u8 = 2;
 pub const KIND_APP_DATA: u8 = 3;
+pub const KIND_APP_DATA_FRAG: u8 = 4;
+/// Largest frame sent on the wire (typical Ethernet MTU).
+pub const MAX_DATAGRAM_SIZE: usize = 1500;
+/// X25519 public key size carried by the hello packets.
+pub const PUBLIC_KEY_SIZE: usize = 32;
+/// ChaCha20-Poly1305 nonce size.
+pub const NONCE_SIZE: usize = 12;
+/// Poly1305 authentication tag size; every AppData ciphertext carries one.
+pub const TAG_SIZE: usize = 16;
 ///  packet header size (1 byte kind + 12 bytes nonce).
-pub const HEADER_SIZE: usize = 1 + 12;
+pub const HEADER_SIZE: usize = 1 + NONCE_SIZE;
+/// Fragment header size (1 byte kind + 4 bytes msg_id + 2 bytes index + 2 bytes count).
+pub const FRAG_HEADER_SIZE: usize = 1 + 4 + 2 + 2;
+/// Payload bytes carried by one fragment.
+pub const MAX_FRAG_PAYLOAD: usize = MAX_DATAGRAM_SIZE - FRAG_HEADER_SIZE;
+/// Most fragments a single message may be split into.
+pub const MAX_FRAGMENTS: u16 = 64;
 
 #[derive(Debug)]
 pub enum Packet<'a> {
@@ -18,50 +33,243 @@ pub enum Packet<'a> {
         nonce: &'a [u8; 12],
         ciphertext: &'a [u8],
     },
+    /// One piece of an AppData payload (`nonce || ciphertext`) too large for
+    /// a single datagram
+    AppDataFrag {
+        msg_id: u32,
+        frag_index: u16,
+        frag_count: u16,
+        payload: &'a [u8],
+    },
 }
 
 impl<'a> Packet<'a> {
     /// Parses a raw UDP frame.
+    ///
+    /// Frames come from arbitrary senders, so every malformed input must be
+    /// rejected with an error rather than a panic.
     pub fn parse(buf: &'a [u8]) -> Result<Self> {
-        if buf.is_empty() {
+        let Some((&kind, body)) = buf.split_first() else {
             bail!("Empty packet");
-        }
+        };
 
-        match buf[0] {
+        match kind {
             KIND_CLIENT_HELLO => {
-                if buf.len() < 33 {
+                let Some(public_key) = array_at::<PUBLIC_KEY_SIZE>(body, 0) else {
                     bail!("Malformed ClientHello");
-                }
-                let pk = array_ref(buf, 1);
-                Ok(Packet::ClientHello { public_key: pk })
+                };
+                Ok(Packet::ClientHello { public_key })
             }
             KIND_SERVER_HELLO => {
-                if buf.len() < 33 {
+                let Some(public_key) = array_at::<PUBLIC_KEY_SIZE>(body, 0) else {
                     bail!("Malformed ServerHello");
-                }
-                let pk = array_ref(buf, 1);
-                Ok(Packet::ServerHello { public_key: pk })
+                };
+                Ok(Packet::ServerHello { public_key })
             }
             KIND_APP_DATA => {
-                if buf.len() < 13 {
+                let Some(nonce) = array_at::<NONCE_SIZE>(body, 0) else {
                     bail!("Malformed AppData (Header too small)");
+                };
+                let ciphertext = &body[NONCE_SIZE..];
+                if ciphertext.len() < TAG_SIZE {
+                    bail!("Malformed AppData (ciphertext shorter than auth tag)");
                 }
-                let nonce = array_ref_12(buf, 1);
-                let ciphertext = &buf[13..];
                 Ok(Packet::AppData { nonce, ciphertext })
             }
-            _ => bail!("Unknown packet kind: {}", buf[0]),
+            KIND_APP_DATA_FRAG => {
+                let (Some(msg_id), Some(frag_index), Some(frag_count)) = (
+                    array_at::<4>(body, 0),
+                    array_at::<2>(body, 4),
+                    array_at::<2>(body, 6),
+                ) else {
+                    bail!("Malformed AppDataFrag (Header too small)");
+                };
+                let frag_index = u16::from_be_bytes(*frag_index);
+                let frag_count = u16::from_be_bytes(*frag_count);
+                if frag_count == 0 || frag_count > MAX_FRAGMENTS || frag_index >= frag_count {
+                    bail!(
+                        "Malformed AppDataFrag (fragment {}/{})",
+                        frag_index,
+                        frag_count
+                    );
+                }
+                let payload = &body[FRAG_HEADER_SIZE - 1..];
+                if payload.is_empty() {
+                    bail!("Malformed AppDataFrag (empty payload)");
+                }
+                Ok(Packet::AppDataFrag {
+                    msg_id: u32::from_be_bytes(*msg_id),
+                    frag_index,
+                    frag_count,
+                    payload,
+                })
+            }
+            _ => bail!("Unknown packet kind: {}", kind),
         }
     }
 }
 
-// Helpers to safely cast slices to arrays
-fn array_ref(slice: &[u8], offset: usize) -> &[u8; 32] {
-    let ptr = slice[offset..].as_ptr() as *const [u8; 32];
-    unsafe { &*ptr }
+/// Frames an encrypted AppData payload (`nonce || ciphertext`) for the wire.
+///
+/// A payload that fits one datagram becomes a single `AppData` frame; larger
+/// ones are split into `AppDataFrag` frames tagged with `msg_id`.
+pub fn encode_app_data(msg_id: u32, payload: &[u8]) -> Result<Vec<Vec<u8>>> {
+    if 1 + payload.len() <= MAX_DATAGRAM_SIZE {
+        let mut frame = Vec::with_capacity(1 + payload.len());
+        frame.push(KIND_APP_DATA);
+        frame.extend_from_slice(payload);
+        return Ok(vec![frame]);
+    }
+
+    let frag_count = payload.len().div_ceil(MAX_FRAG_PAYLOAD);
+    if frag_count > MAX_FRAGMENTS as usize {
+        bail!(
+            "AppData payload of {} bytes needs {} fragments (max {})",
+            payload.len(),
+            frag_count,
+            MAX_FRAGMENTS
+        );
+    }
+
+    Ok(payload
+        .chunks(MAX_FRAG_PAYLOAD)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut frame = Vec::with_capacity(FRAG_HEADER_SIZE + chunk.len());
+            frame.push(KIND_APP_DATA_FRAG);
+            frame.extend_from_slice(&msg_id.to_be_bytes());
+            frame.extend_from_slice(&(index as u16).to_be_bytes());
+            frame.extend_from_slice(&(frag_count as u16).to_be_bytes());
+            frame.extend_from_slice(chunk);
+            frame
+        })
+        .collect())
+}
+
+/// Borrows `N` bytes at `offset` as an array, or `None` if the slice is too short
+fn array_at<const N: usize>(slice: &[u8], offset: usize) -> Option<&[u8; N]> {
+    slice.get(offset..offset.checked_add(N)?)?.try_into().ok()
 }
 
-fn array_ref_12(slice: &[u8], offset: usize) -> &[u8; 12] {
-    let ptr = slice[offset..].as_ptr() as *const [u8; 12];
-    unsafe { &*ptr }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_parse_arbitrary_bytes_never_panics(
+            bytes in prop::collection::vec(any::<u8>(), 0..2048),
+        ) {
+            let _ = Packet::parse(&bytes);
+        }
+
+        #[test]
+        fn test_parse_short_frames_of_every_kind_never_panic(
+            kind in 0u8..=5,
+            body in prop::collection::vec(any::<u8>(), 0..HEADER_SIZE + TAG_SIZE),
+        ) {
+            let mut frame = vec![kind];
+            frame.extend_from_slice(&body);
+            let _ = Packet::parse(&frame);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_frames() {
+        let cases: &[(&[u8], &str)] = &[
+            (&[], "Empty packet"),
+            (&[KIND_CLIENT_HELLO], "Malformed ClientHello"),
+            (&[KIND_CLIENT_HELLO; 32], "Malformed ClientHello"),
+            (&[KIND_SERVER_HELLO; 32], "Malformed ServerHello"),
+            (&[KIND_APP_DATA], "Malformed AppData (Header too small)"),
+            (
+                &[KIND_APP_DATA; HEADER_SIZE - 1],
+                "Malformed AppData (Header too small)",
+            ),
+            (
+                &[KIND_APP_DATA; HEADER_SIZE + TAG_SIZE - 1],
+                "Malformed AppData (ciphertext shorter than auth tag)",
+            ),
+            (
+                &[KIND_APP_DATA_FRAG; FRAG_HEADER_SIZE - 1],
+                "Malformed AppDataFrag (Header too small)",
+            ),
+            (
+                &[KIND_APP_DATA_FRAG, 0, 0, 0, 1, 0, 2, 0, 2, 0xaa],
+                "Malformed AppDataFrag (fragment 2/2)",
+            ),
+            (
+                &[KIND_APP_DATA_FRAG, 0, 0, 0, 1, 0, 0, 0, 0, 0xaa],
+                "Malformed AppDataFrag (fragment 0/0)",
+            ),
+            (
+                &[KIND_APP_DATA_FRAG, 0, 0, 0, 1, 0, 0, 0, 2],
+                "Malformed AppDataFrag (empty payload)",
+            ),
+            (&[0xff; 40], "Unknown packet kind: 255"),
+        ];
+
+        for (frame, expected) in cases {
+            let err = Packet::parse(frame).unwrap_err();
+            assert_eq!(err.to_string(), *expected, "frame len {}", frame.len());
+        }
+    }
+
+    #[test]
+    fn test_parse_valid_frames() {
+        let mut hello = vec![KIND_CLIENT_HELLO];
+        hello.extend_from_slice(&[7u8; PUBLIC_KEY_SIZE]);
+        match Packet::parse(&hello).unwrap() {
+            Packet::ClientHello { public_key } => assert_eq!(public_key, &[7u8; 32]),
+            other => panic!("unexpected packet {:?}", other),
+        }
+
+        let mut app_data = vec![KIND_APP_DATA];
+        app_data.extend_from_slice(&[1u8; NONCE_SIZE]);
+        app_data.extend_from_slice(&[2u8; TAG_SIZE]);
+        match Packet::parse(&app_data).unwrap() {
+            Packet::AppData { nonce, ciphertext } => {
+                assert_eq!(nonce, &[1u8; 12]);
+                assert_eq!(ciphertext, &[2u8; TAG_SIZE]);
+            }
+            other => panic!("unexpected packet {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_encode_app_data_fragments_large_payloads() {
+        let small = vec![9u8; 100];
+        let frames = encode_app_data(1, &small).unwrap();
+        assert_eq!(frames.len(), 1);
+        assert!(matches!(
+            Packet::parse(&frames[0]).unwrap(),
+            Packet::AppData { .. }
+        ));
+
+        let large: Vec<u8> = (0..4000u32).map(|i| i as u8).collect();
+        let frames = encode_app_data(42, &large).unwrap();
+        assert_eq!(frames.len(), 3);
+
+        let mut joined = Vec::new();
+        for (i, frame) in frames.iter().enumerate() {
+            assert!(frame.len() <= MAX_DATAGRAM_SIZE);
+            match Packet::parse(frame).unwrap() {
+                Packet::AppDataFrag {
+                    msg_id,
+                    frag_index,
+                    frag_count,
+                    payload,
+                } => {
+                    assert_eq!((msg_id, frag_index, frag_count), (42, i as u16, 3));
+                    joined.extend_from_slice(payload);
+                }
+                other => panic!("unexpected packet {:?}", other),
+            }
+        }
+        assert_eq!(joined, large);
+
+        let too_large = vec![0u8; MAX_FRAG_PAYLOAD * MAX_FRAGMENTS as usize + 1];
+        assert!(encode_app_data(1, &too_large).is_err());
+    }
 }
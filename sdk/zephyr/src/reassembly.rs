@@ -0,0 +1,262 @@
+//! AppData fragment reassembly.
+//!
+//! Collects `AppDataFrag` frames per `(peer, msg_id)` until every fragment has
+//! arrived, in any order. Incomplete messages expire after a timeout, and the
+//! buffer is bounded per peer and in total so unauthenticated floods of
+//! partial messages can't exhaust memory.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use crate::packet::MAX_FRAGMENTS;
+
+/// Limits for the reassembly buffer
+#[derive(Debug, Clone)]
+pub struct ReassemblyConfig {
+    /// How long an incomplete message is kept
+    pub timeout: Duration,
+    /// Incomplete messages allowed per peer
+    pub max_messages_per_peer: usize,
+    /// Payload bytes buffered across all peers
+    pub max_buffered_bytes: usize,
+}
+
+impl Default for ReassemblyConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            max_messages_per_peer: 4,
+            max_buffered_bytes: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// Why a fragment was refused
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReassemblyError {
+    #[error("invalid fragment {index}/{count}")]
+    InvalidFragment { index: u16, count: u16 },
+    #[error("fragment count changed from {expected} to {got} mid-message")]
+    CountMismatch { expected: u16, got: u16 },
+    #[error("too many incomplete messages from peer")]
+    TooManyPending,
+    #[error("reassembly buffer full")]
+    BufferFull,
+}
+
+struct Partial {
+    fragments: Vec<Option<Vec<u8>>>,
+    received: usize,
+    bytes: usize,
+    started: Instant,
+}
+
+/// Reassembly buffer keyed by `(peer, msg_id)`
+pub struct Reassembler<P> {
+    config: ReassemblyConfig,
+    pending: HashMap<(P, u32), Partial>,
+    buffered_bytes: usize,
+}
+
+impl<P: Eq + Hash + Clone> Reassembler<P> {
+    pub fn new(config: ReassemblyConfig) -> Self {
+        Self {
+            config,
+            pending: HashMap::new(),
+            buffered_bytes: 0,
+        }
+    }
+
+    /// Buffer one fragment; returns the full payload once the last one arrives.
+    ///
+    /// Duplicate fragments are ignored. A refused fragment drops the whole
+    /// message it belongs to.
+    pub fn insert(
+        &mut self,
+        peer: P,
+        msg_id: u32,
+        frag_index: u16,
+        frag_count: u16,
+        payload: &[u8],
+        now: Instant,
+    ) -> Result<Option<Vec<u8>>, ReassemblyError> {
+        if frag_count == 0 || frag_count > MAX_FRAGMENTS || frag_index >= frag_count {
+            return Err(ReassemblyError::InvalidFragment {
+                index: frag_index,
+                count: frag_count,
+            });
+        }
+        if frag_count == 1 {
+            return Ok(Some(payload.to_vec()));
+        }
+
+        let key = (peer, msg_id);
+        if !self.pending.contains_key(&key) {
+            let from_peer = self.pending.keys().filter(|(p, _)| *p == key.0).count();
+            if from_peer >= self.config.max_messages_per_peer {
+                return Err(ReassemblyError::TooManyPending);
+            }
+        }
+        if self.buffered_bytes + payload.len() > self.config.max_buffered_bytes {
+            self.evict_expired(now);
+            if self.buffered_bytes + payload.len() > self.config.max_buffered_bytes {
+                return Err(ReassemblyError::BufferFull);
+            }
+        }
+
+        let partial = self.pending.entry(key.clone()).or_insert_with(|| Partial {
+            fragments: vec![None; frag_count as usize],
+            received: 0,
+            bytes: 0,
+            started: now,
+        });
+        if partial.fragments.len() != frag_count as usize {
+            let expected = partial.fragments.len() as u16;
+            self.remove(&key);
+            return Err(ReassemblyError::CountMismatch {
+                expected,
+                got: frag_count,
+            });
+        }
+
+        let slot = &mut partial.fragments[frag_index as usize];
+        if slot.is_some() {
+            return Ok(None);
+        }
+        *slot = Some(payload.to_vec());
+        partial.received += 1;
+        partial.bytes += payload.len();
+        self.buffered_bytes += payload.len();
+
+        if partial.received < partial.fragments.len() {
+            return Ok(None);
+        }
+        let partial = self.remove(&key).expect("message just completed");
+        Ok(Some(
+            partial.fragments.into_iter().flatten().flatten().collect(),
+        ))
+    }
+
+    /// Drop incomplete messages older than the timeout, returning how many
+    pub fn evict_expired(&mut self, now: Instant) -> usize {
+        let timeout = self.config.timeout;
+        let expired: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| now.saturating_duration_since(p.started) > timeout)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            self.remove(key);
+        }
+        expired.len()
+    }
+
+    /// Incomplete messages currently buffered
+    pub fn pending_messages(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Payload bytes currently buffered
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffered_bytes
+    }
+
+    fn remove(&mut self, key: &(P, u32)) -> Option<Partial> {
+        let partial = self.pending.remove(key)?;
+        self.buffered_bytes -= partial.bytes;
+        Some(partial)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEER: &str = "127.0.0.1:9000";
+
+    fn reassembler() -> Reassembler<&'static str> {
+        Reassembler::new(ReassemblyConfig::default())
+    }
+
+    #[test]
+    fn test_reassembles_in_order() {
+        let mut r = reassembler();
+        let now = Instant::now();
+
+        assert_eq!(r.insert(PEER, 1, 0, 3, b"ab", now), Ok(None));
+        assert_eq!(r.insert(PEER, 1, 1, 3, b"cd", now), Ok(None));
+        assert_eq!(
+            r.insert(PEER, 1, 2, 3, b"e", now),
+            Ok(Some(b"abcde".to_vec()))
+        );
+        assert_eq!(r.pending_messages(), 0);
+        assert_eq!(r.buffered_bytes(), 0);
+    }
+
+    #[test]
+    fn test_reassembles_out_of_order_with_duplicates() {
+        let mut r = reassembler();
+        let now = Instant::now();
+
+        assert_eq!(r.insert(PEER, 7, 2, 3, b"e", now), Ok(None));
+        assert_eq!(r.insert(PEER, 7, 0, 3, b"ab", now), Ok(None));
+        assert_eq!(r.insert(PEER, 7, 0, 3, b"XX", now), Ok(None));
+        // Same msg_id from another peer is a different message
+        assert_eq!(r.insert("10.0.0.1:1", 7, 1, 3, b"zz", now), Ok(None));
+        assert_eq!(
+            r.insert(PEER, 7, 1, 3, b"cd", now),
+            Ok(Some(b"abcde".to_vec()))
+        );
+        assert_eq!(r.pending_messages(), 1);
+    }
+
+    #[test]
+    fn test_incomplete_message_expires() {
+        let mut r = reassembler();
+        let start = Instant::now();
+
+        assert_eq!(r.insert(PEER, 1, 0, 2, b"ab", start), Ok(None));
+        assert_eq!(r.evict_expired(start + Duration::from_secs(5)), 0);
+        assert_eq!(r.evict_expired(start + Duration::from_secs(11)), 1);
+        assert_eq!(r.buffered_bytes(), 0);
+
+        // A late fragment starts a fresh message rather than completing the old one
+        let late = start + Duration::from_secs(12);
+        assert_eq!(r.insert(PEER, 1, 1, 2, b"cd", late), Ok(None));
+    }
+
+    #[test]
+    fn test_buffer_limits() {
+        let mut r = Reassembler::new(ReassemblyConfig {
+            max_messages_per_peer: 2,
+            max_buffered_bytes: 10,
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        assert_eq!(r.insert(PEER, 1, 0, 2, b"aaaa", now), Ok(None));
+        assert_eq!(r.insert(PEER, 2, 0, 2, b"bbbb", now), Ok(None));
+        assert_eq!(
+            r.insert(PEER, 3, 0, 2, b"c", now),
+            Err(ReassemblyError::TooManyPending)
+        );
+        assert_eq!(
+            r.insert("10.0.0.1:1", 1, 0, 2, b"ddd", now),
+            Err(ReassemblyError::BufferFull)
+        );
+        assert_eq!(
+            r.insert(PEER, 1, 1, 3, b"a", now),
+            Err(ReassemblyError::CountMismatch {
+                expected: 2,
+                got: 3
+            })
+        );
+        assert_eq!(r.buffered_bytes(), 4);
+        assert_eq!(
+            r.insert(PEER, 9, 2, 2, b"x", now),
+            Err(ReassemblyError::InvalidFragment { index: 2, count: 2 })
+        );
+    }
+}